@@ -0,0 +1,36 @@
+import argparse
+import os
+import subprocess
+import time
+import statistics
+
+def run_command(command, cwd):
+    start_time = time.time()
+    subprocess.run(command, shell=True, check=True, cwd=cwd, stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL)
+    return time.time() - start_time
+
+def main():
+    parser = argparse.ArgumentParser()
+    parser.add_argument("--repo_dir", type=str, default="deep_tree")
+    parser.add_argument("--runs", type=int, default=5)
+    args = parser.parse_args()
+
+    # Commit the deep subtrees once, then time touching a single top level file
+    if not os.path.exists(os.path.join(args.repo_dir, ".oxen")):
+        run_command("oxen init && oxen add . && oxen commit -m \"deep tree\"", args.repo_dir)
+
+    commands = [
+        {"name":"add single file", "command":"oxen add single.txt"},
+        {"name":"status single file", "command":"oxen status single.txt"},
+    ]
+
+    for command in commands:
+        durations = []
+        for i in range(args.runs):
+            with open(os.path.join(args.repo_dir, "single.txt"), 'w') as f:
+                f.write(f"single file {command['name']} {i}")
+            durations.append(run_command(command["command"], args.repo_dir))
+        print(f"{command['name']}: median {statistics.median(durations):.3f} seconds over {args.runs} runs")
+
+if __name__ == "__main__":
+    main()
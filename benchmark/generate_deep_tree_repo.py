@@ -0,0 +1,32 @@
+import os
+import argparse
+from tqdm import tqdm
+
+def generate_deep_tree(output_dir, num_subdirs, depth, files_per_dir):
+    print(f"Generating {num_subdirs} subtrees {depth} levels deep in {output_dir}")
+    os.makedirs(output_dir, exist_ok=True)
+
+    # The top dir is shallow but wide, each sub dir hides a deep subtree
+    for i in tqdm(range(num_subdirs)):
+        path = os.path.join(output_dir, f"dir_{i}")
+        for d in range(depth):
+            path = os.path.join(path, f"level_{d}")
+            os.makedirs(path, exist_ok=True)
+            for j in range(files_per_dir):
+                with open(os.path.join(path, f"file_{j}.txt"), 'w') as f:
+                    f.write(f"File content {i} {d} {j}")
+
+    # The single file we add and status against
+    with open(os.path.join(output_dir, "single.txt"), 'w') as f:
+        f.write("single file")
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser()
+    parser.add_argument("--output_dir", type=str, default="deep_tree")
+    parser.add_argument("--num_subdirs", type=int, default=100)
+    parser.add_argument("--depth", type=int, default=20)
+    parser.add_argument("--files_per_dir", type=int, default=10)
+    args = parser.parse_args()
+
+    generate_deep_tree(args.output_dir, args.num_subdirs, args.depth, args.files_per_dir)
+    print("Deep tree generation complete!")
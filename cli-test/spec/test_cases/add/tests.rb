@@ -8,7 +8,7 @@ RSpec.describe 'add - test relative paths', type: :aruba do
 
   after(:each) do
     FileUtils.rm_rf('test-relative-paths')
-
+    FileUtils.rm_rf('test-relative-to')
   end
 
   it 'tests oxen add with relative paths from subdirectories' do
@@ -67,4 +67,29 @@ RSpec.describe 'add - test relative paths', type: :aruba do
     Dir.chdir(parent_path)
     
   end
+
+  it 'tests oxen add --relative-to resolves paths from a subdirectory' do
+    directory_path = File.join('tmp', 'aruba', 'test-relative-to')
+    FileUtils.mkdir_p(directory_path)
+    Dir.chdir(directory_path)
+
+    run_system_command('oxen init')
+
+    images_path = File.join('images', 'test')
+    FileUtils.mkdir_p(images_path)
+    File.open(File.join(images_path, 'nested.txt'), 'w') do |file|
+      file.puts 'nested'
+    end
+
+    # Stay at the repo root and resolve the path from images/test
+    run_system_command("oxen add --relative-to #{images_path} nested.txt")
+
+    # The file is staged under its path from the repo root
+    status = `oxen status`
+    expect(status).to include(File.join('images', 'test', 'nested.txt'))
+    expect(status).not_to match(/untracked/i)
+
+    # Return to cli-test
+    Dir.chdir(File.join('..', '..', '..'))
+  end
 end
@@ -6,12 +6,18 @@ use async_trait::async_trait;
 pub mod add;
 pub use add::AddCmd;
 
+pub mod backfill_metadata;
+pub use backfill_metadata::BackfillMetadataCmd;
+
 pub mod branch;
 pub use branch::BranchCmd;
 
 pub mod checkout;
 pub use checkout::CheckoutCmd;
 
+pub mod clean;
+pub use clean::CleanCmd;
+
 pub mod clone;
 pub use clone::CloneCmd;
 
@@ -45,6 +51,9 @@ pub use embeddings::EmbeddingsCmd;
 pub mod fetch;
 pub use fetch::FetchCmd;
 
+pub mod gc;
+pub use gc::GcCmd;
+
 pub mod info;
 pub use info::InfoCmd;
 
@@ -81,6 +90,9 @@ pub use pull::PullCmd;
 pub mod push;
 pub use push::PushCmd;
 
+pub mod reflog;
+pub use reflog::ReflogCmd;
+
 pub mod remote;
 pub use remote::RemoteCmd;
 
@@ -102,12 +114,21 @@ pub use tree::TreeCmd;
 pub mod unpack;
 pub use unpack::UnpackCmd;
 
+pub mod squash;
+pub use squash::SquashCmd;
+
+pub mod stats;
+pub use stats::StatsCmd;
+
 pub mod status;
 pub use status::StatusCmd;
 
 pub mod upload;
 pub use upload::UploadCmd;
 
+pub mod verify_commit;
+pub use verify_commit::VerifyCommitCmd;
+
 pub mod workspace;
 pub use workspace::WorkspaceCmd;
 
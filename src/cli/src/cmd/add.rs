@@ -1,4 +1,6 @@
+use std::io::Read;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use async_trait::async_trait;
 use clap::{Arg, Command};
@@ -8,6 +10,7 @@ use crate::util;
 use liboxen::model::LocalRepository;
 use liboxen::opts::AddOpts;
 use liboxen::repositories;
+use liboxen::util::progress_bar::ProgressFormat;
 
 use crate::cmd::RunCmd;
 use crate::helpers::check_repo_migration_needed;
@@ -22,9 +25,134 @@ pub fn add_args() -> Command {
         .about("Adds the specified files or directories")
         .arg(
             Arg::new("files")
-                .required(true)
+                .required_unless_present_any(["stdin", "stdin0"])
                 .action(clap::ArgAction::Append),
         )
+        .arg(
+            Arg::new("stdin")
+                .long("stdin")
+                .help("Read newline separated paths to add from stdin, in addition to any paths passed as arguments.")
+                .conflicts_with("stdin0")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("stdin0")
+                .long("stdin0")
+                .help("Read NUL separated paths to add from stdin, ie from `find . -print0`.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("relative-to")
+                .long("relative-to")
+                .value_name("DIR")
+                .help("Resolve the provided paths relative to this directory instead of the current working directory. Files are still staged relative to the repository root.")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("allow-download")
+                .long("allow-download")
+                .help("Allow adding http, https or s3 URLs. The file is downloaded into the working tree and then staged.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("as")
+                .long("as")
+                .value_name("PATH")
+                .requires("allow-download")
+                .help("Path to save a downloaded URL to, relative to the repository root. Defaults to the file name in the URL.")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("include-head-removed")
+                .long("include-head-removed")
+                .value_name("true|false")
+                .help("When adding a glob, also stage removals of matching files that were deleted since the last commit. Set to false to only stage files that exist on disk.")
+                .value_parser(clap::value_parser!(bool))
+                .default_value("true")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("pointer")
+                .long("pointer")
+                .value_name("URL")
+                .help("Track the files as pointers to URL without copying their bytes into the version store. If URL ends with '/', each file's path is appended to it. Pulling fetches the bytes from the URL.")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("batch-size")
+                .long("batch-size")
+                .value_name("N")
+                .help("Number of staged entries to write to the index at a time. Larger batches are faster for very large adds, but an interrupted add loses up to N staged entries.")
+                .value_parser(clap::value_parser!(usize))
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("track-empty-dirs")
+                .long("track-empty-dirs")
+                .help("Stage empty directories by writing an empty .oxenkeep file into them, so they are recreated on checkout.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("no-metadata")
+                .long("no-metadata")
+                .help("Skip extracting metadata (tabular schemas, image sizes, ...) to speed up a large initial import. Tabular diffs and schemas will not work for these files until their metadata is backfilled with `oxen backfill-metadata`.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("chmod")
+                .long("chmod")
+                .value_name("+x|-x")
+                .help("Record the files as executable (+x) or not (-x) regardless of their permissions on disk. Checkout restores the recorded mode.")
+                .value_parser(["+x", "-x"])
+                .allow_hyphen_values(true)
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("update")
+                .long("update")
+                .short('u')
+                .help("Only stage modifications and removals of files that are already tracked. Untracked files are not added.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("verbose")
+                .long("verbose")
+                .short('v')
+                .help("Print each file as it is staged with its status and short hash.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("continue")
+                .long("continue")
+                .help("Resume an interrupted add, skipping the directories it already staged.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("sort")
+                .long("sort")
+                .help("Add the files in each directory in name order, one at a time, so the order files are staged and reported in is the same on every machine. Slower than the default.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .help("Fail on the first file or directory that can't be read, instead of skipping it and listing it in the summary.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("chunked-hash")
+                .long("chunked-hash")
+                .help("Store large files as content-defined chunks, so new versions of a file that only changed in places reuse the unchanged chunks.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .help("How to report progress. `json` writes newline delimited JSON events to stderr for other programs to render.")
+                .value_parser(["human", "json"])
+                .default_value("human")
+                .action(clap::ArgAction::Set),
+        )
 }
 
 #[async_trait]
@@ -40,33 +168,109 @@ impl RunCmd for AddCmd {
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
         // Parse Args
-        let paths: Vec<PathBuf> = args
+        let current_dir = std::env::current_dir().map_err(|e| {
+            log::warn!("Failed to get current directory: {}", e);
+            OxenError::basic_str(format!("Failed to get current directory: {}", e))
+        })?;
+        let base_dir = match args.get_one::<String>("relative-to") {
+            Some(dir) => {
+                let dir = current_dir.join(dir);
+                if !dir.is_dir() {
+                    return Err(OxenError::basic_str(format!(
+                        "--relative-to must be a directory: {:?}",
+                        dir
+                    )));
+                }
+                util::fs::canonicalize(&dir)?
+            }
+            None => current_dir,
+        };
+        let mut raw_paths: Vec<String> = args
             .get_many::<String>("files")
-            .expect("Must supply files")
+            .map(|files| files.cloned().collect())
+            .unwrap_or_default();
+        if args.get_flag("stdin0") {
+            raw_paths.extend(read_stdin_paths('\0')?);
+        } else if args.get_flag("stdin") {
+            raw_paths.extend(read_stdin_paths('\n')?);
+        }
+        if raw_paths.is_empty() {
+            return Err(OxenError::basic_str("No paths to add"));
+        }
+
+        let (urls, files): (Vec<&String>, Vec<&String>) = raw_paths
+            .iter()
+            .partition(|p| repositories::add::is_downloadable_url(p));
+        if !urls.is_empty() && !args.get_flag("allow-download") {
+            return Err(OxenError::basic_str(format!(
+                "Pass --allow-download to download and add {}",
+                urls[0]
+            )));
+        }
+        let download_as = args.get_one::<String>("as").map(PathBuf::from);
+        if download_as.is_some() && urls.len() != 1 {
+            return Err(OxenError::basic_str("--as requires exactly one URL"));
+        }
+
+        let paths: Vec<PathBuf> = files
+            .into_iter()
             .map(|p| -> Result<PathBuf, OxenError> {
-                let current_dir = std::env::current_dir().map_err(|e| {
-                    log::warn!("Failed to get current directory: {}", e);
-                    OxenError::basic_str(format!("Failed to get current directory: {}", e))
-                })?;
-                let joined_path = current_dir.join(p);
+                let joined_path = base_dir.join(p);
                 util::fs::canonicalize(&joined_path).or_else(|_| Ok(joined_path))
             })
             .collect::<Result<Vec<PathBuf>, OxenError>>()?;
 
+        let include_head_removed = *args
+            .get_one::<bool>("include-head-removed")
+            .expect("Must supply include-head-removed");
+
         let opts = AddOpts {
             paths,
             is_remote: false,
             directory: None,
+            include_head_removed,
+            pointer_url: args.get_one::<String>("pointer").cloned(),
+            batch_size: args.get_one::<usize>("batch-size").copied(),
+            track_empty_dirs: args.get_flag("track-empty-dirs"),
+            no_metadata: args.get_flag("no-metadata"),
+            verbose: args.get_flag("verbose"),
+            update: args.get_flag("update"),
+            resume: args.get_flag("continue"),
+            chmod: args.get_one::<String>("chmod").map(|mode| mode == "+x"),
+            sort: args.get_flag("sort"),
+            chunked_hash: args.get_flag("chunked-hash"),
+            strict: args.get_flag("strict"),
+            verbose_sink: None,
+            progress: ProgressFormat::from_str(
+                args.get_one::<String>("progress")
+                    .expect("Must supply progress"),
+            )?,
         };
 
         // Recursively look up from the current dir for .oxen directory
         let repository = LocalRepository::from_current_dir()?;
         check_repo_migration_needed(&repository)?;
 
-        for path in &opts.paths {
-            repositories::add(&repository, path)?;
+        for url in urls {
+            let path =
+                repositories::add::add_from_url(&repository, url, download_as.as_deref()).await?;
+            println!("Downloaded {} to {}", url, path.display());
         }
 
+        repositories::add::add_with_opts(&repository, &opts)?;
+
         Ok(())
     }
 }
+
+/// Read the paths piped to stdin, split on `separator` and skipping empty entries
+fn read_stdin_paths(separator: char) -> Result<Vec<String>, OxenError> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    Ok(input
+        .split(separator)
+        .map(|path| path.trim_end_matches('\r'))
+        .filter(|path| !path.is_empty())
+        .map(String::from)
+        .collect())
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::repositories;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "backfill-metadata";
+pub struct BackfillMetadataCmd;
+
+#[async_trait]
+impl RunCmd for BackfillMetadataCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Compute the metadata of files added with `oxen add --no-metadata` and commit it.")
+            .arg(
+                Arg::new("limit")
+                    .long("limit")
+                    .value_name("N")
+                    .help("Only backfill up to N files in this run, run again to continue with the rest.")
+                    .value_parser(clap::value_parser!(usize))
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("message")
+                    .long("message")
+                    .short('m')
+                    .help("The message for the backfill commit.")
+                    .default_value("Backfill metadata")
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let limit = args.get_one::<usize>("limit").copied();
+        let message = args
+            .get_one::<String>("message")
+            .expect("Must supply a commit message");
+
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let head_commit = repositories::commits::head_commit(&repo)?;
+        let stats = repositories::metadata::backfill_with_limit(&repo, &head_commit, limit)?;
+        println!(
+            "Backfilled metadata for {} files, {} without metadata, {} left",
+            stats.num_backfilled + stats.num_already_staged,
+            stats.num_failed,
+            stats.num_remaining
+        );
+
+        if stats.num_backfilled + stats.num_already_staged > 0 {
+            let commit = repositories::commit(&repo, message)?;
+            println!("Commit {} done.", commit.id);
+        }
+        if stats.num_remaining > 0 {
+            println!("Run `oxen {NAME}` again to continue.");
+        }
+
+        Ok(())
+    }
+}
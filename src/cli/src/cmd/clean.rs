@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::opts::CleanOpts;
+use liboxen::repositories;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "clean";
+pub struct CleanCmd;
+
+#[async_trait]
+impl RunCmd for CleanCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Removes untracked files from the working directory")
+            .arg(
+                Arg::new("dry-run")
+                    .long("dry-run")
+                    .short('n')
+                    .help("List the files that would be removed without removing them.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("directories")
+                    .short('d')
+                    .help("Also remove untracked directories.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("ignored")
+                    .short('x')
+                    .help("Also remove the files ignored by .oxenignore.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let opts = CleanOpts {
+            dry_run: args.get_flag("dry-run"),
+            directories: args.get_flag("directories"),
+            ignored: args.get_flag("ignored"),
+        };
+
+        let repository = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repository)?;
+
+        let paths = repositories::status::clean(&repository, &opts)?;
+        let action = if opts.dry_run {
+            "Would remove"
+        } else {
+            "Removing"
+        };
+        for path in paths {
+            println!("{action} {}", path.display());
+        }
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use liboxen::model::LocalRepository;
 use liboxen::repositories;
 
 use crate::cmd::RunCmd;
-use crate::helpers::check_repo_migration_needed;
+use crate::helpers::{check_repo_migration_needed, commit_message_from_args};
 
 pub const NAME: &str = "commit";
 pub struct CommitCmd;
@@ -26,14 +26,22 @@ impl RunCmd for CommitCmd {
                     .help("The message for the commit. Should be descriptive about what changed.")
                     .long("message")
                     .short('m')
-                    .required(true)
+                    .required_unless_present("template")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("template")
+                    .long("template")
+                    .short('t')
+                    .value_name("FILE")
+                    .help("Fill the message into this template file, replacing `{message}` with the -m message. Without -m the template is the whole message.")
                     .action(clap::ArgAction::Set),
             )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
         // Parse Args
-        let Some(message) = args.get_one::<String>("message") else {
+        let Some(message) = commit_message_from_args(args)? else {
             return Err(OxenError::basic_str(
                 "Err: Usage `oxen commit -m <message>`",
             ));
@@ -43,7 +51,7 @@ impl RunCmd for CommitCmd {
         check_repo_migration_needed(&repo)?;
 
         println!("Committing with message: {message}");
-        repositories::commit(&repo, message)?;
+        repositories::commit(&repo, &message)?;
 
         Ok(())
     }
@@ -51,6 +51,30 @@ impl RunCmd for ConfigCmd {
                     .help("Delete a remote from the current working repository.")
                     .action(clap::ArgAction::Set),
             )
+            .arg(
+                Arg::new("enable-hooks")
+                    .long("enable-hooks")
+                    .value_name("true|false")
+                    .help("Run the executables in .oxen/hooks (pre-add, pre-commit) for the current working repository.")
+                    .value_parser(clap::value_parser!(bool))
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("strict-mtime")
+                    .long("strict-mtime")
+                    .value_name("true|false")
+                    .help("Hash files even if their modification time has not changed when computing status and adding.")
+                    .value_parser(clap::value_parser!(bool))
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("normalize-line-endings")
+                    .long("normalize-line-endings")
+                    .value_name("true|false")
+                    .help("Convert CRLF line endings in text files to LF when adding, so files that only differ in line endings are unmodified.")
+                    .value_parser(clap::value_parser!(bool))
+                    .action(clap::ArgAction::Set),
+            )
             .arg(
                 Arg::new("auth-token")
                     .long("auth")
@@ -136,6 +160,36 @@ impl RunCmd for ConfigCmd {
             }
         }
 
+        if let Some(enabled) = args.get_one::<bool>("enable-hooks") {
+            let mut repo = LocalRepository::from_current_dir()?;
+            match command::config::set_hooks_enabled(&mut repo, *enabled) {
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("{err}")
+                }
+            }
+        }
+
+        if let Some(strict) = args.get_one::<bool>("strict-mtime") {
+            let mut repo = LocalRepository::from_current_dir()?;
+            match command::config::set_strict_mtime(&mut repo, *strict) {
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("{err}")
+                }
+            }
+        }
+
+        if let Some(normalize) = args.get_one::<bool>("normalize-line-endings") {
+            let mut repo = LocalRepository::from_current_dir()?;
+            match command::config::set_normalize_line_endings(&mut repo, *normalize) {
+                Ok(_) => {}
+                Err(err) => {
+                    eprintln!("{err}")
+                }
+            }
+        }
+
         Ok(())
     }
 }
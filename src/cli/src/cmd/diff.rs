@@ -9,6 +9,7 @@ use liboxen::core::df::tabular;
 use liboxen::error::OxenError;
 use liboxen::model::diff::tabular_diff::TabularDiffMods;
 use liboxen::model::diff::{ChangeType, DiffResult, TextDiff};
+use liboxen::model::LocalRepository;
 use liboxen::opts::DiffOpts;
 use liboxen::repositories;
 use liboxen::util;
@@ -56,9 +57,17 @@ impl RunCmd for DiffCmd {
                 .short('o')
                 .help("Output directory path to write the results of the comparison. Will write both match.csv (rows with same keys and compares) and diff.csv (rows with different compares between files.")
                 .action(clap::ArgAction::Set))
+            .arg(Arg::new("name-status")
+                .long("name-status")
+                .help("Treat RESOURCE1 and RESOURCE2 as revisions and list the files that changed between them, prefixed with A (added), M (modified) or D (removed).")
+                .action(clap::ArgAction::SetTrue))
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        if args.get_flag("name-status") {
+            return DiffCmd::print_name_status(args);
+        }
+
         // Parse Args
         let opts = DiffCmd::parse_args(args);
 
@@ -141,6 +150,30 @@ impl DiffCmd {
         }
     }
 
+    fn print_name_status(args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repository = LocalRepository::from_current_dir()?;
+        let from = args.get_one::<String>("RESOURCE1").expect("required");
+        let from_commit = repositories::revisions::resolve(&repository, from)?;
+        // Compare against HEAD if only one revision is given
+        let to_commit = match args.get_one::<String>("RESOURCE2") {
+            Some(to) => repositories::revisions::resolve(&repository, to)?,
+            None => repositories::commits::head_commit(&repository)?,
+        };
+
+        let changes = repositories::diffs::changed_entries(&repository, &from_commit, &to_commit)?;
+        for (path, change_type, _hash) in changes {
+            let status = match change_type {
+                ChangeType::Added => "A",
+                ChangeType::Removed => "D",
+                ChangeType::Modified => "M",
+                ChangeType::Unchanged => continue,
+            };
+            println!("{}\t{}", status, path.to_string_lossy());
+        }
+
+        Ok(())
+    }
+
     fn parse_file_and_revision(file_revision: &str) -> (String, Option<String>) {
         let parts: Vec<&str> = file_revision.split(':').collect();
         if parts.len() == 2 {
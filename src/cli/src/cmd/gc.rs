@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::repositories;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "gc";
+pub struct GcCmd;
+
+#[async_trait]
+impl RunCmd for GcCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Delete the version files that are not referenced by any commit, staged file or workspace.")
+            .arg(
+                Arg::new("dry-run")
+                    .long("dry-run")
+                    .help("Report how much space would be reclaimed without deleting anything.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let dry_run = args.get_flag("dry-run");
+        let result = repositories::gc::gc(&repo, dry_run)?;
+        let size = bytesize::ByteSize::b(result.num_bytes);
+        if result.dry_run {
+            println!("would reclaim {} across {} files", size, result.num_files);
+        } else {
+            println!("reclaimed {} across {} files", size, result.num_files);
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
 use clap::{arg, Arg, Command};
 use liboxen::core::versions::MinOxenVersion;
 use liboxen::error::OxenError;
+use liboxen::storage::StorageConfig;
 
 use crate::cmd::RunCmd;
 use crate::helpers::{check_remote_version, get_scheme_and_host_or_default};
@@ -40,6 +42,21 @@ impl RunCmd for InitCmd {
                     .help("The oxen version to use, if you want to test older CLI versions (default: latest)")
                     .action(clap::ArgAction::Set),
             )
+            .arg(
+                Arg::new("version-store")
+                    .long("version-store")
+                    .value_name("local|s3")
+                    .help("Pin the backend the repository stores file versions in (default: local)")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("storage-setting")
+                    .long("storage-setting")
+                    .value_name("KEY=VALUE")
+                    .help("A setting for the version store, ie `compression=zstd` or `bucket=my-bucket`. Can be repeated.")
+                    .requires("version-store")
+                    .action(clap::ArgAction::Append),
+            )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
@@ -59,9 +76,39 @@ impl RunCmd for InitCmd {
 
         // Initialize the repository
         let directory = util::fs::canonicalize(PathBuf::from(&path))?;
-        repositories::init::init_with_version(&directory, oxen_version)?;
+        match args.get_one::<String>("version-store") {
+            Some(store_type) => {
+                let storage = parse_storage_config(store_type, args)?;
+                repositories::init::init_with_storage(&directory, oxen_version, &storage)?;
+            }
+            None => {
+                repositories::init::init_with_version(&directory, oxen_version)?;
+            }
+        }
         println!("🐂 repository initialized at: {directory:?}");
         println!("{}", AFTER_INIT_MSG);
         Ok(())
     }
 }
+
+fn parse_storage_config(
+    store_type: &str,
+    args: &clap::ArgMatches,
+) -> Result<StorageConfig, OxenError> {
+    let mut settings = HashMap::new();
+    for setting in args
+        .get_many::<String>("storage-setting")
+        .unwrap_or_default()
+    {
+        let Some((key, value)) = setting.split_once('=') else {
+            return Err(OxenError::basic_str(format!(
+                "Invalid --storage-setting {setting:?}, expected KEY=VALUE"
+            )));
+        };
+        settings.insert(key.trim().to_string(), value.trim().to_string());
+    }
+    Ok(StorageConfig {
+        type_: store_type.to_string(),
+        settings,
+    })
+}
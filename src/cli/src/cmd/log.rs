@@ -6,8 +6,9 @@ use std::fmt::Write;
 use time::format_description;
 
 use liboxen::error::OxenError;
-use liboxen::model::LocalRepository;
+use liboxen::model::{Commit, LocalRepository};
 use liboxen::repositories;
+use liboxen::util;
 
 use crate::cmd::RunCmd;
 pub const NAME: &str = "log";
@@ -42,6 +43,13 @@ impl RunCmd for LogCmd {
                     .help("Number of commits to show")
                     .default_value("20"),
             )
+            .arg(
+                Arg::new("follow")
+                    .long("follow")
+                    .value_name("PATH")
+                    .help("Only show commits that changed the file, following it across renames")
+                    .action(clap::ArgAction::Set),
+            )
     }
 
     async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
@@ -53,6 +61,14 @@ impl RunCmd for LogCmd {
             .expect("Must supply number")
             .parse::<usize>()
             .expect("number must be a valid integer.");
+        if let Some(path) = args.get_one::<String>("follow") {
+            let path = std::env::current_dir()?.join(path);
+            let path = util::fs::path_relative_to_dir(&path, &repo.path)?;
+            let commits = repositories::commits::history_for_path(&repo, &path, true)?;
+            self.print_commits(commits.iter().take(num_commits), false)?;
+            return Ok(());
+        }
+
         let revision = args.get_one::<String>("revision").map(String::from);
         self.log_commits(&repo, revision, num_commits).await?;
 
@@ -71,9 +87,23 @@ impl LogCmd {
             Some(revision) => revision,
             None => repositories::commits::head_commit(repo)?.id,
         };
-        let commits = repositories::commits::list_from(repo, &revision)?;
-        let commits = commits.iter().take(num_commits);
+        // Ranges stop at their base, so they never reach the shallow boundary
+        if revision.contains("..") {
+            let commits = repositories::commits::list_from(repo, &revision)?;
+            return self.print_commits(commits.iter().take(num_commits), false);
+        }
+
+        let (commits, shallow) = repositories::commits::list_from_until_shallow(repo, &revision)?;
+        // Only mark the boundary if we printed all the way down to it
+        let shallow = shallow && commits.len() <= num_commits;
+        self.print_commits(commits.iter().take(num_commits), shallow)
+    }
 
+    fn print_commits<'a>(
+        &self,
+        commits: impl Iterator<Item = &'a Commit>,
+        shallow: bool,
+    ) -> Result<(), OxenError> {
         // Fri, 21 Oct 2022 16:08:39 -0700
         let format = format_description::parse(
             "[weekday], [day] [month repr:long] [year] [hour]:[minute]:[second] [offset_hour sign:mandatory]",
@@ -91,6 +121,14 @@ impl LogCmd {
             )?;
             write_to_pager(&mut output, &format!("    {}\n", commit.message))?;
         }
+        if shallow {
+            write_to_pager(
+                &mut output,
+                &"(shallow — earlier history not fetched)"
+                    .dimmed()
+                    .to_string(),
+            )?;
+        }
 
         match minus::page_all(output) {
             Ok(_) => {}
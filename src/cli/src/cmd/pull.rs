@@ -4,6 +4,8 @@ use liboxen::model::LocalRepository;
 use liboxen::{error::OxenError, opts::FetchOpts};
 
 use liboxen::repositories;
+use liboxen::util::progress_bar::ProgressFormat;
+use std::str::FromStr;
 
 use crate::helpers::{
     check_remote_version, check_remote_version_blocking, check_repo_migration_needed,
@@ -42,6 +44,34 @@ impl RunCmd for PullCmd {
                     .help("This pulls the full commit history, all the data files, and all the commit databases. Useful if you want to have the entire history locally or push to a new remote.")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("prune")
+                    .long("prune")
+                    .help("Remove local files that were deleted in the pulled commits. Files with local modifications are kept.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("blobless")
+                    .long("blobless")
+                    .help("Only pull the commits and merkle trees, plus the files that changed in the working tree. Versions from the rest of the history are downloaded when a commit is checked out.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("jobs")
+                    .long("jobs")
+                    .short('j')
+                    .help("Max number of files to download at once. Defaults to the number of CPUs.")
+                    .value_parser(clap::value_parser!(usize))
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("progress")
+                    .long("progress")
+                    .help("How to report progress. `json` writes newline delimited JSON events to stderr for other programs to render.")
+                    .value_parser(["human", "json"])
+                    .default_value("human")
+                    .action(clap::ArgAction::Set),
+            )
     }
 
     async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
@@ -54,6 +84,13 @@ impl RunCmd for PullCmd {
             .expect("Must supply a branch");
 
         let all = args.get_flag("all");
+        let prune = args.get_flag("prune");
+        let blobless = args.get_flag("blobless");
+        let jobs = args.get_one::<usize>("jobs").copied();
+        let progress = ProgressFormat::from_str(
+            args.get_one::<String>("progress")
+                .expect("Must supply progress"),
+        )?;
 
         // Get the repo
         let repository = LocalRepository::from_current_dir()?;
@@ -70,6 +107,10 @@ impl RunCmd for PullCmd {
         fetch_opts.depth = repository.depth();
         fetch_opts.subtree_paths = repository.subtree_paths();
         fetch_opts.all = all;
+        fetch_opts.prune = prune;
+        fetch_opts.jobs = jobs;
+        fetch_opts.blobless = blobless;
+        fetch_opts.progress = progress;
         repositories::pull_remote_branch(&repository, &fetch_opts).await?;
         Ok(())
     }
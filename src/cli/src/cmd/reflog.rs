@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use clap::{Arg, ArgMatches, Command};
+use colored::Colorize;
+use time::format_description;
+
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::repositories;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "reflog";
+pub struct ReflogCmd;
+
+#[async_trait]
+impl RunCmd for ReflogCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Show the history of updates to a branch, including deleted branches")
+            .arg(
+                Arg::new("ref")
+                    .help("The branch to show the log for. Defaults to the current branch."),
+            )
+    }
+
+    async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        let repository = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repository)?;
+
+        let name = match args.get_one::<String>("ref") {
+            Some(name) => name.to_owned(),
+            None => match repositories::branches::current_branch(&repository)? {
+                Some(branch) => branch.name,
+                None => return Err(OxenError::must_be_on_valid_branch()),
+            },
+        };
+
+        let entries = repositories::branches::reflog(&repository, &name)?;
+        if entries.is_empty() {
+            println!("No reflog entries for '{name}'");
+            return Ok(());
+        }
+
+        let format =
+            format_description::parse("[year]-[month]-[day] [hour]:[minute]:[second]").unwrap();
+
+        // Newest first, so name@{0} is always the latest update
+        for (i, entry) in entries.iter().rev().enumerate() {
+            let old_id = entry.old_id.as_deref().unwrap_or("-");
+            let new_id = entry.new_id.as_deref().unwrap_or("-");
+            println!(
+                "{}: {} {} -> {} ({})",
+                format!("{name}@{{{i}}}").yellow(),
+                entry.operation,
+                old_id,
+                new_id,
+                entry.timestamp.format(&format).unwrap()
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::error::OxenError;
+use liboxen::model::{Commit, LocalRepository};
+use liboxen::repositories;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "squash";
+pub struct SquashCmd;
+
+#[async_trait]
+impl RunCmd for SquashCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Squash a range of commits at the head of the current branch into one commit.")
+            .arg(
+                Arg::new("range")
+                    .help("The commits to squash as <from>..<to>, where <to> is the head of the current branch.")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("message")
+                    .long("message")
+                    .short('m')
+                    .help("The message for the squashed commit.")
+                    .required(true)
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let range = args.get_one::<String>("range").expect("Must supply range");
+        let message = args
+            .get_one::<String>("message")
+            .expect("Must supply message");
+
+        let Some((from, to)) = range.split_once("..") else {
+            return Err(OxenError::basic_str(format!(
+                "Invalid range {range:?}, expected <from>..<to>"
+            )));
+        };
+        let from = get_commit(&repo, from)?;
+        let to = get_commit(&repo, to)?;
+
+        let commit = repositories::commits::squash(&repo, &from, &to, message)?;
+        println!("Squashed {}..{} into {}", from.id, to.id, commit);
+        Ok(())
+    }
+}
+
+fn get_commit(repo: &LocalRepository, revision: &str) -> Result<Commit, OxenError> {
+    repositories::revisions::get(repo, revision)?
+        .ok_or_else(|| OxenError::revision_not_found(revision.into()))
+}
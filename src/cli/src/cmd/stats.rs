@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::repositories;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "stats";
+pub struct StatsCmd;
+
+#[async_trait]
+impl RunCmd for StatsCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Count the files and bytes under a directory at a commit, by data type.")
+            .arg(
+                Arg::new("path")
+                    .help("The directory or file to count. Defaults to the root of the repository.")
+                    .default_value("")
+                    .action(clap::ArgAction::Set),
+            )
+            .arg(
+                Arg::new("revision")
+                    .long("revision")
+                    .short('r')
+                    .help("The commit id or branch to count at. Defaults to HEAD.")
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let path = args.get_one::<String>("path").expect("Must supply path");
+        let commit = match args.get_one::<String>("revision") {
+            Some(revision) => repositories::revisions::get(&repo, revision)?
+                .ok_or_else(|| OxenError::revision_not_found(revision.as_str().into()))?,
+            None => repositories::commits::head_commit(&repo)?,
+        };
+
+        let stats = repositories::commits::dir_stats(&repo, &commit, path)?;
+        println!(
+            "{} files, {}",
+            stats.total_files,
+            bytesize::ByteSize::b(stats.total_bytes)
+        );
+
+        let mut data_type_counts: Vec<_> = stats.data_type_counts.into_iter().collect();
+        data_type_counts.sort_by(|a, b| b.1.cmp(&a.1));
+        for (data_type, count) in data_type_counts {
+            println!("  {} {}\t{}", data_type.to_emoji(), data_type, count);
+        }
+        Ok(())
+    }
+}
@@ -54,6 +54,32 @@ impl RunCmd for StatusCmd {
                     .help("If present, does not truncate the output of status at all.")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("staged")
+                    .long("staged")
+                    .help("Only list the staged files, without scanning the working directory for untracked or modified files.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("fast")
+                    .long("fast")
+                    .conflicts_with("staged")
+                    .help("Skip directories whose mtime and file sizes and mtimes have not changed since status last found them clean. Much faster on large working trees.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("check")
+                    .long("check")
+                    .help("Check that the staged entries are consistent, every staged file has its parent dirs staged and no staged dir is empty.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("fix")
+                    .long("fix")
+                    .requires("check")
+                    .help("Repair the inconsistencies found by --check, staging the missing parent dirs and removing the empty ones.")
+                    .action(clap::ArgAction::SetTrue),
+            )
             .arg(
                 Arg::new("paths")
                     .num_args(0..)
@@ -78,6 +104,10 @@ impl RunCmd for StatusCmd {
         let repository = LocalRepository::from_current_dir()?;
         check_repo_migration_needed(&repository)?;
 
+        if args.get_flag("check") {
+            return check_staged(&repository, args.get_flag("fix"));
+        }
+
         let paths = args
             .get_many::<String>("paths")
             .map(|vals| vals.map(|v| repository.path.join(v)).collect())
@@ -90,10 +120,15 @@ impl RunCmd for StatusCmd {
             print_all,
             is_remote,
             ignore: parse_ignore_files(args.get_one::<String>("ignore")),
+            use_mtime_index: args.get_flag("fast"),
         };
         log::debug!("status opts: {:?}", opts);
 
-        let repo_status = repositories::status::status_from_opts(&repository, &opts)?;
+        let repo_status = if args.get_flag("staged") {
+            repositories::status::status_staged(&repository, &opts)?
+        } else {
+            repositories::status::status_from_opts(&repository, &opts)?
+        };
 
         if let Some(current_branch) = repositories::branches::current_branch(&repository)? {
             println!(
@@ -113,6 +148,31 @@ impl RunCmd for StatusCmd {
     }
 }
 
+fn check_staged(repository: &LocalRepository, fix: bool) -> Result<(), OxenError> {
+    let inconsistencies = if fix {
+        repositories::status::fix_staged_consistency(repository)?
+    } else {
+        repositories::status::check_staged_consistency(repository)?
+    };
+
+    if inconsistencies.is_empty() {
+        println!("Staged entries are consistent");
+        return Ok(());
+    }
+    for inconsistency in &inconsistencies {
+        println!("{inconsistency}");
+    }
+    if fix {
+        println!("Fixed {} inconsistencies", inconsistencies.len());
+        Ok(())
+    } else {
+        Err(OxenError::basic_str(format!(
+            "Found {} inconsistencies, run `oxen status --check --fix` to repair them",
+            inconsistencies.len()
+        )))
+    }
+}
+
 fn parse_ignore_files(paths: Option<&String>) -> Option<HashSet<PathBuf>> {
     let paths_str = paths?;
 
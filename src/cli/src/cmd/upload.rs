@@ -2,16 +2,19 @@ use async_trait::async_trait;
 use clap::{Arg, ArgMatches, Command};
 
 use liboxen::api;
+use liboxen::config::UserConfig;
 use liboxen::constants::DEFAULT_HOST;
 use liboxen::constants::DEFAULT_REMOTE_NAME;
 use liboxen::constants::DEFAULT_SCHEME;
 use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
 use liboxen::opts::UploadOpts;
 use liboxen::repositories;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::helpers::check_remote_version_blocking;
+use crate::helpers::{check_remote_version_blocking, commit_message_from_args};
 
 use crate::cmd::RunCmd;
 pub const NAME: &str = "upload";
@@ -24,7 +27,7 @@ impl RunCmd for UploadCmd {
     }
     fn args(&self) -> Command {
         Command::new(NAME)
-        .about("Upload a specific file to the remote repository.")
+        .about("Upload files, or directories with --preserve-structure, to the remote repository.")
         .arg(
             Arg::new("paths")
                 .required(true)
@@ -34,7 +37,7 @@ impl RunCmd for UploadCmd {
             Arg::new("dst")
                 .long("destination")
                 .short('d')
-                .help("The destination directory to upload the data to. Defaults to the root './' of the repository.")
+                .help("The destination directory to upload the data to. Defaults to the upload_routes entry for the file extension in ~/.oxen/user_config.toml, or the root './' of the repository.")
                 .action(clap::ArgAction::Set),
         )
         .arg(
@@ -49,9 +52,50 @@ impl RunCmd for UploadCmd {
                 .help("The message for the commit. Should be descriptive about what changed.")
                 .long("message")
                 .short('m')
-                .required(true)
+                .required_unless_present_any(["workspace", "template"])
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("template")
+                .long("template")
+                .short('t')
+                .value_name("FILE")
+                .help("Fill the message into this template file, replacing `{message}` with the -m message. Without -m the template is the whole message.")
+                .action(clap::ArgAction::Set),
+        )
+        .arg(
+            Arg::new("workspace")
+                .long("workspace")
+                .value_name("ID")
+                .help("Upload into this workspace without committing, creating it if it does not exist. Run several uploads into the same workspace, then commit them together with --commit.")
                 .action(clap::ArgAction::Set),
         )
+        .arg(
+            Arg::new("commit")
+                .long("commit")
+                .requires("workspace")
+                .help("Commit the workspace given by --workspace with --message, after uploading any paths passed along with it.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("delete")
+                .long("delete")
+                .value_name("REMOTE_PATH")
+                .help("Remove a file on the remote in the same commit. Can be repeated.")
+                .action(clap::ArgAction::Append),
+        )
+        .arg(
+            Arg::new("preserve-structure")
+                .long("preserve-structure")
+                .help("Upload directories recursively, mirroring their local layout under the destination, ie `mydir/` is uploaded to `<destination>/mydir/`. Files matched by .oxenignore are skipped.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("skip-unchanged")
+                .long("skip-unchanged")
+                .help("Hash the files first and only upload the ones that differ from the file at their destination on the branch. Speeds up re-uploading a mostly unchanged dataset.")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("host")
                 .long("host")
@@ -73,20 +117,34 @@ impl RunCmd for UploadCmd {
     }
 
     async fn run(&self, args: &ArgMatches) -> Result<(), OxenError> {
+        // Per-extension routes only apply when there is no explicit destination
+        let dst = args.get_one::<String>("dst").map(PathBuf::from);
+        let routes = if dst.is_none() {
+            UserConfig::get()
+                .map(|config| config.upload_routes())
+                .unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+
+        let workspace_id = args.get_one::<String>("workspace").map(String::from);
+        // Without a workspace the upload goes to a temporary one, which has to be committed
+        let commit = workspace_id.is_none() || args.get_flag("commit");
+        let message = commit_message_from_args(args)?;
+        if commit && message.is_none() {
+            return Err(OxenError::basic_str(
+                "Must supply a commit message with -m to commit the upload",
+            ));
+        }
+
         let opts = UploadOpts {
             paths: args
                 .get_many::<String>("paths")
                 .expect("Must supply paths")
                 .map(PathBuf::from)
                 .collect(),
-            dst: args
-                .get_one::<String>("dst")
-                .map(PathBuf::from)
-                .unwrap_or(PathBuf::from(".")),
-            message: args
-                .get_one::<String>("message")
-                .map(String::from)
-                .expect("Must supply a commit message"),
+            dst: dst.unwrap_or(PathBuf::from(".")),
+            message: message.unwrap_or_default(),
             branch: args.get_one::<String>("branch").map(String::from),
             remote: args
                 .get_one::<String>("remote")
@@ -100,6 +158,15 @@ impl RunCmd for UploadCmd {
                 .get_one::<String>("scheme")
                 .map(String::from)
                 .unwrap_or(DEFAULT_SCHEME.to_string()),
+            delete: args
+                .get_many::<String>("delete")
+                .map(|paths| paths.map(PathBuf::from).collect())
+                .unwrap_or_default(),
+            routes,
+            preserve_structure: args.get_flag("preserve-structure"),
+            workspace_id,
+            commit,
+            skip_unchanged: args.get_flag("skip-unchanged"),
         };
 
         // `oxen upload $namespace/$repo_name $path`
@@ -122,6 +189,19 @@ impl RunCmd for UploadCmd {
         )
         .await?
         {
+            // Check the message against the local repo's conventions before uploading anything,
+            // but only if the local repo is a clone of the repo being uploaded to
+            if opts.commit {
+                if let Ok(repo) = LocalRepository::from_current_dir() {
+                    let is_upload_target = repo.remotes().iter().any(|remote| {
+                        remote.url.trim_end_matches('/') == remote_repo.url().trim_end_matches('/')
+                    });
+                    if is_upload_target {
+                        repo.validate_commit_message(&opts.message)?;
+                    }
+                }
+            }
+
             // Remove the repo name from the list of paths
             let remote_paths = paths[1..].to_vec();
             let opts = UploadOpts {
@@ -131,7 +211,7 @@ impl RunCmd for UploadCmd {
 
             repositories::workspaces::upload(&remote_repo, &opts).await?;
         } else {
-            eprintln!("Repository does not exist {}", name);
+            return Err(OxenError::remote_repo_not_found(name));
         }
 
         Ok(())
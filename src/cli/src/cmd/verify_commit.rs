@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use clap::{Arg, Command};
+
+use liboxen::error::OxenError;
+use liboxen::model::LocalRepository;
+use liboxen::repositories;
+
+use crate::cmd::RunCmd;
+use crate::helpers::check_repo_migration_needed;
+
+pub const NAME: &str = "verify-commit";
+pub struct VerifyCommitCmd;
+
+#[async_trait]
+impl RunCmd for VerifyCommitCmd {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn args(&self) -> Command {
+        Command::new(NAME)
+            .about("Check that a commit's merkle tree and version files are intact.")
+            .arg(
+                Arg::new("commit")
+                    .help("The commit id or branch to verify. Defaults to HEAD.")
+                    .action(clap::ArgAction::Set),
+            )
+    }
+
+    async fn run(&self, args: &clap::ArgMatches) -> Result<(), OxenError> {
+        let repo = LocalRepository::from_current_dir()?;
+        check_repo_migration_needed(&repo)?;
+
+        let commit = match args.get_one::<String>("commit") {
+            Some(revision) => repositories::revisions::get(&repo, revision)?
+                .ok_or_else(|| OxenError::revision_not_found(revision.as_str().into()))?,
+            None => repositories::commits::head_commit(&repo)?,
+        };
+
+        match repositories::commits::verify(&repo, &commit.id)? {
+            None => {
+                println!("Commit {} OK", commit.id);
+                Ok(())
+            }
+            Some(problem) => Err(OxenError::basic_str(format!(
+                "Commit {} is corrupted: {problem}",
+                commit.id
+            ))),
+        }
+    }
+}
@@ -91,6 +91,7 @@ impl RunCmd for WorkspaceStatusCmd {
             print_all,
             is_remote,
             ignore: None,
+            use_mtime_index: false,
         };
 
         let repo_dir = util::fs::get_repo_root_from_current_dir()
@@ -120,3 +120,16 @@ pub fn check_repo_migration_needed(repo: &LocalRepository) -> Result<(), OxenErr
         "Error: Migration required".to_string().into(),
     ))
 }
+
+/// The commit message from `-m`, filled into the `--template` file if one was given.
+/// `{message}` in the template is replaced with the `-m` message, so a template can add
+/// a ticket reference or trailer around it, or be used as the whole message on its own.
+pub fn commit_message_from_args(args: &clap::ArgMatches) -> Result<Option<String>, OxenError> {
+    let message = args.get_one::<String>("message");
+    let Some(template) = args.get_one::<String>("template") else {
+        return Ok(message.cloned());
+    };
+    let template = liboxen::util::fs::read_from_path(template)?;
+    let message = template.replace("{message}", message.map(String::as_str).unwrap_or_default());
+    Ok(Some(message.trim_end().to_string()))
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::process::ExitCode;
 
-use clap::Command;
+use clap::{Arg, ArgAction, Command};
 use liboxen::util;
 // use env_logger::Env;
 
@@ -26,8 +26,10 @@ async fn main() -> ExitCode {
 
     let cmds: Vec<Box<dyn cmd::RunCmd>> = vec![
         Box::new(cmd::AddCmd),
+        Box::new(cmd::BackfillMetadataCmd),
         Box::new(cmd::BranchCmd),
         Box::new(cmd::CheckoutCmd),
+        Box::new(cmd::CleanCmd),
         Box::new(cmd::CloneCmd),
         Box::new(cmd::CommitCmd),
         Box::new(cmd::ConfigCmd),
@@ -39,6 +41,7 @@ async fn main() -> ExitCode {
         Box::new(cmd::DownloadCmd),
         Box::new(cmd::FetchCmd),
         Box::new(cmd::EmbeddingsCmd),
+        Box::new(cmd::GcCmd),
         Box::new(cmd::InfoCmd),
         Box::new(cmd::InitCmd),
         Box::new(cmd::LoadCmd),
@@ -51,14 +54,18 @@ async fn main() -> ExitCode {
         // Box::new(cmd::PackCmd),
         Box::new(cmd::PullCmd),
         Box::new(cmd::PushCmd),
+        Box::new(cmd::ReflogCmd),
         Box::new(cmd::RestoreCmd),
         Box::new(cmd::RemoteCmd),
         Box::new(cmd::RmCmd),
         Box::new(cmd::SaveCmd),
         Box::new(cmd::SchemasCmd),
+        Box::new(cmd::SquashCmd),
+        Box::new(cmd::StatsCmd),
         Box::new(cmd::StatusCmd),
         Box::new(cmd::TreeCmd),
         Box::new(cmd::UploadCmd),
+        Box::new(cmd::VerifyCommitCmd),
         // Box::new(cmd::UnpackCmd),
         Box::new(cmd::WorkspaceCmd),
     ];
@@ -69,7 +76,14 @@ async fn main() -> ExitCode {
         .long_about(LONG_ABOUT)
         .subcommand_required(true)
         .arg_required_else_help(true)
-        .allow_external_subcommands(true);
+        .allow_external_subcommands(true)
+        .arg(
+            Arg::new("json-errors")
+                .long("json-errors")
+                .global(true)
+                .help("Print errors as JSON with a machine readable `kind` to stderr. Can also be set with OXEN_JSON_ERRORS=1.")
+                .action(ArgAction::SetTrue),
+        );
 
     // Add all the commands to the command line
     let mut runners: HashMap<String, Box<dyn cmd::RunCmd>> = HashMap::new();
@@ -88,7 +102,15 @@ async fn main() -> ExitCode {
                 match runner.run(args).await {
                     Ok(_) => {}
                     Err(err) => {
-                        eprintln!("{err}");
+                        if json_errors(args) {
+                            let json = serde_json::json!({
+                                "error": err.to_string(),
+                                "kind": err.kind(),
+                            });
+                            eprintln!("{json}");
+                        } else {
+                            eprintln!("{err}");
+                        }
                         return ExitCode::FAILURE;
                     }
                 }
@@ -102,3 +124,10 @@ async fn main() -> ExitCode {
 
     ExitCode::SUCCESS
 }
+
+fn json_errors(args: &clap::ArgMatches) -> bool {
+    if matches!(args.try_get_one::<bool>("json-errors"), Ok(Some(true))) {
+        return true;
+    }
+    std::env::var("OXEN_JSON_ERRORS").is_ok_and(|val| val == "1" || val == "true")
+}
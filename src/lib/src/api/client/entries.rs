@@ -1,8 +1,9 @@
 use crate::api::client;
 use crate::config::UserConfig;
 use crate::constants::{AVG_CHUNK_SIZE, DEFAULT_BRANCH_NAME};
+use crate::core::oxenignore;
 use crate::error::OxenError;
-use crate::model::{EntryDataType, MetadataEntry, NewCommitBody, RemoteRepository};
+use crate::model::{EntryDataType, MerkleHash, MetadataEntry, NewCommitBody, RemoteRepository};
 use crate::opts::UploadOpts;
 use crate::repositories;
 use crate::view::entries::{EMetadataEntry, PaginatedMetadataEntriesResponse};
@@ -14,10 +15,12 @@ use async_tar::Archive;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use futures_util::TryStreamExt;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::{self};
 use std::io::prelude::*;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 /// Returns the metadata given a file path
 pub async fn get_entry(
@@ -61,20 +64,33 @@ pub async fn upload_entries(
     remote_repo: &RemoteRepository,
     opts: &UploadOpts,
 ) -> Result<(), OxenError> {
-    if opts.paths.is_empty() {
+    // Committing a workspace filled by earlier uploads does not need any new files
+    let commit_only = opts.workspace_id.is_some() && opts.commit;
+    if opts.paths.is_empty() && opts.delete.is_empty() && !commit_only {
         return Err(OxenError::basic_str("No files to upload"));
     }
 
-    // Filter down to only files
+    // Filter down to only files, directories are only walked with `preserve_structure`
     let mut file_paths: Vec<PathBuf> = Vec::new();
+    let mut dir_files: Vec<(PathBuf, PathBuf)> = Vec::new();
     for path in &opts.paths {
         if path.is_dir() {
-            eprintln!("Directory upload not yet supported: {:?}", path);
+            if opts.preserve_structure {
+                dir_files.extend(files_in_dir_with_destination(path, &opts.dst)?);
+            } else {
+                eprintln!(
+                    "Skipping directory {:?}, pass --preserve-structure to upload directories",
+                    path
+                );
+            }
             continue;
         }
 
         file_paths.push(path.to_owned());
     }
+    if file_paths.is_empty() && dir_files.is_empty() && opts.delete.is_empty() && !commit_only {
+        return Err(OxenError::basic_str("No files to upload"));
+    }
 
     let branch_name = if let Some(branch) = &opts.branch {
         api::client::branches::create_from_branch(remote_repo, branch, DEFAULT_BRANCH_NAME).await?;
@@ -83,21 +99,78 @@ pub async fn upload_entries(
         DEFAULT_BRANCH_NAME.to_string()
     };
 
+    // Make sure every path we are deleting exists before touching the remote
+    let mut missing: Vec<String> = Vec::new();
+    for path in &opts.delete {
+        if get_entry(remote_repo, path, &branch_name).await?.is_none() {
+            missing.push(path.to_string_lossy().to_string());
+        }
+    }
+    if !missing.is_empty() {
+        return Err(OxenError::basic_str(format!(
+            "Cannot delete paths that do not exist on branch '{}': {}",
+            branch_name,
+            missing.join(", ")
+        )));
+    }
+
     log::debug!("Uploading to {}", branch_name);
 
-    // Create uniq workspace id
-    let workspace_id = uuid::Uuid::new_v4().to_string();
-    let workspace =
-        api::client::workspaces::create(remote_repo, &branch_name, &workspace_id).await?;
-    assert_eq!(workspace.id, workspace_id);
-
-    api::client::workspaces::files::add_many(
-        remote_repo,
-        &workspace_id,
-        &opts.dst.to_string_lossy(),
-        file_paths,
-    )
-    .await?;
+    let mut groups = group_by_destination(file_paths, opts);
+    for (dst, path) in dir_files {
+        groups.entry(dst).or_default().push(path);
+    }
+
+    if opts.skip_unchanged {
+        let num_skipped = skip_unchanged_files(remote_repo, &branch_name, &mut groups).await?;
+        println!("Skipped {num_skipped} unchanged files");
+        if groups.is_empty() && opts.delete.is_empty() && !commit_only {
+            println!("Nothing to upload, branch '{branch_name}' is up to date");
+            return Ok(());
+        }
+    }
+
+    let workspace_id = match &opts.workspace_id {
+        Some(workspace_id) => {
+            // Keep adding to the workspace of an earlier upload
+            if api::client::workspaces::get(remote_repo, workspace_id)
+                .await?
+                .is_none()
+            {
+                api::client::workspaces::create(remote_repo, &branch_name, workspace_id).await?;
+            }
+            workspace_id.to_owned()
+        }
+        None => {
+            // Create uniq workspace id
+            let workspace_id = uuid::Uuid::new_v4().to_string();
+            let workspace =
+                api::client::workspaces::create(remote_repo, &branch_name, &workspace_id).await?;
+            assert_eq!(workspace.id, workspace_id);
+            workspace_id
+        }
+    };
+
+    for (dst, file_paths) in groups {
+        api::client::workspaces::files::add_many(
+            remote_repo,
+            &workspace_id,
+            &dst.to_string_lossy(),
+            file_paths,
+        )
+        .await?;
+    }
+
+    for path in &opts.delete {
+        api::client::workspaces::files::stage_removal(remote_repo, &workspace_id, path).await?;
+    }
+
+    if !opts.commit {
+        println!(
+            "Uploaded to workspace {workspace_id}. Commit it with `oxen upload <repo> --workspace {workspace_id} --commit -m <message>`"
+        );
+        return Ok(());
+    }
 
     log::debug!("Committing on {}", branch_name);
 
@@ -116,6 +189,122 @@ pub async fn upload_entries(
     Ok(())
 }
 
+/// Drop the files whose contents match the file with the same name in their destination directory on
+/// `revision`, so re-uploading a mostly unchanged dataset only sends what changed. Returns how many were dropped.
+async fn skip_unchanged_files(
+    remote_repo: &RemoteRepository,
+    revision: &str,
+    groups: &mut BTreeMap<PathBuf, Vec<PathBuf>>,
+) -> Result<usize, OxenError> {
+    let mut num_skipped = 0;
+    for (dst, file_paths) in groups.iter_mut() {
+        let remote_hashes = remote_file_hashes(remote_repo, revision, dst).await?;
+        if remote_hashes.is_empty() {
+            continue;
+        }
+        let mut changed = Vec::new();
+        for path in file_paths.drain(..) {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let unchanged = match remote_hashes.get(file_name.as_ref()) {
+                Some(remote_hash) => {
+                    let hash = MerkleHash::new(util::hasher::u128_hash_file_contents(&path)?);
+                    hash.to_string() == *remote_hash
+                }
+                None => false,
+            };
+            if unchanged {
+                log::debug!("skipping unchanged upload {:?}", path);
+                num_skipped += 1;
+            } else {
+                changed.push(path);
+            }
+        }
+        *file_paths = changed;
+    }
+    groups.retain(|_, file_paths| !file_paths.is_empty());
+    Ok(num_skipped)
+}
+
+/// The content hash of each file directly in `dir` on `revision`, by file name.
+/// Empty if the directory does not exist on the remote yet.
+async fn remote_file_hashes(
+    remote_repo: &RemoteRepository,
+    revision: &str,
+    dir: &Path,
+) -> Result<HashMap<String, String>, OxenError> {
+    let mut hashes = HashMap::new();
+    let mut page = 1;
+    loop {
+        let entries = match api::client::dir::list(remote_repo, revision, dir, page, 1000).await {
+            Ok(entries) => entries,
+            Err(err) if page == 1 => {
+                log::debug!(
+                    "no remote dir {:?} to compare uploads against: {}",
+                    dir,
+                    err
+                );
+                return Ok(hashes);
+            }
+            Err(err) => return Err(err),
+        };
+        for entry in &entries.entries {
+            if !entry.is_dir() {
+                hashes.insert(entry.filename().to_string(), entry.hash());
+            }
+        }
+        if page >= entries.total_pages {
+            break;
+        }
+        page += 1;
+    }
+    Ok(hashes)
+}
+
+/// Route each file to the destination for its extension, or `opts.dst` if there is no route
+fn group_by_destination(
+    file_paths: Vec<PathBuf>,
+    opts: &UploadOpts,
+) -> BTreeMap<PathBuf, Vec<PathBuf>> {
+    let mut groups: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    for path in file_paths {
+        let dst = path
+            .extension()
+            .and_then(|ext| opts.routes.get(&ext.to_string_lossy().to_lowercase()))
+            .unwrap_or(&opts.dst);
+        groups.entry(dst.to_owned()).or_default().push(path);
+    }
+    groups
+}
+
+/// Walk `dir` and pair each file with the remote directory it belongs in, `dst/<dir name>/<relative parent>`.
+/// Files matched by the .oxenignore of the enclosing repository (or of `dir` itself) are skipped.
+fn files_in_dir_with_destination(
+    dir: &Path,
+    dst: &Path,
+) -> Result<Vec<(PathBuf, PathBuf)>, OxenError> {
+    let dir = util::fs::canonicalize(dir)?;
+    let dir_name = dir.file_name().map(PathBuf::from).unwrap_or_default();
+    let ignore_root = util::fs::get_repo_root(&dir).unwrap_or(dir.clone());
+    let gitignore = oxenignore::create_from_dir(&ignore_root);
+
+    let mut files = Vec::new();
+    let walker = WalkDir::new(&dir).into_iter().filter_entry(|e| {
+        e.file_name() != constants::OXEN_HIDDEN_DIR
+            && !oxenignore::is_ignored(e.path(), &gitignore, e.file_type().is_dir())
+    });
+    for entry in walker {
+        let entry = entry.map_err(|e| OxenError::basic_str(format!("{e}")))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let relative = util::fs::path_relative_to_dir(path, &dir)?;
+        let parent = relative.parent().unwrap_or(Path::new(""));
+        files.push((dst.join(&dir_name).join(parent), path.to_path_buf()));
+    }
+    Ok(files)
+}
+
 /// Pings the remote server first to see if the entry exists
 /// and get the size before downloading
 pub async fn download_entry(
@@ -641,10 +830,83 @@ mod tests {
     use crate::constants::DEFAULT_BRANCH_NAME;
     use crate::error::OxenError;
     use crate::model::EntryDataType;
+    use crate::opts::UploadOpts;
     use crate::{api, util};
     use crate::{repositories, test};
 
-    use std::path::Path;
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_group_by_destination_routes_by_extension() {
+        let opts = UploadOpts {
+            paths: vec![],
+            dst: PathBuf::from("."),
+            branch: None,
+            message: "routes".to_string(),
+            host: "localhost".to_string(),
+            scheme: "http".to_string(),
+            remote: "origin".to_string(),
+            delete: vec![],
+            routes: HashMap::from([
+                ("png".to_string(), PathBuf::from("images")),
+                ("json".to_string(), PathBuf::from("labels")),
+            ]),
+            preserve_structure: false,
+            workspace_id: None,
+            commit: true,
+            skip_unchanged: false,
+        };
+        let files = vec![
+            PathBuf::from("cat.png"),
+            PathBuf::from("dog.PNG"),
+            PathBuf::from("cat.json"),
+            PathBuf::from("README.md"),
+        ];
+
+        let groups = super::group_by_destination(files, &opts);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            groups[&PathBuf::from("images")],
+            vec![PathBuf::from("cat.png"), PathBuf::from("dog.PNG")]
+        );
+        assert_eq!(
+            groups[&PathBuf::from("labels")],
+            vec![PathBuf::from("cat.json")]
+        );
+        assert_eq!(
+            groups[&PathBuf::from(".")],
+            vec![PathBuf::from("README.md")]
+        );
+    }
+
+    #[test]
+    fn test_files_in_dir_with_destination_mirrors_layout() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let upload_dir = dir.join("mydir");
+            util::fs::write_to_path(upload_dir.join("a.txt"), "a")?;
+            util::fs::write_to_path(upload_dir.join("nested").join("b.txt"), "b")?;
+            util::fs::write_to_path(upload_dir.join("skip.log"), "skip")?;
+            util::fs::write_to_path(upload_dir.join(".oxenignore"), "*.log\n")?;
+
+            let mut files = super::files_in_dir_with_destination(&upload_dir, Path::new("data"))?;
+            files.sort();
+            let files: Vec<(PathBuf, String)> = files
+                .into_iter()
+                .map(|(dst, path)| (dst, path.file_name().unwrap().to_string_lossy().to_string()))
+                .collect();
+            assert_eq!(
+                files,
+                vec![
+                    (PathBuf::from("data/mydir"), ".oxenignore".to_string()),
+                    (PathBuf::from("data/mydir"), "a.txt".to_string()),
+                    (PathBuf::from("data/mydir/nested"), "b.txt".to_string()),
+                ]
+            );
+
+            Ok(())
+        })
+    }
 
     #[tokio::test]
     async fn test_list_tabular_entries() -> Result<(), OxenError> {
@@ -463,6 +463,7 @@ mod tests {
     use crate::opts::FetchOpts;
     use crate::repositories;
     use crate::test;
+    use crate::util::progress_bar::ProgressFormat;
     use std::fs;
 
     use std::collections::HashSet;
@@ -597,6 +598,10 @@ mod tests {
                 remote: remote_repo_clone.url().to_string(),
                 branch: "main".to_string(),
                 should_update_branch_head: true,
+                prune: false,
+                jobs: None,
+                blobless: false,
+                progress: ProgressFormat::Human,
             };
             api::client::tree::download_trees_from(
                 &download_local_repo_2,
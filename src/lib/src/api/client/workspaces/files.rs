@@ -336,6 +336,23 @@ pub async fn rm(
     Ok(())
 }
 
+/// Stage the removal of a committed file in the workspace, so it is deleted on commit
+pub async fn stage_removal(
+    remote_repo: &RemoteRepository,
+    workspace_id: &str,
+    path: impl AsRef<Path>,
+) -> Result<(), OxenError> {
+    let file_name = path.as_ref().to_string_lossy();
+    let uri = format!("/workspaces/{workspace_id}/rm/{file_name}");
+    let url = api::endpoint::url_from_repo(remote_repo, &uri)?;
+    log::debug!("stage_removal {}", url);
+    let client = client::new_for_url(&url)?;
+    let response = client.post(&url).send().await?;
+    let body = client::parse_json_body(&url, response).await?;
+    log::debug!("stage_removal got body: {}", body);
+    Ok(())
+}
+
 pub async fn download(
     remote_repo: &RemoteRepository,
     workspace_id: &str,
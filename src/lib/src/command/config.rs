@@ -25,3 +25,30 @@ pub fn delete_remote(repo: &mut LocalRepository, name: &str) -> Result<(), OxenE
     repo.save()?;
     Ok(())
 }
+
+/// # Enable or disable hooks for a repository
+/// When enabled, the executables in .oxen/hooks are run before add and commit
+pub fn set_hooks_enabled(repo: &mut LocalRepository, enabled: bool) -> Result<(), OxenError> {
+    repo.set_hooks_enabled(enabled);
+    repo.save()?;
+    Ok(())
+}
+
+/// # Enable or disable strict mtime checks for a repository
+/// When enabled, add and status hash files even if their mtime matches the last commit
+pub fn set_strict_mtime(repo: &mut LocalRepository, strict: bool) -> Result<(), OxenError> {
+    repo.set_strict_mtime(strict);
+    repo.save()?;
+    Ok(())
+}
+
+/// # Enable or disable line ending normalization for a repository
+/// When enabled, text files that differ only in CRLF vs LF line endings hash the same
+pub fn set_normalize_line_endings(
+    repo: &mut LocalRepository,
+    normalize: bool,
+) -> Result<(), OxenError> {
+    repo.set_normalize_line_endings(normalize);
+    repo.save()?;
+    Ok(())
+}
@@ -16,4 +16,5 @@ pub use crate::config::embedding_config::EMBEDDING_CONFIG_FILENAME;
 pub use crate::config::user_config::UserConfig;
 pub use crate::config::user_config::USER_CONFIG_FILENAME;
 
+pub use crate::config::repository_config::CommitConfig;
 pub use crate::config::repository_config::RepositoryConfig;
@@ -6,6 +6,7 @@ use crate::error::OxenError;
 use crate::model::{LocalRepository, Remote};
 use crate::storage::StorageConfig;
 use crate::util;
+use crate::view::fork::ForkSource;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RepositoryConfig {
@@ -21,6 +22,22 @@ pub struct RepositoryConfig {
     pub vnode_size: Option<u64>,
     /// Storage configuration
     pub storage: Option<StorageConfig>,
+    /// Run the scripts in .oxen/hooks before add and commit
+    pub enable_hooks: Option<bool>,
+    /// If the repo is a fork, the repos it was forked from, nearest first
+    pub forked_from: Option<Vec<ForkSource>>,
+    /// Hash files even when their mtime matches the committed node, for tools that reset mtimes
+    pub strict_mtime: Option<bool>,
+    /// Convert CRLF to LF in text files before hashing, like git's core.autocrlf
+    pub normalize_line_endings: Option<bool>,
+    /// Conventions for commit messages, the `[commit]` table
+    pub commit: Option<CommitConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CommitConfig {
+    /// Regex that commit messages must match, ie `^[A-Z]+-[0-9]+: ` to require a ticket reference
+    pub message_pattern: Option<String>,
 }
 
 impl Default for RepositoryConfig {
@@ -42,6 +59,11 @@ impl RepositoryConfig {
             min_version: None,
             vnode_size: None,
             storage: None,
+            enable_hooks: None,
+            forked_from: None,
+            strict_mtime: None,
+            normalize_line_endings: None,
+            commit: None,
         }
     }
 
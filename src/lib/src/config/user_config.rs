@@ -3,6 +3,7 @@ use crate::error::OxenError;
 use crate::model::User;
 use crate::util;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -12,6 +13,9 @@ pub const USER_CONFIG_FILENAME: &str = "user_config.toml";
 pub struct UserConfig {
     pub name: String,
     pub email: String,
+    /// Default destination directory per file extension for `oxen upload`, ie `png = "images"`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub upload_routes: HashMap<String, String>,
 }
 
 impl UserConfig {
@@ -24,6 +28,7 @@ impl UserConfig {
         UserConfig {
             name: user.name.to_owned(),
             email: user.email.to_owned(),
+            upload_routes: HashMap::new(),
         }
     }
 
@@ -38,6 +43,7 @@ impl UserConfig {
         UserConfig {
             name: String::from(""),
             email: String::from(""),
+            upload_routes: HashMap::new(),
         }
     }
 
@@ -78,6 +84,19 @@ impl UserConfig {
         }
     }
 
+    /// The upload routes keyed by lowercase extension without the leading dot
+    pub fn upload_routes(&self) -> HashMap<String, PathBuf> {
+        self.upload_routes
+            .iter()
+            .map(|(ext, dst)| {
+                (
+                    ext.trim_start_matches('.').to_lowercase(),
+                    PathBuf::from(dst),
+                )
+            })
+            .collect()
+    }
+
     pub fn save_default(&self) -> Result<(), OxenError> {
         let config_dir = util::fs::oxen_config_dir()?;
         let config_file = config_dir.join(Path::new(USER_CONFIG_FILENAME));
@@ -101,7 +120,7 @@ mod tests {
     use crate::config::UserConfig;
     use crate::error::OxenError;
     use crate::test;
-    use std::path::Path;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn test_read() {
@@ -123,4 +142,23 @@ mod tests {
         std::fs::remove_file(final_path)?;
         Ok(())
     }
+
+    #[test]
+    fn test_upload_routes() -> Result<(), OxenError> {
+        let config: UserConfig = toml::from_str(
+            r#"
+            name = "Bessie"
+            email = "bessie@oxen.ai"
+
+            [upload_routes]
+            ".PNG" = "images"
+            json = "labels"
+            "#,
+        )?;
+
+        let routes = config.upload_routes();
+        assert_eq!(routes.get("png"), Some(&PathBuf::from("images")));
+        assert_eq!(routes.get("json"), Some(&PathBuf::from("labels")));
+        Ok(())
+    }
 }
@@ -15,6 +15,12 @@ pub const OXEN: &str = "oxen";
 pub const CONFIG_DIR: &str = ".config";
 /// .oxenignore is the name of the file that contains the ignore patterns
 pub const OXEN_IGNORE_FILE: &str = ".oxenignore";
+/// .oxenattributes forces the data type or skips metadata extraction for matching paths on add
+pub const OXEN_ATTRIBUTES_FILE: &str = ".oxenattributes";
+/// .oxenkeep is the empty marker file `oxen add --track-empty-dirs` writes into empty directories
+pub const OXEN_KEEP_FILE: &str = ".oxenkeep";
+/// Number of bytes read from the start of a file without an extension to guess its data type
+pub const SNIFF_NUM_BYTES: usize = 8192;
 /// Root path for repositories
 pub const ROOT_PATH: &str = "/";
 /// Config file for the repository
@@ -23,6 +29,8 @@ pub const REPO_CONFIG_FILENAME: &str = "config.toml";
 pub const HEAD_FILE: &str = "HEAD";
 /// refs/ is a key,val store of branch names to commit ids
 pub const REFS_DIR: &str = "refs";
+/// logs/refs/ holds the reflog, an append only history of updates to each ref
+pub const LOGS_DIR: &str = "logs";
 /// history/ dir is a list of directories named after commit ids
 pub const HISTORY_DIR: &str = "history";
 /// commits/ is a key-value database of commit ids to commit objects
@@ -63,6 +71,8 @@ pub const RIGHT_COMPARE_COMMIT: &str = "RIGHT";
 pub const STATS_DIR: &str = "stats";
 /// prefix for the staged dirs
 pub const STAGED_DIR: &str = "staged";
+/// file listing the directories staged by an interrupted `oxen add`, for `oxen add --continue`
+pub const ADD_CHECKPOINT_FILE: &str = "add_checkpoint";
 /// Name of the table in the duckdb db used for remote staging
 pub const TABLE_NAME: &str = "df";
 /// Oxen's internal row id column in duckdb remote staging tables
@@ -78,6 +88,8 @@ pub const EXCLUDE_OXEN_COLS: [&str; 7] = [
     EVAL_DURATION_COL,
 ];
 pub const OXEN_ROW_ID_COL: &str = "_oxen_row_id";
+/// Author recorded on workspace row edits made without a user identity
+pub const DEFAULT_ROW_CHANGE_AUTHOR: &str = "anonymous";
 /// Oxen's internal id column in duckdb remote staging tables
 pub const OXEN_ID_COL: &str = "_oxen_id";
 /// Name of the folder of the cache dir in which dfs are indexed as duckdbs
@@ -110,8 +122,12 @@ pub const OBJECT_VNODES_DIR: &str = "vnodes";
 pub const OBJECT_SCHEMAS_DIR: &str = "schemas";
 /// File name for files stored in versions directory (>0.8.4). (Was commit id <= 0.8.4)
 pub const VERSION_FILE_NAME: &str = "data";
+/// File name for zstd compressed files stored in versions directory
+pub const COMPRESSED_VERSION_FILE_NAME: &str = "data.zst";
 /// File name for chunks of files stored in versions directory
 pub const VERSION_CHUNK_FILE_NAME: &str = "chunk";
+/// File listing the content-defined chunks a version is stored as, see `oxen add --chunked-hash`
+pub const VERSION_MANIFEST_FILE_NAME: &str = "manifest";
 /// Chunks directory for version files
 pub const VERSION_CHUNKS_DIR: &str = "chunks";
 /// merge/ is where any merge conflicts are stored so that we can get rid of them
@@ -120,6 +136,14 @@ pub const MERGE_DIR: &str = "merge";
 pub const MODS_DIR: &str = "mods";
 /// workspaces/ is where we can make remote changes without having to clone locally
 pub const WORKSPACES_DIR: &str = "workspaces";
+/// status_index records the mtimes of clean directories and their files for `oxen status --fast`
+pub const STATUS_INDEX_FILE: &str = "status_index";
+/// hooks/ is where user defined scripts live that run before add or commit
+pub const HOOKS_DIR: &str = "hooks";
+/// Name of the hook that runs before files are staged
+pub const PRE_ADD_HOOK: &str = "pre-add";
+/// Name of the hook that runs before staged files are committed
+pub const PRE_COMMIT_HOOK: &str = "pre-commit";
 /// workspace commit id
 pub const WORKSPACE_CONFIG: &str = "WORKSPACE_CONFIG";
 /// data.arrow
@@ -189,9 +213,17 @@ pub const DEFAULT_NUM_WORKERS: usize = 8;
 pub const DEFAULT_TIMEOUT_SECS: u64 = 120;
 /// Default vnode size
 pub const DEFAULT_VNODE_SIZE: u64 = 10_000;
+/// Directories with at least this many files get counted before `oxen add` so the progress bar has a total
+pub const ADD_PREFLIGHT_MIN_FILES: u64 = 10_000;
+/// Number of staged entries `oxen add` writes to the staged db at a time
+pub const DEFAULT_STAGED_DB_BATCH_SIZE: usize = 1000;
+/// Number of fully staged directories `oxen add` collects before recording them in the checkpoint
+pub const ADD_CHECKPOINT_INTERVAL: usize = 100;
 
 /// Pagination page size of 10
 pub const DEFAULT_PAGE_SIZE: usize = 100;
+/// Largest page of rows a single request can ask for
+pub const MAX_PAGE_SIZE: usize = 10_000;
 /// Pagination page number of 1
 pub const DEFAULT_PAGE_NUM: usize = 1;
 
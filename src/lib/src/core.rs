@@ -4,10 +4,13 @@
 pub mod commit_sync_status;
 pub mod db;
 pub mod df;
+pub mod hooks;
 pub mod merge;
+pub mod oxenattributes;
 pub mod oxenignore;
 pub mod progress;
 pub mod refs;
+pub mod status_index;
 pub mod v_latest;
 pub mod v_old;
 pub mod versions;
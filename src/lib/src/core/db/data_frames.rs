@@ -2,5 +2,7 @@ pub mod column_changes_db;
 pub mod columns;
 pub mod df_db;
 pub mod row_changes_db;
+pub mod row_history_db;
+pub mod row_ops_db;
 pub mod rows;
 pub mod workspace_df_db;
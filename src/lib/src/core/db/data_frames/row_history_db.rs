@@ -0,0 +1,67 @@
+//! Every edit made to each row of a workspace data frame, oldest first. Keys are the row id
+//! followed by a zero padded sequence number, so a row's history is one sorted prefix scan.
+
+use rocksdb::{Direction, IteratorMode, DB};
+
+use crate::error::OxenError;
+use crate::view::data_frames::DataFrameRowChange;
+
+pub fn append_row_history(db: &DB, change: &DataFrameRowChange) -> Result<(), OxenError> {
+    let next = row_history_keys(db, &change.row_id)?.len();
+    db.put(
+        history_key(&change.row_id, next),
+        serde_json::to_string(change)?,
+    )?;
+    Ok(())
+}
+
+/// The edits made to `row_id`, oldest first
+pub fn get_row_history(db: &DB, row_id: &str) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    let prefix = history_prefix(row_id);
+    let mut history = Vec::new();
+    for item in db.iterator(IteratorMode::From(prefix.as_bytes(), Direction::Forward)) {
+        let (key, value) = item?;
+        if !key.starts_with(prefix.as_bytes()) {
+            break;
+        }
+        history.push(serde_json::from_slice(&value)?);
+    }
+    Ok(history)
+}
+
+/// Drop the latest edit to `row_id`, used when it is undone
+pub fn pop_row_history(db: &DB, row_id: &str) -> Result<(), OxenError> {
+    if let Some(key) = row_history_keys(db, row_id)?.pop() {
+        db.delete(key)?;
+    }
+    Ok(())
+}
+
+/// Drop every edit to `row_id`, used when the row is restored to its committed version
+pub fn delete_row_history(db: &DB, row_id: &str) -> Result<(), OxenError> {
+    for key in row_history_keys(db, row_id)? {
+        db.delete(key)?;
+    }
+    Ok(())
+}
+
+fn row_history_keys(db: &DB, row_id: &str) -> Result<Vec<Box<[u8]>>, OxenError> {
+    let prefix = history_prefix(row_id);
+    let mut keys = Vec::new();
+    for item in db.iterator(IteratorMode::From(prefix.as_bytes(), Direction::Forward)) {
+        let (key, _) = item?;
+        if !key.starts_with(prefix.as_bytes()) {
+            break;
+        }
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+fn history_prefix(row_id: &str) -> String {
+    format!("{row_id}/")
+}
+
+fn history_key(row_id: &str, seq: usize) -> String {
+    format!("{}{seq:020}", history_prefix(row_id))
+}
@@ -0,0 +1,65 @@
+//! The row operations applied to a workspace data frame in the order they were applied, so the
+//! last ones can be undone. Keys are zero padded sequence numbers to keep the log sorted.
+
+use rocksdb::{IteratorMode, DB};
+
+use crate::error::OxenError;
+use crate::view::data_frames::DataFrameRowChange;
+
+pub fn append_row_op(db: &DB, change: &DataFrameRowChange) -> Result<(), OxenError> {
+    let next = match db.iterator(IteratorMode::End).next() {
+        Some(item) => {
+            let (key, _) = item?;
+            parse_seq(&key)? + 1
+        }
+        None => 0,
+    };
+    db.put(format!("{next:020}"), serde_json::to_string(change)?)?;
+    Ok(())
+}
+
+/// Remove the last `count` operations from the log, returning them newest first
+pub fn pop_row_ops(db: &DB, count: usize) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    let mut ops = Vec::new();
+    for item in db.iterator(IteratorMode::End).take(count) {
+        let (key, value) = item?;
+        let op: DataFrameRowChange = serde_json::from_slice(&value)?;
+        ops.push((key, op));
+    }
+    for (key, _) in &ops {
+        db.delete(key)?;
+    }
+    Ok(ops.into_iter().map(|(_, op)| op).collect())
+}
+
+/// The latest operation left in the log for `row_id`
+pub fn last_row_op(db: &DB, row_id: &str) -> Result<Option<DataFrameRowChange>, OxenError> {
+    for item in db.iterator(IteratorMode::End) {
+        let (_, value) = item?;
+        let op: DataFrameRowChange = serde_json::from_slice(&value)?;
+        if op.row_id == row_id {
+            return Ok(Some(op));
+        }
+    }
+    Ok(None)
+}
+
+/// Remove every operation on `row_id` from the log, used when the row is restored so that undoing
+/// older operations does not bring its edits back
+pub fn delete_row_ops(db: &DB, row_id: &str) -> Result<(), OxenError> {
+    for item in db.iterator(IteratorMode::Start) {
+        let (key, value) = item?;
+        let op: DataFrameRowChange = serde_json::from_slice(&value)?;
+        if op.row_id == row_id {
+            db.delete(key)?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_seq(key: &[u8]) -> Result<u64, OxenError> {
+    std::str::from_utf8(key)
+        .ok()
+        .and_then(|key| key.parse::<u64>().ok())
+        .ok_or_else(|| OxenError::basic_str("Invalid key in the row operation log"))
+}
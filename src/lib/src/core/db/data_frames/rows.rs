@@ -13,8 +13,8 @@ use sql_query_builder as sql;
 use crate::constants::{DIFF_HASH_COL, DIFF_STATUS_COL, OXEN_COLS, OXEN_ID_COL};
 
 use crate::core::db;
-use crate::core::db::data_frames::row_changes_db;
 use crate::core::db::data_frames::workspace_df_db::schema_without_oxen_cols;
+use crate::core::db::data_frames::{row_changes_db, row_history_db, row_ops_db};
 use crate::core::df::tabular;
 use crate::model::staged_row_status::StagedRowStatus;
 use crate::view::data_frames::DataFrameRowChange;
@@ -24,6 +24,29 @@ use polars::prelude::*; // or use polars::lazy::*; if you're working in a lazy c
 use super::df_db;
 
 pub fn append_row(conn: &duckdb::Connection, df: &DataFrame) -> Result<DataFrame, OxenError> {
+    p_append_row(conn, df, None)
+}
+
+/// Append a row under the given `_oxen_id` instead of a new one, so a row merged in from another
+/// workspace keeps its id
+pub fn append_row_with_id(
+    conn: &duckdb::Connection,
+    df: &DataFrame,
+    oxen_id: &str,
+) -> Result<DataFrame, OxenError> {
+    if df.height() != 1 {
+        return Err(OxenError::basic_str(
+            "Append row with id requires exactly one row",
+        ));
+    }
+    p_append_row(conn, df, Some(oxen_id))
+}
+
+fn p_append_row(
+    conn: &duckdb::Connection,
+    df: &DataFrame,
+    oxen_id: Option<&str>,
+) -> Result<DataFrame, OxenError> {
     let table_schema = schema_without_oxen_cols(conn, TABLE_NAME)?;
     let df_schema = df.schema();
 
@@ -39,7 +62,13 @@ pub fn append_row(conn: &duckdb::Connection, df: &DataFrame) -> Result<DataFrame
         )
         .into(),
     );
-    let df = df.hstack(&[added_column])?;
+    let mut df = df.hstack(&[added_column])?;
+    if let Some(oxen_id) = oxen_id {
+        df.with_column(Series::new(
+            PlSmallStr::from_str(OXEN_ID_COL),
+            vec![oxen_id],
+        ))?;
+    }
 
     // Handle initialization for completely null {} create objects coming over from the hub
     let df = if df.height() == 0 {
@@ -375,12 +404,15 @@ pub fn record_row_change(
     operation: String,
     value: Value,
     new_value: Option<Value>,
-) -> Result<(), OxenError> {
+    author: &str,
+) -> Result<DataFrameRowChange, OxenError> {
     let change = DataFrameRowChange {
         row_id: row_id.to_owned(),
         operation,
         value,
         new_value,
+        author: author.to_owned(),
+        row_key: None,
     };
 
     let opts = db::key_val::opts::default();
@@ -388,7 +420,41 @@ pub fn record_row_change(
 
     maybe_revert_row_changes(&db, row_id.to_owned())?;
 
-    row_changes_db::write_data_frame_row_change(&change, &db)
+    row_changes_db::write_data_frame_row_change(&change, &db)?;
+    Ok(change)
+}
+
+/// Append a change to the operation log of the data frame, see `row_ops_db`
+pub fn record_row_op(row_ops_path: &Path, change: &DataFrameRowChange) -> Result<(), OxenError> {
+    let opts = db::key_val::opts::default();
+    let db = DB::open(&opts, dunce::simplified(row_ops_path))?;
+    row_ops_db::append_row_op(&db, change)
+}
+
+/// Append a change to the history of its row, see `row_history_db`
+pub fn record_row_history(
+    row_history_path: &Path,
+    change: &DataFrameRowChange,
+) -> Result<(), OxenError> {
+    let opts = db::key_val::opts::default();
+    let db = DB::open(&opts, dunce::simplified(row_history_path))?;
+    row_history_db::append_row_history(&db, change)
+}
+
+/// Put a row back exactly as it was, oxen columns included, or drop it if `row` is None
+pub fn replace_row(
+    conn: &duckdb::Connection,
+    uuid: &str,
+    row: Option<&DataFrame>,
+) -> Result<(), OxenError> {
+    let stmt = sql::Delete::new()
+        .delete_from(TABLE_NAME)
+        .where_clause(&format!("{} = '{}'", OXEN_ID_COL, uuid));
+    conn.execute(&stmt.to_string(), [])?;
+    if let Some(row) = row {
+        insert_polars_df(conn, TABLE_NAME, row)?;
+    }
+    Ok(())
 }
 
 pub fn maybe_revert_row_changes(db: &DB, row_id: String) -> Result<(), OxenError> {
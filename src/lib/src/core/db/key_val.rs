@@ -1,3 +1,4 @@
+pub mod batched_writer;
 pub mod index_db;
 pub mod kv_db;
 pub mod opts;
@@ -0,0 +1,86 @@
+//! Group key value writes into rocksdb `WriteBatch`es.
+//!
+//! Writing each entry with its own `put` pays the write-ahead-log and sync overhead per entry,
+//! which dominates when staging hundreds of thousands of files. A `BatchedWriter` buffers the
+//! entries and writes them `batch_size` at a time.
+//!
+//! Crash consistency: every batch is applied atomically, so the db only ever contains whole
+//! batches. A crash mid-add loses at most the entries of the unflushed batch, where per entry
+//! puts would lose at most the entry being written. Either way the lost files are simply not
+//! staged and the next `oxen add` picks them up again.
+//!
+//! Entries in an unflushed batch are not visible to reads on the db until `flush` is called.
+
+use std::sync::Mutex;
+
+use rocksdb::{DBWithThreadMode, MultiThreaded, WriteBatch};
+
+use crate::error::OxenError;
+
+/// Something staged entries can be written to, either the db itself or a `BatchedWriter` over it
+pub trait KeyValWriter: Sync {
+    fn put_entry(&self, key: &str, value: &[u8]) -> Result<(), OxenError>;
+
+    /// Make every entry put so far durable, a no-op for writers that do not buffer
+    fn flush_entries(&self) -> Result<(), OxenError> {
+        Ok(())
+    }
+}
+
+impl KeyValWriter for DBWithThreadMode<MultiThreaded> {
+    fn put_entry(&self, key: &str, value: &[u8]) -> Result<(), OxenError> {
+        self.put(key, value)?;
+        Ok(())
+    }
+}
+
+pub struct BatchedWriter<'a> {
+    db: &'a DBWithThreadMode<MultiThreaded>,
+    batch_size: usize,
+    batch: Mutex<WriteBatch>,
+}
+
+impl<'a> BatchedWriter<'a> {
+    pub fn new(db: &'a DBWithThreadMode<MultiThreaded>, batch_size: usize) -> Self {
+        Self {
+            db,
+            batch_size: batch_size.max(1),
+            batch: Mutex::new(WriteBatch::default()),
+        }
+    }
+
+    /// Write the final partial batch
+    pub fn flush(&self) -> Result<(), OxenError> {
+        let mut batch = self.batch.lock().unwrap();
+        if batch.is_empty() {
+            return Ok(());
+        }
+        self.db.write(std::mem::take(&mut *batch))?;
+        Ok(())
+    }
+}
+
+impl KeyValWriter for BatchedWriter<'_> {
+    fn put_entry(&self, key: &str, value: &[u8]) -> Result<(), OxenError> {
+        let mut batch = self.batch.lock().unwrap();
+        batch.put(key, value);
+        if batch.len() >= self.batch_size {
+            log::debug!("flushing {} staged entries", batch.len());
+            self.db.write(std::mem::take(&mut *batch))?;
+        }
+        Ok(())
+    }
+
+    fn flush_entries(&self) -> Result<(), OxenError> {
+        self.flush()
+    }
+}
+
+impl Drop for BatchedWriter<'_> {
+    fn drop(&mut self) {
+        // Keep what was processed before an early return, like per entry puts would have
+        if let Err(err) = self.flush() {
+            log::error!("Could not flush staged entries: {:?}", err);
+        }
+    }
+}
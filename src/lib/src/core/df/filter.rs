@@ -58,6 +58,17 @@ impl DFFilterOp {
             DFFilterOp::NEQ => "!=",
         }
     }
+
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            DFFilterOp::EQ => "=",
+            DFFilterOp::LT => "<",
+            DFFilterOp::GT => ">",
+            DFFilterOp::LTE => "<=",
+            DFFilterOp::GTE => ">=",
+            DFFilterOp::NEQ => "!=",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -67,6 +78,18 @@ pub struct DFFilterVal {
     pub value: String,
 }
 
+impl DFFilterVal {
+    /// The value is a string literal, which duckdb casts to the type of the column it is compared to
+    pub fn to_sql(&self) -> String {
+        format!(
+            "\"{}\" {} '{}'",
+            self.field.replace('"', "\"\""),
+            self.op.as_sql(),
+            self.value.replace('\'', "''")
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DFFilterExp {
     // logical ops are all the ops we want to chain "&&","||"
@@ -75,6 +98,24 @@ pub struct DFFilterExp {
     pub vals: Vec<DFFilterVal>,
 }
 
+impl DFFilterExp {
+    /// The filter as a SQL condition, chaining the logical ops left to right like the polars filter does
+    pub fn to_sql(&self) -> String {
+        let mut vals = self.vals.iter().map(DFFilterVal::to_sql);
+        let Some(mut sql) = vals.next() else {
+            return "TRUE".to_string();
+        };
+        for (op, val) in self.logical_ops.iter().zip(vals) {
+            let op = match op {
+                DFLogicalOp::AND => "AND",
+                DFLogicalOp::OR => "OR",
+            };
+            sql = format!("({sql}) {op} {val}");
+        }
+        sql
+    }
+}
+
 // Scan query to find the first logical op if there is one
 fn find_next_logical_op(
     query: &str,
@@ -220,4 +261,17 @@ mod tests {
         assert_eq!(opt.vals[2].value, "1.0");
         Ok(())
     }
+
+    #[test]
+    fn test_filter_to_sql_chains_left_to_right() -> Result<(), OxenError> {
+        let query = Some("label == person's || min_x > 0 && is_true == false".to_string());
+
+        let opt = parse(query)?.unwrap();
+
+        assert_eq!(
+            opt.to_sql(),
+            "((\"label\" = 'person''s') OR \"min_x\" > '0') AND \"is_true\" = 'false'"
+        );
+        Ok(())
+    }
 }
@@ -1,7 +1,7 @@
 use duckdb::ToSql;
 use polars::prelude::*;
 use serde_json::json;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs::File;
 use std::num::NonZeroUsize;
 
@@ -21,6 +21,7 @@ use crate::opts::{CountLinesOpts, DFOpts, PaginateOpts};
 use crate::repositories;
 use crate::util::fs;
 use crate::util::hasher;
+use crate::view::data_frames::columns::ColumnStats;
 
 use comfy_table::Table;
 use indicatif::ProgressBar;
@@ -691,6 +692,90 @@ pub fn any_val_to_bytes(value: &AnyValue) -> Vec<u8> {
     }
 }
 
+/// Compute the null count, min, max and mean of the schema's columns, or only of `columns` if
+/// given. Oxen's internal columns are skipped.
+pub fn column_stats(
+    df: &DataFrame,
+    schema: &crate::model::Schema,
+    columns: Option<&[String]>,
+) -> Result<BTreeMap<String, ColumnStats>, OxenError> {
+    if let Some(columns) = columns {
+        for column in columns {
+            if !schema.has_field_name(column) {
+                return Err(OxenError::column_name_not_found(column));
+            }
+        }
+    }
+
+    let mut results = BTreeMap::new();
+    for field in &schema.fields {
+        if constants::OXEN_COLS.contains(&field.name.as_str()) {
+            continue;
+        }
+        if let Some(columns) = columns {
+            if !columns.contains(&field.name) {
+                continue;
+            }
+        }
+
+        let column = df.column(&field.name)?;
+        let dtype = column.dtype();
+        let is_numeric = matches!(
+            dtype,
+            polars::prelude::DataType::Int8
+                | polars::prelude::DataType::Int16
+                | polars::prelude::DataType::Int32
+                | polars::prelude::DataType::Int64
+                | polars::prelude::DataType::UInt8
+                | polars::prelude::DataType::UInt16
+                | polars::prelude::DataType::UInt32
+                | polars::prelude::DataType::UInt64
+                | polars::prelude::DataType::Float32
+                | polars::prelude::DataType::Float64
+        );
+        let is_ordered = is_numeric
+            || matches!(
+                dtype,
+                polars::prelude::DataType::String
+                    | polars::prelude::DataType::Boolean
+                    | polars::prelude::DataType::Date
+                    | polars::prelude::DataType::Datetime(_, _)
+            );
+
+        let mut stats = ColumnStats {
+            data_type: field.dtype.to_owned(),
+            null_count: column.null_count(),
+            min: None,
+            max: None,
+            mean: None,
+        };
+
+        if is_ordered {
+            let name = field.name.as_str();
+            let mut exprs = vec![col(name).min().alias("min"), col(name).max().alias("max")];
+            if is_numeric {
+                exprs.push(
+                    col(name)
+                        .cast(polars::prelude::DataType::Float64)
+                        .mean()
+                        .alias("mean"),
+                );
+            }
+            let agg = df.select([name])?.lazy().select(exprs).collect()?;
+            stats.min = Some(any_val_to_json(agg.column("min")?.get(0)?));
+            stats.max = Some(any_val_to_json(agg.column("max")?.get(0)?));
+            if is_numeric {
+                if let AnyValue::Float64(mean) = agg.column("mean")?.get(0)? {
+                    stats.mean = Some(mean);
+                }
+            }
+        }
+
+        results.insert(field.name.to_owned(), stats);
+    }
+    Ok(results)
+}
+
 fn any_val_to_json(value: AnyValue) -> Value {
     match value {
         AnyValue::Null => Value::Null,
@@ -1814,6 +1899,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_column_stats() -> Result<(), OxenError> {
+        use crate::model::Schema;
+        use serde_json::json;
+
+        let df = df!(
+            "label" => &[Some("cat"), None, Some("dog")],
+            "width" => &[Some(10i64), Some(20), None],
+            "_oxen_id" => &["a", "b", "c"],
+        )
+        .unwrap();
+        let schema = Schema::from_polars(&df.schema());
+
+        let stats = tabular::column_stats(&df, &schema, None)?;
+        assert_eq!(stats.len(), 2);
+
+        let width = &stats["width"];
+        assert_eq!(width.null_count, 1);
+        assert_eq!(width.min, Some(json!(10)));
+        assert_eq!(width.max, Some(json!(20)));
+        assert_eq!(width.mean, Some(15.0));
+
+        let label = &stats["label"];
+        assert_eq!(label.null_count, 1);
+        assert_eq!(label.min, Some(json!("cat")));
+        assert_eq!(label.max, Some(json!("dog")));
+        assert_eq!(label.mean, None);
+
+        let stats = tabular::column_stats(&df, &schema, Some(&["label".to_string()]))?;
+        assert_eq!(stats.len(), 1);
+
+        assert!(tabular::column_stats(&df, &schema, Some(&["nope".to_string()])).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_any_val_to_json_primitive_types() -> Result<(), OxenError> {
         use polars::prelude::AnyValue;
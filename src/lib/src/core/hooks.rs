@@ -0,0 +1,227 @@
+//! Hooks are user defined executables in `.oxen/hooks/` that run before add or commit.
+//!
+//! They are opt-in via `enable_hooks = true` in the repository config.
+//! The paths affected by the operation are written to the hook's stdin, one per line.
+//! A non-zero exit status aborts the operation.
+//!
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::constants::HOOKS_DIR;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util;
+
+/// Path to the executable for a hook, ie `.oxen/hooks/pre-commit`
+pub fn hook_path(repo: &LocalRepository, name: impl AsRef<str>) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path)
+        .join(HOOKS_DIR)
+        .join(name.as_ref())
+}
+
+/// True if hooks are enabled and the executable for the hook exists, so callers can skip
+/// collecting the paths for a hook that won't run
+pub fn is_installed(repo: &LocalRepository, name: impl AsRef<str>) -> bool {
+    repo.hooks_enabled() && hook_path(repo, name).is_file()
+}
+
+/// Run the hook if hooks are enabled and the executable exists, passing the paths on stdin
+pub fn run(
+    repo: &LocalRepository,
+    name: impl AsRef<str>,
+    paths: &[PathBuf],
+) -> Result<(), OxenError> {
+    let name = name.as_ref();
+    if !is_installed(repo, name) {
+        log::debug!("hook {} is not enabled or not installed, skipping", name);
+        return Ok(());
+    }
+
+    let hook_path = hook_path(repo, name);
+
+    log::debug!("running hook {} on {} paths", name, paths.len());
+    let mut child = Command::new(&hook_path)
+        .current_dir(&repo.path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| OxenError::file_error(&hook_path, e))?;
+
+    // Feed stdin from another thread while stderr is drained below. A hook that writes a full pipe
+    // buffer to stderr before reading all of its paths would otherwise block us both.
+    let lines: Vec<String> = paths
+        .iter()
+        .map(|path| relative_path(repo, path).to_string_lossy().into_owned())
+        .collect();
+    let writer = child.stdin.take().map(|mut stdin| {
+        std::thread::spawn(move || {
+            for line in lines {
+                // The hook may exit before reading all of stdin, that is up to the hook
+                if writeln!(stdin, "{}", line).is_err() {
+                    break;
+                }
+            }
+        })
+    });
+
+    let output = child.wait_with_output()?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(OxenError::hook_failed(name, stderr));
+    }
+
+    Ok(())
+}
+
+fn relative_path(repo: &LocalRepository, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        util::fs::path_relative_to_dir(path, &repo.path).unwrap_or(path.to_path_buf())
+    } else {
+        path.to_path_buf()
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+
+    use crate::constants::{PRE_ADD_HOOK, PRE_COMMIT_HOOK};
+    use crate::core::hooks;
+    use crate::error::OxenError;
+    use crate::model::LocalRepository;
+    use crate::repositories;
+    use crate::test;
+    use crate::util;
+
+    fn write_hook(repo: &LocalRepository, name: &str, script: &str) -> Result<(), OxenError> {
+        let path = hooks::hook_path(repo, name);
+        util::fs::create_dir_all(path.parent().unwrap())?;
+        util::fs::write_to_path(&path, script)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_hooks_are_ignored_when_disabled() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            write_hook(&repo, PRE_ADD_HOOK, "#!/bin/sh\nexit 1\n")?;
+
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello World")?;
+            repositories::add(&repo, &hello_file)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_pre_add_hook_aborts_add() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            repo.set_hooks_enabled(true);
+            repo.save()?;
+            write_hook(
+                &repo,
+                PRE_ADD_HOOK,
+                "#!/bin/sh\necho 'adds are blocked' >&2\nexit 1\n",
+            )?;
+
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello World")?;
+            let result = repositories::add(&repo, &hello_file);
+            assert!(result.is_err());
+            assert!(result.unwrap_err().to_string().contains("adds are blocked"));
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 0);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_hook_writing_to_stderr_before_reading_stdin_does_not_block() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            repo.set_hooks_enabled(true);
+            repo.save()?;
+            // More than a pipe buffer of stderr before any of the paths are read
+            write_hook(
+                &repo,
+                PRE_COMMIT_HOOK,
+                "#!/bin/sh
+head -c 1000000 /dev/zero >&2
+cat > /dev/null
+",
+            )?;
+
+            let paths: Vec<PathBuf> = (0..100_000)
+                .map(|i| PathBuf::from(format!("data/{i}.txt")))
+                .collect();
+            hooks::run(&repo, PRE_COMMIT_HOOK, &paths)?;
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_pre_add_hook_receives_the_files_in_added_dirs() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            repo.set_hooks_enabled(true);
+            repo.save()?;
+            // Adding the dir passes the files inside it, not the dir itself
+            write_hook(
+                &repo,
+                PRE_ADD_HOOK,
+                "#!/bin/sh
+grep -q '^data/nested/hello.txt$' || { echo 'missing data/nested/hello.txt' >&2; exit 1; }
+",
+            )?;
+
+            let data_dir = repo.path.join("data");
+            util::fs::write_to_path(data_dir.join("nested").join("hello.txt"), "Hello World")?;
+            repositories::add(&repo, &data_dir)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_pre_commit_hook_receives_staged_paths() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            repo.set_hooks_enabled(true);
+            repo.save()?;
+            // Only allow the commit if hello.txt is one of the staged paths
+            write_hook(
+                &repo,
+                PRE_COMMIT_HOOK,
+                "#!/bin/sh\ngrep -q '^hello.txt$' || { echo 'missing hello.txt' >&2; exit 1; }\n",
+            )?;
+
+            let other_file = repo.path.join("other.txt");
+            util::fs::write_to_path(&other_file, "Other")?;
+            repositories::add(&repo, &other_file)?;
+            let result = repositories::commit(&repo, "Should fail");
+            assert!(result.is_err());
+
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello World")?;
+            repositories::add(&repo, &hello_file)?;
+            repositories::commit(&repo, "Should succeed")?;
+
+            Ok(())
+        })
+    }
+}
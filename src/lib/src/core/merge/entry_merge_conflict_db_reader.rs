@@ -40,9 +40,10 @@ impl EntryMergeConflictDBReader {
     }
 
     pub fn has_conflicts(db: &DB) -> Result<bool, OxenError> {
-        Ok(db.iterator(IteratorMode::Start).count() > 0)
+        Ok(db.iterator(IteratorMode::Start).next().is_some())
     }
 
+    /// All the conflicts, sorted by path
     pub fn list_conflicts(db: &DB) -> Result<Vec<EntryMergeConflict>, OxenError> {
         let mut conflicts: Vec<EntryMergeConflict> = vec![];
         let iter = db.iterator(IteratorMode::Start);
@@ -60,6 +61,7 @@ impl EntryMergeConflictDBReader {
                 }
             }
         }
+        conflicts.sort_by(|a, b| a.merge_entry.path.cmp(&b.merge_entry.path));
         Ok(conflicts)
     }
 }
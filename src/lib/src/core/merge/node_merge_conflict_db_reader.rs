@@ -39,9 +39,11 @@ impl NodeMergeConflictDBReader {
     }
 
     pub fn has_conflicts(db: &DB) -> Result<bool, OxenError> {
-        Ok(db.iterator(IteratorMode::Start).count() > 0)
+        Ok(db.iterator(IteratorMode::Start).next().is_some())
     }
 
+    /// All the conflicts, sorted by path. The db iterates in byte order of the keys, which puts
+    /// `a.txt` before `a/b.txt`, so sort by path components to match how paths are listed elsewhere.
     pub fn list_conflicts(db: &DB) -> Result<Vec<NodeMergeConflict>, OxenError> {
         let mut conflicts: Vec<NodeMergeConflict> = vec![];
         let iter = db.iterator(IteratorMode::Start);
@@ -59,6 +61,55 @@ impl NodeMergeConflictDBReader {
                 }
             }
         }
+        conflicts.sort_by(|a, b| a.path().cmp(b.path()));
         Ok(conflicts)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use rocksdb::DB;
+
+    use crate::core::db;
+    use crate::core::merge::node_merge_conflict_db_reader::NodeMergeConflictDBReader;
+    use crate::error::OxenError;
+    use crate::model::merge_conflict::NodeMergeConflict;
+    use crate::model::merkle_tree::node::FileNode;
+    use crate::test;
+
+    #[test]
+    fn test_list_conflicts_sorted_by_path() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let opts = db::key_val::opts::default();
+            let db = DB::open(&opts, dunce::simplified(dir))?;
+            for path in ["a.txt", "a/b.txt", "a/b/c.txt", "b.txt"] {
+                let entry = (FileNode::default(), PathBuf::from(path));
+                let conflict = NodeMergeConflict {
+                    lca_entry: entry.clone(),
+                    base_entry: entry.clone(),
+                    merge_entry: entry,
+                };
+                db.put(
+                    path.as_bytes(),
+                    serde_json::to_string(&conflict)?.as_bytes(),
+                )?;
+            }
+
+            let conflicts = NodeMergeConflictDBReader::list_conflicts(&db)?;
+            let paths: Vec<&Path> = conflicts.iter().map(|c| c.path()).collect();
+            assert_eq!(
+                paths,
+                vec![
+                    Path::new("a/b/c.txt"),
+                    Path::new("a/b.txt"),
+                    Path::new("a.txt"),
+                    Path::new("b.txt"),
+                ]
+            );
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,177 @@
+//! .oxenattributes assigns attributes to paths when they are added, like .gitattributes.
+//!
+//! Each line is a pattern followed by attributes:
+//!
+//! ```text
+//! # Comments and blank lines are skipped
+//! *.log       data_type=text
+//! *.bin       data_type=binary -metadata
+//! /raw/**     -metadata
+//! ```
+//!
+//! Patterns without a `/` match the file name in any directory, other patterns match the path
+//! relative to the repository root. When several lines match a path, the last one wins per attribute.
+//!
+//! Supported attributes:
+//! - `data_type=<dir|text|image|video|audio|tabular|binary>` forces the data type instead of detecting it
+//! - `-metadata` skips metadata extraction, `metadata` turns it back on for a more specific pattern
+//!
+
+use glob::{MatchOptions, Pattern};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::constants::OXEN_ATTRIBUTES_FILE;
+use crate::error::OxenError;
+use crate::model::{EntryDataType, LocalRepository};
+
+/// The attributes that apply to a single path
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PathAttributes {
+    pub data_type: Option<EntryDataType>,
+    pub metadata: Option<bool>,
+}
+
+#[derive(Clone, Debug)]
+struct AttributeRule {
+    pattern: Pattern,
+    match_file_name: bool,
+    attributes: PathAttributes,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct OxenAttributes {
+    rules: Vec<AttributeRule>,
+}
+
+/// Load the .oxenattributes at the root of the repository, None if there is not one
+pub fn create(repo: &LocalRepository) -> Result<Option<OxenAttributes>, OxenError> {
+    let path = repo.path.join(OXEN_ATTRIBUTES_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(&path).map_err(|e| OxenError::file_error(&path, e))?;
+    parse(&contents).map(Some)
+}
+
+/// Parse the contents of an .oxenattributes file
+pub fn parse(contents: &str) -> Result<OxenAttributes, OxenError> {
+    let mut rules = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let pattern = parts.next().unwrap_or_default();
+        let invalid = |reason: String| {
+            OxenError::basic_str(format!(
+                "Invalid {} line {}: {reason}",
+                OXEN_ATTRIBUTES_FILE,
+                i + 1
+            ))
+        };
+
+        let mut attributes = PathAttributes::default();
+        for attribute in parts {
+            match attribute.split_once('=') {
+                Some(("data_type", value)) => {
+                    let data_type = EntryDataType::from_str(value)
+                        .map_err(|_| invalid(format!("unknown data_type `{value}`")))?;
+                    attributes.data_type = Some(data_type);
+                }
+                None if attribute == "metadata" => attributes.metadata = Some(true),
+                None if attribute == "-metadata" => attributes.metadata = Some(false),
+                _ => return Err(invalid(format!("unknown attribute `{attribute}`"))),
+            }
+        }
+
+        // A leading slash anchors the pattern to the root, like gitignore
+        let anchored = pattern.strip_prefix('/');
+        let match_file_name = anchored.is_none() && !pattern.contains('/');
+        let pattern = Pattern::new(anchored.unwrap_or(pattern))
+            .map_err(|e| invalid(format!("bad pattern `{pattern}`: {e}")))?;
+        rules.push(AttributeRule {
+            pattern,
+            match_file_name,
+            attributes,
+        });
+    }
+    Ok(OxenAttributes { rules })
+}
+
+impl OxenAttributes {
+    /// The combined attributes of every rule that matches `path`, relative to the repository root
+    pub fn attributes_for(&self, path: impl AsRef<Path>) -> PathAttributes {
+        let path = path.as_ref();
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        let file_name = path.file_name().map(Path::new).unwrap_or(path);
+
+        let mut result = PathAttributes::default();
+        for rule in &self.rules {
+            let candidate = if rule.match_file_name {
+                file_name
+            } else {
+                path
+            };
+            if !rule.pattern.matches_path_with(candidate, options) {
+                continue;
+            }
+            if rule.attributes.data_type.is_some() {
+                result.data_type = rule.attributes.data_type.clone();
+            }
+            if rule.attributes.metadata.is_some() {
+                result.metadata = rule.attributes.metadata;
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::core::oxenattributes;
+    use crate::error::OxenError;
+    use crate::model::EntryDataType;
+
+    #[test]
+    fn test_attributes_for_path() -> Result<(), OxenError> {
+        let attributes = oxenattributes::parse(
+            "# types\n*.log data_type=text\n*.bin data_type=binary -metadata\n\n/raw/** -metadata\nraw/keep.bin metadata\n",
+        )?;
+
+        let log = attributes.attributes_for(Path::new("logs").join("run.log"));
+        assert_eq!(log.data_type, Some(EntryDataType::Text));
+        assert_eq!(log.metadata, None);
+
+        let bin = attributes.attributes_for("model.bin");
+        assert_eq!(bin.data_type, Some(EntryDataType::Binary));
+        assert_eq!(bin.metadata, Some(false));
+
+        let raw = attributes.attributes_for(Path::new("raw").join("a").join("b.csv"));
+        assert_eq!(raw.data_type, None);
+        assert_eq!(raw.metadata, Some(false));
+
+        // The later, more specific line wins for metadata, the type still comes from *.bin
+        let keep = attributes.attributes_for(Path::new("raw").join("keep.bin"));
+        assert_eq!(keep.data_type, Some(EntryDataType::Binary));
+        assert_eq!(keep.metadata, Some(true));
+
+        let other = attributes.attributes_for("data.csv");
+        assert_eq!(other, Default::default());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_attributes() {
+        assert!(oxenattributes::parse("*.log data_type=spreadsheet\n").is_err());
+        assert!(oxenattributes::parse("*.log binary\n").is_err());
+    }
+}
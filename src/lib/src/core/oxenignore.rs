@@ -7,7 +7,12 @@ use crate::model::LocalRepository;
 
 /// Create will load the .oxenignore if it exists. If it does not exist, it will return None.
 pub fn create(repo: &LocalRepository) -> Option<Gitignore> {
-    let path = repo.path.join(constants::OXEN_IGNORE_FILE);
+    create_from_dir(&repo.path)
+}
+
+/// Load the .oxenignore at the root of `dir`, for directories that are not a local repository
+pub fn create_from_dir(dir: impl AsRef<Path>) -> Option<Gitignore> {
+    let path = dir.as_ref().join(constants::OXEN_IGNORE_FILE);
     match Gitignore::new(path) {
         (gitignore, None) => {
             // log::debug!("loaded .oxenignore file from {}", path.display());
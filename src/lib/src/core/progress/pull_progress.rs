@@ -1,4 +1,5 @@
 use crate::core::progress::sync_progress::{SyncProgress, SyncType};
+use crate::util::progress_bar::ProgressFormat;
 use std::borrow::Cow;
 
 pub struct PullProgress {
@@ -24,6 +25,12 @@ impl PullProgress {
         }
     }
 
+    pub fn with_format(self, format: ProgressFormat) -> Self {
+        PullProgress {
+            sync_progress: self.sync_progress.with_format(format),
+        }
+    }
+
     pub fn set_message(&self, message: impl Into<Cow<'static, str>>) {
         self.sync_progress.set_message(message);
     }
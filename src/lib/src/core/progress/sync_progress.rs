@@ -1,4 +1,5 @@
-use indicatif::{ProgressBar, ProgressStyle};
+use crate::util::progress_bar::{JsonProgress, ProgressFormat};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::{
     borrow::Cow,
     sync::{
@@ -28,6 +29,7 @@ pub struct SyncProgress {
     progress_bar: ProgressBar,
     total_files: Option<u64>,
     total_bytes: Option<u64>,
+    json_progress: Option<JsonProgress>,
 }
 
 impl SyncProgress {
@@ -43,6 +45,7 @@ impl SyncProgress {
             progress_bar,
             total_files: None,
             total_bytes: None,
+            json_progress: None,
         }
     }
 
@@ -64,9 +67,20 @@ impl SyncProgress {
             progress_bar,
             total_files: Some(total_files),
             total_bytes: Some(total_bytes),
+            json_progress: None,
         }
     }
 
+    /// Hide the bar and report the counters as JSON lines on stderr instead when `format` is json
+    pub fn with_format(mut self, format: ProgressFormat) -> Self {
+        if format == ProgressFormat::Json {
+            self.progress_bar
+                .set_draw_target(ProgressDrawTarget::hidden());
+            self.json_progress = Some(JsonProgress::new(self.sync_type.as_str()));
+        }
+        self
+    }
+
     pub fn set_totals(&mut self, total_files: u64, total_bytes: u64) {
         self.total_files = Some(total_files);
         self.total_bytes = Some(total_bytes);
@@ -79,6 +93,10 @@ impl SyncProgress {
     pub fn update_message(&self) {
         let files = self.file_counter.load(Ordering::Relaxed);
         let bytes = self.byte_counter.load(Ordering::Relaxed);
+        if let Some(json_progress) = &self.json_progress {
+            json_progress.update(files, bytes);
+            return;
+        }
         match (self.total_files, self.total_bytes) {
             (Some(total_files), Some(_)) => {
                 // let message = format!(
@@ -130,6 +148,9 @@ impl SyncProgress {
     }
 
     pub fn finish(&self) {
+        if let Some(json_progress) = &self.json_progress {
+            json_progress.finish(self.get_num_files(), self.get_num_bytes());
+        }
         self.progress_bar.finish_and_clear();
     }
 }
@@ -1,4 +1,5 @@
 pub mod ref_manager;
+pub mod reflog;
 
 pub use ref_manager::remove_from_cache;
 pub use ref_manager::with_ref_manager;
@@ -9,8 +9,9 @@ use rocksdb::{IteratorMode, DB};
 
 use crate::constants::{HEAD_FILE, REFS_DIR};
 use crate::core::db;
+use crate::core::refs::reflog;
 use crate::error::OxenError;
-use crate::model::{Branch, LocalRepository};
+use crate::model::{Branch, LocalRepository, ReflogEntry};
 use crate::repositories;
 use crate::util;
 
@@ -191,6 +192,11 @@ impl RefManager {
         Ok(branch_names)
     }
 
+    /// Every logged update to the ref, oldest first
+    pub fn reflog(&self, name: &str) -> Result<Vec<ReflogEntry>, OxenError> {
+        reflog::read(&self.repository.path, name)
+    }
+
     // Write operations (from RefWriter)
 
     pub fn set_head(&self, name: impl AsRef<str>) {
@@ -257,7 +263,12 @@ impl RefManager {
         } else {
             let old_id = self.refs_db.get(old_name)?.unwrap();
             self.refs_db.delete(old_name)?;
-            self.refs_db.put(new_name, old_id)?;
+            self.refs_db.put(new_name, &old_id)?;
+
+            let id = str::from_utf8(&old_id)?;
+            reflog::rename(&self.repository.path, old_name, new_name)?;
+            let entry = ReflogEntry::new(Some(id), Some(id), format!("rename from {old_name}"));
+            reflog::append(&self.repository.path, new_name, &entry)?;
             Ok(())
         }
     }
@@ -268,6 +279,10 @@ impl RefManager {
             return Err(OxenError::basic_str(err));
         };
         self.refs_db.delete(name)?;
+
+        // Keep the log around so the branch can be recovered from its last commit id
+        let entry = ReflogEntry::new(Some(&branch.commit_id), None, "delete");
+        reflog::append(&self.repository.path, name, &entry)?;
         Ok(branch)
     }
 
@@ -278,7 +293,15 @@ impl RefManager {
     ) -> Result<(), OxenError> {
         let name = name.as_ref();
         let commit_id = commit_id.as_ref();
+        let old_id = self.get_commit_id_for_branch(name)?;
+        if old_id.as_deref() == Some(commit_id) {
+            return Ok(());
+        }
         self.refs_db.put(name, commit_id)?;
+
+        let operation = if old_id.is_some() { "update" } else { "create" };
+        let entry = ReflogEntry::new(old_id.as_deref(), Some(commit_id), operation);
+        reflog::append(&self.repository.path, name, &entry)?;
         Ok(())
     }
 
@@ -519,6 +542,68 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_reflog_records_ref_updates() -> Result<(), OxenError> {
+        test::run_one_commit_local_repo_test(|repo| {
+            with_ref_manager(&repo, |manager| {
+                manager.create_branch("feature/reflog", "1")?;
+                manager.set_branch_commit_id("feature/reflog", "2")?;
+                // a no-op update is not logged
+                manager.set_branch_commit_id("feature/reflog", "2")?;
+                manager.rename_branch("feature/reflog", "renamed")?;
+                manager.delete_branch("renamed")?;
+
+                // the log moved with the rename
+                assert!(manager.reflog("feature/reflog")?.is_empty());
+
+                let entries = manager.reflog("renamed")?;
+                let ids: Vec<(Option<&str>, Option<&str>, &str)> = entries
+                    .iter()
+                    .map(|e| {
+                        (
+                            e.old_id.as_deref(),
+                            e.new_id.as_deref(),
+                            e.operation.as_str(),
+                        )
+                    })
+                    .collect();
+                assert_eq!(
+                    ids,
+                    vec![
+                        (None, Some("1"), "create"),
+                        (Some("1"), Some("2"), "update"),
+                        (Some("2"), Some("2"), "rename from feature/reflog"),
+                        (Some("2"), None, "delete"),
+                    ]
+                );
+                Ok(())
+            })
+        })
+    }
+
+    #[test]
+    fn test_reflog_rename_onto_deleted_branch_keeps_both_logs() -> Result<(), OxenError> {
+        test::run_one_commit_local_repo_test(|repo| {
+            with_ref_manager(&repo, |manager| {
+                manager.create_branch("old", "1")?;
+                manager.delete_branch("old")?;
+                manager.create_branch("new", "2")?;
+                manager.rename_branch("new", "old")?;
+
+                let operations: Vec<String> = manager
+                    .reflog("old")?
+                    .into_iter()
+                    .map(|e| e.operation)
+                    .collect();
+                assert_eq!(
+                    operations,
+                    vec!["create", "delete", "create", "rename from new"]
+                );
+                Ok(())
+            })
+        })
+    }
+
     #[test]
     fn test_invalid_branch_names() -> Result<(), OxenError> {
         test::run_one_commit_local_repo_test(|repo| {
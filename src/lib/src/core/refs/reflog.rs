@@ -0,0 +1,80 @@
+//! Append-only history of every update to a ref, so a bad reset or a deleted
+//! branch can be recovered from its logged commit id.
+//!
+//! Each ref gets one file of newline delimited json under `.oxen/logs/refs/`.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::constants::{LOGS_DIR, REFS_DIR};
+use crate::error::OxenError;
+use crate::model::ReflogEntry;
+use crate::util;
+
+/// Path to the log file for a ref. Branch names may contain `/`, which is
+/// escaped so that `feature` and `feature/x` don't collide on disk.
+pub fn reflog_path(repo_path: impl AsRef<Path>, name: impl AsRef<str>) -> PathBuf {
+    let file_name = name.as_ref().replace('%', "%25").replace('/', "%2F");
+    util::fs::oxen_hidden_dir(repo_path)
+        .join(LOGS_DIR)
+        .join(REFS_DIR)
+        .join(file_name)
+}
+
+pub fn append(
+    repo_path: impl AsRef<Path>,
+    name: impl AsRef<str>,
+    entry: &ReflogEntry,
+) -> Result<(), OxenError> {
+    let path = reflog_path(repo_path, name);
+    if let Some(parent) = path.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Moves the log along with a renamed ref, so its history is kept. A deleted branch keeps its
+/// log, so if the new name was used before the old log is appended after its entries.
+pub fn rename(
+    repo_path: impl AsRef<Path>,
+    old_name: impl AsRef<str>,
+    new_name: impl AsRef<str>,
+) -> Result<(), OxenError> {
+    let repo_path = repo_path.as_ref();
+    let old_path = reflog_path(repo_path, old_name);
+    if !old_path.exists() {
+        return Ok(());
+    }
+    let new_path = reflog_path(repo_path, new_name);
+    if new_path.exists() {
+        let contents = util::fs::read_from_path(&old_path)?;
+        let mut file = OpenOptions::new().append(true).open(&new_path)?;
+        file.write_all(contents.as_bytes())?;
+        util::fs::remove_file(&old_path)?;
+    } else {
+        util::fs::rename(&old_path, &new_path)?;
+    }
+    Ok(())
+}
+
+/// All the entries for a ref, oldest first. Empty if the ref was never logged.
+pub fn read(
+    repo_path: impl AsRef<Path>,
+    name: impl AsRef<str>,
+) -> Result<Vec<ReflogEntry>, OxenError> {
+    let path = reflog_path(repo_path, name);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let contents = util::fs::read_from_path(&path)?;
+    let mut entries = vec![];
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        entries.push(serde_json::from_str(line)?);
+    }
+    Ok(entries)
+}
@@ -0,0 +1,234 @@
+//! The status index records the mtime of every directory `oxen status --fast` found clean,
+//! meaning none of its own entries were untracked, modified or removed, along with the size and
+//! mtime of each of its tracked files.
+//!
+//! A directory's mtime changes whenever an entry is added, removed or renamed in it, but not when
+//! a file in it is rewritten in place. So while the directory's mtime and the stat of each of its
+//! files still match the index, it can be skipped without listing it, looking its files up in the
+//! merkle tree or hashing them. Like git's index an edit that keeps the size and lands in the same
+//! mtime tick is missed. Plain `oxen status` always scans everything.
+//!
+//! The index is tied to the commit it was built against. Committing carries it forward to the
+//! new commit with the stamps of the committed files, checking out another commit carries it
+//! over with the stamps of the files the checkout wrote.
+//!
+
+use filetime::FileTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+use crate::constants::STATUS_INDEX_FILE;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::util;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StatusIndex {
+    /// The commit the directories were found clean against
+    pub commit_id: String,
+    /// Stamps of the clean directories, by path relative to the repo root
+    pub dirs: HashMap<PathBuf, DirStamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DirStamp {
+    pub mtime_seconds: i64,
+    pub mtime_nanos: u32,
+    /// The subdirectories, which still have to be checked against their own stamps
+    pub subdirs: Vec<String>,
+    /// The tracked files, stat-ed to catch the edits that do not touch the directory's mtime
+    pub files: Vec<FileStamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileStamp {
+    pub name: String,
+    pub num_bytes: u64,
+    pub mtime_seconds: i64,
+    pub mtime_nanos: u32,
+}
+
+impl DirStamp {
+    pub fn new(mtime: FileTime, subdirs: Vec<String>, files: Vec<FileStamp>) -> DirStamp {
+        DirStamp {
+            mtime_seconds: mtime.unix_seconds(),
+            mtime_nanos: mtime.nanoseconds(),
+            subdirs,
+            files,
+        }
+    }
+
+    pub fn matches(&self, mtime: FileTime) -> bool {
+        self.mtime_seconds == mtime.unix_seconds() && self.mtime_nanos == mtime.nanoseconds()
+    }
+
+    /// Whether every tracked file in `full_path` still has the size and mtime it was stamped with
+    pub fn files_match(&self, full_path: &Path) -> bool {
+        self.files.iter().all(|file| {
+            std::fs::metadata(full_path.join(&file.name)).is_ok_and(|meta| file.matches(&meta))
+        })
+    }
+
+    /// Add or replace the stamp of the file called `stamp.name`
+    pub fn set_file(&mut self, stamp: FileStamp) {
+        match self.files.iter_mut().find(|file| file.name == stamp.name) {
+            Some(file) => *file = stamp,
+            None => self.files.push(stamp),
+        }
+    }
+}
+
+impl FileStamp {
+    pub fn new(name: impl AsRef<str>, num_bytes: u64, mtime: FileTime) -> FileStamp {
+        FileStamp {
+            name: name.as_ref().to_string(),
+            num_bytes,
+            mtime_seconds: mtime.unix_seconds(),
+            mtime_nanos: mtime.nanoseconds(),
+        }
+    }
+
+    pub fn from_metadata(name: impl AsRef<str>, meta: &Metadata) -> FileStamp {
+        FileStamp::new(
+            name,
+            meta.len(),
+            FileTime::from_last_modification_time(meta),
+        )
+    }
+
+    pub fn matches(&self, meta: &Metadata) -> bool {
+        let mtime = FileTime::from_last_modification_time(meta);
+        self.num_bytes == meta.len()
+            && self.mtime_seconds == mtime.unix_seconds()
+            && self.mtime_nanos == mtime.nanoseconds()
+    }
+}
+
+impl StatusIndex {
+    pub fn new(commit_id: impl AsRef<str>) -> StatusIndex {
+        StatusIndex {
+            commit_id: commit_id.as_ref().to_string(),
+            dirs: HashMap::new(),
+        }
+    }
+
+    /// The subdirectories of `dir` if it is in the index and neither its mtime nor the stat of
+    /// any of its files has changed since. `full_path` is where `dir` is on disk.
+    pub fn unchanged_subdirs(
+        &self,
+        dir: &Path,
+        full_path: &Path,
+        mtime: FileTime,
+    ) -> Option<&[String]> {
+        self.dirs
+            .get(dir)
+            .filter(|stamp| stamp.matches(mtime) && stamp.files_match(full_path))
+            .map(|stamp| stamp.subdirs.as_slice())
+    }
+}
+
+/// Path to the index, ie `.oxen/status_index`
+pub fn index_path(repo: &LocalRepository) -> PathBuf {
+    util::fs::oxen_hidden_dir(&repo.path).join(STATUS_INDEX_FILE)
+}
+
+/// Read the index if it was built against `commit_id`. A missing, unreadable or stale index
+/// is treated as empty, the next `status --fast` rebuilds it.
+pub fn read(repo: &LocalRepository, commit_id: impl AsRef<str>) -> Option<StatusIndex> {
+    let bytes = std::fs::read(index_path(repo)).ok()?;
+    let index: StatusIndex = match bincode::deserialize(&bytes) {
+        Ok(index) => index,
+        Err(err) => {
+            log::debug!("could not read status index: {}", err);
+            return None;
+        }
+    };
+    (index.commit_id == commit_id.as_ref()).then_some(index)
+}
+
+pub fn write(repo: &LocalRepository, index: &StatusIndex) -> Result<(), OxenError> {
+    let bytes = bincode::serialize(index)?;
+    let path = index_path(repo);
+    std::fs::write(&path, bytes).map_err(|err| OxenError::file_error(&path, err))
+}
+
+/// Drop the index, for when the working tree is rewritten to another commit
+pub fn clear(repo: &LocalRepository) -> Result<(), OxenError> {
+    let path = index_path(repo);
+    if path.exists() {
+        util::fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
+/// Carry the index forward to a new commit. The clean directories stay clean, because the
+/// staged changes they may hold are now part of the commit. `committed` are the stamps the
+/// committed files were staged with, by path, so a file edited and committed after its directory
+/// was stamped matches again as long as it has not changed since it was added.
+pub fn advance(
+    repo: &LocalRepository,
+    parent_ids: &[String],
+    commit_id: impl AsRef<str>,
+    committed: Vec<(PathBuf, FileStamp)>,
+) -> Result<(), OxenError> {
+    let Some(mut index) = parent_ids
+        .iter()
+        .find_map(|parent_id| read(repo, parent_id))
+    else {
+        return Ok(());
+    };
+    for (path, stamp) in committed {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        if let Some(dir_stamp) = index.dirs.get_mut(dir) {
+            dir_stamp.set_file(stamp);
+        }
+    }
+    index.commit_id = commit_id.as_ref().to_string();
+    write(repo, &index)
+}
+
+/// Carry the index over to the commit a checkout moved the working tree to. Every file that
+/// differs between the two commits was written or removed by the checkout, or kept because it
+/// was modified, so the directories it did not touch are as clean as they were. `written` are
+/// the files the checkout wrote: their stamps are refreshed where the directory's mtime shows no
+/// entry was added, the directories that gained an entry are dropped and rescanned.
+pub fn carry_over(
+    repo: &LocalRepository,
+    index: Option<StatusIndex>,
+    commit_id: impl AsRef<str>,
+    written: &[PathBuf],
+) -> Result<(), OxenError> {
+    let Some(mut index) = index else {
+        return Ok(());
+    };
+    let mut written_by_dir: HashMap<&Path, Vec<String>> = HashMap::new();
+    for path in written {
+        let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+            continue;
+        };
+        written_by_dir
+            .entry(dir)
+            .or_default()
+            .push(name.to_string_lossy().to_string());
+    }
+    for (dir, names) in written_by_dir {
+        let Some(dir_stamp) = index.dirs.get_mut(dir) else {
+            continue;
+        };
+        let full_path = repo.path.join(dir);
+        let unchanged = std::fs::metadata(&full_path)
+            .is_ok_and(|meta| dir_stamp.matches(FileTime::from_last_modification_time(&meta)));
+        if !unchanged {
+            index.dirs.remove(dir);
+            continue;
+        }
+        for name in names {
+            let meta = util::fs::metadata(full_path.join(&name))?;
+            dir_stamp.set_file(FileStamp::from_metadata(&name, &meta));
+        }
+    }
+    index.commit_id = commit_id.as_ref().to_string();
+    write(repo, &index)
+}
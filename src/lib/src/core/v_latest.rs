@@ -10,6 +10,7 @@ pub mod diff;
 pub mod download;
 pub mod entries;
 pub mod fetch;
+pub mod gc;
 pub mod index;
 pub mod init;
 pub mod merge;
@@ -27,6 +28,6 @@ pub mod workspaces;
 
 pub use add::add;
 pub use commits::commit;
-pub use init::{init, init_with_version};
+pub use init::{init, init_with_storage, init_with_version};
 pub use pull::{pull, pull_all, pull_remote_branch};
 pub use rm::rm;
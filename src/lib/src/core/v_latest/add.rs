@@ -5,23 +5,34 @@ use rayon::prelude::*;
 use rocksdb::{DBWithThreadMode, MultiThreaded};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicUsize;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
 use tokio::time::Duration;
 use walkdir::WalkDir;
 
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 use rmp_serde::Serializer;
 use serde::Serialize;
 
-use crate::constants::{OXEN_HIDDEN_DIR, STAGED_DIR};
+use crate::constants::{
+    ADD_CHECKPOINT_FILE, ADD_CHECKPOINT_INTERVAL, ADD_PREFLIGHT_MIN_FILES,
+    DEFAULT_STAGED_DB_BATCH_SIZE, OXEN_HIDDEN_DIR, OXEN_KEEP_FILE, PRE_ADD_HOOK, STAGED_DIR,
+};
 use crate::core;
 use crate::core::db;
+use crate::core::db::key_val::batched_writer::{BatchedWriter, KeyValWriter};
+use crate::core::hooks;
+use crate::core::oxenattributes::{self, OxenAttributes};
 use crate::core::oxenignore;
 use crate::model::merkle_tree::node::file_node::FileNodeOpts;
 use crate::model::metadata::generic_metadata::GenericMetadata;
+use crate::model::metadata::MetadataPointer;
 use crate::model::{Commit, EntryDataType, MerkleHash, StagedEntryStatus};
-use crate::opts::RmOpts;
+use crate::opts::{AddOpts, RmOpts};
 use crate::storage::version_store::VersionStore;
+use crate::util::progress_bar::{JsonProgress, ProgressBarType, ProgressFormat};
 use crate::{error::OxenError, model::LocalRepository};
 use crate::{repositories, util};
 use ignore::gitignore::Gitignore;
@@ -29,7 +40,7 @@ use std::ops::AddAssign;
 
 use crate::core::v_latest::index::CommitMerkleTree;
 use crate::model::merkle_tree::node::{
-    EMerkleTreeNode, FileNode, MerkleTreeNode, StagedMerkleTreeNode,
+    EMerkleTreeNode, FileChunkType, FileNode, MerkleTreeNode, StagedMerkleTreeNode,
 };
 
 #[derive(Clone, Debug)]
@@ -41,19 +52,51 @@ pub struct FileStatus {
     pub mtime: FileTime,
     pub previous_metadata: Option<GenericMetadata>,
     pub previous_file_node: Option<FileNode>,
+    /// Set for `oxen add --pointer`, the file is tracked by this url instead of the version store
+    pub pointer_url: Option<String>,
+    /// Set for `oxen add --no-metadata`, the file is staged without parsing its metadata
+    pub skip_metadata: bool,
+    /// Set from `data_type=` in .oxenattributes, used instead of detecting the data type
+    pub data_type_override: Option<EntryDataType>,
+    /// Set when the CRLF line endings were converted to LF before hashing, the converted contents are the version
+    pub normalized_line_endings: bool,
+    /// Set for `oxen add --chmod`, the executable bit to record instead of keeping the previous one
+    pub executable: Option<bool>,
+    /// Set for `oxen add --chunked-hash` on large files, the version is stored as these content-defined chunks
+    pub chunk_hashes: Vec<u128>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct CumulativeStats {
+    /// The files that were staged, new and modified
     pub total_files: usize,
     pub total_bytes: u64,
     pub data_type_counts: HashMap<EntryDataType, usize>,
+    /// The part of `total_files` that was already committed with different contents
+    pub modified_files: usize,
+    pub removed_files: usize,
+    /// Files that were looked at but match the head commit, so nothing was staged for them
+    pub unchanged_files: usize,
+    /// Paths that could not be read, or missing paths that could not be staged for removal, and were
+    /// left out of the add, with the error. Empty with `--strict`, which fails on the first one instead.
+    pub skipped_paths: Vec<(PathBuf, String)>,
+}
+
+impl CumulativeStats {
+    /// The files that were staged and were not in the head commit
+    pub fn added_files(&self) -> usize {
+        self.total_files - self.modified_files
+    }
 }
 
 impl AddAssign<CumulativeStats> for CumulativeStats {
     fn add_assign(&mut self, other: CumulativeStats) {
         self.total_files += other.total_files;
         self.total_bytes += other.total_bytes;
+        self.modified_files += other.modified_files;
+        self.removed_files += other.removed_files;
+        self.unchanged_files += other.unchanged_files;
+        self.skipped_paths.extend(other.skipped_paths);
         for (data_type, count) in other.data_type_counts {
             *self.data_type_counts.entry(data_type).or_insert(0) += count;
         }
@@ -61,11 +104,23 @@ impl AddAssign<CumulativeStats> for CumulativeStats {
 }
 
 pub fn add(repo: &LocalRepository, path: impl AsRef<Path>) -> Result<(), OxenError> {
+    add_with_opts(repo, path, &AddOpts::default())
+}
+
+pub fn add_with_opts(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+    opts: &AddOpts,
+) -> Result<(), OxenError> {
+    repo.check_exists()?;
+
     // Collect paths that match the glob pattern either:
     // 1. In the repo working directory (untracked or modified files)
-    // 2. In the commit entry db (removed files)
+    // 2. In the commit entry db (removed files), unless opts.include_head_removed is false
 
     let path = path.as_ref();
+    // The head commit does not change during an add, look it up once and pass it down
+    let maybe_head_commit = repositories::commits::head_commit_maybe(repo)?;
     let mut paths: HashSet<PathBuf> = HashSet::new();
     if let Some(path_str) = path.to_str() {
         // TODO: At least on Windows, this is improperly case sensitive
@@ -77,9 +132,11 @@ pub fn add(repo: &LocalRepository, path: impl AsRef<Path>) -> Result<(), OxenErr
             }
 
             // For removed files?
-            if let Some(commit) = repositories::commits::head_commit_maybe(repo)? {
+            if !opts.include_head_removed {
+                log::debug!("skipping removed entries for glob: {}", path_str);
+            } else if let Some(commit) = &maybe_head_commit {
                 let pattern_entries =
-                    repositories::commits::search_entries(repo, &commit, path_str)?;
+                    repositories::commits::search_entries(repo, commit, path_str)?;
                 log::debug!("pattern entries: {:?}", pattern_entries);
                 paths.extend(pattern_entries);
             }
@@ -93,80 +150,505 @@ pub fn add(repo: &LocalRepository, path: impl AsRef<Path>) -> Result<(), OxenErr
     let version_store = repo.version_store()?;
 
     // Open the staged db once at the beginning and reuse the connection
-    let opts = db::key_val::opts::default();
+    let db_opts = db::key_val::opts::default();
     let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
     let staged_db: DBWithThreadMode<MultiThreaded> =
-        DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
-    let _stats = add_files(repo, &paths, &staged_db, &version_store)?;
+        DBWithThreadMode::open(&db_opts, dunce::simplified(&db_path))?;
+    let _stats = match &opts.pointer_url {
+        Some(pointer_url) => {
+            add_pointer_files(repo, &maybe_head_commit, &paths, pointer_url, &staged_db)?
+        }
+        None if opts.update => add_tracked_changes(
+            repo,
+            &maybe_head_commit,
+            &paths,
+            &staged_db,
+            &version_store,
+            opts,
+        )?,
+        None => add_files(
+            repo,
+            &maybe_head_commit,
+            &paths,
+            &staged_db,
+            &version_store,
+            opts,
+        )?,
+    };
 
     Ok(())
 }
 
+/// Stage files that point at `pointer_url` without copying their bytes into the version store.
+/// If `pointer_url` ends with `/`, each file's path relative to the repository is appended to it.
+fn add_pointer_files(
+    repo: &LocalRepository,
+    maybe_head_commit: &Option<Commit>,
+    paths: &HashSet<PathBuf>,
+    pointer_url: &str,
+    staged_db: &DBWithThreadMode<MultiThreaded>,
+) -> Result<CumulativeStats, OxenError> {
+    let hook_paths: Vec<PathBuf> = paths.iter().cloned().collect();
+    hooks::run(repo, PRE_ADD_HOOK, &hook_paths)?;
+
+    let dir_nodes = DirNodeCache::new();
+    let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
+    let conflicts: HashSet<PathBuf> = HashSet::new();
+    let mut total = CumulativeStats::default();
+    for path in paths {
+        if !path.is_file() {
+            return Err(OxenError::basic_str(format!(
+                "Can only add files with --pointer, {path:?} is not a file"
+            )));
+        }
+
+        let relative_path = util::fs::path_relative_to_dir(path, &repo.path)?;
+        let parent_path = relative_path.parent().unwrap_or(Path::new(""));
+        let maybe_dir_node = dir_nodes.get(repo, maybe_head_commit, parent_path)?;
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let mut file_status = determine_file_status_with_opts(
+            &maybe_dir_node,
+            &file_name,
+            path,
+            repo.strict_mtime(),
+            repo.normalize_line_endings(),
+        )?;
+        file_status.pointer_url = Some(if pointer_url.ends_with('/') {
+            let relative_path = relative_path.to_string_lossy().replace('\\', "/");
+            format!("{pointer_url}{relative_path}")
+        } else {
+            pointer_url.to_string()
+        });
+
+        let entry = process_add_file(
+            repo,
+            &repo.path,
+            &file_status,
+            staged_db,
+            path,
+            &seen_dirs,
+            &conflicts,
+        )?;
+        if let Some(entry) = entry {
+            if let EMerkleTreeNode::File(file_node) = &entry.node.node {
+                total.total_files += 1;
+                total.total_bytes += file_node.num_bytes();
+                *total
+                    .data_type_counts
+                    .entry(file_node.data_type().clone())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    println!(
+        "🐂 oxen added {} pointer files ({})",
+        total.total_files,
+        bytesize::ByteSize::b(total.total_bytes)
+    );
+    Ok(total)
+}
+
+/// Prints a line per file for `oxen add --verbose`. The rayon workers send their lines to a single
+/// printer thread so the output of different files is never interleaved.
+pub struct VerbosePrinter {
+    sender: Sender<String>,
+    handle: JoinHandle<()>,
+}
+
+impl VerbosePrinter {
+    pub fn new() -> VerbosePrinter {
+        VerbosePrinter::with_sink(None)
+    }
+
+    /// Forward the lines to `sink` instead of stdout if it is set
+    pub fn with_sink(sink: Option<Sender<String>>) -> VerbosePrinter {
+        let (sender, receiver) = mpsc::channel::<String>();
+        let handle = std::thread::spawn(move || {
+            for line in receiver {
+                match &sink {
+                    Some(sink) => {
+                        let _ = sink.send(line);
+                    }
+                    None => println!("{line}"),
+                }
+            }
+        });
+        VerbosePrinter { sender, handle }
+    }
+
+    pub fn print(&self, path: &Path, status: impl std::fmt::Display, hash: Option<&MerkleHash>) {
+        let hash = hash.map(|h| h.to_short_str()).unwrap_or_default();
+        let line = format!("{status:<10} {hash:<10} {}", path.display());
+        self.send(path, line);
+    }
+
+    pub fn print_error(&self, path: &Path, err: &OxenError) {
+        let line = format!("{:<10} {:<10} {}: {err}", "Error", "", path.display());
+        self.send(path, line);
+    }
+
+    fn send(&self, path: &Path, line: String) {
+        if self.sender.send(line).is_err() {
+            log::warn!("Verbose printer closed before {:?} was printed", path);
+        }
+    }
+
+    /// Wait for every queued line to be printed
+    pub fn finish(self) {
+        drop(self.sender);
+        if self.handle.join().is_err() {
+            log::error!("Verbose printer thread panicked");
+        }
+    }
+}
+
+impl Default for VerbosePrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Print the outcome of adding a single file if running with --verbose
+fn print_add_result(
+    printer: &Option<VerbosePrinter>,
+    path: &Path,
+    file_status: &FileStatus,
+    result: &Result<Option<StagedMerkleTreeNode>, OxenError>,
+) {
+    let Some(printer) = printer else {
+        return;
+    };
+    match result {
+        Ok(Some(node)) => {
+            printer.print(path, format!("{:?}", node.status), Some(&file_status.hash))
+        }
+        Ok(None) => printer.print(path, "Unmodified", Some(&file_status.hash)),
+        Err(err) => printer.print_error(path, err),
+    }
+}
+
+/// The head commit's directory nodes loaded during a single add, keyed by their path. Adding many
+/// files from the same directory loads its node from the tree db once instead of once per file.
+#[derive(Default)]
+pub struct DirNodeCache {
+    dirs: Mutex<HashMap<PathBuf, Arc<Option<MerkleTreeNode>>>>,
+    num_loads: AtomicUsize,
+}
+
+impl DirNodeCache {
+    pub fn new() -> DirNodeCache {
+        DirNodeCache::default()
+    }
+
+    /// The dir node with its immediate children, None if there is no head commit or the dir is not in it
+    pub fn get(
+        &self,
+        repo: &LocalRepository,
+        maybe_head_commit: &Option<Commit>,
+        dir: &Path,
+    ) -> Result<Arc<Option<MerkleTreeNode>>, OxenError> {
+        let Some(head_commit) = maybe_head_commit else {
+            return Ok(Arc::new(None));
+        };
+        if let Some(dir_node) = self.dirs.lock().unwrap().get(dir) {
+            return Ok(dir_node.clone());
+        }
+
+        let dir_node = Arc::new(CommitMerkleTree::dir_with_immediate_children(
+            repo,
+            head_commit,
+            dir,
+        )?);
+        self.num_loads
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.dirs
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), dir_node.clone());
+        Ok(dir_node)
+    }
+
+    /// How many dir nodes were read from the tree db
+    pub fn num_loads(&self) -> usize {
+        self.num_loads.load(std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
+/// Records the directories whose files are all staged, so `oxen add --continue` can skip them after
+/// an interrupted add. Directories are only written out once their staged entries are flushed.
+pub struct AddCheckpoint {
+    path: PathBuf,
+    done: HashSet<PathBuf>,
+    pending: Mutex<Vec<PathBuf>>,
+}
+
+impl AddCheckpoint {
+    /// Load the directories of the previous add when resuming, otherwise start a new checkpoint
+    pub fn open(repo: &LocalRepository, resume: bool) -> Result<AddCheckpoint, OxenError> {
+        let path = util::fs::oxen_hidden_dir(&repo.path).join(ADD_CHECKPOINT_FILE);
+        let mut done = HashSet::new();
+        if resume && path.exists() {
+            done = util::fs::read_from_path(&path)?
+                .lines()
+                .map(PathBuf::from)
+                .collect();
+            log::debug!("add --continue skipping {} staged dirs", done.len());
+        } else if path.exists() {
+            util::fs::remove_file(&path)?;
+        }
+        Ok(AddCheckpoint {
+            path,
+            done,
+            pending: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn is_done(&self, dir: &Path) -> bool {
+        self.done.contains(dir)
+    }
+
+    pub fn complete(&self, dir: &Path, staged_db: &impl KeyValWriter) -> Result<(), OxenError> {
+        let mut pending = self.pending.lock().unwrap();
+        pending.push(dir.to_path_buf());
+        if pending.len() < ADD_CHECKPOINT_INTERVAL {
+            return Ok(());
+        }
+
+        staged_db.flush_entries()?;
+        let lines: String = pending
+            .drain(..)
+            .map(|dir| format!("{}\n", dir.to_string_lossy()))
+            .collect();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| OxenError::file_error(&self.path, e))?;
+        std::io::Write::write_all(&mut file, lines.as_bytes())?;
+        Ok(())
+    }
+
+    /// The add finished, there is nothing to resume
+    pub fn clear(&self) -> Result<(), OxenError> {
+        if self.path.exists() {
+            util::fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The paths with unresolved merge conflicts, empty without touching the conflict entries if there are none
+pub fn merge_conflict_paths(repo: &LocalRepository) -> Result<HashSet<PathBuf>, OxenError> {
+    if !core::v_latest::merge::has_conflicts_in_db(repo)? {
+        return Ok(HashSet::new());
+    }
+    Ok(repositories::merge::list_conflicts(repo)?
+        .into_iter()
+        .map(|conflict| conflict.merge_entry.path)
+        .collect())
+}
+
+/// `oxen add --update`, stages the files in the head commit under `paths` that were modified or
+/// removed from the working tree. Untracked files are left alone.
+fn add_tracked_changes(
+    repo: &LocalRepository,
+    maybe_head_commit: &Option<Commit>,
+    paths: &HashSet<PathBuf>,
+    staged_db: &DBWithThreadMode<MultiThreaded>,
+    version_store: &Arc<dyn VersionStore>,
+    opts: &AddOpts,
+) -> Result<CumulativeStats, OxenError> {
+    let Some(head_commit) = maybe_head_commit else {
+        // Nothing is tracked before the first commit
+        return Ok(CumulativeStats::default());
+    };
+    let Some(root) = repositories::tree::get_root_with_children(repo, head_commit)? else {
+        return Ok(CumulativeStats::default());
+    };
+
+    let relative_paths = paths
+        .iter()
+        .map(|path| util::fs::path_relative_to_dir(path, &repo.path))
+        .collect::<Result<Vec<PathBuf>, OxenError>>()?;
+
+    let mut modified = HashSet::new();
+    let mut removed = HashSet::new();
+    for file in repositories::tree::list_all_files(&root)? {
+        let file_path = file.dir.join(file.file_node.name());
+        if !relative_paths
+            .iter()
+            .any(|path| file_path.starts_with(path))
+        {
+            continue;
+        }
+
+        let full_path = repo.path.join(&file_path);
+        if !full_path.exists() {
+            removed.insert(full_path);
+            continue;
+        }
+        let file_status = determine_file_status_from_node(
+            Some(file.file_node),
+            &full_path,
+            repo.strict_mtime(),
+            repo.normalize_line_endings(),
+        )?;
+        if file_status.status == StagedEntryStatus::Modified {
+            modified.insert(full_path);
+        }
+    }
+    log::debug!(
+        "add --update found {} modified and {} removed files",
+        modified.len(),
+        removed.len()
+    );
+
+    let mut total = CumulativeStats::default();
+    if !modified.is_empty() {
+        total += add_files(
+            repo,
+            maybe_head_commit,
+            &modified,
+            staged_db,
+            version_store,
+            opts,
+        )?;
+    }
+    if !removed.is_empty() {
+        let rm_opts = RmOpts::from_path(&repo.path);
+        let rm_stats = core::v_latest::rm::rm_with_staged_db(&removed, repo, &rm_opts, staged_db)?;
+        total.removed_files += rm_stats.total_files;
+    }
+    Ok(total)
+}
+
+/// Staged entries are written in batches of `opts.batch_size`, see `BatchedWriter`
 pub fn add_files(
     repo: &LocalRepository,
+    maybe_head_commit: &Option<Commit>,
     paths: &HashSet<PathBuf>,
     staged_db: &DBWithThreadMode<MultiThreaded>,
     version_store: &Arc<dyn VersionStore>,
+    opts: &AddOpts,
 ) -> Result<CumulativeStats, OxenError> {
     log::debug!("add files: {:?}", paths);
 
     // Start a timer
     let start = std::time::Instant::now();
 
-    // Lookup the head commit
-    let maybe_head_commit = repositories::commits::head_commit_maybe(repo)?;
-
-    let mut total = CumulativeStats {
-        total_files: 0,
-        total_bytes: 0,
-        data_type_counts: HashMap::new(),
-    };
+    let mut total = CumulativeStats::default();
     let excluded_hashes = None;
     let gitignore = oxenignore::create(repo);
 
-    for path in paths {
+    // Give the pre-add hook a chance to reject the files before anything is staged
+    if hooks::is_installed(repo, PRE_ADD_HOOK) {
+        let hook_paths = files_to_add(paths, &gitignore);
+        hooks::run(repo, PRE_ADD_HOOK, &hook_paths)?;
+    }
+    let attributes = oxenattributes::create(repo)?;
+    let batch_size = opts.batch_size.unwrap_or(DEFAULT_STAGED_DB_BATCH_SIZE);
+    let writer = BatchedWriter::new(staged_db, batch_size);
+    let printer = opts
+        .verbose
+        .then(|| VerbosePrinter::with_sink(opts.verbose_sink.clone()));
+    // Most repos never merge, so look the conflicts up once instead of per directory and file
+    let conflicts = merge_conflict_paths(repo)?;
+    let checkpoint = Some(AddCheckpoint::open(repo, opts.resume)?);
+    let dir_nodes = DirNodeCache::new();
+
+    let mut ordered_paths: Vec<&PathBuf> = paths.iter().collect();
+    if opts.sort {
+        ordered_paths.sort();
+    }
+    // With --strict a missing path that can't be removed stops the add, but the files staged so
+    // far are still flushed and reported before the error is returned
+    let mut failed = None;
+    for path in ordered_paths {
         log::debug!("path is {path:?}");
 
         if path.is_dir() {
             total += add_dir_inner(
                 repo,
-                &maybe_head_commit,
+                maybe_head_commit,
                 path.clone(),
-                staged_db,
+                &writer,
                 version_store,
                 &excluded_hashes,
                 &gitignore,
+                &attributes,
+                &conflicts,
+                &checkpoint,
+                opts,
+                &printer,
             )?;
         } else if path.is_file() {
             if oxenignore::is_ignored(path, &gitignore, path.is_dir()) {
+                if let Some(printer) = &printer {
+                    let relative_path = util::fs::path_relative_to_dir(path, &repo.path)?;
+                    printer.print(&relative_path, "Ignored", None);
+                }
                 continue;
             }
 
-            let entry = add_file_inner(repo, &maybe_head_commit, path, staged_db, version_store)?;
-            if let Some(entry) = entry {
-                if let EMerkleTreeNode::File(file_node) = &entry.node.node {
-                    let data_type = file_node.data_type();
-                    total.total_files += 1;
-                    total.total_bytes += file_node.num_bytes();
-                    total
-                        .data_type_counts
-                        .entry(data_type.clone())
-                        .and_modify(|count| *count += 1)
-                        .or_insert(1);
+            let entry = add_file_inner(
+                repo,
+                maybe_head_commit,
+                &dir_nodes,
+                path,
+                &writer,
+                version_store,
+                &attributes,
+                &conflicts,
+                opts,
+                &printer,
+            )?;
+            let Some(entry) = entry else {
+                total.unchanged_files += 1;
+                continue;
+            };
+            if let EMerkleTreeNode::File(file_node) = &entry.node.node {
+                let data_type = file_node.data_type();
+                total.total_files += 1;
+                total.total_bytes += file_node.num_bytes();
+                if entry.status == StagedEntryStatus::Modified {
+                    total.modified_files += 1;
                 }
+                total
+                    .data_type_counts
+                    .entry(data_type.clone())
+                    .and_modify(|count| *count += 1)
+                    .or_insert(1);
             }
         } else {
             log::debug!("Found nonexistent path {path:?}. Staging for removal. Recursive flag set");
-            let mut opts = RmOpts::from_path(path);
-            opts.recursive = true;
-            core::v_latest::rm::rm_with_staged_db(paths, repo, &opts, staged_db)?;
-
-            // TODO: Make rm_with_staged_db return the stats of the files it removes
-
-            return Ok(total);
+            let mut rm_opts = RmOpts::from_path(path);
+            rm_opts.recursive = true;
+            writer.flush()?;
+            let missing = HashSet::from([path.clone()]);
+            match core::v_latest::rm::rm_with_staged_db(&missing, repo, &rm_opts, staged_db) {
+                Ok(rm_stats) => total.removed_files += rm_stats.total_files,
+                Err(err) if opts.strict => {
+                    failed = Some(err);
+                    break;
+                }
+                Err(err) => {
+                    log::warn!("skipping missing path {:?}: {}", path, err);
+                    total.skipped_paths.push((path.clone(), err.to_string()));
+                }
+            }
         }
     }
 
+    writer.flush()?;
+    if let Some(checkpoint) = &checkpoint {
+        checkpoint.clear()?;
+    }
+    if let Some(printer) = printer {
+        printer.finish();
+    }
+
     // Stop the timer, and round the duration to the nearest second
     let duration = Duration::from_millis(start.elapsed().as_millis() as u64);
     log::debug!("---END--- oxen add: {:?} duration: {:?}", paths, duration);
@@ -178,18 +660,43 @@ pub fn add_files(
         bytesize::ByteSize::b(total.total_bytes),
         humantime::format_duration(duration)
     );
+    println!(
+        "   {} new, {} modified, {} removed, {} unchanged",
+        total.added_files(),
+        total.modified_files,
+        total.removed_files,
+        total.unchanged_files
+    );
+    if !total.skipped_paths.is_empty() {
+        println!(
+            "   {} skipped because they could not be added:",
+            total.skipped_paths.len()
+        );
+        for (path, err) in &total.skipped_paths {
+            println!("     {}: {}", path.display(), err);
+        }
+    }
 
+    if let Some(err) = failed {
+        return Err(err);
+    }
     Ok(total)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn add_dir_inner(
     repo: &LocalRepository,
     maybe_head_commit: &Option<Commit>,
     path: PathBuf,
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
     version_store: &Arc<dyn VersionStore>,
     excluded_hashes: &Option<HashSet<MerkleHash>>,
     gitignore: &Option<Gitignore>,
+    attributes: &Option<OxenAttributes>,
+    conflicts: &HashSet<PathBuf>,
+    checkpoint: &Option<AddCheckpoint>,
+    opts: &AddOpts,
+    printer: &Option<VerbosePrinter>,
 ) -> Result<CumulativeStats, OxenError> {
     process_add_dir(
         repo,
@@ -199,6 +706,11 @@ fn add_dir_inner(
         path,
         excluded_hashes,
         gitignore,
+        attributes,
+        conflicts,
+        checkpoint,
+        opts,
+        printer,
     )
 }
 
@@ -209,6 +721,7 @@ pub fn add_dir_except(
     path: PathBuf,
     excluded_hashes: HashSet<MerkleHash>,
 ) -> Result<CumulativeStats, OxenError> {
+    repo.check_exists()?;
     let opts = db::key_val::opts::default();
     let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
     let staged_db: DBWithThreadMode<MultiThreaded> =
@@ -218,32 +731,77 @@ pub fn add_dir_except(
     let version_store = repo.version_store()?;
     let excluded_hashes = Some(excluded_hashes);
     let gitignore = None;
+    let attributes = oxenattributes::create(repo)?;
+    let conflicts = merge_conflict_paths(repo)?;
+    let writer = BatchedWriter::new(&staged_db, DEFAULT_STAGED_DB_BATCH_SIZE);
 
-    add_dir_inner(
+    let stats = add_dir_inner(
         repo,
         maybe_head_commit,
         path,
-        &staged_db,
+        &writer,
         &version_store,
         &excluded_hashes,
         &gitignore,
-    )
+        &attributes,
+        &conflicts,
+        &None,
+        &AddOpts::default(),
+        &None,
+    )?;
+    writer.flush()?;
+    Ok(stats)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_add_dir(
     repo: &LocalRepository,
     maybe_head_commit: &Option<Commit>,
     version_store: &Arc<dyn VersionStore>,
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
     path: PathBuf,
     excluded_hashes: &Option<HashSet<MerkleHash>>,
     gitignore: &Option<Gitignore>,
+    attributes: &Option<OxenAttributes>,
+    conflicts: &HashSet<PathBuf>,
+    checkpoint: &Option<AddCheckpoint>,
+    opts: &AddOpts,
+    printer: &Option<VerbosePrinter>,
 ) -> Result<CumulativeStats, OxenError> {
     let start = std::time::Instant::now();
 
     let progress_1 = Arc::new(ProgressBar::new_spinner());
     progress_1.set_style(ProgressStyle::default_spinner());
     progress_1.enable_steady_tick(Duration::from_millis(100));
+    // The per file lines would be drawn over by the spinner
+    let hide_progress = printer.is_some() || opts.progress == ProgressFormat::Json;
+    if hide_progress {
+        progress_1.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    let json_progress = match opts.progress {
+        ProgressFormat::Json => Some(JsonProgress::new("add")),
+        ProgressFormat::Human => None,
+    };
+
+    // Large trees get a bar with a total. Counting takes as long as the walk itself, so it runs
+    // alongside the add and the spinner turns into a bar once the count is in. Nobody sees the bar
+    // when it is hidden, so there is nothing to count for.
+    let preflight_total_bytes: Arc<OnceLock<u64>> = Arc::new(OnceLock::new());
+    let preflight_done = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let preflight = (!hide_progress).then(|| {
+        let path = path.clone();
+        let gitignore = gitignore.clone();
+        let progress = Arc::clone(&progress_1);
+        let total_bytes = Arc::clone(&preflight_total_bytes);
+        let done = Arc::clone(&preflight_done);
+        std::thread::spawn(move || {
+            if let Some((num_files, num_bytes)) = preflight_add_dir(&path, &gitignore, &done) {
+                progress.set_length(num_files);
+                util::progress_bar::oxify_bar(progress, ProgressBarType::Counter);
+                let _ = total_bytes.set(num_bytes);
+            }
+        })
+    });
 
     let path = path.clone();
     let repo = repo.clone();
@@ -254,13 +812,25 @@ pub fn process_add_dir(
     use std::sync::Arc;
     let byte_counter = Arc::new(AtomicU64::new(0));
     let added_file_counter = Arc::new(AtomicU64::new(0));
+    let modified_file_counter = Arc::new(AtomicU64::new(0));
     let unchanged_file_counter = Arc::new(AtomicU64::new(0));
     let progress_1_clone = Arc::clone(&progress_1);
 
-    let mut cumulative_stats = CumulativeStats {
-        total_files: 0,
-        total_bytes: 0,
-        data_type_counts: HashMap::new(),
+    let mut cumulative_stats = CumulativeStats::default();
+
+    // One unreadable file or dir should not abort a long import, so it is skipped and reported
+    // at the end instead. With --strict the first one fails the add.
+    let skipped: Mutex<Vec<(PathBuf, String)>> = Mutex::new(vec![]);
+    let skip = |path: &Path, err: OxenError| -> Result<(), OxenError> {
+        if opts.strict {
+            return Err(err);
+        }
+        log::warn!("skipping unreadable path {:?}: {}", path, err);
+        skipped
+            .lock()
+            .unwrap()
+            .push((path.to_path_buf(), err.to_string()));
+        Ok(())
     };
 
     // If any dirs are excluded, get the dir_hashes map from the head commit
@@ -271,111 +841,301 @@ pub fn process_add_dir(
         None
     };
 
-    let conflicts: HashSet<PathBuf> = repositories::merge::list_conflicts(&repo)?
-        .into_iter()
-        .map(|conflict| conflict.merge_entry.path)
-        .collect();
-
-    let walker = WalkDir::new(&path).into_iter();
-    walker
-        .filter_entry(|e| {
-            e.file_type().is_dir()
-                && e.file_name() != OXEN_HIDDEN_DIR
-                && !oxenignore::is_ignored(e.path(), gitignore, e.file_type().is_dir())
-        })
-        .par_bridge()
-        .try_for_each(|entry| -> Result<(), OxenError> {
-            let entry = entry.unwrap();
-            let dir = entry.path();
+    // read_dir order depends on the filesystem, so --sort walks the dirs and their files by name,
+    // one at a time, to stage them in the same order on every machine
+    let mut walker = WalkDir::new(&path);
+    if opts.sort {
+        walker = walker.sort_by_file_name();
+    }
+    let dirs = walker.into_iter().filter_entry(|e| {
+        e.file_type().is_dir()
+            && e.file_name() != OXEN_HIDDEN_DIR
+            && !oxenignore::is_ignored(e.path(), gitignore, e.file_type().is_dir())
+    });
+    let process_dir = |entry: Result<walkdir::DirEntry, walkdir::Error>| -> Result<(), OxenError> {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                let err_path = err.path().unwrap_or(path.as_path()).to_path_buf();
+                return skip(&err_path, OxenError::basic_str(err.to_string()));
+            }
+        };
+        let dir = entry.path();
 
-            //println!("Entry is: {dir:?}");
+        //println!("Entry is: {dir:?}");
 
-            let dir_path = util::fs::path_relative_to_dir(dir, repo_path).unwrap();
+        let dir_path = util::fs::path_relative_to_dir(dir, repo_path).unwrap();
 
-            // Check if the dir is excluded
-            if let Some(dir_hashes) = &dir_hashes {
-                if let Some(dir_hash) = dir_hashes.get(&dir_path) {
-                    if excluded_hashes.clone().unwrap().contains(dir_hash) {
-                        //println!("Previous entry {dir:?} was excldued!");
-                        return Ok(());
-                    }
+        // Check if the dir is excluded
+        if let Some(dir_hashes) = &dir_hashes {
+            if let Some(dir_hash) = dir_hashes.get(&dir_path) {
+                if excluded_hashes.clone().unwrap().contains(dir_hash) {
+                    //println!("Previous entry {dir:?} was excldued!");
+                    return Ok(());
                 }
             }
+        }
 
-            let dir_node = maybe_load_directory(&repo, &maybe_head_commit, &dir_path).unwrap();
+        // Staged by the add that was interrupted, see `oxen add --continue`
+        if checkpoint
+            .as_ref()
+            .is_some_and(|checkpoint| checkpoint.is_done(&dir_path))
+        {
+            return Ok(());
+        }
 
-            let byte_counter_clone = Arc::clone(&byte_counter);
-            let added_file_counter_clone = Arc::clone(&added_file_counter);
-            let unchanged_file_counter_clone = Arc::clone(&unchanged_file_counter);
-            let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
+        let Some(mut entries) = read_dir_entries(dir, &skip)? else {
+            return Ok(());
+        };
 
-            // Change the closure to return a Result
-            add_dir_to_staged_db(staged_db, &dir_path, &seen_dirs)?;
+        let dir_node = maybe_load_directory(&repo, &maybe_head_commit, &dir_path).unwrap();
 
-            let entries: Vec<_> = std::fs::read_dir(dir)?.collect::<Result<_, _>>()?;
+        let byte_counter_clone = Arc::clone(&byte_counter);
+        let added_file_counter_clone = Arc::clone(&added_file_counter);
+        let modified_file_counter_clone = Arc::clone(&modified_file_counter);
+        let unchanged_file_counter_clone = Arc::clone(&unchanged_file_counter);
+        let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
 
-            entries.par_iter().for_each(|dir_entry| {
-                log::debug!("Dir Entry is: {dir_entry:?}");
-                let path = dir_entry.path();
+        // Change the closure to return a Result
+        add_dir_to_staged_db(staged_db, &dir_path, &seen_dirs)?;
 
-                let total_bytes = byte_counter_clone.load(Ordering::Relaxed);
-                let duration = start.elapsed().as_secs_f32();
-                let mbps = (total_bytes as f32 / duration) / 1_000_000.0;
+        // Directories are only recreated on checkout as the parents of files,
+        // so give an empty directory a marker file to track it by
+        if opts.track_empty_dirs
+            && !dir_path.as_os_str().is_empty()
+            && entries
+                .iter()
+                .all(|e| oxenignore::is_ignored(&e.path(), gitignore, e.path().is_dir()))
+        {
+            log::debug!("tracking empty dir {:?} with {}", dir_path, OXEN_KEEP_FILE);
+            util::fs::write_to_path(dir.join(OXEN_KEEP_FILE), "")?;
+            entries = read_dir_entries(dir, &skip)?.unwrap_or_default();
+        }
+        if opts.sort {
+            entries.sort_by_key(|e| e.file_name());
+        }
 
-                progress_1.set_message(format!(
-                    "🐂 add {} files, {} unchanged ({}) {:.2} MB/s",
+        // Find all the committed nodes for the entries in one pass over the dir node
+        let file_names: Vec<PathBuf> = entries
+            .iter()
+            .map(|e| PathBuf::from(e.file_name()))
+            .collect();
+        let file_nodes = get_file_nodes(&dir_node, &file_names)?;
+
+        let process_entry = |dir_entry: &std::fs::DirEntry| -> Result<(), OxenError> {
+            log::debug!("Dir Entry is: {dir_entry:?}");
+            let path = dir_entry.path();
+
+            let total_bytes = byte_counter_clone.load(Ordering::Relaxed);
+            let duration = start.elapsed().as_secs_f32();
+            let mbps = (total_bytes as f32 / duration) / 1_000_000.0;
+
+            let total_bytes_msg = match preflight_total_bytes.get() {
+                Some(num_bytes) => format!(" of {}", bytesize::ByteSize::b(*num_bytes)),
+                None => String::new(),
+            };
+            progress_1.set_message(format!(
+                "🐂 add {} files, {} unchanged ({}{}) {:.2} MB/s",
+                added_file_counter_clone.load(Ordering::Relaxed),
+                unchanged_file_counter_clone.load(Ordering::Relaxed),
+                bytesize::ByteSize::b(total_bytes),
+                total_bytes_msg,
+                mbps
+            ));
+            if let Some(json_progress) = &json_progress {
+                json_progress.update(
                     added_file_counter_clone.load(Ordering::Relaxed),
-                    unchanged_file_counter_clone.load(Ordering::Relaxed),
-                    bytesize::ByteSize::b(total_bytes),
-                    mbps
-                ));
+                    total_bytes,
+                );
+            }
+
+            if path.is_dir() || oxenignore::is_ignored(&path, gitignore, path.is_dir()) {
+                return Ok(());
+            }
+            progress_1.inc(1);
+
+            let file_name = Path::new(path.file_name().unwrap_or_default());
+            let mut file_status = match determine_file_status_from_node(
+                file_nodes.get(file_name).cloned(),
+                &path,
+                repo.strict_mtime(),
+                repo.normalize_line_endings(),
+            ) {
+                Ok(file_status) => file_status,
+                Err(err) => return skip(&path, err),
+            };
+            let relative_path = dir_path.join(file_name);
+            apply_add_opts(&mut file_status, &relative_path, attributes, opts);
+            // Store the version before staging, so a file that can't be read is never staged without one
+            if let Err(err) = store_chunked_version(version_store, &mut file_status, &path, opts) {
+                return skip(&path, err);
+            }
+            if !is_unmodified(&file_status.status) {
+                if let Err(err) = store_file_version(version_store, &file_status, &path) {
+                    return skip(&path, err);
+                }
+            }
 
-                if path.is_dir() || oxenignore::is_ignored(&path, gitignore, path.is_dir()) {
-                    return;
+            if file_status.status == StagedEntryStatus::Added {
+                if let Err(e) = stage_case_only_rename(&dir_node, &dir_path, &path, staged_db) {
+                    log::error!("Error staging case-only rename: {:?}", e);
                 }
+            }
 
-                let file_name = &path.file_name().unwrap_or_default().to_string_lossy();
-                let file_status =
-                    core::v_latest::add::determine_file_status(&dir_node, file_name, &path)
-                        .unwrap();
-
-                let seen_dirs_clone = Arc::clone(&seen_dirs);
-                match process_add_file(
-                    &repo,
-                    repo_path,
-                    &file_status,
-                    staged_db,
-                    &path,
-                    &seen_dirs_clone,
-                    &conflicts,
-                ) {
-                    Ok(Some(node)) => {
-                        version_store
-                            .store_version_from_path(&file_status.hash.to_string(), &path)
-                            .unwrap();
-
-                        if let EMerkleTreeNode::File(file_node) = &node.node.node {
-                            byte_counter_clone.fetch_add(file_node.num_bytes(), Ordering::Relaxed);
-                            added_file_counter_clone.fetch_add(1, Ordering::Relaxed);
+            let seen_dirs_clone = Arc::clone(&seen_dirs);
+            let result = process_add_file(
+                &repo,
+                repo_path,
+                &file_status,
+                staged_db,
+                &path,
+                &seen_dirs_clone,
+                conflicts,
+            );
+            print_add_result(printer, &relative_path, &file_status, &result);
+            match result {
+                Ok(Some(node)) => {
+                    if let EMerkleTreeNode::File(file_node) = &node.node.node {
+                        byte_counter_clone.fetch_add(file_node.num_bytes(), Ordering::Relaxed);
+                        added_file_counter_clone.fetch_add(1, Ordering::Relaxed);
+                        if node.status == StagedEntryStatus::Modified {
+                            modified_file_counter_clone.fetch_add(1, Ordering::Relaxed);
                         }
                     }
-                    Ok(None) => {
-                        unchanged_file_counter_clone.fetch_add(1, Ordering::Relaxed);
-                    }
-                    Err(e) => {
-                        log::error!("Error adding file: {:?}", e);
-                    }
                 }
-            });
+                Ok(None) => {
+                    unchanged_file_counter_clone.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(e) => {
+                    log::error!("Error adding file: {:?}", e);
+                    return skip(&path, e);
+                }
+            }
             Ok(())
-        })?;
+        };
+        if opts.sort {
+            entries.iter().try_for_each(process_entry)?;
+        } else {
+            entries.par_iter().try_for_each(process_entry)?;
+        }
 
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.complete(&dir_path, staged_db)?;
+        }
+        Ok(())
+    };
+    if opts.sort {
+        dirs.try_for_each(process_dir)?;
+    } else {
+        dirs.par_bridge().try_for_each(process_dir)?;
+    }
+
+    // Stop counting if the add beat it, so the bar is not set up after it is cleared
+    preflight_done.store(true, Ordering::Relaxed);
+    if let Some(preflight) = preflight {
+        let _ = preflight.join();
+    }
     progress_1_clone.finish_and_clear();
+    if let Some(json_progress) = &json_progress {
+        json_progress.finish(
+            added_file_counter.load(Ordering::Relaxed),
+            byte_counter.load(Ordering::Relaxed),
+        );
+    }
     cumulative_stats.total_files = added_file_counter.load(Ordering::Relaxed) as usize;
     cumulative_stats.total_bytes = byte_counter.load(Ordering::Relaxed);
+    cumulative_stats.modified_files = modified_file_counter.load(Ordering::Relaxed) as usize;
+    cumulative_stats.unchanged_files = unchanged_file_counter.load(Ordering::Relaxed) as usize;
+    cumulative_stats.skipped_paths = skipped.into_inner().unwrap();
     Ok(cumulative_stats)
 }
 
+/// The entries of a directory that could be read, the rest are passed to `skip`.
+/// None if the directory itself could not be read.
+fn read_dir_entries(
+    dir: &Path,
+    skip: &impl Fn(&Path, OxenError) -> Result<(), OxenError>,
+) -> Result<Option<Vec<std::fs::DirEntry>>, OxenError> {
+    let read_dir = match std::fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) => {
+            skip(dir, err.into())?;
+            return Ok(None);
+        }
+    };
+    let mut entries = vec![];
+    for entry in read_dir {
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(err) => skip(dir, err.into())?,
+        }
+    }
+    Ok(Some(entries))
+}
+
+/// The files an add of `paths` will look at: the files under each dir that are not ignored, and
+/// the files and missing paths as they are
+fn files_to_add(paths: &HashSet<PathBuf>, gitignore: &Option<Gitignore>) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for path in paths {
+        if !path.is_dir() {
+            if !oxenignore::is_ignored(path, gitignore, false) {
+                files.push(path.clone());
+            }
+            continue;
+        }
+        let walker = WalkDir::new(path).into_iter().filter_entry(|e| {
+            e.file_name() != OXEN_HIDDEN_DIR
+                && !oxenignore::is_ignored(e.path(), gitignore, e.file_type().is_dir())
+        });
+        for entry in walker.flatten() {
+            if !entry.file_type().is_dir() {
+                files.push(entry.into_path());
+            }
+        }
+    }
+    files
+}
+
+/// Count the files and bytes under `path` without hashing anything, so the add progress bar has a total.
+/// Returns None for trees with fewer than `ADD_PREFLIGHT_MIN_FILES` files, where a spinner is plenty,
+/// or if `done` is set before the count finishes.
+fn preflight_add_dir(
+    path: &Path,
+    gitignore: &Option<Gitignore>,
+    done: &std::sync::atomic::AtomicBool,
+) -> Option<(u64, u64)> {
+    let files = || {
+        WalkDir::new(path)
+            .into_iter()
+            .filter_entry(move |e| {
+                e.file_name() != OXEN_HIDDEN_DIR
+                    && !oxenignore::is_ignored(e.path(), gitignore, e.file_type().is_dir())
+            })
+            .flatten()
+            .filter(|e| !e.file_type().is_dir())
+            .take_while(move |_| !done.load(std::sync::atomic::Ordering::Relaxed))
+    };
+
+    // Most trees are small, so first only read the dirs until there are enough files to need a
+    // bar, and only stat every file once we know the tree is large
+    let num_small_files = files().take(ADD_PREFLIGHT_MIN_FILES as usize).count() as u64;
+    if num_small_files < ADD_PREFLIGHT_MIN_FILES {
+        return None;
+    }
+
+    let mut num_files: u64 = 0;
+    let mut num_bytes: u64 = 0;
+    for entry in files() {
+        num_files += 1;
+        num_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+    }
+    if done.load(std::sync::atomic::Ordering::Relaxed) {
+        return None;
+    }
+    Some((num_files, num_bytes))
+}
+
 fn maybe_load_directory(
     repo: &LocalRepository,
     maybe_head_commit: &Option<Commit>,
@@ -389,6 +1149,24 @@ fn maybe_load_directory(
     }
 }
 
+/// Look up the committed file nodes for all the `file_names` in a directory with one walk of the node
+fn get_file_nodes(
+    dir_node: &Option<MerkleTreeNode>,
+    file_names: &[PathBuf],
+) -> Result<HashMap<PathBuf, FileNode>, OxenError> {
+    let Some(dir_node) = dir_node else {
+        return Ok(HashMap::new());
+    };
+    Ok(dir_node
+        .get_by_paths(file_names)?
+        .into_iter()
+        .filter_map(|(path, node)| match node.node {
+            EMerkleTreeNode::File(file_node) => Some((path, file_node)),
+            _ => None,
+        })
+        .collect())
+}
+
 fn get_file_node(
     dir_node: &Option<MerkleTreeNode>,
     path: impl AsRef<Path>,
@@ -408,57 +1186,196 @@ fn get_file_node(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn add_file_inner(
     repo: &LocalRepository,
     maybe_head_commit: &Option<Commit>,
+    dir_nodes: &DirNodeCache,
     path: &Path,
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
     version_store: &Arc<dyn VersionStore>,
+    attributes: &Option<OxenAttributes>,
+    conflicts: &HashSet<PathBuf>,
+    opts: &AddOpts,
+    printer: &Option<VerbosePrinter>,
 ) -> Result<Option<StagedMerkleTreeNode>, OxenError> {
     let repo_path = &repo.path.clone();
-    let mut maybe_dir_node = None;
-    if let Some(head_commit) = maybe_head_commit {
-        let path = util::fs::path_relative_to_dir(path, repo_path)?;
-        let parent_path = path.parent().unwrap_or(Path::new(""));
-        maybe_dir_node = CommitMerkleTree::dir_with_children(repo, head_commit, parent_path)?;
+    let relative_path = util::fs::path_relative_to_dir(path, repo_path)?;
+    if relative_path.starts_with(OXEN_HIDDEN_DIR) {
+        return Err(OxenError::cannot_add_oxen_hidden_dir(&relative_path));
     }
+    let parent_path = relative_path.parent().unwrap_or(Path::new(""));
+    let maybe_dir_node = dir_nodes.get(repo, maybe_head_commit, parent_path)?;
 
     let file_name = path.file_name().unwrap_or_default().to_string_lossy();
-    let file_status = determine_file_status(&maybe_dir_node, &file_name, path)?;
-    version_store.store_version_from_path(&file_status.hash.to_string(), path)?;
+    let mut file_status = determine_file_status_with_opts(
+        &maybe_dir_node,
+        &file_name,
+        path,
+        repo.strict_mtime(),
+        repo.normalize_line_endings(),
+    )?;
+    apply_add_opts(&mut file_status, &relative_path, attributes, opts);
+    store_chunked_version(version_store, &mut file_status, path, opts)?;
+    store_file_version(version_store, &file_status, path)?;
 
-    let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
-    let conflicts: HashSet<PathBuf> = repositories::merge::list_conflicts(repo)?
-        .into_iter()
-        .map(|conflict| conflict.merge_entry.path)
-        .collect();
+    if file_status.status == StagedEntryStatus::Added {
+        stage_case_only_rename(&maybe_dir_node, parent_path, path, staged_db)?;
+    }
 
-    process_add_file(
+    let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
+    let result = process_add_file(
         repo,
         repo_path,
         &file_status,
         staged_db,
         path,
         &seen_dirs,
-        &conflicts,
-    )
+        conflicts,
+    );
+    print_add_result(printer, &relative_path, &file_status, &result);
+    result
+}
+
+/// Apply the per file options from the command line and .oxenattributes to a file about to be added
+fn apply_add_opts(
+    file_status: &mut FileStatus,
+    relative_path: &Path,
+    attributes: &Option<OxenAttributes>,
+    opts: &AddOpts,
+) {
+    file_status.skip_metadata = opts.no_metadata;
+    if let Some(executable) = opts.chmod {
+        file_status.executable = Some(executable);
+        // Changing only the mode still has to be staged
+        let previous = file_status.previous_file_node.as_ref();
+        if file_status.status == StagedEntryStatus::Unmodified
+            && previous.is_some_and(|node| node.is_executable() != executable)
+        {
+            file_status.status = StagedEntryStatus::Modified;
+        }
+    }
+    if let Some(attributes) = attributes {
+        let path_attributes = attributes.attributes_for(relative_path);
+        file_status.data_type_override = path_attributes.data_type;
+        // --no-metadata applies to every file, so the attributes can only turn extraction off
+        if path_attributes.metadata == Some(false) {
+            file_status.skip_metadata = true;
+        }
+    }
+}
+
+/// On case-insensitive filesystems (macOS, Windows) renaming `File.txt` to `file.txt` looks like a
+/// brand new file, and the old name would stay in the tree next to it. If the committed dir has a file
+/// whose name only differs by case, and that exact name is no longer on disk, stage it as removed.
+fn stage_case_only_rename(
+    maybe_dir_node: &Option<MerkleTreeNode>,
+    dir_path: &Path, // Path to the parent dir relative to the repo
+    path: &Path,     // Full path to the added file
+    staged_db: &impl KeyValWriter,
+) -> Result<(), OxenError> {
+    let Some(dir_node) = maybe_dir_node else {
+        return Ok(());
+    };
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let lowercase_name = file_name.to_lowercase();
+
+    for vnode in &dir_node.children {
+        for child in &vnode.children {
+            let EMerkleTreeNode::File(file_node) = &child.node else {
+                continue;
+            };
+            let name = file_node.name();
+            if name == file_name || name.to_lowercase() != lowercase_name {
+                continue;
+            }
+
+            // On a case-sensitive filesystem both files can exist side by side
+            if old_name_on_disk(path, name)? {
+                continue;
+            }
+
+            let old_path = dir_path.join(name);
+            log::debug!("case-only rename from {:?} to {:?}", old_path, path);
+            core::v_latest::rm::process_remove_file(&old_path, file_node, staged_db)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Path::exists is case-insensitive on some filesystems, so compare against the dir listing instead
+fn old_name_on_disk(path: &Path, name: &str) -> Result<bool, OxenError> {
+    let Some(parent) = path.parent() else {
+        return Ok(false);
+    };
+    for entry in std::fs::read_dir(parent)? {
+        if entry?.file_name().to_string_lossy() == name {
+            return Ok(true);
+        }
+    }
+    Ok(false)
 }
 
 pub fn determine_file_status(
     maybe_dir_node: &Option<MerkleTreeNode>,
     file_name: impl AsRef<str>,  // Name of the file in the repository
     data_path: impl AsRef<Path>, // Path to the data file (maybe in the version store)
+) -> Result<FileStatus, OxenError> {
+    determine_file_status_with_opts(maybe_dir_node, file_name, data_path, false, false)
+}
+
+/// Same as `determine_file_status`, but with `strict_mtime` a file whose size and mtime match the
+/// committed node is still hashed, and treated as modified if the contents changed.
+/// With `normalize_line_endings` text files are hashed with their CRLF line endings converted to LF.
+pub fn determine_file_status_with_opts(
+    maybe_dir_node: &Option<MerkleTreeNode>,
+    file_name: impl AsRef<str>,  // Name of the file in the repository
+    data_path: impl AsRef<Path>, // Path to the data file (maybe in the version store)
+    strict_mtime: bool,
+    normalize_line_endings: bool,
 ) -> Result<FileStatus, OxenError> {
     // Check if the file is already in the head commit
     let file_path = file_name.as_ref();
-    let data_path = data_path.as_ref();
     log::debug!(
         "determine_file_status data_path {:?} file_name {:?}",
-        data_path,
+        data_path.as_ref(),
         file_path
     );
     let maybe_file_node = get_file_node(maybe_dir_node, file_path)?;
+    determine_file_status_from_node(
+        maybe_file_node,
+        data_path,
+        strict_mtime,
+        normalize_line_endings,
+    )
+}
+
+/// Same as `determine_file_status_with_opts`, given the file node already looked up in the head commit
+pub fn determine_file_status_from_node(
+    maybe_file_node: Option<FileNode>,
+    data_path: impl AsRef<Path>, // Path to the data file (maybe in the version store)
+    strict_mtime: bool,
+    normalize_line_endings: bool,
+) -> Result<FileStatus, OxenError> {
+    let data_path = data_path.as_ref();
     let mut previous_oxen_metadata: Option<GenericMetadata> = None;
+    let mut normalized_line_endings = false;
+    // Returns the hash and size of the contents, converting the line endings first if enabled
+    let mut hash_contents = |metadata: &std::fs::Metadata| -> Result<(u128, u64), OxenError> {
+        if normalize_line_endings {
+            if let Some(normalized) =
+                util::hasher::get_normalized_hash_and_size(data_path, metadata)?
+            {
+                normalized_line_endings = true;
+                return Ok(normalized);
+            }
+        }
+        Ok((
+            util::hasher::get_hash_given_metadata(data_path, metadata)?,
+            metadata.len(),
+        ))
+    };
     // This is ugly - but makes sure we don't have to rehash the file if it hasn't changed
     let (status, hash, num_bytes, mtime) = if let Some(file_node) = &maybe_file_node {
         log::debug!(
@@ -470,15 +1387,25 @@ pub fn determine_file_status(
         let metadata = util::fs::metadata(data_path)?;
         let mtime = FileTime::from_last_modification_time(&metadata);
         previous_oxen_metadata = file_node.metadata();
-        if util::fs::is_modified_from_node(data_path, file_node)? {
-            log::debug!("has_different_modification_time true {}", file_node);
-            let hash = util::hasher::get_hash_given_metadata(data_path, &metadata)?;
+        let mtime_modified = util::fs::is_modified_from_node(data_path, file_node)?;
+        if mtime_modified || strict_mtime {
+            log::debug!(
+                "has_different_modification_time {} {}",
+                mtime_modified,
+                file_node
+            );
+            let (hash, num_bytes) = hash_contents(&metadata)?;
             if file_node.hash().to_u128() != hash {
                 log::debug!(
                     "has_different_modification_time hash is different true {}",
                     file_node
                 );
-                let num_bytes = metadata.len();
+                if !mtime_modified {
+                    log::warn!(
+                        "{:?} has the same mtime as the last commit but different contents",
+                        data_path
+                    );
+                }
                 (
                     StagedEntryStatus::Modified,
                     MerkleHash::new(hash),
@@ -489,7 +1416,7 @@ pub fn determine_file_status(
                 (
                     StagedEntryStatus::Unmodified,
                     MerkleHash::new(hash),
-                    file_node.num_bytes(),
+                    num_bytes,
                     mtime,
                 )
             }
@@ -504,11 +1431,11 @@ pub fn determine_file_status(
     } else {
         let metadata = util::fs::metadata(data_path)?;
         let mtime = FileTime::from_last_modification_time(&metadata);
-        let hash = util::hasher::get_hash_given_metadata(data_path, &metadata)?;
+        let (hash, num_bytes) = hash_contents(&metadata)?;
         (
             StagedEntryStatus::Added,
             MerkleHash::new(hash),
-            metadata.len(),
+            num_bytes,
             mtime,
         )
     };
@@ -521,20 +1448,85 @@ pub fn determine_file_status(
         mtime,
         previous_metadata: previous_oxen_metadata,
         previous_file_node: maybe_file_node,
+        pointer_url: None,
+        skip_metadata: false,
+        data_type_override: None,
+        normalized_line_endings,
+        executable: None,
+        chunk_hashes: vec![],
     })
 }
 
+/// For `oxen add --chunked-hash`, split a large file into content-defined chunks and store each one
+/// as its own version, so the chunks an edit did not touch are shared with the previous version.
+/// The whole file is then only stored as a manifest of its chunks, see `VersionStore::store_version_manifest`.
+fn store_chunked_version(
+    version_store: &Arc<dyn VersionStore>,
+    file_status: &mut FileStatus,
+    path: &Path,
+    opts: &AddOpts,
+) -> Result<(), OxenError> {
+    if !opts.chunked_hash
+        || file_status.num_bytes < util::chunker::CHUNKED_HASH_MIN_FILE_SIZE
+        || file_status.pointer_url.is_some()
+        || file_status.normalized_line_endings
+        || is_unmodified(&file_status.status)
+    {
+        return Ok(());
+    }
+
+    let hash = file_status.hash.to_string();
+    let mut chunk_hashes = vec![];
+    let mut file = std::fs::File::open(path)?;
+    util::chunker::for_each_chunk(&mut file, |chunk| {
+        let chunk_hash = util::hasher::hash_buffer_128bit(chunk);
+        version_store.store_version(&MerkleHash::new(chunk_hash).to_string(), chunk)?;
+        chunk_hashes.push(chunk_hash);
+        Ok(())
+    })?;
+    let chunk_hash_strs: Vec<String> = chunk_hashes
+        .iter()
+        .map(|chunk_hash| MerkleHash::new(*chunk_hash).to_string())
+        .collect();
+    version_store.store_version_manifest(&hash, &chunk_hash_strs)?;
+    file_status.chunk_hashes = chunk_hashes;
+    Ok(())
+}
+
+/// Store the contents of the file in the version store, with the line endings converted if they were when hashing
+fn store_file_version(
+    version_store: &Arc<dyn VersionStore>,
+    file_status: &FileStatus,
+    path: &Path,
+) -> Result<(), OxenError> {
+    if !file_status.chunk_hashes.is_empty() {
+        // Already stored by `store_chunked_version`
+        return Ok(());
+    }
+    let hash = file_status.hash.to_string();
+    if file_status.normalized_line_endings {
+        let bytes = util::fs::read_bytes_from_path(path)?;
+        let normalized = util::fs::crlf_to_lf(&bytes).unwrap_or(bytes);
+        version_store.store_version(&hash, &normalized)
+    } else {
+        version_store.store_version_from_path(&hash, path)
+    }
+}
+
 pub fn process_add_file(
     repo: &LocalRepository,
     repo_path: &Path,         // Path to the repository
     file_status: &FileStatus, // All the metadata including if the file is added, modified, or deleted
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
     path: &Path, // Path to the file in the repository, or path defined by the user
     seen_dirs: &Arc<Mutex<HashSet<PathBuf>>>,
     merge_conflicts: &HashSet<PathBuf>,
 ) -> Result<Option<StagedMerkleTreeNode>, OxenError> {
     log::debug!("process_add_file {:?}", path);
     let relative_path = util::fs::path_relative_to_dir(path, repo_path)?;
+    if relative_path.starts_with(OXEN_HIDDEN_DIR) {
+        return Err(OxenError::cannot_add_oxen_hidden_dir(&relative_path));
+    }
     let full_path = repo_path.join(&relative_path);
 
     if !full_path.is_file() {
@@ -556,44 +1548,99 @@ pub fn process_add_file(
 
     log::debug!("status {status:?} hash {hash:?} num_bytes {num_bytes:?} mtime {mtime:?} file_node {maybe_file_node:?}");
 
-    if let Some(_file_node) = &maybe_file_node {
-        if merge_conflicts.contains(&relative_path) {
-            log::debug!("merge conflict resolved: {relative_path:?}");
-            status = StagedEntryStatus::Modified; // Mark as modified if there's a conflict
-            repositories::merge::mark_conflict_as_resolved(repo, &relative_path)?;
-        }
+    // Empty unless the repo is in the middle of a merge, see `merge_conflict_paths`
+    if !merge_conflicts.is_empty()
+        && maybe_file_node.is_some()
+        && merge_conflicts.contains(&relative_path)
+    {
+        log::debug!("merge conflict resolved: {relative_path:?}");
+        status = StagedEntryStatus::Modified; // Mark as modified if there's a conflict
+        repositories::merge::mark_conflict_as_resolved(repo, &relative_path)?;
     }
 
     // Don't have to add the file to the staged db if it hasn't changed
-    if status == StagedEntryStatus::Unmodified {
+    if is_unmodified(&status) {
         log::debug!("file has not changed - skipping add");
         return Ok(None);
     }
 
     // Get the data type of the file
-    let mime_type = util::fs::file_mime_type(path);
-    let mut data_type = util::fs::datatype_from_mimetype(path, &mime_type);
-    let metadata = match &previous_metadata {
-        Some(previous_oxen_metadata) => {
-            let df_metadata = repositories::metadata::get_file_metadata(&full_path, &data_type)?;
-            maybe_construct_generic_metadata_for_tabular(
-                df_metadata,
-                previous_oxen_metadata.clone(),
-            )
+    let mime_type = util::fs::file_mime_type(&full_path);
+    let mut data_type = util::fs::datatype_from_mimetype(&full_path, &mime_type);
+    let file_extension = relative_path
+        .extension()
+        .unwrap_or_default()
+        .to_string_lossy();
+    // Without an extension the mime type is only a guess, so look at the contents instead
+    let mut metadata_extension = file_extension.to_string();
+    if file_extension.is_empty() {
+        if let Some((sniffed_type, sniffed_extension)) = util::fs::sniff_data_type(&full_path) {
+            log::debug!(
+                "sniffed {:?} as {} ({})",
+                relative_path,
+                sniffed_type,
+                sniffed_extension
+            );
+            data_type = sniffed_type;
+            metadata_extension = sniffed_extension.to_string();
+        }
+    }
+    if let Some(forced_type) = &file_status.data_type_override {
+        log::debug!(
+            "{:?} forced to {} by .oxenattributes",
+            relative_path,
+            forced_type
+        );
+        data_type = forced_type.clone();
+    }
+    let metadata = if let Some(url) = &file_status.pointer_url {
+        Some(GenericMetadata::MetadataPointer(MetadataPointer::new(
+            url,
+            num_bytes,
+            hash.to_string(),
+        )))
+    } else if file_status.skip_metadata {
+        // Left for `oxen backfill-metadata` to fill in later
+        None
+    } else {
+        match (&maybe_file_node, &previous_metadata) {
+            // The contents did not change (ie a resolved merge conflict), so skip reparsing the file
+            (Some(file_node), Some(previous_oxen_metadata))
+                if file_node.has_metadata_inputs(&hash, &data_type, &file_extension) =>
+            {
+                log::debug!("reusing previous metadata for {:?}", relative_path);
+                Some(previous_oxen_metadata.clone())
+            }
+            (_, Some(previous_oxen_metadata)) => {
+                let df_metadata = repositories::metadata::get_file_metadata_with_extension(
+                    &full_path,
+                    &data_type,
+                    &metadata_extension,
+                )?;
+                maybe_construct_generic_metadata_for_tabular(
+                    df_metadata,
+                    previous_oxen_metadata.clone(),
+                )
+            }
+            (_, None) => repositories::metadata::get_file_metadata_with_extension(
+                &full_path,
+                &data_type,
+                &metadata_extension,
+            )?,
         }
-        None => repositories::metadata::get_file_metadata(&full_path, &data_type)?,
     };
 
     // If the metadata is None, but the data type is tabular, we need to set the data type to binary
-    // because this means we failed to parse the metadata from the file
-    if metadata.is_none() && data_type == EntryDataType::Tabular {
+    // because this means we failed to parse the metadata from the file.
+    // When metadata was skipped on purpose, keep the type from the mime type so it can be backfilled.
+    // A type forced by .oxenattributes is always kept.
+    if metadata.is_none()
+        && data_type == EntryDataType::Tabular
+        && !file_status.skip_metadata
+        && file_status.data_type_override.is_none()
+    {
         data_type = EntryDataType::Binary;
     }
-
-    let file_extension = relative_path
-        .extension()
-        .unwrap_or_default()
-        .to_string_lossy();
     let relative_path_str = relative_path.to_str().unwrap_or_default();
     let (hash, metadata_hash, combined_hash) = if let Some(metadata) = &metadata {
         let metadata_hash = util::hasher::get_metadata_hash(&Some(metadata.clone()))?;
@@ -605,7 +1652,17 @@ pub fn process_add_file(
     } else {
         (hash, None, hash)
     };
-    let file_node = FileNode::new(
+    // Keep the recorded mode unless --chmod asks for a new one
+    let executable = file_status.executable.unwrap_or_else(|| {
+        maybe_file_node
+            .as_ref()
+            .is_some_and(|node| node.is_executable())
+    });
+    let combined_hash = MerkleHash::new(util::hasher::get_combined_hash_with_mode(
+        combined_hash.to_u128(),
+        executable,
+    ));
+    let mut file_node = FileNode::new(
         repo,
         FileNodeOpts {
             name: relative_path_str.to_string(),
@@ -621,6 +1678,11 @@ pub fn process_add_file(
             extension: file_extension.to_string(),
         },
     )?;
+    file_node.set_executable(executable);
+    if !file_status.chunk_hashes.is_empty() {
+        file_node.set_chunk_hashes(file_status.chunk_hashes.clone());
+        file_node.set_chunk_type(FileChunkType::Chunked);
+    }
 
     p_add_file_node_to_staged_db(staged_db, relative_path_str, status, &file_node, seen_dirs)
 }
@@ -646,7 +1708,7 @@ pub fn process_add_version_file(
     log::debug!("status {status:?} hash {hash:?} num_bytes {num_bytes:?} mtime {mtime:?} file_node {maybe_file_node:?}");
 
     // Don't have to add the file to the staged db if it hasn't changed
-    if status == StagedEntryStatus::Unmodified {
+    if is_unmodified(&status) {
         log::debug!("file has not changed - skipping add");
         return Ok(None);
     }
@@ -654,15 +1716,38 @@ pub fn process_add_version_file(
     // Get the data type of the file
     let mime_type = util::fs::file_mime_type(version_path);
     let mut data_type = util::fs::datatype_from_mimetype(version_path, &mime_type);
+    let file_extension = dst_path.extension().unwrap_or_default().to_string_lossy();
+    // The version file never has an extension, so parse it with the destination's, or sniff it
+    let mut metadata_extension = file_extension.to_string();
+    if file_extension.is_empty() {
+        if let Some((sniffed_type, sniffed_extension)) = util::fs::sniff_data_type(version_path) {
+            log::debug!(
+                "sniffed {:?} as {} ({})",
+                dst_path,
+                sniffed_type,
+                sniffed_extension
+            );
+            data_type = sniffed_type;
+            metadata_extension = sniffed_extension.to_string();
+        }
+    }
     let metadata = match &previous_metadata {
         Some(previous_oxen_metadata) => {
-            let df_metadata = repositories::metadata::get_file_metadata(version_path, &data_type)?;
+            let df_metadata = repositories::metadata::get_file_metadata_with_extension(
+                version_path,
+                &data_type,
+                &metadata_extension,
+            )?;
             maybe_construct_generic_metadata_for_tabular(
                 df_metadata,
                 previous_oxen_metadata.clone(),
             )
         }
-        None => repositories::metadata::get_file_metadata(version_path, &data_type)?,
+        None => repositories::metadata::get_file_metadata_with_extension(
+            version_path,
+            &data_type,
+            &metadata_extension,
+        )?,
     };
 
     // If the metadata is None, but the data type is tabular, we need to set the data type to binary
@@ -671,7 +1756,6 @@ pub fn process_add_version_file(
         data_type = EntryDataType::Binary;
     }
 
-    let file_extension = dst_path.extension().unwrap_or_default().to_string_lossy();
     let relative_path_str = dst_path.to_str().unwrap_or_default();
     let (hash, metadata_hash, combined_hash) = if let Some(metadata) = &metadata {
         let metadata_hash = util::hasher::get_metadata_hash(&Some(metadata.clone()))?;
@@ -683,7 +1767,15 @@ pub fn process_add_version_file(
     } else {
         (hash, None, hash)
     };
-    let file_node = FileNode::new(
+    // Uploads don't carry a mode, so keep the one the file was committed with
+    let executable = maybe_file_node
+        .as_ref()
+        .is_some_and(|node| node.is_executable());
+    let combined_hash = MerkleHash::new(util::hasher::get_combined_hash_with_mode(
+        combined_hash.to_u128(),
+        executable,
+    ));
+    let mut file_node = FileNode::new(
         repo,
         FileNodeOpts {
             name: relative_path_str.to_string(),
@@ -699,6 +1791,7 @@ pub fn process_add_version_file(
             extension: file_extension.to_string(),
         },
     )?;
+    file_node.set_executable(executable);
 
     p_add_file_node_to_staged_db(staged_db, relative_path_str, status, &file_node, seen_dirs)
 }
@@ -738,7 +1831,7 @@ pub fn maybe_construct_generic_metadata_for_tabular(
 
 /// Used to add a file node to the staged db in a workspace
 pub fn add_file_node_to_staged_db(
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
     relative_path: impl AsRef<Path>,
     status: StagedEntryStatus,
     file_node: &FileNode,
@@ -747,20 +1840,24 @@ pub fn add_file_node_to_staged_db(
     p_add_file_node_to_staged_db(staged_db, relative_path, status, file_node, &seen_dirs)
 }
 
+/// Unmodified entries are never written to the staged db, they would only bloat it with no-op entries
+fn is_unmodified(status: &StagedEntryStatus) -> bool {
+    *status == StagedEntryStatus::Unmodified
+}
+
 pub fn p_add_file_node_to_staged_db(
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
     relative_path: impl AsRef<Path>,
     status: StagedEntryStatus,
     file_node: &FileNode,
     seen_dirs: &Arc<Mutex<HashSet<PathBuf>>>,
 ) -> Result<Option<StagedMerkleTreeNode>, OxenError> {
     let relative_path = relative_path.as_ref();
-    log::debug!(
-        "writing {:?} [{:?}] to staged db: {:?}",
-        relative_path,
-        status,
-        staged_db.path()
-    );
+    if is_unmodified(&status) {
+        log::debug!("not writing unmodified {:?} to staged db", relative_path);
+        return Ok(None);
+    }
+    log::debug!("writing {:?} [{:?}] to staged db", relative_path, status);
     let staged_file_node = StagedMerkleTreeNode {
         status,
         node: MerkleTreeNode::from_file(file_node.clone()),
@@ -773,7 +1870,7 @@ pub fn p_add_file_node_to_staged_db(
         .unwrap();
 
     let relative_path_str = relative_path.to_str().unwrap_or_default();
-    staged_db.put(relative_path_str, &buf)?;
+    staged_db.put_entry(relative_path_str, &buf)?;
 
     // Add all the parent dirs to the staged db
     let mut parent_path = relative_path.to_path_buf();
@@ -791,7 +1888,7 @@ pub fn p_add_file_node_to_staged_db(
 }
 
 pub fn add_dir_to_staged_db(
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
     relative_path: impl AsRef<Path>,
     seen_dirs: &Arc<Mutex<HashSet<PathBuf>>>,
 ) -> Result<(), OxenError> {
@@ -810,7 +1907,7 @@ pub fn add_dir_to_staged_db(
     log::debug!("writing dir to staged db: {}", dir_entry);
     let mut buf = Vec::new();
     dir_entry.serialize(&mut Serializer::new(&mut buf)).unwrap();
-    staged_db.put(relative_path_str, &buf).unwrap();
+    staged_db.put_entry(relative_path_str, &buf)?;
     Ok(())
 }
 
@@ -822,8 +1919,506 @@ pub fn has_different_modification_time(node: &FileNode, time: &FileTime) -> bool
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::opts::RestoreOpts;
     use crate::test;
 
+    #[test]
+    fn test_add_dir_with_small_batch_size() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            util::fs::create_dir_all(data_dir.join("nested"))?;
+            for i in 0..5 {
+                util::fs::write_to_path(data_dir.join(format!("{i}.txt")), format!("{i}"))?;
+                util::fs::write_to_path(
+                    data_dir.join("nested").join(format!("{i}.txt")),
+                    format!("nested {i}"),
+                )?;
+            }
+
+            // Batches of 3 leave a partial batch at the end that has to be flushed
+            let opts = AddOpts {
+                batch_size: Some(3),
+                ..Default::default()
+            };
+            add_with_opts(&repo, &data_dir, &opts)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 10);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_continue_skips_checkpointed_dirs() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            util::fs::write_to_path(data_dir.join("done").join("a.txt"), "a")?;
+            util::fs::write_to_path(data_dir.join("todo").join("b.txt"), "b")?;
+
+            // Pretend an interrupted add already staged data/done
+            let checkpoint_path = util::fs::oxen_hidden_dir(&repo.path).join(ADD_CHECKPOINT_FILE);
+            let done_dir = Path::new("data").join("done");
+            util::fs::write_to_path(&checkpoint_path, format!("{}\n", done_dir.display()))?;
+
+            let opts = AddOpts {
+                resume: true,
+                ..Default::default()
+            };
+            add_with_opts(&repo, &data_dir, &opts)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+            assert!(status
+                .staged_files
+                .contains_key(&Path::new("data").join("todo").join("b.txt")));
+            // A finished add has nothing to resume
+            assert!(!checkpoint_path.exists());
+
+            // Without --continue everything is walked again
+            add_with_opts(&repo, &data_dir, &AddOpts::default())?;
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 2);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_track_empty_dirs() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            util::fs::create_dir_all(data_dir.join("empty"))?;
+            util::fs::write_to_path(data_dir.join("full").join("a.txt"), "a")?;
+
+            // Without the flag the empty dir is not given a marker
+            add_with_opts(&repo, &data_dir, &AddOpts::default())?;
+            assert!(!data_dir.join("empty").join(OXEN_KEEP_FILE).exists());
+
+            let opts = AddOpts {
+                track_empty_dirs: true,
+                ..Default::default()
+            };
+            add_with_opts(&repo, &data_dir, &opts)?;
+            assert!(data_dir.join("empty").join(OXEN_KEEP_FILE).exists());
+            assert!(!data_dir.join("full").join(OXEN_KEEP_FILE).exists());
+
+            let commit = repositories::commit(&repo, "Adding empty dir")?;
+            let keep_path = Path::new("data").join("empty").join(OXEN_KEEP_FILE);
+            assert!(repositories::tree::get_file_by_path(&repo, &commit, &keep_path)?.is_some());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_no_metadata_skips_metadata() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let csv_path = repo.path.join("data.csv");
+            util::fs::write_to_path(&csv_path, "a,b\n1,2\n3,4\n")?;
+
+            let opts = AddOpts {
+                no_metadata: true,
+                ..Default::default()
+            };
+            add_with_opts(&repo, &csv_path, &opts)?;
+            let commit = repositories::commit(&repo, "Adding data without metadata")?;
+
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("data.csv"))?
+                    .unwrap();
+            assert!(file_node.metadata().is_none());
+            assert_eq!(*file_node.data_type(), EntryDataType::Tabular);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_add_chmod_records_executable_bit() -> Result<(), OxenError> {
+        use std::os::unix::fs::PermissionsExt;
+
+        test::run_empty_local_repo_test(|repo| {
+            let script_path = repo.path.join("run.sh");
+            util::fs::write_to_path(&script_path, "echo hello\n")?;
+            repositories::add(&repo, &script_path)?;
+            repositories::commit(&repo, "Adding script")?;
+
+            // Only the mode changes, the contents on disk stay the same
+            let opts = AddOpts {
+                chmod: Some(true),
+                ..Default::default()
+            };
+            add_with_opts(&repo, &script_path, &opts)?;
+            let commit = repositories::commit(&repo, "Making script executable")?;
+
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("run.sh"))?.unwrap();
+            assert!(file_node.is_executable());
+
+            // Restoring the file gives it the recorded mode
+            util::fs::remove_file(&script_path)?;
+            repositories::restore::restore(&repo, RestoreOpts::from_path("run.sh"))?;
+            let mode = std::fs::metadata(&script_path)?.permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_dir_node_cache_loads_each_dir_once() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            for i in 0..10 {
+                util::fs::write_to_path(data_dir.join(format!("{i}.txt")), format!("{i}"))?;
+            }
+            repositories::add(&repo, &data_dir)?;
+            let head_commit = Some(repositories::commit(&repo, "Adding data")?);
+
+            // A flat directory of files reads its dir node once instead of once per file
+            let dir_nodes = DirNodeCache::new();
+            for _ in 0..10 {
+                let dir_node = dir_nodes.get(&repo, &head_commit, Path::new("data"))?;
+                assert!(dir_node.is_some());
+            }
+            assert_eq!(dir_nodes.num_loads(), 1);
+
+            dir_nodes.get(&repo, &head_commit, Path::new(""))?;
+            assert_eq!(dir_nodes.num_loads(), 2);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_files_reports_modified_removed_and_unchanged() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            for i in 0..4 {
+                util::fs::write_to_path(data_dir.join(format!("{i}.txt")), format!("{i}"))?;
+            }
+            add(&repo, &data_dir)?;
+            repositories::commit(&repo, "Adding data")?;
+
+            util::fs::write_to_path(data_dir.join("0.txt"), "changed")?;
+            util::fs::write_to_path(data_dir.join("new.txt"), "new")?;
+            util::fs::remove_file(data_dir.join("1.txt"))?;
+
+            let maybe_head_commit = repositories::commits::head_commit_maybe(&repo)?;
+            let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
+            let staged_db: DBWithThreadMode<MultiThreaded> =
+                DBWithThreadMode::open(&db::key_val::opts::default(), &db_path)?;
+            let version_store = repo.version_store()?;
+            let opts = AddOpts::default();
+
+            let paths = HashSet::from([data_dir.clone()]);
+            let stats = add_files(
+                &repo,
+                &maybe_head_commit,
+                &paths,
+                &staged_db,
+                &version_store,
+                &opts,
+            )?;
+            assert_eq!(stats.total_files, 2);
+            assert_eq!(stats.modified_files, 1);
+            assert_eq!(stats.added_files(), 1);
+            assert_eq!(stats.unchanged_files, 2);
+
+            let paths = HashSet::from([data_dir.join("1.txt")]);
+            let stats = add_files(
+                &repo,
+                &maybe_head_commit,
+                &paths,
+                &staged_db,
+                &version_store,
+                &opts,
+            )?;
+            assert_eq!(stats.removed_files, 1);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_files_with_a_missing_path_adds_the_rest() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            for i in 0..4 {
+                util::fs::write_to_path(data_dir.join(format!("{i}.txt")), format!("{i}"))?;
+            }
+            add(&repo, &data_dir)?;
+            repositories::commit(&repo, "Adding data")?;
+
+            util::fs::write_to_path(data_dir.join("0.txt"), "changed")?;
+            util::fs::write_to_path(data_dir.join("new.txt"), "new")?;
+            util::fs::remove_file(data_dir.join("1.txt"))?;
+
+            let maybe_head_commit = repositories::commits::head_commit_maybe(&repo)?;
+            let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
+            let staged_db: DBWithThreadMode<MultiThreaded> =
+                DBWithThreadMode::open(&db::key_val::opts::default(), &db_path)?;
+            let version_store = repo.version_store()?;
+            let (sink, lines) = std::sync::mpsc::channel();
+            let opts = AddOpts {
+                verbose: true,
+                verbose_sink: Some(sink),
+                ..Default::default()
+            };
+
+            // A removed file, a path that never existed, and files that are still there
+            let paths = HashSet::from([
+                data_dir.join("0.txt"),
+                data_dir.join("1.txt"),
+                data_dir.join("missing.txt"),
+                data_dir.join("new.txt"),
+            ]);
+            let stats = add_files(
+                &repo,
+                &maybe_head_commit,
+                &paths,
+                &staged_db,
+                &version_store,
+                &opts,
+            )?;
+            drop(opts);
+            assert_eq!(stats.total_files, 2);
+            assert_eq!(stats.modified_files, 1);
+            assert_eq!(stats.removed_files, 1);
+            assert_eq!(stats.skipped_paths.len(), 1);
+            assert_eq!(stats.skipped_paths[0].0, data_dir.join("missing.txt"));
+
+            // The files still on disk were printed and staged whatever order the paths came in,
+            // and removing the missing path left them in the working tree
+            let lines: Vec<String> = lines.iter().collect();
+            assert_eq!(lines.len(), 2);
+            assert!(data_dir.join("0.txt").exists());
+            assert!(data_dir.join("new.txt").exists());
+            drop(staged_db);
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 3);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_chunked_hash_shares_unchanged_chunks() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let mut state: u64 = 7;
+            let mut data: Vec<u8> = (0..util::chunker::CHUNKED_HASH_MIN_FILE_SIZE)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect();
+            let path = repo.path.join("large.bin");
+            util::fs::write(&path, &data)?;
+
+            let opts = AddOpts {
+                chunked_hash: true,
+                ..Default::default()
+            };
+            add_with_opts(&repo, &path, &opts)?;
+            let first_commit = repositories::commit(&repo, "Add large file")?;
+
+            data.extend_from_slice(b"appended to the end");
+            util::fs::write(&path, &data)?;
+            add_with_opts(&repo, &path, &opts)?;
+            let second_commit = repositories::commit(&repo, "Append to large file")?;
+
+            let first =
+                repositories::tree::get_file_by_path(&repo, &first_commit, Path::new("large.bin"))?
+                    .unwrap();
+            let second = repositories::tree::get_file_by_path(
+                &repo,
+                &second_commit,
+                Path::new("large.bin"),
+            )?
+            .unwrap();
+            assert_eq!(*first.chunk_type(), FileChunkType::Chunked);
+            assert!(first.chunk_hashes().len() > 1);
+            // Only the last chunk changed
+            let n = first.chunk_hashes().len();
+            assert_eq!(
+                first.chunk_hashes()[..n - 1],
+                second.chunk_hashes()[..n - 1]
+            );
+
+            // The first version is only stored as its chunks, and restores from them
+            let original = &data[..data.len() - "appended to the end".len()];
+            repositories::restore::restore(
+                &repo,
+                RestoreOpts::from_path_ref("large.bin", &first_commit.id),
+            )?;
+            assert_eq!(util::fs::read_bytes_from_path(&path)?, original);
+            Ok(())
+        })
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_add_dir_skips_unreadable_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            util::fs::write_to_path(data_dir.join("ok.txt"), "readable")?;
+            // A dangling symlink can't be read, even by root
+            std::os::unix::fs::symlink(data_dir.join("missing.txt"), data_dir.join("broken.txt"))?;
+
+            let strict_opts = AddOpts {
+                strict: true,
+                ..Default::default()
+            };
+            assert!(add_with_opts(&repo, &data_dir, &strict_opts).is_err());
+
+            add_with_opts(&repo, &data_dir, &AddOpts::default())?;
+            let status = repositories::status(&repo)?;
+            assert!(status
+                .staged_files
+                .contains_key(&Path::new("data").join("ok.txt")));
+            assert!(!status
+                .staged_files
+                .contains_key(&Path::new("data").join("broken.txt")));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_sorted_stages_the_same_entries() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let data_dir = repo.path.join("data");
+            for i in 0..5 {
+                util::fs::write_to_path(data_dir.join(format!("{i}.txt")), format!("{i}"))?;
+                util::fs::write_to_path(
+                    data_dir.join(format!("dir_{i}")).join("file.txt"),
+                    format!("nested {i}"),
+                )?;
+            }
+
+            let (sink, lines) = std::sync::mpsc::channel();
+            let opts = AddOpts {
+                sort: true,
+                verbose: true,
+                verbose_sink: Some(sink),
+                ..Default::default()
+            };
+            add_with_opts(&repo, &data_dir, &opts)?;
+            drop(opts);
+
+            // The files of a dir are staged by name before its sub dirs, which are walked by name
+            let mut expected = Vec::new();
+            for i in 0..5 {
+                expected.push(Path::new("data").join(format!("{i}.txt")));
+            }
+            for i in 0..5 {
+                expected.push(Path::new("data").join(format!("dir_{i}")).join("file.txt"));
+            }
+            let printed: Vec<PathBuf> = lines
+                .iter()
+                .map(|line| PathBuf::from(line.split_whitespace().last().unwrap()))
+                .collect();
+            assert_eq!(printed, expected);
+
+            let status = repositories::status(&repo)?;
+            let mut staged: Vec<PathBuf> = status.staged_files.keys().cloned().collect();
+            staged.sort();
+            expected.sort();
+            assert_eq!(staged, expected);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_respects_oxenattributes() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(
+                repo.path.join(".oxenattributes"),
+                "*.log data_type=tabular\nraw/*.csv -metadata\n",
+            )?;
+            util::fs::write_to_path(repo.path.join("run.log"), "plain text\n")?;
+            let raw_dir = repo.path.join("raw");
+            util::fs::create_dir_all(&raw_dir)?;
+            util::fs::write_to_path(raw_dir.join("data.csv"), "a,b\n1,2\n")?;
+
+            add(&repo, &repo.path)?;
+            let commit = repositories::commit(&repo, "Adding with attributes")?;
+
+            // The type is forced, even though the file does not parse as a table
+            let log_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("run.log"))?
+                    .unwrap();
+            assert_eq!(*log_node.data_type(), EntryDataType::Tabular);
+
+            let csv_node = repositories::tree::get_file_by_path(
+                &repo,
+                &commit,
+                Path::new("raw").join("data.csv"),
+            )?
+            .unwrap();
+            assert!(csv_node.metadata().is_none());
+            assert_eq!(*csv_node.data_type(), EntryDataType::Tabular);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_without_merge_does_not_create_conflict_db() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(repo.path.join("hello.txt"), "hello")?;
+            add(&repo, &repo.path)?;
+
+            assert!(merge_conflict_paths(&repo)?.is_empty());
+            assert!(!core::merge::db_path(&repo).exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_verbose_stages_every_file() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let nested = repo.path.join("images").join("train");
+            util::fs::create_dir_all(&nested)?;
+            for i in 0..20 {
+                util::fs::write_to_path(nested.join(format!("{i}.txt")), format!("file {i}"))?;
+            }
+            util::fs::write_to_path(repo.path.join("README.md"), "readme")?;
+
+            let (sink, lines) = std::sync::mpsc::channel();
+            let opts = AddOpts {
+                verbose: true,
+                verbose_sink: Some(sink),
+                ..Default::default()
+            };
+            add_with_opts(&repo, &repo.path, &opts)?;
+            drop(opts);
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 21);
+
+            // One "Added <hash> <path>" line per staged file
+            let lines: Vec<String> = lines.iter().collect();
+            assert_eq!(lines.len(), 21);
+            let mut printed = HashSet::new();
+            for line in &lines {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                assert_eq!(fields.len(), 3, "unexpected line {line:?}");
+                assert_eq!(fields[0], "Added");
+                printed.insert(PathBuf::from(fields[2]));
+            }
+            let staged: HashSet<PathBuf> = status.staged_files.keys().cloned().collect();
+            assert_eq!(printed, staged);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_add_respects_oxenignore() -> Result<(), OxenError> {
         test::run_empty_local_repo_test(|repo| {
@@ -866,6 +2461,77 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_unmodified_entries_are_never_staged() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello World")?;
+            add(&repo, &hello_file)?;
+            repositories::commit(&repo, "Adding hello")?;
+
+            let head_commit = repositories::commits::head_commit(&repo)?;
+            let file_node = repositories::entries::get_file(&repo, &head_commit, "hello.txt")?
+                .expect("hello.txt should be committed");
+
+            let opts = db::key_val::opts::default();
+            let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
+            let staged_db: DBWithThreadMode<MultiThreaded> =
+                DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
+
+            let result = add_file_node_to_staged_db(
+                &staged_db,
+                "hello.txt",
+                StagedEntryStatus::Unmodified,
+                &file_node,
+            )?;
+            assert!(result.is_none());
+            assert!(staged_db.get("hello.txt")?.is_none());
+            // Parent dirs should not be written either
+            assert!(staged_db.get("")?.is_none());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_case_only_rename() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let upper_file = repo.path.join("File.txt");
+            util::fs::write_to_path(&upper_file, "Hello World")?;
+            add(&repo, &upper_file)?;
+            repositories::commit(&repo, "Adding File.txt")?;
+
+            // Without rename detection the commit would contain both File.txt and file.txt
+            let lower_file = repo.path.join("file.txt");
+            std::fs::rename(&upper_file, &lower_file)?;
+            add(&repo, &lower_file)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(
+                status
+                    .staged_files
+                    .get(Path::new("File.txt"))
+                    .unwrap()
+                    .status,
+                StagedEntryStatus::Removed
+            );
+            assert_eq!(
+                status
+                    .staged_files
+                    .get(Path::new("file.txt"))
+                    .unwrap()
+                    .status,
+                StagedEntryStatus::Added
+            );
+
+            let commit = repositories::commit(&repo, "Renaming to file.txt")?;
+            assert!(repositories::entries::get_file(&repo, &commit, "File.txt")?.is_none());
+            assert!(repositories::entries::get_file(&repo, &commit, "file.txt")?.is_some());
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_add_respects_dir_ignore_patterns() -> Result<(), OxenError> {
         test::run_empty_local_repo_test(|repo| {
@@ -1,5 +1,6 @@
 use indicatif::{ProgressBar, ProgressStyle};
 
+use crate::core::status_index;
 use crate::core::v_latest::fetch;
 use crate::core::v_latest::index::restore::{self, FileToRestore};
 use crate::core::v_latest::index::CommitMerkleTree;
@@ -180,6 +181,9 @@ pub async fn checkout_subtrees(
     subtree_paths: &[PathBuf],
     depth: i32,
 ) -> Result<(), OxenError> {
+    // Checking out the whole tree leaves every dir matching the target commit, so the
+    // `status --fast` index can be carried over to it
+    let whole_tree = depth == i32::MAX && subtree_paths.iter().all(|path| path == Path::new(""));
     for subtree_path in subtree_paths {
         let Some(target_root) = repositories::tree::get_subtree_by_depth(
             repo,
@@ -217,6 +221,13 @@ pub async fn checkout_subtrees(
         };
 
         // TODO: The below logic (probably) can be merged with using set_working_repo_to_commit
+        // When only a subtree is moved to the target commit, the dirs outside of it can't be
+        // carried over
+        let mtime_index = match &maybe_from_commit {
+            Some(from_commit) if whole_tree => status_index::read(repo, &from_commit.id),
+            _ => None,
+        };
+        status_index::clear(repo)?;
         let mut progress = CheckoutProgressBar::new(to_commit.id.clone());
         let parent_path = subtree_path.parent().unwrap_or(Path::new(""));
         let mut results = CheckoutResult::new();
@@ -255,7 +266,11 @@ pub async fn checkout_subtrees(
             ));
         }
 
+        // Files skipped by `oxen pull --blobless` are downloaded now
+        fetch::fetch_missing_versions(repo, &results.files_to_restore).await?;
+
         let version_store = repo.version_store()?;
+        let mut written = Vec::with_capacity(results.files_to_restore.len());
         for file_to_restore in results.files_to_restore {
             restore::restore_file(
                 repo,
@@ -263,7 +278,9 @@ pub async fn checkout_subtrees(
                 &file_to_restore.path,
                 &version_store,
             )?;
+            written.push(file_to_restore.path);
         }
+        status_index::carry_over(repo, mtime_index, &to_commit.id, &written)?;
     }
 
     Ok(())
@@ -336,6 +353,13 @@ pub async fn set_working_repo_to_commit(
         None
     };
 
+    // The working tree is about to change under the dirs `status --fast` remembered as clean,
+    // drop the index until it is carried over to the target commit below
+    let mtime_index = maybe_from_commit
+        .as_ref()
+        .and_then(|from_commit| status_index::read(repo, &from_commit.id));
+    status_index::clear(repo)?;
+
     let mut results = CheckoutResult::new();
     let mut hashes = CheckoutHashes::from_hashes(shared_hashes);
 
@@ -365,7 +389,11 @@ pub async fn set_working_repo_to_commit(
         cleanup_removed_files(repo, &from_tree.unwrap(), &mut progress, &mut hashes)?;
     }
 
+    // Files skipped by `oxen pull --blobless` are downloaded now
+    fetch::fetch_missing_versions(repo, &results.files_to_restore).await?;
+
     let version_store = repo.version_store()?;
+    let mut written = Vec::with_capacity(results.files_to_restore.len());
     for file_to_restore in results.files_to_restore {
         restore::restore_file(
             repo,
@@ -373,7 +401,9 @@ pub async fn set_working_repo_to_commit(
             &file_to_restore.path,
             &version_store,
         )?;
+        written.push(file_to_restore.path);
     }
+    status_index::carry_over(repo, mtime_index, &to_commit.id, &written)?;
 
     Ok(())
 }
@@ -437,7 +467,12 @@ fn r_remove_if_not_in_target(
                 let full_path = repo.path.join(&file_path);
                 // Before staging for removal, verify the path exists, doesn't refer to a different file in the target tree, and isn't modified
                 if full_path.exists() && !hashes.seen_paths.contains(&file_path) {
-                    if util::fs::is_modified_from_node(&full_path, file_node)? {
+                    if util::fs::is_modified_from_node_with_opts(
+                        &full_path,
+                        file_node,
+                        repo.strict_mtime(),
+                        repo.normalize_line_endings(),
+                    )? {
                         cannot_overwrite_entries.push(file_path.clone());
                     } else {
                         paths_to_remove.push(full_path.clone());
@@ -4,13 +4,17 @@ use std::path::Path;
 use glob::Pattern;
 use time::OffsetDateTime;
 
+use crate::config::UserConfig;
 use crate::core;
 use crate::core::refs::with_ref_manager;
+use crate::core::v_latest::add::CumulativeStats;
 use crate::error::OxenError;
 use crate::model::merkle_tree::node::commit_node::CommitNodeOpts;
-use crate::model::merkle_tree::node::{CommitNode, EMerkleTreeNode};
-use crate::model::{Commit, LocalRepository, MerkleHash, User};
+use crate::model::merkle_tree::node::{CommitNode, EMerkleTreeNode, FileNode, MerkleTreeNode};
+use crate::model::{Commit, LocalRepository, MerkleHash, NewCommit, User};
 use crate::opts::PaginateOpts;
+use crate::repositories::commits::commit_writer;
+use crate::storage::version_store::VersionStore;
 use crate::view::{PaginatedCommits, StatusMessage};
 use crate::{repositories, util};
 
@@ -178,6 +182,108 @@ pub fn get_by_hash(repo: &LocalRepository, hash: &MerkleHash) -> Result<Option<C
     Ok(Some(commit.to_commit()))
 }
 
+/// Walk the merkle tree of a commit and return a description of the first inconsistency found, None
+/// if the tree is intact. Every dir and vnode must be stored under the hash its parent references,
+/// every file's combined hash must match its content and metadata hashes, and every file's version
+/// must be in the version store, unless it is a pointer or the repo is shallow.
+///
+/// Dir hashes are not recomputed: they are hashed from the staged entries at commit time and vnode
+/// ids can include a random component, so the committed children are not enough to reproduce them.
+pub fn verify(repo: &LocalRepository, commit: &Commit) -> Result<Option<String>, OxenError> {
+    let commit_hash = MerkleHash::from_str(&commit.id)?;
+    let version_store = repo.version_store()?;
+    let check_versions = !repo.is_shallow();
+    verify_node(
+        repo,
+        &version_store,
+        check_versions,
+        &commit_hash,
+        Path::new(""),
+    )
+}
+
+fn verify_node(
+    repo: &LocalRepository,
+    version_store: &std::sync::Arc<dyn VersionStore>,
+    check_versions: bool,
+    hash: &MerkleHash,
+    path: &Path,
+) -> Result<Option<String>, OxenError> {
+    if !MerkleNodeDB::exists(repo, hash) {
+        return Ok(Some(format!("missing tree node {hash} for {path:?}")));
+    }
+    let node = MerkleTreeNode::from_hash(repo, hash)?;
+    if node.node.hash() != hash {
+        return Ok(Some(format!(
+            "tree node {hash} for {path:?} is stored with hash {}",
+            node.node.hash()
+        )));
+    }
+
+    let mut node_db = MerkleNodeDB::open_read_only(repo, hash)?;
+    for (child_hash, child) in node_db.map()? {
+        let problem = match &child.node {
+            EMerkleTreeNode::Directory(dir_node) => verify_node(
+                repo,
+                version_store,
+                check_versions,
+                &child_hash,
+                &path.join(dir_node.name()),
+            )?,
+            EMerkleTreeNode::VNode(_) => {
+                verify_node(repo, version_store, check_versions, &child_hash, path)?
+            }
+            EMerkleTreeNode::File(file_node) => verify_file(
+                version_store,
+                check_versions,
+                file_node,
+                &path.join(file_node.name()),
+            )?,
+            _ => None,
+        };
+        if problem.is_some() {
+            return Ok(problem);
+        }
+    }
+    Ok(None)
+}
+
+fn verify_file(
+    version_store: &std::sync::Arc<dyn VersionStore>,
+    check_versions: bool,
+    file_node: &FileNode,
+    path: &Path,
+) -> Result<Option<String>, OxenError> {
+    // Same formula as process_add_file
+    let hash = file_node.hash();
+    let expected_combined_hash = match file_node.metadata_hash() {
+        Some(metadata_hash) => MerkleHash::new(util::hasher::get_combined_hash(
+            Some(metadata_hash.to_u128()),
+            hash.to_u128(),
+        )?),
+        None => *hash,
+    };
+    let expected_combined_hash = MerkleHash::new(util::hasher::get_combined_hash_with_mode(
+        expected_combined_hash.to_u128(),
+        file_node.is_executable(),
+    ));
+    if *file_node.combined_hash() != expected_combined_hash {
+        return Ok(Some(format!(
+            "file {path:?} has combined hash {} but its hash and metadata hash give {}",
+            file_node.combined_hash(),
+            expected_combined_hash
+        )));
+    }
+
+    if check_versions
+        && !file_node.is_pointer()
+        && !version_store.version_exists(&hash.to_string())?
+    {
+        return Ok(Some(format!("missing version file {hash} for {path:?}")));
+    }
+    Ok(None)
+}
+
 pub fn create_empty_commit(
     repo: &LocalRepository,
     branch_name: impl AsRef<str>,
@@ -225,6 +331,97 @@ pub fn create_empty_commit(
     Ok(commit_node.to_commit())
 }
 
+/// Replace the commits `from`..=`to` on the current branch with one commit that has the tree of `to`
+pub fn squash(
+    repo: &LocalRepository,
+    from: &Commit,
+    to: &Commit,
+    message: impl AsRef<str>,
+) -> Result<Commit, OxenError> {
+    let message = message.as_ref();
+    let Some(branch) = repositories::branches::current_branch(repo)? else {
+        return Err(OxenError::basic_str(
+            "Cannot squash in a detached HEAD state, checkout a branch first",
+        ));
+    };
+    // Squashing below the tip would drop the commits that come after `to`
+    if branch.commit_id != to.id {
+        return Err(OxenError::basic_str(format!(
+            "Can only squash up to the head of branch {}, {} is not the head",
+            branch.name, to.id
+        )));
+    }
+
+    let commits = list_between(repo, from, to)?;
+    if commits.last() != Some(from) {
+        return Err(OxenError::basic_str(format!(
+            "Commit {} is not an ancestor of {}",
+            from.id, to.id
+        )));
+    }
+    if let Some(merge) = commits.iter().find(|c| c.parent_ids.len() > 1) {
+        return Err(OxenError::basic_str(format!(
+            "Cannot squash across merge commit {}",
+            merge.id
+        )));
+    }
+
+    let to_id = to.hash()?;
+    let to_node = repositories::tree::get_node_by_id_with_children(repo, &to_id)?.ok_or(
+        OxenError::basic_str(format!(
+            "Merkle tree node not found for commit: '{}'",
+            to.id
+        )),
+    )?;
+
+    let cfg = UserConfig::get()?;
+    let timestamp = OffsetDateTime::now_utc();
+    let new_commit = NewCommit {
+        parent_ids: from.parent_ids.clone(),
+        message: message.to_string(),
+        author: cfg.name.clone(),
+        email: cfg.email.clone(),
+        timestamp,
+    };
+    let commit_id = commit_writer::compute_commit_id(&new_commit)?;
+    let parent_ids = from
+        .parent_ids
+        .iter()
+        .map(|id| MerkleHash::from_str(id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let commit_node = CommitNode::new(
+        repo,
+        CommitNodeOpts {
+            hash: commit_id,
+            parent_ids: parent_ids.clone(),
+            email: new_commit.email,
+            author: new_commit.author,
+            message: new_commit.message,
+            timestamp,
+        },
+    )?;
+
+    // The squashed commit points at the same root dir as `to`
+    let mut commit_db =
+        MerkleNodeDB::open_read_write(repo, &commit_node, parent_ids.first().copied())?;
+    let dir_node = to_node
+        .children
+        .first()
+        .ok_or(OxenError::basic_str(format!(
+            "Commit {} has no root directory",
+            to.id
+        )))?
+        .dir()?;
+    commit_db.add_child(&dir_node)?;
+    repositories::tree::cp_dir_hashes_to(repo, &to_id, commit_node.hash())?;
+
+    with_ref_manager(repo, |manager| {
+        manager.set_branch_commit_id(&branch.name, commit_node.hash().to_string())
+    })?;
+
+    Ok(commit_node.to_commit())
+}
+
 /// List commits on the current branch from HEAD
 pub fn list(repo: &LocalRepository) -> Result<Vec<Commit>, OxenError> {
     let mut results = vec![];
@@ -345,6 +542,44 @@ pub fn list_from(
     Ok(results)
 }
 
+/// List the history from `revision`, stopping at commits whose parents were not fetched because
+/// the repository was cloned with a depth. Returns the commits and whether that boundary was hit.
+/// A missing parent in a repository with its full history is an error rather than the end of the log.
+pub fn list_from_until_shallow(
+    repo: &LocalRepository,
+    revision: impl AsRef<str>,
+) -> Result<(Vec<Commit>, bool), OxenError> {
+    let revision = revision.as_ref();
+    let commit = repositories::revisions::get(repo, revision)?
+        .ok_or(OxenError::revision_not_found(revision.into()))?;
+
+    let mut results = vec![];
+    let mut visited = HashSet::new();
+    let mut reached_boundary = false;
+    let mut stack = vec![commit];
+    while let Some(commit) = stack.pop() {
+        if !visited.insert(commit.id.clone()) {
+            continue;
+        }
+        // Push in reverse so the first parent is walked first, like list_recursive
+        for parent_id in commit.parent_ids.iter().rev() {
+            let parent_hash = MerkleHash::from_str(parent_id)?;
+            match get_by_hash(repo, &parent_hash)? {
+                Some(parent) => stack.push(parent),
+                None if repo.depth().is_some() => reached_boundary = true,
+                None => {
+                    return Err(OxenError::basic_str(format!(
+                        "Parent {} of commit {} is missing, the repository may be corrupt",
+                        parent_id, commit.id
+                    )));
+                }
+            }
+        }
+        results.push(commit);
+    }
+    Ok((results, reached_boundary))
+}
+
 /// Get commit history given a revision (branch name or commit id)
 pub fn list_from_with_depth(
     repo: &LocalRepository,
@@ -402,6 +637,35 @@ pub fn list_between(
     Ok(results)
 }
 
+/// Count the files, bytes and files per data type under `path` at `commit`
+pub fn dir_stats(
+    repo: &LocalRepository,
+    commit: &Commit,
+    path: impl AsRef<Path>,
+) -> Result<CumulativeStats, OxenError> {
+    let path = path.as_ref();
+    let node = repositories::tree::get_node_by_path_with_children(repo, commit, path)?
+        .ok_or(OxenError::path_does_not_exist(path))?;
+    let file_nodes = match node.node {
+        EMerkleTreeNode::File(file_node) => vec![file_node],
+        _ => repositories::tree::list_all_files(&node)?
+            .into_iter()
+            .map(|file| file.file_node)
+            .collect(),
+    };
+
+    let mut stats = CumulativeStats::default();
+    for file_node in file_nodes {
+        stats.total_files += 1;
+        stats.total_bytes += file_node.num_bytes();
+        *stats
+            .data_type_counts
+            .entry(file_node.data_type().clone())
+            .or_insert(0) += 1;
+    }
+    Ok(stats)
+}
+
 /// Retrieve entries with filepaths matching a provided glob pattern
 pub fn search_entries(
     repo: &LocalRepository,
@@ -424,6 +688,99 @@ pub fn search_entries(
     Ok(results)
 }
 
+/// Retrieve directories with paths matching a provided glob pattern
+pub fn search_dirs(
+    repo: &LocalRepository,
+    commit: &Commit,
+    pattern: impl AsRef<str>,
+) -> Result<HashSet<PathBuf>, OxenError> {
+    let pattern = Pattern::new(pattern.as_ref())?;
+
+    let tree = repositories::tree::get_root_with_children(repo, commit)?
+        .ok_or(OxenError::basic_str("Root not found"))?;
+    let (_, dirs) = repositories::tree::list_files_and_dirs(&tree)?;
+    Ok(dirs
+        .into_iter()
+        .map(|dir| dir.path)
+        .filter(|path| !path.as_os_str().is_empty() && pattern.matches_path(path))
+        .collect())
+}
+
+/// List the commits that changed the file at `path`, newest to oldest, starting from HEAD.
+/// With `follow_renames`, the history continues under the old path when the file first
+/// appears in a commit that removed a file with the same contents.
+pub fn history_for_path(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+    follow_renames: bool,
+) -> Result<Vec<Commit>, OxenError> {
+    let Some(head_commit) = repositories::commits::head_commit_maybe(repo)? else {
+        return Ok(vec![]);
+    };
+
+    let mut path = path.as_ref().to_path_buf();
+    let mut results = vec![];
+    for commit in list_from(repo, &head_commit.id)? {
+        let Some(file_node) = repositories::tree::get_file_by_path(repo, &commit, &path)? else {
+            continue;
+        };
+
+        let parent_commit = match commit.parent_ids.first() {
+            Some(parent_id) => repositories::commits::get_by_id(repo, parent_id)?,
+            None => None,
+        };
+        let Some(parent_commit) = parent_commit else {
+            results.push(commit);
+            continue;
+        };
+
+        match repositories::tree::get_file_by_path(repo, &parent_commit, &path)? {
+            Some(parent_node) => {
+                if parent_node.hash() != file_node.hash() {
+                    results.push(commit);
+                }
+            }
+            None => {
+                if follow_renames {
+                    if let Some(old_path) =
+                        find_renamed_from(repo, &commit, &parent_commit, file_node.hash())?
+                    {
+                        log::debug!("history_for_path {:?} renamed from {:?}", path, old_path);
+                        path = old_path;
+                    }
+                }
+                results.push(commit);
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Find a file in the parent with the same contents that no longer exists in the commit
+fn find_renamed_from(
+    repo: &LocalRepository,
+    commit: &Commit,
+    parent_commit: &Commit,
+    hash: &MerkleHash,
+) -> Result<Option<PathBuf>, OxenError> {
+    let tree = repositories::tree::get_root_with_children(repo, parent_commit)?
+        .ok_or(OxenError::basic_str("Root not found"))?;
+    let (files, _) = repositories::tree::list_files_and_dirs(&tree)?;
+    let mut candidates: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|file| file.file_node.hash() == hash)
+        .map(|file| file.dir.join(file.file_node.name()))
+        .collect();
+    candidates.sort();
+
+    for candidate in candidates {
+        if repositories::tree::get_file_by_path(repo, commit, &candidate)?.is_none() {
+            return Ok(Some(candidate));
+        }
+    }
+    Ok(None)
+}
+
 /// List commits by path (directory or file) recursively
 pub fn list_by_path_recursive(
     repo: &LocalRepository,
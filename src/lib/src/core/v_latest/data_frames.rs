@@ -7,7 +7,7 @@ use crate::model::metadata::generic_metadata::GenericMetadata;
 use crate::model::metadata::metadata_tabular::MetadataTabularImpl;
 use crate::model::{Commit, DataFrameSize, LocalRepository, Schema, Workspace};
 use crate::opts::DFOpts;
-use crate::{repositories, util};
+use crate::repositories;
 use polars::prelude::IntoLazy as _;
 
 use std::path::Path;
@@ -50,9 +50,10 @@ pub fn get_slice(
     if let Ok(response) = handle_sql_result {
         return Ok(response);
     }
-    // Read the data frame from the version path
-    let version_path = util::fs::version_path_from_hash(repo, file_node.hash().to_string());
-    let df = tabular::read_df_with_extension(version_path, file_node.extension(), opts)?;
+    // Read the data frame from the version file
+    let version_file =
+        repositories::revisions::get_version_file_from_node(repo, &file_node, &path)?;
+    let df = tabular::read_df_with_extension(&version_file, file_node.extension(), opts)?;
     log::debug!("get_slice df {:?}", df.height());
 
     // Check what the view height is
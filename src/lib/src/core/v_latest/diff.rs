@@ -4,13 +4,16 @@ use crate::model::diff::diff_entry_status::DiffEntryStatus;
 use crate::model::diff::diff_file_node::DiffFileNode;
 use crate::model::diff::generic_diff_summary::GenericDiffSummary;
 use crate::model::diff::AddRemoveModifyCounts;
-use crate::model::merkle_tree::node::{DirNodeWithPath, FileNode, FileNodeWithDir};
-use crate::model::{Commit, DiffEntry, LocalRepository};
+use crate::model::diff::ChangeType;
+use crate::model::merkle_tree::node::{
+    DirNodeWithPath, EMerkleTreeNode, FileNode, FileNodeWithDir, MerkleTreeNode,
+};
+use crate::model::{Commit, DiffEntry, LocalRepository, MerkleHash};
 use crate::opts::DFOpts;
 use crate::repositories;
 use crate::util;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -487,6 +490,109 @@ pub fn get_dir_diff_entry_with_summary(
     }
 }
 
+/// Walk the merkle trees of two commits side by side, skipping any subtrees whose hashes match,
+/// and return every file that was added, removed or modified going from `base_commit` to `head_commit`.
+/// The hash is the file's content hash in `head_commit`, or in `base_commit` if it was removed.
+pub fn changed_entries(
+    repo: &LocalRepository,
+    base_commit: &Commit,
+    head_commit: &Commit,
+) -> Result<Vec<(PathBuf, ChangeType, MerkleHash)>, OxenError> {
+    let base_root = repositories::tree::get_root_with_children(repo, base_commit)?
+        .ok_or(OxenError::commit_id_does_not_exist(&base_commit.id))?;
+    let head_root = repositories::tree::get_root_with_children(repo, head_commit)?
+        .ok_or(OxenError::commit_id_does_not_exist(&head_commit.id))?;
+    let base_dir = repositories::tree::get_root_dir(&base_root)?;
+    let head_dir = repositories::tree::get_root_dir(&head_root)?;
+
+    let mut changes = Vec::new();
+    r_changed_entries(Some(base_dir), Some(head_dir), Path::new(""), &mut changes)?;
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(changes)
+}
+
+fn r_changed_entries(
+    base_dir: Option<&MerkleTreeNode>,
+    head_dir: Option<&MerkleTreeNode>,
+    path: &Path,
+    changes: &mut Vec<(PathBuf, ChangeType, MerkleHash)>,
+) -> Result<(), OxenError> {
+    if let (Some(base_dir), Some(head_dir)) = (base_dir, head_dir) {
+        if base_dir.hash == head_dir.hash {
+            return Ok(());
+        }
+    }
+
+    let base_children = dir_children_by_name(base_dir);
+    let head_children = dir_children_by_name(head_dir);
+    let names: HashSet<&String> = base_children.keys().chain(head_children.keys()).collect();
+
+    for name in names {
+        let child_path = path.join(name);
+        let base_child = base_children.get(name).copied();
+        let head_child = head_children.get(name).copied();
+        let base_file = base_child.and_then(|node| node.file().ok());
+        let head_file = head_child.and_then(|node| node.file().ok());
+
+        match (&base_file, &head_file) {
+            (Some(base_file), Some(head_file)) => {
+                if base_file.hash() != head_file.hash() {
+                    changes.push((child_path.clone(), ChangeType::Modified, *head_file.hash()));
+                }
+            }
+            (Some(base_file), None) => {
+                changes.push((child_path.clone(), ChangeType::Removed, *base_file.hash()));
+            }
+            (None, Some(head_file)) => {
+                changes.push((child_path.clone(), ChangeType::Added, *head_file.hash()));
+            }
+            (None, None) => {}
+        }
+
+        // A path can switch between a file and a directory, so recurse into whichever side is a dir
+        let base_sub_dir = base_child.filter(|node| node.is_dir());
+        let head_sub_dir = head_child.filter(|node| node.is_dir());
+        if base_sub_dir.is_some() || head_sub_dir.is_some() {
+            r_changed_entries(base_sub_dir, head_sub_dir, &child_path, changes)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Collect the direct files and dirs of a directory node, looking through the vnodes
+fn dir_children_by_name(dir: Option<&MerkleTreeNode>) -> BTreeMap<String, &MerkleTreeNode> {
+    let mut children = BTreeMap::new();
+    let Some(dir) = dir else {
+        return children;
+    };
+    for child in &dir.children {
+        match &child.node {
+            EMerkleTreeNode::VNode(_) => {
+                for grandchild in &child.children {
+                    if let Some(name) = node_name(grandchild) {
+                        children.insert(name, grandchild);
+                    }
+                }
+            }
+            _ => {
+                if let Some(name) = node_name(child) {
+                    children.insert(name, child);
+                }
+            }
+        }
+    }
+    children
+}
+
+fn node_name(node: &MerkleTreeNode) -> Option<String> {
+    match &node.node {
+        EMerkleTreeNode::File(file_node) => Some(file_node.name().to_string()),
+        EMerkleTreeNode::Directory(dir_node) => Some(dir_node.name().to_string()),
+        _ => None,
+    }
+}
+
 pub fn diff_entries(
     repo: &LocalRepository,
     file_path: impl AsRef<Path>,
@@ -1,14 +1,20 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
+use futures::StreamExt;
+use tokio::sync::Semaphore;
+
 use crate::constants::{AVG_CHUNK_SIZE, OXEN_HIDDEN_DIR};
 use crate::core;
 use crate::core::refs::with_ref_manager;
+use crate::core::v_latest::index::restore::FileToRestore;
 use crate::error::OxenError;
 use crate::model::entry::commit_entry::Entry;
 use crate::model::merkle_tree::node::{EMerkleTreeNode, FileNodeWithDir, MerkleTreeNode};
+use crate::model::metadata::MetadataPointer;
 use crate::model::{Branch, Commit, CommitEntry};
 use crate::model::{LocalRepository, MerkleHash, RemoteBranch, RemoteRepository};
 use crate::repositories;
@@ -24,18 +30,19 @@ pub async fn fetch_remote_branch(
     fetch_opts: &FetchOpts,
 ) -> Result<Branch, OxenError> {
     log::debug!(
-        "fetching remote branch {} --all {} --subtree {:?} --depth {:?}",
+        "fetching remote branch {} --all {} --subtree {:?} --depth {:?} --blobless {}",
         fetch_opts.branch,
         fetch_opts.all,
         fetch_opts.subtree_paths,
         fetch_opts.depth,
+        fetch_opts.blobless,
     );
 
     // Start the timer
     let start = std::time::Instant::now();
 
     // Keep track of how many bytes we have downloaded
-    let pull_progress = Arc::new(PullProgress::new());
+    let pull_progress = Arc::new(PullProgress::new().with_format(fetch_opts.progress));
     pull_progress.set_message(format!("Fetching remote branch {}", fetch_opts.branch));
 
     // Find the head commit on the remote branch
@@ -88,6 +95,20 @@ pub async fn fetch_remote_branch(
         }
     }
 
+    if fetch_opts.blobless {
+        // Leave the commits unsynced so a later full pull or checkout downloads their files
+        pull_progress.finish();
+        repo.write_is_shallow(true)?;
+        if fetch_opts.should_update_branch_head {
+            repositories::branches::update(repo, &fetch_opts.branch, &remote_branch.commit_id)?;
+        }
+        println!(
+            "🐂 oxen fetched commits and trees for {} without file contents",
+            fetch_opts.branch
+        );
+        return Ok(remote_branch);
+    }
+
     // If all, fetch all the missing entries from all the commits
     // Otherwise, fetch the missing entries from the head commit
     let commits = if fetch_opts.all {
@@ -105,7 +126,8 @@ pub async fn fetch_remote_branch(
     };
     log::debug!("Fetch got {} commits", commits.len());
 
-    let missing_entries =
+    // Pointer files are downloaded from their urls when they are checked out, see `fetch_pointer_files_for_commit`
+    let (missing_entries, _) =
         collect_missing_entries(repo, &commits, &fetch_opts.subtree_paths, &fetch_opts.depth)?;
     log::debug!(
         "Fetch got {} potentially missing entries",
@@ -114,11 +136,18 @@ pub async fn fetch_remote_branch(
     let missing_entries: Vec<Entry> = missing_entries.into_iter().collect();
     pull_progress.finish();
     let total_bytes = missing_entries.iter().map(|e| e.num_bytes()).sum();
-    let pull_progress = Arc::new(PullProgress::new_with_totals(
-        missing_entries.len() as u64,
-        total_bytes,
-    ));
-    pull_entries_to_versions_dir(remote_repo, &missing_entries, &repo.path, &pull_progress).await?;
+    let pull_progress = Arc::new(
+        PullProgress::new_with_totals(missing_entries.len() as u64, total_bytes)
+            .with_format(fetch_opts.progress),
+    );
+    pull_entries_to_versions_dir(
+        remote_repo,
+        &missing_entries,
+        &repo.path,
+        fetch_opts.jobs,
+        &pull_progress,
+    )
+    .await?;
 
     // If we fetched the data, we're no longer shallow
     repo.write_is_shallow(false)?;
@@ -136,11 +165,13 @@ pub async fn fetch_remote_branch(
     pull_progress.finish();
     let duration = std::time::Duration::from_millis(start.elapsed().as_millis() as u64);
 
+    let mbps = (pull_progress.get_num_bytes() as f64 / start.elapsed().as_secs_f64()) / 1_000_000.0;
     println!(
-        "🐂 oxen downloaded {} ({} files) in {}",
+        "🐂 oxen downloaded {} ({} files) in {} ({:.2} MB/s)",
         bytesize::ByteSize::b(pull_progress.get_num_bytes()),
         pull_progress.get_num_files(),
-        humantime::format_duration(duration)
+        humantime::format_duration(duration),
+        mbps
     );
 
     Ok(remote_branch)
@@ -225,8 +256,9 @@ fn collect_missing_entries(
     commits: &HashSet<Commit>,
     subtree_paths: &Option<Vec<PathBuf>>,
     depth: &Option<i32>,
-) -> Result<HashSet<Entry>, OxenError> {
+) -> Result<(HashSet<Entry>, HashMap<MerkleHash, MetadataPointer>), OxenError> {
     let mut missing_entries: HashSet<Entry> = HashSet::new();
+    let mut pointers: HashMap<MerkleHash, MetadataPointer> = HashMap::new();
     for commit in commits {
         if let Some(subtree_paths) = subtree_paths {
             log::debug!(
@@ -248,7 +280,7 @@ fn collect_missing_entries(
                     );
                     continue;
                 };
-                collect_missing_entries_for_subtree(&tree, &mut missing_entries)?;
+                collect_missing_entries_for_subtree(&tree, &mut missing_entries, &mut pointers)?;
             }
         } else {
             let Some(tree) = repositories::tree::get_subtree_by_depth(repo, commit, &None, depth)?
@@ -259,18 +291,23 @@ fn collect_missing_entries(
                 );
                 continue;
             };
-            collect_missing_entries_for_subtree(&tree, &mut missing_entries)?;
+            collect_missing_entries_for_subtree(&tree, &mut missing_entries, &mut pointers)?;
         }
     }
-    Ok(missing_entries)
+    Ok((missing_entries, pointers))
 }
 
 fn collect_missing_entries_for_subtree(
     tree: &MerkleTreeNode,
     missing_entries: &mut HashSet<Entry>,
+    pointers: &mut HashMap<MerkleHash, MetadataPointer>,
 ) -> Result<(), OxenError> {
     let files: HashSet<FileNodeWithDir> = repositories::tree::list_all_files(tree)?;
     for file in files {
+        if let Some(pointer) = file.file_node.pointer() {
+            pointers.insert(*file.file_node.hash(), pointer);
+            continue;
+        }
         missing_entries.insert(Entry::CommitEntry(CommitEntry {
             commit_id: file.file_node.last_commit_id().to_string(),
             path: file.dir.join(file.file_node.name()),
@@ -283,6 +320,65 @@ fn collect_missing_entries_for_subtree(
     Ok(())
 }
 
+/// Download the pointer files of `commit` that are not in the version store yet, before they are
+/// written to the working tree. Fetching a branch only downloads the files stored on the remote.
+pub async fn fetch_pointer_files_for_commit(
+    repo: &LocalRepository,
+    commit: &Commit,
+    subtree_paths: &Option<Vec<PathBuf>>,
+    depth: &Option<i32>,
+) -> Result<(), OxenError> {
+    let commits = HashSet::from([commit.clone()]);
+    let (_, pointers) = collect_missing_entries(repo, &commits, subtree_paths, depth)?;
+    fetch_pointer_files(repo, &pointers).await
+}
+
+/// Download the bytes for files added with `oxen add --pointer` from their urls into the version store
+async fn fetch_pointer_files(
+    repo: &LocalRepository,
+    pointers: &HashMap<MerkleHash, MetadataPointer>,
+) -> Result<(), OxenError> {
+    let version_store = repo.version_store()?;
+    let tmp_dir = util::fs::oxen_hidden_dir(&repo.path)
+        .join("tmp")
+        .join("pointers");
+    for (hash, pointer) in pointers {
+        let hash_str = hash.to_string();
+        if version_store.version_exists(&hash_str)? {
+            continue;
+        }
+
+        let url = &pointer.pointer.url;
+        log::debug!("fetching pointer {} from {}", hash_str, url);
+        let res = reqwest::get(url).await?;
+        if !res.status().is_success() {
+            return Err(OxenError::basic_str(format!(
+                "Could not fetch pointer file `{url}`, server responded with {}",
+                res.status()
+            )));
+        }
+
+        util::fs::create_dir_all(&tmp_dir)?;
+        let tmp_path = tmp_dir.join(&hash_str);
+        let mut file = util::fs::file_create(&tmp_path)?;
+        let mut stream = res.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            file.write_all(&chunk?)?;
+        }
+        file.flush()?;
+
+        if util::hasher::u128_hash_file_contents(&tmp_path)? != hash.to_u128() {
+            util::fs::remove_file(&tmp_path)?;
+            return Err(OxenError::basic_str(format!(
+                "Hash mismatch for pointer file `{url}`, expected {hash_str}"
+            )));
+        }
+        version_store.store_version_from_path(&hash_str, &tmp_path)?;
+        util::fs::remove_file(&tmp_path)?;
+    }
+    Ok(())
+}
+
 pub async fn fetch_tree_and_hashes_for_commit_id(
     repo: &LocalRepository,
     remote_repo: &RemoteRepository,
@@ -420,6 +516,103 @@ pub async fn maybe_fetch_missing_entries(
     Ok(())
 }
 
+/// Download the version files of the files about to be restored that are not in the local
+/// version store yet, ie in a repository pulled with `oxen pull --blobless`
+pub async fn fetch_missing_versions(
+    repo: &LocalRepository,
+    files: &[FileToRestore],
+) -> Result<(), OxenError> {
+    let version_store = repo.version_store()?;
+    let mut missing_entries: Vec<Entry> = vec![];
+    let mut pointers: HashMap<MerkleHash, MetadataPointer> = HashMap::new();
+    for file in files {
+        let file_node = &file.file_node;
+        if version_store.version_exists(&file_node.hash().to_string())? {
+            continue;
+        }
+        if let Some(pointer) = file_node.pointer() {
+            pointers.insert(*file_node.hash(), pointer);
+            continue;
+        }
+        missing_entries.push(Entry::CommitEntry(CommitEntry {
+            commit_id: file_node.last_commit_id().to_string(),
+            path: file.path.clone(),
+            hash: file_node.hash().to_string(),
+            num_bytes: file_node.num_bytes(),
+            last_modified_seconds: file_node.last_modified_seconds(),
+            last_modified_nanoseconds: file_node.last_modified_nanoseconds(),
+        }));
+    }
+    if missing_entries.is_empty() && pointers.is_empty() {
+        return Ok(());
+    }
+
+    let rb = RemoteBranch::default();
+    let Some(remote) = repo.get_remote(&rb.remote) else {
+        return Err(OxenError::basic_str(format!(
+            "{} files to check out have not been downloaded and there is no remote `{}` to fetch them from",
+            missing_entries.len() + pointers.len(),
+            rb.remote
+        )));
+    };
+    let remote_repo = api::client::repositories::get_by_remote(&remote)
+        .await?
+        .ok_or(OxenError::remote_not_found(remote.clone()))?;
+
+    println!(
+        "🐂 Fetching {} files that have not been downloaded yet",
+        missing_entries.len() + pointers.len()
+    );
+    let total_bytes = missing_entries.iter().map(|e| e.num_bytes()).sum();
+    let pull_progress = Arc::new(PullProgress::new_with_totals(
+        missing_entries.len() as u64,
+        total_bytes,
+    ));
+    pull_entries_to_versions_dir(
+        &remote_repo,
+        &missing_entries,
+        &repo.path,
+        None,
+        &pull_progress,
+    )
+    .await?;
+    fetch_pointer_files(repo, &pointers).await?;
+    pull_progress.finish();
+    Ok(())
+}
+
+/// `fetch_missing_versions` for the sync readers of the version store, ie restore and data frame
+/// reads. The download runs on its own thread and runtime, so this can be called from inside or
+/// outside of an async context.
+pub fn fetch_missing_versions_blocking(
+    repo: &LocalRepository,
+    files: &[FileToRestore],
+) -> Result<(), OxenError> {
+    let version_store = repo.version_store()?;
+    let mut missing = vec![];
+    for file in files {
+        if !version_store.version_exists(&file.file_node.hash().to_string())? {
+            missing.push(FileToRestore {
+                file_node: file.file_node.clone(),
+                path: file.path.clone(),
+            });
+        }
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let repo = repo.clone();
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(fetch_missing_versions(&repo, &missing))
+    })
+    .join()
+    .map_err(|_| OxenError::basic_str("Fetching the missing versions panicked"))?
+}
+
 async fn r_download_entries(
     repo: &LocalRepository,
     remote_repo: &RemoteRepository,
@@ -458,7 +651,7 @@ async fn r_download_entries(
 
         for child in &node.children {
             if let EMerkleTreeNode::File(file_node) = &child.node {
-                if !missing_hashes.contains(&child.hash) {
+                if !missing_hashes.contains(&child.hash) || file_node.is_pointer() {
                     continue;
                 }
 
@@ -473,8 +666,14 @@ async fn r_download_entries(
             }
         }
 
-        pull_entries_to_versions_dir(remote_repo, &missing_entries, &repo.path, pull_progress)
-            .await?;
+        pull_entries_to_versions_dir(
+            remote_repo,
+            &missing_entries,
+            &repo.path,
+            None,
+            pull_progress,
+        )
+        .await?;
     }
 
     if let EMerkleTreeNode::Commit(commit_node) = &node.node {
@@ -491,10 +690,19 @@ pub async fn pull_entries_to_versions_dir(
     remote_repo: &RemoteRepository,
     entries: &[Entry],
     dst: &Path,
+    jobs: Option<usize>,
     progress_bar: &Arc<PullProgress>,
 ) -> Result<(), OxenError> {
     let to_working_dir = false;
-    pull_entries(remote_repo, entries, dst, to_working_dir, progress_bar).await?;
+    pull_entries(
+        remote_repo,
+        entries,
+        dst,
+        to_working_dir,
+        jobs,
+        progress_bar,
+    )
+    .await?;
     Ok(())
 }
 
@@ -505,7 +713,15 @@ pub async fn pull_entries_to_working_dir(
     progress_bar: &Arc<PullProgress>,
 ) -> Result<(), OxenError> {
     let to_working_dir = true;
-    pull_entries(remote_repo, entries, dst, to_working_dir, progress_bar).await?;
+    pull_entries(
+        remote_repo,
+        entries,
+        dst,
+        to_working_dir,
+        None,
+        progress_bar,
+    )
+    .await?;
     Ok(())
 }
 
@@ -514,6 +730,7 @@ pub async fn pull_entries(
     entries: &[Entry],
     dst: &Path,
     to_working_dir: bool,
+    jobs: Option<usize>,
     progress_bar: &Arc<PullProgress>,
 ) -> Result<(), OxenError> {
     log::debug!("entries.len() {}", entries.len());
@@ -557,11 +774,19 @@ pub async fn pull_entries(
         (small_entry_paths, large_entry_paths)
     };
 
+    // Bound the number of downloads in flight so we don't exhaust file descriptors or server connections.
+    // The large and small downloads run at the same time, so they share one pool of `jobs` permits.
+    let num_jobs = concurrency::num_download_jobs(jobs);
+    let jobs = Semaphore::new(num_jobs);
+    let start = std::time::Instant::now();
+
     let large_entries_sync = pull_large_entries(
         remote_repo,
         larger_entries,
-        &dst,
         large_entry_paths,
+        num_jobs,
+        &jobs,
+        start,
         progress_bar,
     );
 
@@ -570,6 +795,9 @@ pub async fn pull_entries(
         smaller_entries,
         &dst,
         small_entry_paths,
+        num_jobs,
+        &jobs,
+        start,
         progress_bar,
     );
 
@@ -577,15 +805,14 @@ pub async fn pull_entries(
         (Ok(_), Ok(_)) => {
             log::debug!("Successfully synced entries!");
         }
-        (Err(err), Ok(_)) => {
+        (Err(err), _) => {
             let err = format!("Error syncing large entries: {err}");
             return Err(OxenError::basic_str(err));
         }
-        (Ok(_), Err(err)) => {
+        (_, Err(err)) => {
             let err = format!("Error syncing small entries: {err}");
             return Err(OxenError::basic_str(err));
         }
-        _ => return Err(OxenError::basic_str("Unknown error syncing entries")),
     }
 
     Ok(())
@@ -594,89 +821,51 @@ pub async fn pull_entries(
 async fn pull_large_entries(
     remote_repo: &RemoteRepository,
     entries: Vec<Entry>,
-    dst: impl AsRef<Path>,
     download_paths: Vec<PathBuf>,
+    num_jobs: usize,
+    jobs: &Semaphore,
+    start: std::time::Instant,
     progress_bar: &Arc<PullProgress>,
 ) -> Result<(), OxenError> {
     if entries.is_empty() {
         return Ok(());
     }
-    // Pull the large entries in parallel
-    use tokio::time::{sleep, Duration};
-    type PieceOfWork = (RemoteRepository, Entry, PathBuf, PathBuf);
-    type TaskQueue = deadqueue::limited::Queue<PieceOfWork>;
-    type FinishedTaskQueue = deadqueue::limited::Queue<bool>;
 
-    log::debug!("Chunking and sending {} larger files", entries.len());
-    let entries: Vec<PieceOfWork> = entries
-        .iter()
-        .zip(download_paths.iter())
-        .map(|(e, path)| {
-            (
-                remote_repo.to_owned(),
-                e.to_owned(),
-                dst.as_ref().to_owned(),
-                path.to_owned(),
-            )
-        })
-        .collect();
-
-    let queue = Arc::new(TaskQueue::new(entries.len()));
-    let finished_queue = Arc::new(FinishedTaskQueue::new(entries.len()));
-    for entry in entries.iter() {
-        queue.try_push(entry.to_owned()).unwrap();
-        finished_queue.try_push(false).unwrap();
-    }
-
-    let worker_count = concurrency::num_threads_for_items(entries.len());
     log::debug!(
-        "worker_count {} entries len {}",
-        worker_count,
-        entries.len()
+        "Downloading {} larger files with {} jobs",
+        entries.len(),
+        num_jobs
     );
-    let tmp_dir = util::fs::oxen_hidden_dir(dst).join("tmp").join("pulled");
-    log::debug!("Backing up pulls to tmp dir: {:?}", &tmp_dir);
-    for worker in 0..worker_count {
-        let queue = queue.clone();
-        let finished_queue = finished_queue.clone();
-        let progress_bar = Arc::clone(progress_bar);
-        tokio::spawn(async move {
-            loop {
-                let (remote_repo, entry, _dst, download_path) = queue.pop().await;
-
-                log::debug!("worker[{}] processing task...", worker);
-
-                // Chunk and individual files
-                let remote_path = &entry.path();
-
-                // Download to the tmp path, then copy over to the entries dir
-                match api::client::entries::download_large_entry(
-                    &remote_repo,
-                    &remote_path,
-                    &download_path,
-                    &entry.commit_id(),
-                    entry.num_bytes(),
-                )
+    let mut downloads = futures::stream::iter(entries.into_iter().zip(download_paths))
+        .map(|(entry, download_path)| async move {
+            let _permit = jobs
+                .acquire()
                 .await
-                {
-                    Ok(_) => {
-                        // log::debug!("Downloaded large entry {:?} to versions dir", remote_path);
-                        progress_bar.add_bytes(entry.num_bytes());
-                        progress_bar.add_files(1);
-                    }
-                    Err(err) => {
-                        log::error!("Could not download chunk... {}", err)
-                    }
-                }
+                .map_err(|err| OxenError::basic_str(format!("Error acquiring semaphore: {err}")))?;
+            let remote_path = entry.path();
+            api::client::entries::download_large_entry(
+                remote_repo,
+                &remote_path,
+                &download_path,
+                &entry.commit_id(),
+                entry.num_bytes(),
+            )
+            .await
+            .map_err(|err| {
+                OxenError::basic_str(format!("Could not download {remote_path:?}: {err}"))
+            })?;
+            verify_downloaded_entry(&entry, &download_path)?;
 
-                finished_queue.pop().await;
-            }
-        });
-    }
+            progress_bar.add_bytes(entry.num_bytes());
+            progress_bar.add_files(1);
+            report_throughput(progress_bar, start);
+            Ok::<(), OxenError>(())
+        })
+        .buffer_unordered(num_jobs);
 
-    while !finished_queue.is_empty() {
-        // log::debug!("Before waiting for {} workers to finish...", queue.len());
-        sleep(Duration::from_secs(1)).await;
+    // Returning early drops the stream, which cancels the downloads still in flight
+    while let Some(result) = downloads.next().await {
+        result?;
     }
     log::debug!("All large file tasks done. :-)");
 
@@ -688,11 +877,15 @@ async fn pull_small_entries(
     entries: Vec<Entry>,
     dst: impl AsRef<Path>,
     content_ids: Vec<(String, PathBuf)>,
+    num_jobs: usize,
+    jobs: &Semaphore,
+    start: std::time::Instant,
     progress_bar: &Arc<PullProgress>,
 ) -> Result<(), OxenError> {
     if content_ids.is_empty() {
         return Ok(());
     }
+    let dst = dst.as_ref();
 
     let total_size = repositories::entries::compute_generic_entries_size(&entries)?;
 
@@ -709,70 +902,73 @@ async fn pull_small_entries(
         content_ids.len()
     );
 
-    // Split into chunks, zip up, and post to server
-    use tokio::time::{sleep, Duration};
-    type PieceOfWork = (RemoteRepository, Vec<(String, PathBuf)>, PathBuf);
-    type TaskQueue = deadqueue::limited::Queue<PieceOfWork>;
-    type FinishedTaskQueue = deadqueue::limited::Queue<bool>;
-
+    // Split into chunks, zip up, and download them with at most `jobs` requests in flight
     log::debug!("pull_small_entries creating {num_chunks} chunks from {total_size} bytes with size {chunk_size}");
-    let chunks: Vec<PieceOfWork> = content_ids
-        .chunks(chunk_size)
-        .map(|chunk| {
-            (
-                remote_repo.to_owned(),
-                chunk.to_owned(),
-                dst.as_ref().to_owned(),
-            )
-        })
-        .collect();
-
-    let worker_count = concurrency::num_threads_for_items(entries.len());
-    let queue = Arc::new(TaskQueue::new(chunks.len()));
-    let finished_queue = Arc::new(FinishedTaskQueue::new(entries.len()));
-    for chunk in chunks {
-        queue.try_push(chunk).unwrap();
-        finished_queue.try_push(false).unwrap();
-    }
-
-    for worker in 0..worker_count {
-        let queue = queue.clone();
-        let finished_queue = finished_queue.clone();
-        let progress_bar = Arc::clone(progress_bar);
-        tokio::spawn(async move {
-            loop {
-                let (remote_repo, chunk, path) = queue.pop().await;
-                log::debug!("worker[{}] processing task...", worker);
-
-                match api::client::entries::download_data_from_version_paths(
-                    &remote_repo,
-                    &chunk,
-                    &path,
-                )
+    let mut downloads = futures::stream::iter(
+        entries
+            .chunks(chunk_size)
+            .zip(content_ids.chunks(chunk_size)),
+    )
+    .map(|(entries, chunk)| async move {
+        let _permit = jobs
+            .acquire()
+            .await
+            .map_err(|err| OxenError::basic_str(format!("Error acquiring semaphore: {err}")))?;
+        let download_size =
+            api::client::entries::download_data_from_version_paths(remote_repo, chunk, dst)
                 .await
-                {
-                    Ok(download_size) => {
-                        progress_bar.add_bytes(download_size);
-                        progress_bar.add_files(chunk.len() as u64);
-                    }
-                    Err(err) => {
-                        log::error!("Could not download entries... {}", err)
-                    }
-                }
+                .map_err(|err| {
+                    OxenError::basic_str(format!(
+                        "Could not download {} entries: {err}",
+                        chunk.len()
+                    ))
+                })?;
+        for (entry, (_, path)) in entries.iter().zip(chunk.iter()) {
+            verify_downloaded_entry(entry, &dst.join(path))?;
+        }
 
-                finished_queue.pop().await;
-            }
-        });
-    }
-    while !finished_queue.is_empty() {
-        // log::debug!("Waiting for {} workers to finish...", queue.len());
-        sleep(Duration::from_millis(1)).await;
+        progress_bar.add_bytes(download_size);
+        progress_bar.add_files(chunk.len() as u64);
+        report_throughput(progress_bar, start);
+        Ok::<(), OxenError>(())
+    })
+    .buffer_unordered(num_jobs);
+
+    // Returning early drops the stream, which cancels the downloads still in flight
+    while let Some(result) = downloads.next().await {
+        result?;
     }
     log::debug!("All tasks done. :-)");
 
     Ok(())
 }
 
+/// Make sure the bytes we wrote hash to what the remote said they would
+fn verify_downloaded_entry(entry: &Entry, path: &Path) -> Result<(), OxenError> {
+    let hash = util::hasher::hash_file_contents(path)?;
+    if hash != entry.hash() {
+        util::fs::remove_file(path)?;
+        return Err(OxenError::basic_str(format!(
+            "Hash mismatch for {:?}: expected {} got {}",
+            entry.path(),
+            entry.hash(),
+            hash
+        )));
+    }
+    Ok(())
+}
+
+fn report_throughput(progress_bar: &PullProgress, start: std::time::Instant) {
+    let duration = start.elapsed().as_secs_f32();
+    let mbps = (progress_bar.get_num_bytes() as f32 / duration) / 1_000_000.0;
+    progress_bar.set_message(format!(
+        "🐂 pull {} files ({}) {:.2} MB/s",
+        progress_bar.get_num_files(),
+        bytesize::ByteSize::b(progress_bar.get_num_bytes()),
+        mbps
+    ));
+}
+
 fn get_missing_entries(entries: &[Entry], dst: &Path) -> Vec<Entry> {
     let dst: &Path = dst;
 
@@ -865,3 +1061,57 @@ fn version_dir_paths_from_large_entries(entries: &[Entry], dst: &Path) -> Vec<Pa
     }
     paths
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::error::OxenError;
+    use crate::opts::AddOpts;
+    use crate::repositories;
+    use crate::test;
+    use crate::util;
+
+    #[tokio::test]
+    async fn test_fetch_pointer_files_for_commit() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let contents = "pretend this is very large";
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/bucket/data/large.bin")
+                .with_body(contents)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let large_file = repo.path.join("data").join("large.bin");
+            util::fs::create_dir_all(large_file.parent().unwrap())?;
+            util::fs::write_to_path(&large_file, contents)?;
+            let opts = AddOpts {
+                paths: vec![large_file.clone()],
+                pointer_url: Some(format!("{}/bucket/", server.url())),
+                ..Default::default()
+            };
+            repositories::add::add_with_opts(&repo, &opts)?;
+            let commit = repositories::commit(&repo, "Adding pointer file")?;
+
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("data/large.bin"))?
+                    .unwrap();
+            let hash = file_node.hash().to_string();
+            let version_store = repo.version_store()?;
+            assert!(!version_store.version_exists(&hash)?);
+
+            super::fetch_pointer_files_for_commit(&repo, &commit, &None, &None).await?;
+            assert!(version_store.version_exists(&hash)?);
+            assert_eq!(version_store.get_version(&hash)?, contents.as_bytes());
+
+            // Already in the version store, so it is not downloaded again
+            super::fetch_pointer_files_for_commit(&repo, &commit, &None, &None).await?;
+            mock.assert_async().await;
+
+            Ok(())
+        })
+        .await
+    }
+}
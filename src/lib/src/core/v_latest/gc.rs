@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use indicatif::ProgressBar;
+
+use crate::core::v_latest::index::CommitMerkleTree;
+use crate::core::v_latest::status;
+use crate::error::OxenError;
+use crate::model::merkle_tree::node::{EMerkleTreeNode, MerkleTreeNode};
+use crate::model::{Commit, LocalRepository, MerkleHash};
+use crate::repositories;
+
+/// The version files that are not referenced by any commit, staged file or workspace
+#[derive(Clone, Debug, Default)]
+pub struct GcResult {
+    pub num_files: usize,
+    pub num_bytes: u64,
+    /// True if the versions were only counted, not deleted
+    pub dry_run: bool,
+}
+
+pub fn gc(repo: &LocalRepository, dry_run: bool) -> Result<GcResult, OxenError> {
+    let live_hashes = live_version_hashes(repo)?;
+    let version_store = repo.version_store()?;
+
+    let mut result = GcResult {
+        dry_run,
+        ..Default::default()
+    };
+    for hash in version_store.list_versions()? {
+        if live_hashes.contains(&hash) {
+            continue;
+        }
+        result.num_files += 1;
+        result.num_bytes += version_store.version_size(&hash)?;
+        if !dry_run {
+            log::debug!("gc deleting unreferenced version {}", hash);
+            version_store.delete_version(&hash)?;
+        }
+    }
+    Ok(result)
+}
+
+/// Every version hash that is still needed: the files in the tree of every commit in the repo,
+/// reachable from a branch or not, the staged files, and the files staged in workspaces
+fn live_version_hashes(repo: &LocalRepository) -> Result<HashSet<String>, OxenError> {
+    let mut commits: Vec<Commit> = repositories::commits::list_all(repo)?.into_iter().collect();
+    if let Some(head) = repositories::commits::head_commit_maybe(repo)? {
+        commits.push(head);
+    }
+
+    let mut hashes = HashSet::new();
+    let mut seen_commits = HashSet::new();
+    for commit in commits {
+        if !seen_commits.insert(commit.id.clone()) {
+            continue;
+        }
+        let tree = CommitMerkleTree::from_commit(repo, &commit)?;
+        collect_file_hashes(&tree.root, &mut hashes);
+    }
+
+    collect_staged_hashes(repo, &mut hashes)?;
+    for workspace in repositories::workspaces::list(repo)? {
+        if seen_commits.insert(workspace.commit.id.clone()) {
+            let tree = CommitMerkleTree::from_commit(repo, &workspace.commit)?;
+            collect_file_hashes(&tree.root, &mut hashes);
+        }
+        collect_staged_hashes(&workspace.workspace_repo, &mut hashes)?;
+    }
+    Ok(hashes)
+}
+
+fn collect_file_hashes(node: &MerkleTreeNode, hashes: &mut HashSet<String>) {
+    node.walk_tree(|node| {
+        if let EMerkleTreeNode::File(file_node) = &node.node {
+            hashes.insert(file_node.hash().to_string());
+            // Files added with --chunked-hash are stored as chunks, keep those too
+            for chunk_hash in file_node.chunk_hashes() {
+                hashes.insert(MerkleHash::new(*chunk_hash).to_string());
+            }
+        }
+    });
+}
+
+fn collect_staged_hashes(
+    repo: &LocalRepository,
+    hashes: &mut HashSet<String>,
+) -> Result<(), OxenError> {
+    let Some(staged_db) = status::open_staged_db(repo)? else {
+        return Ok(());
+    };
+    let (dir_entries, _) = status::read_staged_entries(repo, &staged_db, &ProgressBar::hidden())?;
+    for entry in dir_entries.values().flatten() {
+        collect_file_hashes(&entry.node, hashes);
+    }
+    Ok(())
+}
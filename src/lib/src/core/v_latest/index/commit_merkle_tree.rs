@@ -231,6 +231,33 @@ impl CommitMerkleTree {
         }
     }
 
+    /// Read the dir with its direct files and dirs, without opening the sub dirs.
+    /// `dir_with_children` also reads the VNodes of every sub dir, which adds up in wide dirs
+    /// when all we want is to look up one file.
+    pub fn dir_with_immediate_children(
+        repo: &LocalRepository,
+        commit: &Commit,
+        path: impl AsRef<Path>,
+    ) -> Result<Option<MerkleTreeNode>, OxenError> {
+        let node_path = path.as_ref();
+        let dir_hashes = CommitMerkleTree::dir_hashes(repo, commit)?;
+        let Some(node_hash) = dir_hashes.get(node_path) else {
+            return Ok(None);
+        };
+        log::debug!("Look up dir immediate children {:?}", node_path);
+
+        // Depth 0 gives us the VNodes, then load each VNode's children without recursing into dirs
+        let Some(mut dir_node) = CommitMerkleTree::read_depth(repo, node_hash, 0)? else {
+            return Ok(None);
+        };
+        for vnode in dir_node.children.iter_mut() {
+            if let Some(vnode_with_children) = CommitMerkleTree::read_depth(repo, &vnode.hash, 0)? {
+                *vnode = vnode_with_children;
+            }
+        }
+        Ok(Some(dir_node))
+    }
+
     pub fn dir_with_children_recursive(
         repo: &LocalRepository,
         commit: &Commit,
@@ -918,6 +945,30 @@ mod tests {
     use crate::test;
     use crate::test::add_n_files_m_dirs;
 
+    #[test]
+    fn test_dir_with_immediate_children() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let repo = repositories::init::init_with_version(dir, MinOxenVersion::LATEST)?;
+            add_n_files_m_dirs(&repo, 10, 3)?;
+            let commit = repositories::commits::commit(&repo, "First commit")?;
+
+            let root = CommitMerkleTree::dir_with_immediate_children(&repo, &commit, "")?
+                .expect("root dir");
+            assert!(root.get_by_path("README.md")?.is_some());
+            let files = root.get_by_path("files")?.expect("files dir");
+            assert_eq!(files.node.node_type(), MerkleTreeNodeType::Dir);
+            // The sub dir is not opened
+            assert!(files.children.is_empty());
+
+            // The full read loads the sub dir VNodes
+            let root = CommitMerkleTree::dir_with_children(&repo, &commit, "")?.expect("root dir");
+            let files = root.get_by_path("files")?.expect("files dir");
+            assert!(!files.children.is_empty());
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_load_dir_nodes() -> Result<(), OxenError> {
         test::run_empty_dir_test(|dir| {
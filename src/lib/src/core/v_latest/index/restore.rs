@@ -5,10 +5,11 @@ use std::path::{Path, PathBuf};
 
 use crate::constants::STAGED_DIR;
 use crate::core::db::{self};
+use crate::core::v_latest::fetch;
 use crate::core::v_latest::index::CommitMerkleTree;
 use crate::error::OxenError;
 use crate::model::merkle_tree::node::{EMerkleTreeNode, FileNode, MerkleTreeNode};
-use crate::model::{Commit, LocalRepository, MerkleHash, PartialNode};
+use crate::model::{Commit, EntryDataType, LocalRepository, MerkleHash, PartialNode};
 use crate::opts::RestoreOpts;
 use crate::repositories;
 use crate::storage::version_store::VersionStore;
@@ -183,6 +184,16 @@ fn restore_dir(
         file_nodes_with_paths.len()
     );
 
+    // Download the versions skipped by `oxen pull --blobless` in one go rather than file by file
+    let files_to_restore: Vec<FileToRestore> = file_nodes_with_paths
+        .iter()
+        .map(|(file_node, file_path)| FileToRestore {
+            file_node: file_node.clone(),
+            path: file_path.clone(),
+        })
+        .collect();
+    fetch::fetch_missing_versions_blocking(repo, &files_to_restore)?;
+
     let msg = format!("Restoring Directory: {:?}", dir);
     let bar =
         util::progress_bar::oxen_progress_bar_with_msg(file_nodes_with_paths.len() as u64, &msg);
@@ -353,9 +364,32 @@ pub fn restore_file(
     let parent = working_path.parent().unwrap();
     util::fs::create_dir_all(parent)?;
 
-    // Use the version store to copy the file to the working path
+    // Use the version store to copy the file to the working path, files skipped by
+    // `oxen pull --blobless` are downloaded first
     let hash_str = file_hash.to_string();
-    version_store.copy_version_to_path(&hash_str, &working_path)?;
+    fetch::fetch_missing_versions_blocking(
+        repo,
+        &[FileToRestore {
+            file_node: file_node.clone(),
+            path: path.to_path_buf(),
+        }],
+    )?;
+    if !version_store.version_exists(&hash_str)? {
+        return Err(OxenError::basic_str(format!(
+            "The contents of {path:?} are not in the local version store. If the repository was pulled with --blobless, run `oxen pull` to download them."
+        )));
+    }
+    if cfg!(windows)
+        && repo.normalize_line_endings()
+        && *file_node.data_type() == EntryDataType::Text
+    {
+        // Versions are stored with LF line endings, give them back the native ones
+        let bytes = version_store.get_version(&hash_str)?;
+        util::fs::write_data(&working_path, &util::fs::lf_to_crlf(&bytes))?;
+    } else {
+        version_store.copy_version_to_path(&hash_str, &working_path)?;
+    }
+    util::fs::set_executable(&working_path, file_node.is_executable())?;
 
     let last_modified = std::time::SystemTime::UNIX_EPOCH
         + std::time::Duration::from_secs(last_modified_seconds as u64)
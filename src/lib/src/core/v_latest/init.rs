@@ -3,6 +3,7 @@ use std::path::Path;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::LocalRepository;
+use crate::storage::StorageConfig;
 use crate::util;
 
 pub fn init(path: &Path) -> Result<LocalRepository, OxenError> {
@@ -26,6 +27,18 @@ pub fn init_with_version(
     path: &Path,
     version: MinOxenVersion,
 ) -> Result<LocalRepository, OxenError> {
+    init_with_storage(path, version, None)
+}
+
+/// Initialize a repository that keeps its versions in the `storage` backend, the default local store if None
+pub fn init_with_storage(
+    path: &Path,
+    version: MinOxenVersion,
+    storage: Option<&StorageConfig>,
+) -> Result<LocalRepository, OxenError> {
+    if let Some(storage) = storage {
+        storage.validate()?;
+    }
     let hidden_dir = util::fs::oxen_hidden_dir(path);
 
     util::fs::create_dir_all(hidden_dir)?;
@@ -34,7 +47,14 @@ pub fn init_with_version(
         return Err(OxenError::basic_str(err));
     }
 
-    let repo = LocalRepository::new_from_version(path, version.to_string())?;
+    let mut repo = LocalRepository::new_from_version(path, version.to_string())?;
+    if let Some(storage) = storage {
+        // Do not leave a half initialized repository behind if the backend can not be set up
+        if let Err(err) = repo.init_version_store_from_config(storage) {
+            util::fs::remove_dir_all(util::fs::oxen_hidden_dir(path))?;
+            return Err(err);
+        }
+    }
     repo.save()?;
 
     Ok(repo)
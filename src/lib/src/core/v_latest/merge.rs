@@ -15,7 +15,7 @@ use crate::model::{MerkleHash, PartialNode};
 use crate::opts::RmOpts;
 use crate::repositories;
 use crate::repositories::commits::commit_writer;
-use crate::repositories::merge::MergeCommits;
+use crate::repositories::merge::{ConflictContext, MergeCommits, MergeResolver, Resolution};
 use crate::util;
 
 use rocksdb::DB;
@@ -69,6 +69,14 @@ pub fn list_conflicts(repo: &LocalRepository) -> Result<Vec<NodeMergeConflict>,
     }
 }
 
+/// Whether the merge conflict db has any entries, without creating the db if it does not exist yet
+pub fn has_conflicts_in_db(repo: &LocalRepository) -> Result<bool, OxenError> {
+    if !db_path(repo).exists() {
+        return Ok(false);
+    }
+    NodeMergeConflictReader::new(repo)?.has_conflicts()
+}
+
 pub fn mark_conflict_as_resolved(repo: &LocalRepository, path: &Path) -> Result<(), OxenError> {
     node_merge_conflict_writer::mark_conflict_as_resolved_in_db(repo, path)
 }
@@ -213,7 +221,7 @@ pub fn merge_into_base(
         merge: merge_commit,
     };
 
-    merge_commits(repo, &commits)
+    merge_commits(repo, &commits, None)
 }
 
 /// Merge into the current branch, returns the merge commit if successful, and None if there is conflicts
@@ -221,6 +229,24 @@ pub fn merge(
     repo: &LocalRepository,
     branch_name: impl AsRef<str>,
 ) -> Result<Option<Commit>, OxenError> {
+    let commits = merge_commits_into_head(repo, branch_name)?;
+    merge_commits(repo, &commits, None)
+}
+
+/// Same as `merge`, the resolver decides each conflict before it is recorded
+pub fn merge_with_resolver(
+    repo: &LocalRepository,
+    branch_name: impl AsRef<str>,
+    resolver: &MergeResolver,
+) -> Result<Option<Commit>, OxenError> {
+    let commits = merge_commits_into_head(repo, branch_name)?;
+    merge_commits(repo, &commits, Some(resolver))
+}
+
+fn merge_commits_into_head(
+    repo: &LocalRepository,
+    branch_name: impl AsRef<str>,
+) -> Result<MergeCommits, OxenError> {
     let branch_name = branch_name.as_ref();
 
     let merge_branch = repositories::branches::get_by_name(repo, branch_name)?
@@ -229,12 +255,11 @@ pub fn merge(
     let base_commit = repositories::commits::head_commit(repo)?;
     let merge_commit = get_commit_or_head(repo, Some(merge_branch.commit_id.clone()))?;
     let lca = lowest_common_ancestor_from_commits(repo, &base_commit, &merge_commit)?;
-    let commits = MergeCommits {
+    Ok(MergeCommits {
         lca,
         base: base_commit,
         merge: merge_commit,
-    };
-    merge_commits(repo, &commits)
+    })
 }
 
 pub fn merge_commit_into_base(
@@ -255,7 +280,7 @@ pub fn merge_commit_into_base(
         merge: merge_commit.to_owned(),
     };
 
-    merge_commits(repo, &commits)
+    merge_commits(repo, &commits, None)
 }
 
 pub fn merge_commit_into_base_on_branch(
@@ -264,6 +289,27 @@ pub fn merge_commit_into_base_on_branch(
     base_commit: &Commit,
     branch: &Branch,
 ) -> Result<Option<Commit>, OxenError> {
+    let merge_commits = find_merge_commits_on_branch(repo, merge_commit, base_commit)?;
+    merge_commits_on_branch(repo, &merge_commits, branch, None)
+}
+
+/// Same as `merge_commit_into_base_on_branch`, the resolver decides each conflict before it is recorded
+pub fn merge_commit_into_base_on_branch_with_resolver(
+    repo: &LocalRepository,
+    merge_commit: &Commit,
+    base_commit: &Commit,
+    branch: &Branch,
+    resolver: &MergeResolver,
+) -> Result<Option<Commit>, OxenError> {
+    let merge_commits = find_merge_commits_on_branch(repo, merge_commit, base_commit)?;
+    merge_commits_on_branch(repo, &merge_commits, branch, Some(resolver))
+}
+
+fn find_merge_commits_on_branch(
+    repo: &LocalRepository,
+    merge_commit: &Commit,
+    base_commit: &Commit,
+) -> Result<MergeCommits, OxenError> {
     let lca = lowest_common_ancestor_from_commits(repo, base_commit, merge_commit)?;
 
     log::debug!(
@@ -273,13 +319,11 @@ pub fn merge_commit_into_base_on_branch(
         base_commit
     );
 
-    let merge_commits = MergeCommits {
+    Ok(MergeCommits {
         lca,
         base: base_commit.to_owned(),
         merge: merge_commit.to_owned(),
-    };
-
-    merge_commits_on_branch(repo, &merge_commits, branch)
+    })
 }
 
 pub fn has_file(repo: &LocalRepository, path: &Path) -> Result<bool, OxenError> {
@@ -330,6 +374,7 @@ fn merge_commits_on_branch(
     repo: &LocalRepository,
     merge_commits: &MergeCommits,
     branch: &Branch,
+    resolver: Option<&MergeResolver>,
 ) -> Result<Option<Commit>, OxenError> {
     // User output
     println!(
@@ -362,6 +407,7 @@ fn merge_commits_on_branch(
         let mut shared_hashes = HashSet::new();
         let conflicts =
             find_merge_conflicts(repo, merge_commits, write_to_disk, &mut shared_hashes)?;
+        let conflicts = resolve_conflicts(repo, conflicts, resolver)?;
         log::debug!("Got {} conflicts", conflicts.len());
 
         if conflicts.is_empty() {
@@ -381,14 +427,15 @@ Found {} conflicts, please resolve them before merging.
 ",
                 conflicts.len()
             );
-            let db_path = db_path(repo);
-            log::debug!("Merger::new() DB {:?}", db_path);
-            let opts = db::key_val::opts::default();
-            let merge_db = DB::open(&opts, dunce::simplified(&db_path))?;
-
-            node_merge_conflict_writer::write_conflicts_to_disk(
+            for conflict in conflicts.iter().filter(|conflict| conflict.is_binary()) {
+                println!(
+                    "  {:?} is binary, resolve it with --ours or --theirs",
+                    conflict.path()
+                );
+            }
+            // Persist the three versions of each conflict for status, checkout --theirs/--ours and the UI
+            node_merge_conflict_writer::write_conflicts_to_db(
                 repo,
-                &merge_db,
                 &merge_commits.merge,
                 &merge_commits.base,
                 &conflicts,
@@ -691,6 +738,7 @@ fn r_ff_base_dir(
 fn merge_commits(
     repo: &LocalRepository,
     merge_commits: &MergeCommits,
+    resolver: Option<&MergeResolver>,
 ) -> Result<Option<Commit>, OxenError> {
     // User output
     println!(
@@ -724,6 +772,7 @@ fn merge_commits(
         let mut shared_hashes = HashSet::new();
         let conflicts =
             find_merge_conflicts(repo, merge_commits, write_to_disk, &mut shared_hashes)?;
+        let conflicts = resolve_conflicts(repo, conflicts, resolver)?;
 
         if !conflicts.is_empty() {
             println!(
@@ -746,14 +795,9 @@ Found {} conflicts, please resolve them before merging.
             let commit = create_merge_commit(repo, merge_commits, shared_hashes)?;
             Ok(Some(commit))
         } else {
-            let db_path = db_path(repo);
-            log::debug!("Merger::new() DB {:?}", db_path);
-            let opts = db::key_val::opts::default();
-            let merge_db = DB::open(&opts, dunce::simplified(&db_path))?;
-
-            node_merge_conflict_writer::write_conflicts_to_disk(
+            // Persist the three versions of each conflict for status, checkout --theirs/--ours and the UI
+            node_merge_conflict_writer::write_conflicts_to_db(
                 repo,
-                &merge_db,
                 &merge_commits.merge,
                 &merge_commits.base,
                 &conflicts,
@@ -763,6 +807,41 @@ Found {} conflicts, please resolve them before merging.
     }
 }
 
+/// Ask the resolver about each conflict, taking the merge version onto disk when it picks that side.
+/// Returns the conflicts that are left unresolved.
+fn resolve_conflicts(
+    repo: &LocalRepository,
+    conflicts: Vec<NodeMergeConflict>,
+    resolver: Option<&MergeResolver>,
+) -> Result<Vec<NodeMergeConflict>, OxenError> {
+    let Some(resolver) = resolver else {
+        return Ok(conflicts);
+    };
+
+    let version_store = repo.version_store()?;
+    let mut unresolved = vec![];
+    for conflict in conflicts {
+        let (merge_node, path) = &conflict.merge_entry;
+        let context = ConflictContext {
+            repo,
+            path,
+            lca: &conflict.lca_entry.0,
+            base: &conflict.base_entry.0,
+            merge: merge_node,
+        };
+        match resolver(&context) {
+            // The base version is already in the working directory
+            Resolution::Base => log::debug!("resolver kept base version of {:?}", path),
+            Resolution::Merge => {
+                log::debug!("resolver took merge version of {:?}", path);
+                restore::restore_file(repo, merge_node, path, &version_store)?;
+            }
+            Resolution::Unresolved => unresolved.push(conflict),
+        }
+    }
+    Ok(unresolved)
+}
+
 fn create_merge_commit(
     repo: &LocalRepository,
     merge_commits: &MergeCommits,
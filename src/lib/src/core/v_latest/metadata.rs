@@ -1,14 +1,28 @@
 //! Helper functions to get metadata from the local filesystem.
 //!
 
+use crate::constants::{DEFAULT_STAGED_DB_BATCH_SIZE, STAGED_DIR};
+use crate::core::db;
+use crate::core::db::key_val::batched_writer::BatchedWriter;
+use crate::core::v_latest::add::p_add_file_node_to_staged_db;
 use crate::error::OxenError;
 use crate::model::entry::metadata_entry::CLIMetadataEntry;
+use crate::model::merkle_tree::node::file_node_with_dir::FileNodeWithDir;
 use crate::model::merkle_tree::node::EMerkleTreeNode;
-use crate::model::{Commit, LocalRepository};
+use crate::model::{Commit, EntryDataType, LocalRepository, MerkleHash, StagedEntryStatus};
 use crate::repositories;
+use crate::repositories::metadata::MetadataBackfillStats;
+use crate::storage::version_store::VersionStore;
 use crate::util;
+use crate::util::progress_bar::{oxify_bar, ProgressBarType};
 
-use std::path::Path;
+use indicatif::ProgressBar;
+use rayon::prelude::*;
+use rocksdb::{DBWithThreadMode, MultiThreaded};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 use super::index::CommitMerkleTree;
 
@@ -57,3 +71,154 @@ pub fn get_cli(
         extension,
     })
 }
+
+/// Compute the metadata of the files in `commit` that were added without it (`oxen add --no-metadata`)
+/// and stage the updated file nodes, to be committed on top of `commit`.
+///
+/// Files that are already staged are skipped, so an interrupted run picks up where it left off,
+/// and `limit` bounds how many files are processed so a huge repo can be backfilled over several commits.
+pub fn backfill(
+    repo: &LocalRepository,
+    commit: &Commit,
+    limit: Option<usize>,
+) -> Result<MetadataBackfillStats, OxenError> {
+    let head_commit = repositories::commits::head_commit(repo)?;
+    if head_commit.id != commit.id {
+        return Err(OxenError::basic_str(format!(
+            "Can only backfill metadata for the head commit {}, checkout {} first",
+            head_commit.id, commit.id
+        )));
+    }
+
+    let root = repositories::tree::get_root_with_children(repo, commit)?
+        .ok_or(OxenError::commit_id_does_not_exist(&commit.id))?;
+
+    repo.check_exists()?;
+    let opts = db::key_val::opts::default();
+    let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
+    let staged_db: DBWithThreadMode<MultiThreaded> =
+        DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
+
+    let mut files: Vec<FileNodeWithDir> = Vec::new();
+    let mut num_already_staged = 0;
+    for file in repositories::tree::list_all_files(&root)? {
+        if file.file_node.metadata().is_some() || !has_metadata(file.file_node.data_type()) {
+            continue;
+        }
+        let path = file.dir.join(file.file_node.name());
+        if staged_db.get(path.to_string_lossy().as_bytes())?.is_some() {
+            num_already_staged += 1;
+            continue;
+        }
+        files.push(file);
+    }
+    // Sort so that a limited run always picks the same files first
+    files.sort_by(|a, b| {
+        a.dir
+            .join(a.file_node.name())
+            .cmp(&b.dir.join(b.file_node.name()))
+    });
+    let num_remaining = files.len();
+    if let Some(limit) = limit {
+        files.truncate(limit);
+    }
+    let num_remaining = num_remaining - files.len();
+
+    let version_store = repo.version_store()?;
+    let writer = BatchedWriter::new(&staged_db, DEFAULT_STAGED_DB_BATCH_SIZE);
+    let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
+    let num_backfilled = AtomicUsize::new(0);
+    let num_failed = AtomicUsize::new(0);
+
+    let progress = Arc::new(ProgressBar::new(files.len() as u64));
+    oxify_bar(Arc::clone(&progress), ProgressBarType::Counter);
+    progress.set_message("🐂 backfilling metadata");
+
+    files.par_iter().for_each(|file| {
+        let relative_path: PathBuf = file.dir.join(file.file_node.name());
+        match backfill_file(&version_store, &writer, &seen_dirs, file, &relative_path) {
+            Ok(true) => {
+                num_backfilled.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(false) => {
+                log::debug!("no metadata for {:?}, skipping", relative_path);
+                num_failed.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(err) => {
+                log::error!(
+                    "could not backfill metadata for {:?}: {}",
+                    relative_path,
+                    err
+                );
+                num_failed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        progress.inc(1);
+    });
+    writer.flush()?;
+    progress.finish_and_clear();
+
+    Ok(MetadataBackfillStats {
+        num_backfilled: num_backfilled.load(Ordering::Relaxed),
+        num_failed: num_failed.load(Ordering::Relaxed),
+        num_already_staged,
+        num_remaining,
+    })
+}
+
+/// Binary files and dirs never get metadata, so there is nothing to backfill for them
+fn has_metadata(data_type: &EntryDataType) -> bool {
+    matches!(
+        data_type,
+        EntryDataType::Tabular
+            | EntryDataType::Image
+            | EntryDataType::Video
+            | EntryDataType::Audio
+            | EntryDataType::Text
+    )
+}
+
+/// Compute and stage the metadata for a single file, returns false if none could be computed
+fn backfill_file(
+    version_store: &Arc<dyn VersionStore>,
+    writer: &BatchedWriter<'_>,
+    seen_dirs: &Arc<Mutex<HashSet<PathBuf>>>,
+    file: &FileNodeWithDir,
+    relative_path: &Path,
+) -> Result<bool, OxenError> {
+    let hash = file.file_node.hash();
+    let hash_str = hash.to_string();
+    let version_file = version_store.get_version_file(&hash_str, file.file_node.extension())?;
+    let metadata = repositories::metadata::get_file_metadata_with_extension(
+        &version_file,
+        file.file_node.data_type(),
+        file.file_node.extension(),
+    )?;
+    drop(version_file);
+    let Some(metadata) = metadata else {
+        return Ok(false);
+    };
+
+    // Same hashing as process_add_file
+    let metadata_hash = util::hasher::get_metadata_hash(&Some(metadata.clone()))?;
+    let metadata_hash = MerkleHash::new(metadata_hash);
+    let combined_hash =
+        util::hasher::get_combined_hash(Some(metadata_hash.to_u128()), hash.to_u128())?;
+    let combined_hash =
+        util::hasher::get_combined_hash_with_mode(combined_hash, file.file_node.is_executable());
+
+    let mut file_node = file.file_node.clone();
+    file_node.set_name(&relative_path.to_string_lossy());
+    file_node.set_metadata(Some(metadata));
+    file_node.set_metadata_hash(Some(metadata_hash));
+    file_node.set_combined_hash(&MerkleHash::new(combined_hash));
+
+    p_add_file_node_to_staged_db(
+        writer,
+        relative_path,
+        StagedEntryStatus::Modified,
+        &file_node,
+        seen_dirs,
+    )?;
+    Ok(true)
+}
@@ -44,6 +44,10 @@ pub struct FileNodeData {
 
     pub chunk_type: FileChunkType, // How the data is stored on disk
     pub storage_backend: FileStorageType, // Where the file is stored in the backend
+
+    // Executable bit recorded with `oxen add --chmod`, defaults to false for older nodes
+    #[serde(default)]
+    pub executable: bool,
 }
 
 impl TFileNode for FileNodeData {
@@ -139,7 +143,19 @@ impl TFileNode for FileNodeData {
         &self.chunk_type
     }
 
+    fn set_chunk_type(&mut self, chunk_type: FileChunkType) {
+        self.chunk_type = chunk_type;
+    }
+
     fn storage_backend(&self) -> &FileStorageType {
         &self.storage_backend
     }
+
+    fn executable(&self) -> bool {
+        self.executable
+    }
+
+    fn set_executable(&mut self, executable: bool) {
+        self.executable = executable;
+    }
 }
@@ -1,10 +1,14 @@
+use std::path::PathBuf;
+
 use crate::api;
 use crate::error::OxenError;
-use crate::model::LocalRepository;
-use crate::repositories;
+use crate::model::diff::ChangeType;
+use crate::model::{Commit, LocalRepository};
+use crate::{repositories, util};
 
-use crate::core::v_latest::fetch;
+use crate::core::v_latest::{branches, fetch};
 use crate::opts::fetch_opts::FetchOpts;
+use crate::opts::{RestoreOpts, RmOpts};
 
 pub async fn pull(repo: &LocalRepository) -> Result<(), OxenError> {
     let mut fetch_opts = FetchOpts::new();
@@ -49,10 +53,31 @@ pub async fn pull_remote_branch(
     fetch_opts.should_update_branch_head = false;
     let remote_branch = fetch::fetch_remote_branch(repo, &remote_repo, &fetch_opts).await?;
 
+    if fetch_opts.blobless {
+        pull_blobless(
+            repo,
+            branch,
+            &remote_branch.commit_id,
+            &previous_head_commit,
+        )
+        .await?;
+        api::client::repositories::post_pull(&remote_repo).await?;
+        return Ok(());
+    }
+
     let mut new_head_commit = repositories::revisions::get(repo, &remote_branch.commit_id)?.ok_or(
         OxenError::revision_not_found(remote_branch.commit_id.to_owned().into()),
     )?;
 
+    // The merge below writes the pulled files to the working tree, so pointer files are needed now
+    fetch::fetch_pointer_files_for_commit(
+        repo,
+        &new_head_commit,
+        &fetch_opts.subtree_paths,
+        &fetch_opts.depth,
+    )
+    .await?;
+
     if let Some(previous_head_commit) = &previous_head_commit {
         log::debug!(
             "checking if we need to merge previous {} new {}",
@@ -60,6 +85,11 @@ pub async fn pull_remote_branch(
             new_head_commit.id
         );
         if previous_head_commit.id != new_head_commit.id {
+            let pruned = if fetch_opts.prune {
+                prune_removed_files(repo, previous_head_commit, &new_head_commit)?
+            } else {
+                vec![]
+            };
             match repositories::merge::merge_commit_into_base(
                 repo,
                 &new_head_commit,
@@ -68,11 +98,15 @@ pub async fn pull_remote_branch(
                 Ok(Some(commit)) => new_head_commit = commit,
                 Ok(None) => {
                     // Merge conflict, keep the previous commit
+                    unprune_files(repo, previous_head_commit, &pruned)?;
                     return Err(OxenError::merge_conflict(
                         "There was a merge conflict, please resolve it before pulling",
                     ));
                 }
-                Err(e) => return Err(e),
+                Err(e) => {
+                    unprune_files(repo, previous_head_commit, &pruned)?;
+                    return Err(e);
+                }
             }
         }
     }
@@ -83,8 +117,101 @@ pub async fn pull_remote_branch(
         remote_branch.commit_id
     );
 
-    repositories::branches::update(repo, branch, new_head_commit.id)?;
+    repositories::branches::update(repo, branch, &new_head_commit.id)?;
+
     api::client::repositories::post_pull(&remote_repo).await?;
 
     Ok(())
 }
+
+/// Fast forward the branch and the working tree to the fetched commit. Only the files that
+/// differ from the working tree are downloaded, the versions in between stay on the remote.
+async fn pull_blobless(
+    repo: &LocalRepository,
+    branch: &str,
+    commit_id: &str,
+    previous_head_commit: &Option<Commit>,
+) -> Result<(), OxenError> {
+    let new_head_commit = repositories::revisions::get(repo, commit_id)?
+        .ok_or(OxenError::revision_not_found(commit_id.to_owned().into()))?;
+
+    if let Some(previous_head_commit) = previous_head_commit {
+        if previous_head_commit.id == new_head_commit.id {
+            return Ok(());
+        }
+        let lca = repositories::merge::lowest_common_ancestor_from_commits(
+            repo,
+            previous_head_commit,
+            &new_head_commit,
+        )?;
+        if lca.id != previous_head_commit.id {
+            return Err(OxenError::basic_str(
+                "Cannot pull --blobless, the local branch has commits that are not on the remote. Pull without --blobless to merge them.",
+            ));
+        }
+    }
+
+    branches::set_working_repo_to_commit(repo, &new_head_commit, previous_head_commit).await?;
+    repositories::branches::update(repo, branch, &new_head_commit.id)?;
+    Ok(())
+}
+
+/// A fast forward already removes the files deleted on the remote, but a three way merge only
+/// brings in the remote's new and changed files, so the merge commit would keep the deleted ones.
+/// Before such a merge, remove and stage the removal of the files deleted on the remote since the
+/// common ancestor. Files changed locally, committed or not, are left alone. Returns the pruned
+/// paths so they can be put back if the merge fails.
+fn prune_removed_files(
+    repo: &LocalRepository,
+    base_commit: &Commit,
+    merge_commit: &Commit,
+) -> Result<Vec<PathBuf>, OxenError> {
+    let lca =
+        repositories::merge::lowest_common_ancestor_from_commits(repo, base_commit, merge_commit)?;
+    if lca.id == base_commit.id {
+        return Ok(vec![]);
+    }
+
+    let mut pruned = vec![];
+
+    let changes = repositories::diffs::changed_entries(repo, &lca, merge_commit)?;
+    for (path, change_type, hash) in changes {
+        if change_type != ChangeType::Removed {
+            continue;
+        }
+
+        let full_path = repo.path.join(&path);
+        if !full_path.is_file() {
+            continue;
+        }
+
+        let base_file = repositories::tree::get_file_by_path(repo, base_commit, &path)?;
+        if base_file.map(|file| file.hash().to_u128()) != Some(hash.to_u128())
+            || util::hasher::u128_hash_file_contents(&full_path)? != hash.to_u128()
+        {
+            eprintln!("Warning: not pruning {path:?}, it has local modifications");
+            continue;
+        }
+
+        log::debug!("pruning {:?} removed on the remote", path);
+        repositories::rm(repo, &RmOpts::from_path(&path))?;
+        pruned.push(path);
+    }
+
+    Ok(pruned)
+}
+
+/// Unstage the removals staged by `prune_removed_files` and restore the files from the base
+/// commit, so a failed merge doesn't leave extra staged deletions behind
+fn unprune_files(
+    repo: &LocalRepository,
+    base_commit: &Commit,
+    pruned: &[PathBuf],
+) -> Result<(), OxenError> {
+    for path in pruned {
+        log::debug!("restoring pruned {:?} after a failed merge", path);
+        repositories::restore(repo, RestoreOpts::from_staged_path(path))?;
+        repositories::restore(repo, RestoreOpts::from_path_ref(path, &base_commit.id))?;
+    }
+    Ok(())
+}
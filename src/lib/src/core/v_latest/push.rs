@@ -16,6 +16,7 @@ use crate::error::OxenError;
 use crate::model::entry::commit_entry::Entry;
 use crate::model::merkle_tree::node::{EMerkleTreeNode, MerkleTreeNode};
 use crate::model::{Branch, Commit, CommitEntry, LocalRepository, MerkleHash, RemoteRepository};
+use crate::storage::version_store::ReadSeek;
 use crate::util::{self, concurrency};
 use crate::{api, repositories};
 
@@ -125,7 +126,8 @@ fn collect_missing_files(
     );
     for child in &node.children {
         if let EMerkleTreeNode::File(file_node) = &child.node {
-            if !hashes.contains(&child.hash) {
+            // Pointer files are fetched from their url, there are no bytes to push
+            if !hashes.contains(&child.hash) || file_node.is_pointer() {
                 continue;
             }
             entries.insert(Entry::CommitEntry(CommitEntry {
@@ -424,6 +426,21 @@ async fn chunk_and_send_large_entries(
     Ok(())
 }
 
+/// Open the contents of an entry to push. File versions are read through the version store, so
+/// compressed and chunked versions are never unpacked into the versions dir.
+fn open_entry_version(
+    repo: &LocalRepository,
+    entry: &Entry,
+) -> Result<Box<dyn ReadSeek>, OxenError> {
+    match entry {
+        Entry::CommitEntry(commit_entry) => repo.version_store()?.open_version(&commit_entry.hash),
+        Entry::SchemaEntry(_) => {
+            let version_path = util::fs::version_path_for_entry(repo, entry);
+            Ok(Box::new(std::fs::File::open(version_path)?))
+        }
+    }
+}
+
 /// Chunk and send large file in parallel
 async fn upload_large_file_chunks(
     entry: Entry,
@@ -433,9 +450,19 @@ async fn upload_large_file_chunks(
     chunk_size: u64,
     progress: &Arc<PushProgress>,
 ) {
-    // Open versioned file
+    // Open versioned file, compressed and chunked versions are unpacked to a temp file
+    // that goes away once the upload is done
     let version_path = util::fs::version_path_for_entry(&repo, &entry);
-    let f = std::fs::File::open(&version_path).unwrap();
+    let f = match open_entry_version(&repo, &entry) {
+        Ok(f) => f,
+        Err(err) => {
+            log::error!(
+                "upload_large_file_chunks could not open version {:?}: {err}",
+                entry.path()
+            );
+            return;
+        }
+    };
     let mut reader = BufReader::new(f);
 
     // These variables are the same for every chunk
@@ -719,7 +746,21 @@ async fn bundle_and_send_small_entries(
                     let version_path = util::fs::version_path_for_entry(&repo, entry);
                     let name = util::fs::path_relative_to_dir(&version_path, &hidden_dir).unwrap();
 
-                    match tar.append_path_with_name(version_path, name) {
+                    // Small versions are read into memory, so compressed and chunked ones
+                    // are sent as their contents without unpacking them on disk
+                    let mut data = vec![];
+                    if let Err(e) = open_entry_version(&repo, entry)
+                        .and_then(|mut f| Ok(f.read_to_end(&mut data)?))
+                    {
+                        log::error!("Failed to read version for archive: {}", e);
+                        continue; // TODO: error handling, same as above
+                    }
+                    let mut header = tar::Header::new_gnu();
+                    header.set_size(data.len() as u64);
+                    header.set_mode(0o644);
+                    header.set_cksum();
+
+                    match tar.append_data(&mut header, name, data.as_slice()) {
                         Ok(_) => {}
                         Err(e) => {
                             log::error!("Failed to add file to archive: {}", e);
@@ -1,14 +1,17 @@
+use crate::core::v_latest::fetch;
+use crate::core::v_latest::index::restore::FileToRestore;
 use crate::error::OxenError;
-use crate::util;
+use crate::model::merkle_tree::node::FileNode;
+use crate::storage::VersionFile;
 use crate::{model::LocalRepository, repositories};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
-/// Get the version file path from a commit id
+/// Get the version file from a commit id
 pub fn get_version_file_from_commit_id(
     repo: &LocalRepository,
     commit_id: impl AsRef<str>,
     path: impl AsRef<Path>,
-) -> Result<PathBuf, OxenError> {
+) -> Result<VersionFile, OxenError> {
     let commit_id = commit_id.as_ref();
     let path = path.as_ref();
     let commit = repositories::commits::get_by_id(repo, commit_id)?
@@ -17,6 +20,28 @@ pub fn get_version_file_from_commit_id(
     let file_node = repositories::tree::get_file_by_path(repo, &commit, path)?
         .ok_or(OxenError::entry_does_not_exist_in_commit(path, commit_id))?;
 
-    let version_path = util::fs::version_path_from_hash(repo, file_node.hash().to_string());
-    Ok(version_path)
+    get_version_file_from_node(repo, &file_node, path)
+}
+
+/// Get a file on disk with the contents of the file node at `path`, however its version is stored
+pub fn get_version_file_from_node(
+    repo: &LocalRepository,
+    file_node: &FileNode,
+    path: impl AsRef<Path>,
+) -> Result<VersionFile, OxenError> {
+    log::debug!(
+        "get_version_file_from_node {:?} {}",
+        path.as_ref(),
+        file_node.hash()
+    );
+    // Versions skipped by `oxen pull --blobless` are downloaded the first time they are read
+    fetch::fetch_missing_versions_blocking(
+        repo,
+        &[FileToRestore {
+            file_node: file_node.clone(),
+            path: path.as_ref().to_path_buf(),
+        }],
+    )?;
+    let version_store = repo.version_store()?;
+    version_store.get_version_file(&file_node.hash().to_string(), file_node.extension())
 }
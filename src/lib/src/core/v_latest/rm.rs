@@ -1,4 +1,5 @@
 use crate::core::db;
+use crate::core::db::key_val::batched_writer::KeyValWriter;
 use crate::error::OxenError;
 use crate::model::staged_data::StagedDataOpts;
 use crate::model::LocalRepository;
@@ -25,7 +26,6 @@ use crate::model::StagedEntryStatus;
 use rmp_serde::Serializer;
 use serde::Serialize;
 
-use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::str;
@@ -40,11 +40,13 @@ pub fn rm(
     opts: &RmOpts,
 ) -> Result<(), OxenError> {
     let db_opts = db::key_val::opts::default();
+    repo.check_exists()?;
     let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
     let staged_db: DBWithThreadMode<MultiThreaded> =
         DBWithThreadMode::open(&db_opts, dunce::simplified(&db_path))?;
 
-    rm_with_staged_db(paths, repo, opts, &staged_db)
+    rm_with_staged_db(paths, repo, opts, &staged_db)?;
+    Ok(())
 }
 
 pub fn rm_with_staged_db(
@@ -52,20 +54,22 @@ pub fn rm_with_staged_db(
     repo: &LocalRepository,
     opts: &RmOpts,
     staged_db: &DBWithThreadMode<MultiThreaded>,
-) -> Result<(), OxenError> {
+) -> Result<CumulativeStats, OxenError> {
     if has_modified_files(repo, paths)? {
         let error = "There are modified files in the working directory.\n\tUse `oxen status` to see the modified files.".to_string();
         return Err(OxenError::basic_str(error));
     }
 
+    // Unstaging does not remove anything from the tree, so there are no stats to report
     if opts.staged && opts.recursive {
-        return remove_staged_recursively_inner(repo, paths, staged_db);
+        remove_staged_recursively_inner(repo, paths, staged_db)?;
+        return Ok(CumulativeStats::default());
     } else if opts.staged {
-        return remove_staged_inner(repo, paths, opts, staged_db);
+        remove_staged_inner(repo, paths, opts, staged_db)?;
+        return Ok(CumulativeStats::default());
     }
 
-    remove_inner(paths, repo, opts, staged_db)?;
-    Ok(())
+    remove_inner(paths, repo, opts, staged_db)
 }
 
 // We have the inner function here so we can open the staged db once
@@ -120,6 +124,7 @@ pub fn remove_staged_recursively(
 ) -> Result<(), OxenError> {
     log::debug!("remove_staged_recursively paths: {:?}", paths);
     let opts = db::key_val::opts::default();
+    repo.check_exists()?;
     let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
     let staged_db: DBWithThreadMode<MultiThreaded> =
         DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
@@ -216,6 +221,7 @@ pub fn remove_staged(
     rm_opts: &RmOpts,
 ) -> Result<(), OxenError> {
     let opts = db::key_val::opts::default();
+    repo.check_exists()?;
     let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
     let staged_db: DBWithThreadMode<MultiThreaded> =
         DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
@@ -254,11 +260,7 @@ fn remove_file_inner(
 ) -> Result<CumulativeStats, OxenError> {
     let path = util::fs::path_relative_to_dir(path, &repo.path)?;
     log::debug!("remove_file path is {path:?}");
-    let mut total = CumulativeStats {
-        total_files: 0,
-        total_bytes: 0,
-        data_type_counts: HashMap::new(),
-    };
+    let mut total = CumulativeStats::default();
 
     // TODO: This is ugly, but the only current solution to get the stats from the removed file
     match process_remove_file_and_parents(repo, &path, staged_db, file_node) {
@@ -291,6 +293,7 @@ pub fn remove_file(
     file_node: &FileNode,
 ) -> Result<CumulativeStats, OxenError> {
     let opts = db::key_val::opts::default();
+    repo.check_exists()?;
     let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
     let staged_db: DBWithThreadMode<MultiThreaded> =
         DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
@@ -371,11 +374,7 @@ fn remove_inner(
         return Err(OxenError::basic_str(error));
     };
 
-    let mut total = CumulativeStats {
-        total_files: 0,
-        total_bytes: 0,
-        data_type_counts: HashMap::new(),
-    };
+    let mut total = CumulativeStats::default();
 
     for path in paths {
         // Get parent node
@@ -451,7 +450,7 @@ fn remove_inner(
 pub fn process_remove_file(
     path: &Path,
     file_node: &FileNode,
-    staged_db: &DBWithThreadMode<MultiThreaded>,
+    staged_db: &impl KeyValWriter,
 ) -> Result<Option<StagedMerkleTreeNode>, OxenError> {
     let mut update_node = file_node.clone();
     update_node.set_name(&path.to_string_lossy());
@@ -471,7 +470,7 @@ pub fn process_remove_file(
         .unwrap();
 
     let relative_path_str = path.to_str().unwrap();
-    staged_db.put(relative_path_str, &buf).unwrap();
+    staged_db.put_entry(relative_path_str, &buf)?;
 
     Ok(Some(staged_entry))
 }
@@ -499,6 +498,7 @@ pub fn remove_dir(
     path: &Path,
 ) -> Result<CumulativeStats, OxenError> {
     let opts = db::key_val::opts::default();
+    repo.check_exists()?;
     let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
     let staged_db: DBWithThreadMode<MultiThreaded> =
         DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
@@ -575,11 +575,7 @@ fn r_process_remove_dir(
     node: &MerkleTreeNode,
     staged_db: &DBWithThreadMode<MultiThreaded>,
 ) -> Result<CumulativeStats, OxenError> {
-    let mut total = CumulativeStats {
-        total_files: 0,
-        total_bytes: 0,
-        data_type_counts: HashMap::new(),
-    };
+    let mut total = CumulativeStats::default();
 
     // Iterate through children, removing files
     for child in &node.children {
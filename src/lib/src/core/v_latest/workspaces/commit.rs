@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::constants::STAGED_DIR;
 use crate::core;
@@ -21,14 +21,38 @@ use crate::view::merge::{MergeConflictFile, Mergeable};
 
 use filetime::FileTime;
 use indicatif::ProgressBar;
-use rocksdb::{DBWithThreadMode, SingleThreaded};
+use rocksdb::{DBWithThreadMode, IteratorMode, SingleThreaded};
 
 pub fn commit(
     workspace: &Workspace,
     new_commit: &NewCommitBody,
     branch_name: impl AsRef<str>,
 ) -> Result<Commit, OxenError> {
-    let branch_name = branch_name.as_ref();
+    commit_staged(workspace, new_commit, branch_name.as_ref(), None)
+}
+
+/// Commit only the staged changes to `path` and its parent dirs, leaving anything else staged in
+/// the workspace for a later commit
+pub fn commit_path(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    new_commit: &NewCommitBody,
+    branch_name: impl AsRef<str>,
+) -> Result<Commit, OxenError> {
+    commit_staged(
+        workspace,
+        new_commit,
+        branch_name.as_ref(),
+        Some(path.as_ref()),
+    )
+}
+
+fn commit_staged(
+    workspace: &Workspace,
+    new_commit: &NewCommitBody,
+    branch_name: &str,
+    only_path: Option<&Path>,
+) -> Result<Commit, OxenError> {
     let repo = &workspace.base_repo;
     let commit = &workspace.commit;
 
@@ -50,7 +74,7 @@ pub fn commit(
 
     log::debug!("workspaces::commit staged db path: {:?}", staged_db_path);
     let opts = db::key_val::opts::default();
-    let commit = {
+    let (commit, has_remaining_changes) = {
         let staged_db: DBWithThreadMode<SingleThreaded> =
             DBWithThreadMode::open(&opts, dunce::simplified(&staged_db_path))?;
 
@@ -62,6 +86,10 @@ pub fn commit(
             &staged_db,
             &commit_progress_bar,
         )?;
+        let dir_entries = match only_path {
+            Some(path) => staged_entries_for_path(dir_entries, path)?,
+            None => dir_entries,
+        };
 
         let conflicts = list_conflicts(workspace, &dir_entries, &branch)?;
         if !conflicts.is_empty() {
@@ -70,18 +98,26 @@ pub fn commit(
 
         let dir_entries = export_tabular_data_frames(workspace, dir_entries)?;
 
-        repositories::commits::commit_writer::commit_dir_entries(
+        let commit = repositories::commits::commit_writer::commit_dir_entries(
             &workspace.base_repo,
             dir_entries,
             new_commit,
             branch_name,
             &commit_progress_bar,
-        )?
+        )?;
+
+        let has_remaining_changes = match only_path {
+            Some(path) => unstage_committed_path(&staged_db, path)?,
+            None => false,
+        };
+        (commit, has_remaining_changes)
     };
 
-    // Clear the staged db
-    log::debug!("Removing staged_db_path: {staged_db_path:?}");
-    util::fs::remove_dir_all(staged_db_path)?;
+    if !has_remaining_changes {
+        // Clear the staged db
+        log::debug!("Removing staged_db_path: {staged_db_path:?}");
+        util::fs::remove_dir_all(staged_db_path)?;
+    }
 
     // DEBUG
     // let tree = repositories::tree::get_by_commit(&workspace.base_repo, &commit)?;
@@ -94,9 +130,9 @@ pub fn commit(
         manager.set_branch_commit_id(branch_name, &commit_id)
     })?;
 
-    if workspace.name.is_some() {
-        // Named workspaces aren't deleted on commit, instead we
-        // update the workspace config to point to the new commit
+    if workspace.name.is_some() || has_remaining_changes {
+        // Named workspaces, and ones with changes left to commit, aren't deleted on commit,
+        // instead we update the workspace config to point to the new commit
         repositories::workspaces::update_commit(workspace, &commit_id)?;
     } else {
         // Unnamed workspaces are deleted on commit
@@ -106,6 +142,54 @@ pub fn commit(
     Ok(commit)
 }
 
+/// Keep only the staged entries for `path` and the dirs above it
+fn staged_entries_for_path(
+    dir_entries: HashMap<PathBuf, Vec<StagedMerkleTreeNode>>,
+    path: &Path,
+) -> Result<HashMap<PathBuf, Vec<StagedMerkleTreeNode>>, OxenError> {
+    let mut filtered = HashMap::new();
+    for (dir, entries) in dir_entries {
+        if !path.starts_with(&dir) {
+            continue;
+        }
+        let mut kept = Vec::new();
+        for entry in entries {
+            if path.starts_with(entry.node.maybe_path()?) {
+                kept.push(entry);
+            }
+        }
+        filtered.insert(dir, kept);
+    }
+    Ok(filtered)
+}
+
+/// Remove `path` from the staged db, along with the dirs above it that have nothing else staged.
+/// Returns whether any staged changes are left.
+fn unstage_committed_path(
+    staged_db: &DBWithThreadMode<SingleThreaded>,
+    path: &Path,
+) -> Result<bool, OxenError> {
+    let mut keys: Vec<PathBuf> = Vec::new();
+    for item in staged_db.iterator(IteratorMode::Start) {
+        let (key, _) = item?;
+        keys.push(PathBuf::from(std::str::from_utf8(&key)?));
+    }
+
+    staged_db.delete(path.to_string_lossy().as_bytes())?;
+    let mut remaining: Vec<PathBuf> = keys.into_iter().filter(|key| key != path).collect();
+    for dir in path.ancestors().skip(1) {
+        let has_other_entries = remaining
+            .iter()
+            .any(|key| key != dir && key.starts_with(dir));
+        if has_other_entries {
+            break;
+        }
+        staged_db.delete(dir.to_string_lossy().as_bytes())?;
+        remaining.retain(|key| key != dir);
+    }
+    Ok(!remaining.is_empty())
+}
+
 pub fn mergeability(
     workspace: &Workspace,
     branch_name: impl AsRef<str>,
@@ -271,14 +355,7 @@ fn export_tabular_data_frames(
             );
             match &dir_entry.node.node {
                 EMerkleTreeNode::File(file_node) => {
-                    // TODO: This is hacky - because we don't know if a file node is the full path or relative to the dir_path
-                    // need a better way to distinguish
-                    let mut node_path = PathBuf::from(file_node.name());
-                    if !node_path.starts_with(&dir_path)
-                        || (dir_path == PathBuf::from("") && node_path.components().count() == 1)
-                    {
-                        node_path = dir_path.join(node_path);
-                    }
+                    let node_path = staged_file_path(&dir_path, file_node);
                     if *file_node.data_type() == EntryDataType::Tabular {
                         log::debug!(
                             "Exporting tabular data frame: {:?} -> {:?}",
@@ -297,11 +374,12 @@ fn export_tabular_data_frames(
 
                         log::debug!("exported path: {:?}", exported_path);
 
-                        // Update the metadata in the new staged merkle tree node
+                        // Update the metadata in the new staged merkle tree node, keeping the staged mode
                         let new_staged_merkle_tree_node = compute_staged_merkle_tree_node(
                             workspace,
                             &exported_path,
                             dir_entry.status,
+                            file_node.is_executable(),
                         )?;
 
                         log::debug!(
@@ -331,10 +409,25 @@ fn export_tabular_data_frames(
     Ok(new_dir_entries)
 }
 
+/// The path of a staged file node relative to the repo root
+pub fn staged_file_path(dir_path: &Path, file_node: &FileNode) -> PathBuf {
+    // TODO: This is hacky - because we don't know if a file node is the full path or relative to the dir_path
+    // need a better way to distinguish
+    let node_path = PathBuf::from(file_node.name());
+    if !node_path.starts_with(dir_path)
+        || (dir_path == Path::new("") && node_path.components().count() == 1)
+    {
+        dir_path.join(node_path)
+    } else {
+        node_path
+    }
+}
+
 fn compute_staged_merkle_tree_node(
     workspace: &Workspace,
     path: &PathBuf,
     status: StagedEntryStatus,
+    executable: bool,
 ) -> Result<StagedMerkleTreeNode, OxenError> {
     // This logic is copied from add.rs but add has some optimizations that make it hard to be reused here
     let metadata = util::fs::metadata(path)?;
@@ -365,6 +458,7 @@ fn compute_staged_merkle_tree_node(
     // Compute the metadata hash and combined hash
     let metadata_hash = util::hasher::get_metadata_hash(&metadata)?;
     let combined_hash = util::hasher::get_combined_hash(Some(metadata_hash), hash.to_u128())?;
+    let combined_hash = util::hasher::get_combined_hash_with_mode(combined_hash, executable);
     let combined_hash = MerkleHash::new(combined_hash);
 
     // Copy the file to the versioned directory
@@ -381,7 +475,7 @@ fn compute_staged_merkle_tree_node(
     util::fs::copy(path, &dst).unwrap();
     let file_extension = path.extension().unwrap_or_default().to_string_lossy();
     let relative_path_str = relative_path.to_str().unwrap();
-    let file_node = FileNode::new(
+    let mut file_node = FileNode::new(
         &workspace.base_repo,
         FileNodeOpts {
             name: relative_path_str.to_string(),
@@ -397,6 +491,7 @@ fn compute_staged_merkle_tree_node(
             extension: file_extension.to_string(),
         },
     )?;
+    file_node.set_executable(executable);
 
     Ok(StagedMerkleTreeNode {
         status,
@@ -151,22 +151,21 @@ pub fn index(workspace: &Workspace, path: &Path) -> Result<(), OxenError> {
     if df_db::table_exists(&conn, TABLE_NAME)? {
         df_db::drop_table(&conn, TABLE_NAME)?;
     }
-    let version_path = util::fs::version_path_from_node(repo, file_hash.to_string(), path);
-
-    log::debug!(
-        "core::v_latest::index::workspaces::data_frames::index({:?}) got version path: {:?}",
-        path,
-        version_path
-    );
-
-    let extension = match &commit_merkle_tree.root.node {
-        EMerkleTreeNode::File(file_node) => file_node.extension(),
+    let file_node = match &commit_merkle_tree.root.node {
+        EMerkleTreeNode::File(file_node) => file_node,
         _ => {
             return Err(OxenError::basic_str("File node is not a file node"));
         }
     };
+    let version_file = repositories::revisions::get_version_file_from_node(repo, file_node, path)?;
 
-    df_db::index_file_with_id(&version_path, &conn, extension)?;
+    log::debug!(
+        "core::v_latest::index::workspaces::data_frames::index({:?}) got version file: {:?}",
+        path,
+        version_file.path()
+    );
+
+    df_db::index_file_with_id(version_file.path(), &conn, file_node.extension())?;
     log::debug!(
         "core::v_latest::index::workspaces::data_frames::index({:?}) finished!",
         path
@@ -217,17 +216,17 @@ pub fn rename(
         if let Some(existing_file_node) =
             repositories::tree::get_file_by_path(&workspace.base_repo, &workspace.commit, path)?
         {
-            let version_path = util::fs::version_path_from_node(
+            let version_file = repositories::revisions::get_version_file_from_node(
                 &workspace.base_repo,
-                existing_file_node.hash().to_string(),
+                &existing_file_node,
                 path,
-            );
+            )?;
             log::debug!(
-                "rename: copying version path: {:?} to {:?}",
-                version_path,
+                "rename: copying version file: {:?} to {:?}",
+                version_file.path(),
                 workspace_file_path
             );
-            util::fs::copy_mkdir(version_path, &workspace_file_path)?;
+            util::fs::copy_mkdir(version_file.path(), &workspace_file_path)?;
         }
 
         // Check if the new path exists in the merkle tree, if it does, it is modified
@@ -5,23 +5,27 @@ use polars::prelude::PlSmallStr;
 use polars::series::Series;
 use rocksdb::DB;
 use serde_json::Value;
+use sql_query_builder::Select;
 
-use crate::constants::DIFF_STATUS_COL;
+use crate::constants::{DIFF_STATUS_COL, OXEN_ID_COL, TABLE_NAME};
 use crate::core::db;
 use crate::core::v_latest::index::CommitMerkleTree;
 use crate::model::merkle_tree::node::EMerkleTreeNode;
 use crate::opts::DFOpts;
 
-use crate::core::db::data_frames::{df_db, rows};
+use crate::core::db::data_frames::{
+    df_db, row_changes_db, row_history_db, row_ops_db, rows, workspace_df_db,
+};
 use crate::core::df::tabular;
 use crate::core::v_latest::{rm, workspaces};
 use crate::error::OxenError;
+use crate::model::data_frame::schema::DataType;
 use crate::model::data_frame::update_result::UpdateResult;
 use crate::model::diff::DiffResult;
 use crate::model::staged_row_status::StagedRowStatus;
-use crate::model::{Commit, LocalRepository, Workspace};
+use crate::model::{Commit, LocalRepository, Schema, Workspace};
 use crate::repositories;
-use crate::util;
+use crate::view::data_frames::DataFrameRowChange;
 use crate::view::JsonDataFrameView;
 
 use std::collections::HashMap;
@@ -32,6 +36,7 @@ pub fn add(
     workspace: &Workspace,
     path: impl AsRef<Path>,
     data: &serde_json::Value,
+    author: &str,
 ) -> Result<DataFrame, OxenError> {
     let path = path.as_ref();
     let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
@@ -44,7 +49,8 @@ pub fn add(
     );
     let conn = df_db::get_connection(db_path)?;
 
-    let df = tabular::parse_json_to_df(data)?;
+    let data = coerce_row_types(&conn, data)?;
+    let df = tabular::parse_json_to_df(&data)?;
     log::debug!("add() df: {:?}", df);
 
     let mut result = rows::append_row(&conn, &df)?;
@@ -60,7 +66,15 @@ pub fn add(
 
     let row = JsonDataFrameView::json_from_df(&mut result);
 
-    rows::record_row_change(&row_changes_path, row_id, "added".to_owned(), row, None)?;
+    let change = rows::record_row_change(
+        &row_changes_path,
+        row_id,
+        "added".to_owned(),
+        row,
+        None,
+        author,
+    )?;
+    log_row_change(workspace, path, &change)?;
 
     workspaces::files::track_modified_data_frame(workspace, path)?;
 
@@ -104,6 +118,7 @@ pub fn delete(
     workspace: &Workspace,
     path: impl AsRef<Path>,
     row_id: &str,
+    author: &str,
 ) -> Result<DataFrame, OxenError> {
     log::debug!("delete() row_id: {row_id} from path: {:?}", path.as_ref());
     let path = path.as_ref();
@@ -118,13 +133,15 @@ pub fn delete(
 
     let row = JsonDataFrameView::json_from_df(&mut deleted_row);
 
-    rows::record_row_change(
+    let change = rows::record_row_change(
         &row_changes_path,
         row_id.to_owned(),
         "deleted".to_owned(),
         row,
         None,
+        author,
     )?;
+    log_row_change(workspace, path, &change)?;
 
     // TODO: Better way of tracking when a file is restored to its original state without diffing
     //       this could be really slow
@@ -154,15 +171,18 @@ pub fn update(
     path: impl AsRef<Path>,
     row_id: &str,
     data: &serde_json::Value,
+    author: &str,
 ) -> Result<DataFrame, OxenError> {
     let path = path.as_ref();
     let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
     let conn = df_db::get_connection(db_path)?;
     let row_changes_path = repositories::workspaces::data_frames::row_changes_path(workspace, path);
 
-    let mut df = tabular::parse_json_to_df(data)?;
+    let data = coerce_row_types(&conn, data)?;
+    let mut df = tabular::parse_json_to_df(&data)?;
 
-    let mut row = repositories::workspaces::data_frames::rows::get_by_id(workspace, path, row_id)?;
+    let mut row =
+        repositories::workspaces::data_frames::rows::get_by_oxen_id(workspace, path, row_id)?;
 
     let mut result = rows::modify_row(&conn, &mut df, row_id)?;
 
@@ -170,13 +190,15 @@ pub fn update(
 
     let row_after = JsonDataFrameView::json_from_df(&mut result);
 
-    rows::record_row_change(
+    let change = rows::record_row_change(
         &row_changes_path,
         row_id.to_owned(),
         "updated".to_owned(),
         row_before,
         Some(row_after),
+        author,
     )?;
+    log_row_change(workspace, path, &change)?;
 
     let diff = repositories::workspaces::data_frames::full_diff(workspace, path)?;
     log::debug!("update() diff: {:?}", diff);
@@ -194,10 +216,150 @@ pub fn update(
     Ok(result)
 }
 
+/// Append a change to the undo log and to the history of its row
+fn log_row_change(
+    workspace: &Workspace,
+    path: &Path,
+    change: &DataFrameRowChange,
+) -> Result<(), OxenError> {
+    rows::record_row_op(
+        &repositories::workspaces::data_frames::row_ops_path(workspace, path),
+        change,
+    )?;
+    rows::record_row_history(
+        &repositories::workspaces::data_frames::row_history_path(workspace, path),
+        change,
+    )
+}
+
+/// Revert the last `count` row operations on the data frame, newest first, returning the ones undone.
+/// Each row is put back exactly as it was before the operation, and its entry in the change log goes
+/// back to the operation before it.
+pub fn undo(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    count: usize,
+) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    let path = path.as_ref();
+    let ops_path = repositories::workspaces::data_frames::row_ops_path(workspace, path);
+    if !ops_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let undone = {
+        let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
+        let conn = df_db::get_connection(db_path)?;
+        let opts = db::key_val::opts::default();
+        let ops_db = DB::open(&opts, dunce::simplified(&ops_path))?;
+        let row_changes_path =
+            repositories::workspaces::data_frames::row_changes_path(workspace, path);
+        let changes_db = DB::open(&opts, dunce::simplified(&row_changes_path))?;
+        let history_path = repositories::workspaces::data_frames::row_history_path(workspace, path);
+        let history_db = DB::open(&opts, dunce::simplified(&history_path))?;
+
+        let undone = row_ops_db::pop_row_ops(&ops_db, count)?;
+        for op in &undone {
+            // An added row did not exist before, updates and deletes record the row before them
+            let previous_row = match op.operation.as_str() {
+                "added" => None,
+                _ => Some(tabular::parse_json_to_df(&op.value)?),
+            };
+            rows::replace_row(&conn, &op.row_id, previous_row.as_ref())?;
+            row_history_db::pop_row_history(&history_db, &op.row_id)?;
+
+            match row_ops_db::last_row_op(&ops_db, &op.row_id)? {
+                Some(previous) => {
+                    row_changes_db::write_data_frame_row_change(&previous, &changes_db)?
+                }
+                None => rows::revert_row_changes(&changes_db, op.row_id.clone())?,
+            }
+        }
+        undone
+    };
+
+    let diff = repositories::workspaces::data_frames::full_diff(workspace, path)?;
+    if let DiffResult::Tabular(diff) = diff {
+        if !diff.has_changes() {
+            rm::remove_staged_recursively(
+                &workspace.workspace_repo,
+                &HashSet::from([path.to_path_buf()]),
+            )?;
+        } else {
+            workspaces::files::track_modified_data_frame(workspace, path)?;
+        }
+    }
+
+    Ok(undone)
+}
+
+/// Clients often send `"42"` or `"true"` for numeric and boolean columns. Convert string values to
+/// the column type of the frame when they parse as it, erroring with the column name when they don't.
+pub fn coerce_row_types(conn: &duckdb::Connection, data: &Value) -> Result<Value, OxenError> {
+    let schema = workspace_df_db::schema_without_oxen_cols(conn, TABLE_NAME)?;
+    match data {
+        Value::Array(rows) => Ok(Value::Array(
+            rows.iter()
+                .map(|row| coerce_row(&schema, row))
+                .collect::<Result<_, _>>()?,
+        )),
+        row => coerce_row(&schema, row),
+    }
+}
+
+fn coerce_row(schema: &Schema, row: &Value) -> Result<Value, OxenError> {
+    let Value::Object(fields) = row else {
+        return Ok(row.to_owned());
+    };
+    let mut coerced = serde_json::Map::new();
+    for (name, value) in fields {
+        let value = match (schema.get_field(name), value) {
+            (Some(field), Value::String(raw)) => {
+                let dtype = DataType::from_string(&field.dtype);
+                coerce_value(&dtype, raw)
+                    .map_err(|_| {
+                        OxenError::basic_str(format!(
+                            "Invalid value {:?} for column `{}`, expected {}",
+                            raw, name, dtype
+                        ))
+                    })?
+                    .unwrap_or_else(|| value.to_owned())
+            }
+            _ => value.to_owned(),
+        };
+        coerced.insert(name.to_owned(), value);
+    }
+    Ok(Value::Object(coerced))
+}
+
+/// None when the column type is not numeric or boolean and the string is kept as is
+fn coerce_value(dtype: &DataType, raw: &str) -> Result<Option<Value>, ()> {
+    let raw = raw.trim();
+    let value = match dtype {
+        DataType::Boolean => match raw.to_lowercase().as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => return Err(()),
+        },
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            Value::from(raw.parse::<i64>().map_err(|_| ())?)
+        }
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            Value::from(raw.parse::<u64>().map_err(|_| ())?)
+        }
+        DataType::Float32 | DataType::Float64 => {
+            let number = raw.parse::<f64>().map_err(|_| ())?;
+            Value::Number(serde_json::Number::from_f64(number).ok_or(())?)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
 pub fn batch_update(
     workspace: &Workspace,
     path: impl AsRef<Path>,
     data: &Value,
+    author: &str,
 ) -> Result<Vec<UpdateResult>, OxenError> {
     let path = path.as_ref();
 
@@ -229,7 +391,45 @@ pub fn batch_update(
         })
         .collect::<Result<_, OxenError>>()?;
 
-    rows::modify_rows(&conn, row_map)?;
+    // Keep the rows as they were so each one can be undone on its own
+    let mut rows_before = HashMap::new();
+    for key in &keys {
+        let mut row =
+            repositories::workspaces::data_frames::rows::get_by_oxen_id(workspace, path, key)?;
+        rows_before.insert(key.to_owned(), JsonDataFrameView::json_from_df(&mut row));
+    }
+
+    let modified = rows::modify_rows(&conn, row_map)?;
+
+    let mut rows_after = HashMap::new();
+    for idx in 0..modified.height() {
+        let mut row = modified.slice(idx as i64, 1);
+        let row_id = row
+            .column(OXEN_ID_COL)?
+            .get(0)?
+            .get_str()
+            .map(str::to_owned);
+        if let Some(row_id) = row_id {
+            rows_after.insert(row_id, JsonDataFrameView::json_from_df(&mut row));
+        }
+    }
+
+    let row_changes_path = repositories::workspaces::data_frames::row_changes_path(workspace, path);
+    for key in &keys {
+        let (Some(row_before), Some(row_after)) = (rows_before.remove(key), rows_after.remove(key))
+        else {
+            continue;
+        };
+        let change = rows::record_row_change(
+            &row_changes_path,
+            key.to_owned(),
+            "updated".to_owned(),
+            row_before,
+            Some(row_after),
+            author,
+        )?;
+        log_row_change(workspace, path, &change)?;
+    }
 
     let results: Vec<UpdateResult> = keys
         .iter()
@@ -239,6 +439,102 @@ pub fn batch_update(
     Ok(results)
 }
 
+/// A row edit from another workspace, see `apply_merged_changes`
+pub enum MergedRowChange {
+    /// A new row, inserted under the `_oxen_id` it was added with in the other workspace
+    Added {
+        row_id: String,
+        data: Value,
+    },
+    Updated {
+        row_id: String,
+        data: Value,
+    },
+    Deleted {
+        row_id: String,
+    },
+}
+
+/// Apply the row edits merged in from another workspace in a single duckdb transaction, so a merge that
+/// fails part way leaves the data frame as it was. The changes are only logged once all of them applied.
+pub fn apply_merged_changes(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    changes: &[MergedRowChange],
+    author: &str,
+) -> Result<(), OxenError> {
+    let path = path.as_ref();
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
+    let mut conn = df_db::get_connection(db_path)?;
+    let tx = conn.transaction()?;
+    // (row_id, operation, value, new_value) of each change, to record after the commit
+    let mut applied: Vec<(String, &str, Value, Option<Value>)> = vec![];
+    for change in changes {
+        match change {
+            MergedRowChange::Added { row_id, data } => {
+                let data = coerce_row_types(&tx, data)?;
+                let df = tabular::parse_json_to_df(&data)?;
+                let mut result = rows::append_row_with_id(&tx, &df, row_id)?;
+                let row = JsonDataFrameView::json_from_df(&mut result);
+                applied.push((row_id.to_owned(), "added", row, None));
+            }
+            MergedRowChange::Updated { row_id, data } => {
+                let select = Select::new()
+                    .select("*")
+                    .from(TABLE_NAME)
+                    .where_clause(&format!("{} = '{}'", OXEN_ID_COL, row_id));
+                let mut row_before = df_db::select(&tx, &select, None)?;
+                let data = coerce_row_types(&tx, data)?;
+                let mut df = tabular::parse_json_to_df(&data)?;
+                let mut row_after = rows::modify_row(&tx, &mut df, row_id)?;
+                applied.push((
+                    row_id.to_owned(),
+                    "updated",
+                    JsonDataFrameView::json_from_df(&mut row_before),
+                    Some(JsonDataFrameView::json_from_df(&mut row_after)),
+                ));
+            }
+            MergedRowChange::Deleted { row_id } => {
+                let mut deleted_row = rows::delete_row(&tx, row_id)?;
+                let row = JsonDataFrameView::json_from_df(&mut deleted_row);
+                applied.push((row_id.to_owned(), "deleted", row, None));
+            }
+        }
+    }
+    tx.commit()?;
+    drop(conn);
+
+    let row_changes_path = repositories::workspaces::data_frames::row_changes_path(workspace, path);
+    for (row_id, operation, value, new_value) in applied {
+        let change = rows::record_row_change(
+            &row_changes_path,
+            row_id,
+            operation.to_owned(),
+            value,
+            new_value,
+            author,
+        )?;
+        log_row_change(workspace, path, &change)?;
+    }
+
+    let diff = repositories::workspaces::data_frames::full_diff(workspace, path)?;
+    if let DiffResult::Tabular(diff) = diff {
+        if !diff.has_changes() {
+            rm::remove_staged_recursively(
+                &workspace.workspace_repo,
+                &HashSet::from([path.to_path_buf()]),
+            )?;
+        } else {
+            workspaces::files::track_modified_data_frame(workspace, path)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn prepare_modified_or_removed_row(
     repo: &LocalRepository,
     commit: &Commit,
@@ -261,20 +557,17 @@ pub fn prepare_modified_or_removed_row(
     );
 
     // let scan_rows = 10000 as usize;
-    let committed_df_path = util::fs::version_path_from_node(
-        repo,
-        commit_merkle_tree.root.hash.to_string(),
-        path.as_ref(),
-    );
+    let committed_df_file =
+        repositories::revisions::get_version_file_from_node(repo, &file_node, path.as_ref())?;
 
     log::debug!(
         "prepare_modified_or_removed_row() committed_df_path: {:?}",
-        committed_df_path
+        committed_df_file.path()
     );
 
     // TODONOW should not be using all rows - just need to parse delim
     let lazy_df = tabular::read_df_with_extension(
-        committed_df_path,
+        &committed_df_file,
         file_node.extension(),
         &DFOpts::empty(),
     )?;
@@ -302,13 +595,16 @@ pub fn restore_row_in_db(
     let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path.as_ref());
     let conn = df_db::get_connection(db_path)?;
     let opts = db::key_val::opts::default();
-    let column_changes_path =
-        repositories::workspaces::data_frames::column_changes_path(workspace, path.as_ref());
-    let db = DB::open(&opts, dunce::simplified(&column_changes_path))?;
+    let row_changes_path =
+        repositories::workspaces::data_frames::row_changes_path(workspace, path.as_ref());
+    let db = DB::open(&opts, dunce::simplified(&row_changes_path))?;
 
     // Get the row by id
-    let row =
-        repositories::workspaces::data_frames::rows::get_by_id(workspace, path.as_ref(), row_id)?;
+    let row = repositories::workspaces::data_frames::rows::get_by_oxen_id(
+        workspace,
+        path.as_ref(),
+        row_id,
+    )?;
 
     if row.height() == 0 {
         return Err(OxenError::resource_not_found(row_id));
@@ -343,6 +639,15 @@ pub fn restore_row_in_db(
         }
     };
 
+    // The row is back to its committed version, so its edits can no longer be undone
+    let ops_path = repositories::workspaces::data_frames::row_ops_path(workspace, path.as_ref());
+    let ops_db = DB::open(&opts, dunce::simplified(&ops_path))?;
+    row_ops_db::delete_row_ops(&ops_db, row_id)?;
+    let history_path =
+        repositories::workspaces::data_frames::row_history_path(workspace, path.as_ref());
+    let history_db = DB::open(&opts, dunce::simplified(&history_path))?;
+    row_history_db::delete_row_history(&history_db, row_id)?;
+
     log::debug!("we're returning this row: {:?}", result_row);
 
     Ok(result_row)
@@ -1,13 +1,23 @@
-use crate::constants::TABLE_NAME;
+use crate::constants::{STAGED_DIR, TABLE_NAME};
+use crate::core;
+use crate::core::db;
 use crate::core::db::data_frames::{df_db, workspace_df_db};
+use crate::core::v_latest::workspaces::commit::staged_file_path;
 use crate::error::OxenError;
+use crate::model::diff::diff_entry_status::DiffEntryStatus;
 use crate::model::diff::tabular_diff::{
     TabularDiffDupes, TabularDiffMods, TabularDiffParameters, TabularDiffSchemas,
     TabularDiffSummary, TabularSchemaDiff,
 };
 use crate::model::diff::{AddRemoveModifyCounts, DiffResult, TabularDiff};
-use crate::model::Workspace;
+use crate::model::merkle_tree::node::EMerkleTreeNode;
+use crate::model::{EntryDataType, StagedEntryStatus, Workspace};
 use crate::repositories;
+use crate::util;
+use crate::view::workspaces::WorkspaceFileDiff;
+
+use indicatif::ProgressBar;
+use rocksdb::{DBWithThreadMode, SingleThreaded};
 use std::path::Path;
 
 pub fn diff(workspace: &Workspace, path: impl AsRef<Path>) -> Result<DiffResult, OxenError> {
@@ -68,6 +78,78 @@ pub fn diff(workspace: &Workspace, path: impl AsRef<Path>) -> Result<DiffResult,
     Ok(DiffResult::Tabular(diff_result))
 }
 
+/// The changes committing the workspace would make to the head of `branch_name`.
+/// Staged files are compared to the branch tree, indexed data frames also get their row change counts.
+pub fn diff_branch(
+    workspace: &Workspace,
+    branch_name: impl AsRef<str>,
+) -> Result<Vec<WorkspaceFileDiff>, OxenError> {
+    let branch_name = branch_name.as_ref();
+    let repo = &workspace.base_repo;
+    let Some(branch) = repositories::branches::get_by_name(repo, branch_name)? else {
+        return Err(OxenError::revision_not_found(
+            branch_name.to_string().into(),
+        ));
+    };
+    let Some(branch_commit) = repositories::commits::get_by_id(repo, &branch.commit_id)? else {
+        return Err(OxenError::revision_not_found(
+            branch.commit_id.clone().into(),
+        ));
+    };
+
+    let staged_db_path = util::fs::oxen_hidden_dir(&workspace.workspace_repo.path).join(STAGED_DIR);
+    let opts = db::key_val::opts::default();
+    let staged_db: DBWithThreadMode<SingleThreaded> =
+        DBWithThreadMode::open(&opts, dunce::simplified(&staged_db_path))?;
+    let (dir_entries, _) = core::v_latest::status::read_staged_entries(
+        &workspace.workspace_repo,
+        &staged_db,
+        &ProgressBar::hidden(),
+    )?;
+
+    let mut diffs = vec![];
+    for (dir_path, entries) in dir_entries {
+        for entry in entries {
+            let EMerkleTreeNode::File(file_node) = &entry.node.node else {
+                continue;
+            };
+            let path = staged_file_path(&dir_path, file_node);
+            let branch_file = repositories::tree::get_file_by_path(repo, &branch_commit, &path)?;
+
+            let row_counts = if *file_node.data_type() == EntryDataType::Tabular
+                && repositories::workspaces::data_frames::is_indexed(workspace, &path)?
+            {
+                let diff_df = repositories::workspaces::data_frames::diff(workspace, &path)?;
+                Some(AddRemoveModifyCounts::from_diff_df(&diff_df)?)
+            } else {
+                None
+            };
+            let has_row_changes = row_counts
+                .as_ref()
+                .is_some_and(|c| c.added + c.removed + c.modified > 0);
+
+            let status = match (&entry.status, &branch_file) {
+                (StagedEntryStatus::Removed, Some(_)) => DiffEntryStatus::Removed,
+                (StagedEntryStatus::Removed, None) => continue,
+                (_, None) => DiffEntryStatus::Added,
+                (_, Some(branch_file)) => {
+                    if branch_file.hash() == file_node.hash() && !has_row_changes {
+                        continue;
+                    }
+                    DiffEntryStatus::Modified
+                }
+            };
+            diffs.push(WorkspaceFileDiff {
+                path: path.to_string_lossy().to_string(),
+                status,
+                row_counts,
+            });
+        }
+    }
+    diffs.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(diffs)
+}
+
 pub fn is_indexed(workspace: &Workspace, path: &Path) -> Result<bool, OxenError> {
     log::debug!("checking dataset is indexed for {:?}", path);
     let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
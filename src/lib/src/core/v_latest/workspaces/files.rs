@@ -98,6 +98,21 @@ pub fn delete(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), OxenE
     Ok(())
 }
 
+/// Stage the removal of a file that exists in the workspace's base commit
+pub fn rm(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), OxenError> {
+    let path = path.as_ref();
+    let workspace_repo = &workspace.workspace_repo;
+
+    let Some(file_node) =
+        repositories::tree::get_file_by_path(&workspace.base_repo, &workspace.commit, path)?
+    else {
+        return Err(OxenError::path_does_not_exist(path));
+    };
+
+    core::v_latest::rm::remove_file(workspace_repo, &workspace_repo.path.join(path), &file_node)?;
+    Ok(())
+}
+
 pub fn exists(workspace: &Workspace, path: impl AsRef<Path>) -> Result<bool, OxenError> {
     let path = path.as_ref();
     let workspace_repo = &workspace.workspace_repo;
@@ -469,10 +484,7 @@ fn p_add_file(
     let hash_str = file_status.hash.to_string();
     version_store.store_version_from_path(&hash_str, &full_path)?;
 
-    let conflicts: HashSet<PathBuf> = repositories::merge::list_conflicts(workspace_repo)?
-        .into_iter()
-        .map(|conflict| conflict.merge_entry.path)
-        .collect();
+    let conflicts = core::v_latest::add::merge_conflict_paths(workspace_repo)?;
 
     let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
     process_add_file(
@@ -12,6 +12,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::path::StripPrefixError;
 
+use crate::constants::OXEN_HIDDEN_DIR;
 use crate::model::Schema;
 use crate::model::Workspace;
 use crate::model::{Commit, ParsedResource};
@@ -44,6 +45,7 @@ pub enum OxenError {
     // Repo
     RepoNotFound(Box<RepoNew>),
     LocalRepoNotFound(Box<PathBufError>),
+    NotARepository(Box<PathBufError>),
     RepoAlreadyExists(Box<RepoNew>),
     RepoAlreadyExistsAtDestination(Box<StringError>),
 
@@ -82,6 +84,7 @@ pub enum OxenError {
 
     // Entry
     CommitEntryNotFound(StringError),
+    InvalidHash(StringError),
 
     // Schema
     InvalidSchema(Box<Schema>),
@@ -142,7 +145,14 @@ pub enum OxenError {
 impl fmt::Display for OxenError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            OxenError::OxenUpdateRequired(err) | OxenError::Basic(err) => write!(f, "{}", err),
+            OxenError::OxenUpdateRequired(err)
+            | OxenError::InvalidHash(err)
+            | OxenError::Basic(err) => write!(f, "{}", err),
+            OxenError::NotARepository(path) => write!(
+                f,
+                "Not an oxen repository (or any of the parent directories): {}\n\nRun `oxen init` to create one, or `oxen clone <url>` to clone an existing one.",
+                path
+            ),
             _ => {
                 write!(f, "{:?}", self)
             }
@@ -151,6 +161,79 @@ impl fmt::Display for OxenError {
 }
 
 impl OxenError {
+    /// A stable, machine readable name for the kind of error, ie `"branch_not_found"`.
+    /// Unlike the message, this won't change between releases so callers can match on it.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OxenError::UserConfigNotFound(..) => "user_config_not_found",
+            OxenError::RepoNotFound(..) => "repo_not_found",
+            OxenError::LocalRepoNotFound(..) => "local_repo_not_found",
+            OxenError::NotARepository(..) => "not_a_repository",
+            OxenError::RepoAlreadyExists(..) => "repo_already_exists",
+            OxenError::RepoAlreadyExistsAtDestination(..) => "repo_already_exists_at_destination",
+            OxenError::ForkStatusNotFound(..) => "fork_status_not_found",
+            OxenError::RemoteRepoNotFound(..) => "remote_repo_not_found",
+            OxenError::RemoteAheadOfLocal(..) => "remote_ahead_of_local",
+            OxenError::IncompleteLocalHistory(..) => "incomplete_local_history",
+            OxenError::RemoteBranchLocked(..) => "remote_branch_locked",
+            OxenError::UpstreamMergeConflict(..) => "upstream_merge_conflict",
+            OxenError::BranchNotFound(..) => "branch_not_found",
+            OxenError::RevisionNotFound(..) => "revision_not_found",
+            OxenError::RootCommitDoesNotMatch(..) => "root_commit_does_not_match",
+            OxenError::NothingToCommit(..) => "nothing_to_commit",
+            OxenError::NoCommitsFound(..) => "no_commits_found",
+            OxenError::HeadNotFound(..) => "head_not_found",
+            OxenError::WorkspaceNotFound(..) => "workspace_not_found",
+            OxenError::QueryableWorkspaceNotFound() => "queryable_workspace_not_found",
+            OxenError::WorkspaceBehind(..) => "workspace_behind",
+            OxenError::ResourceNotFound(..) => "resource_not_found",
+            OxenError::PathDoesNotExist(..) => "path_does_not_exist",
+            OxenError::ParsedResourceNotFound(..) => "parsed_resource_not_found",
+            OxenError::MigrationRequired(..) => "migration_required",
+            OxenError::OxenUpdateRequired(..) => "oxen_update_required",
+            OxenError::InvalidVersion(..) => "invalid_version",
+            OxenError::InvalidHash(..) => "invalid_hash",
+            OxenError::CommitEntryNotFound(..) => "commit_entry_not_found",
+            OxenError::InvalidSchema(..) => "invalid_schema",
+            OxenError::IncompatibleSchemas(..) => "incompatible_schemas",
+            OxenError::InvalidFileType(..) => "invalid_file_type",
+            OxenError::ColumnNameAlreadyExists(..) => "column_name_already_exists",
+            OxenError::ColumnNameNotFound(..) => "column_name_not_found",
+            OxenError::UnsupportedOperation(..) => "unsupported_operation",
+            OxenError::ImageMetadataParseError(..) => "image_metadata_parse_error",
+            OxenError::SQLParseError(..) => "sql_parse_error",
+            OxenError::NoRowsFound(..) => "no_rows_found",
+            OxenError::OperationCancelled(..) => "operation_cancelled",
+            OxenError::StripPrefixError(..) => "strip_prefix_error",
+            OxenError::DataFrameError(..) => "data_frame_error",
+            OxenError::ImportFileError(..) => "import_file_error",
+            OxenError::IO(..) => "io",
+            OxenError::Authentication(..) => "authentication",
+            OxenError::ArrowError(..) => "arrow_error",
+            OxenError::BinCodeError(..) => "bincode_error",
+            OxenError::TomlSer(..) => "toml_ser",
+            OxenError::TomlDe(..) => "toml_de",
+            OxenError::URI(..) => "uri",
+            OxenError::URL(..) => "url",
+            OxenError::JSON(..) => "json",
+            OxenError::HTTP(..) => "http",
+            OxenError::UTF8Error(..) => "utf8_error",
+            OxenError::DB(..) => "db",
+            OxenError::DUCKDB(..) => "duckdb",
+            OxenError::ENV(..) => "env",
+            OxenError::ImageError(..) => "image_error",
+            OxenError::RedisError(..) => "redis_error",
+            OxenError::R2D2Error(..) => "r2d2_error",
+            OxenError::JwalkError(..) => "jwalk_error",
+            OxenError::PatternError(..) => "pattern_error",
+            OxenError::GlobError(..) => "glob_error",
+            OxenError::PolarsError(..) => "polars_error",
+            OxenError::ParseIntError(..) => "parse_int_error",
+            OxenError::RmpDecodeError(..) => "rmp_decode_error",
+            OxenError::Basic(..) => "basic",
+        }
+    }
+
     pub fn basic_str(s: impl AsRef<str>) -> Self {
         OxenError::Basic(StringError::from(s.as_ref()))
     }
@@ -167,6 +250,14 @@ impl OxenError {
         OxenError::InvalidVersion(StringError::from(s.as_ref()))
     }
 
+    pub fn invalid_hash(s: impl AsRef<str>) -> Self {
+        let err = format!(
+            "Invalid hash {:?}, expected 1 to 32 hexadecimal characters",
+            s.as_ref()
+        );
+        OxenError::InvalidHash(StringError::from(err))
+    }
+
     pub fn oxen_update_required(s: impl AsRef<str>) -> Self {
         OxenError::OxenUpdateRequired(StringError::from(s.as_ref()))
     }
@@ -282,6 +373,11 @@ impl OxenError {
         OxenError::LocalRepoNotFound(Box::new(dir.as_ref().into()))
     }
 
+    /// There is no .oxen directory in `dir`, ie a command was run outside of a repository
+    pub fn not_a_repository(dir: impl AsRef<Path>) -> OxenError {
+        OxenError::NotARepository(Box::new(dir.as_ref().into()))
+    }
+
     pub fn email_and_name_not_set() -> OxenError {
         OxenError::user_config_not_found(EMAIL_AND_NAME_NOT_FOUND.to_string().into())
     }
@@ -465,6 +561,36 @@ impl OxenError {
         ))
     }
 
+    pub fn cannot_add_oxen_hidden_dir(path: impl AsRef<Path>) -> OxenError {
+        let err = format!(
+            "Cannot add {:?}, files inside the {} directory are managed by oxen",
+            path.as_ref(),
+            OXEN_HIDDEN_DIR
+        );
+        OxenError::basic_str(err)
+    }
+
+    pub fn hook_failed(name: impl AsRef<str>, stderr: impl AsRef<str>) -> OxenError {
+        let err = format!(
+            "Hook `{}` exited with a non-zero status, aborting.\n\n{}",
+            name.as_ref(),
+            stderr.as_ref().trim_end()
+        );
+        OxenError::basic_str(err)
+    }
+
+    pub fn commit_message_rejected(
+        message: impl AsRef<str>,
+        pattern: impl AsRef<str>,
+    ) -> OxenError {
+        let err = format!(
+            "Commit message {:?} does not match commit.message_pattern {:?} in the repository config",
+            message.as_ref(),
+            pattern.as_ref()
+        );
+        OxenError::basic_str(err)
+    }
+
     pub fn entry_does_not_exist_in_commit(
         path: impl AsRef<Path>,
         commit_id: impl AsRef<str>,
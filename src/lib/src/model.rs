@@ -16,6 +16,7 @@ pub mod namespace;
 pub mod object_id;
 pub mod parsed_resource;
 pub mod partial_node;
+pub mod reflog_entry;
 pub mod remote;
 pub mod remote_branch;
 pub mod repository;
@@ -41,6 +42,7 @@ pub use crate::model::commit::{Commit, CommitStats, NewCommit, NewCommitBody};
 
 // Branch
 pub use crate::model::branch::Branch;
+pub use crate::model::reflog_entry::ReflogEntry;
 pub use crate::model::remote_branch::RemoteBranch;
 
 // Entry (TODO: These should just be nodes in the tree)
@@ -81,7 +83,7 @@ pub use crate::model::data_frame::schema::Schema;
 pub use crate::model::workspace::Workspace;
 
 // Merkle Tree Node
-pub use crate::model::merkle_tree::merkle_hash::MerkleHash;
+pub use crate::model::merkle_tree::merkle_hash::{MerkleHash, MerkleHashEncoding};
 pub use crate::model::merkle_tree::node_type::{
     MerkleTreeNodeIdType, MerkleTreeNodeType, TMerkleTreeNode,
 };
@@ -7,6 +7,7 @@ use crate::model::merkle_tree::node::FileNode;
 use crate::model::metadata::generic_metadata::GenericMetadata;
 use crate::model::{CommitEntry, DataFrameSize, LocalRepository};
 use crate::opts::DFOpts;
+use crate::repositories;
 use crate::util;
 
 // THE DIFFERENCE BETWEEN WRAPPER AND SUMMARY IS JUST THE KEY NAME IN THE JSON RESPONSE
@@ -156,8 +157,9 @@ impl TabularDiffWrapper {
     ) -> Option<DataFrame> {
         match node {
             Some(node) => {
-                let version_path = util::fs::version_path_from_hash(repo, node.hash().to_string());
-                tabular::read_df_with_extension(version_path, node.extension(), &DFOpts::empty())
+                let version_file =
+                    repositories::revisions::get_version_file_from_node(repo, node, "").ok()?;
+                tabular::read_df_with_extension(&version_file, node.extension(), &DFOpts::empty())
                     .ok()
             }
             None => None,
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use crate::model::CommitEntry;
+use crate::model::{CommitEntry, MerkleHash};
 use serde::{Deserialize, Serialize};
 
 use super::merkle_tree::node::FileNode;
@@ -10,6 +10,9 @@ pub struct EntryMergeConflict {
     pub lca_entry: CommitEntry,   // Least Common Ancestor Entry
     pub base_entry: CommitEntry,  // Entry that existed in the base commit
     pub merge_entry: CommitEntry, // Entry we are trying to merge in
+    /// Only resolvable with --ours or --theirs, see `FileNode::is_binary_conflict`
+    #[serde(default)]
+    pub is_binary: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -24,6 +27,9 @@ pub struct MergeConflict {
     pub lca_entry: MergeConflictEntry,
     pub base_entry: MergeConflictEntry,
     pub merge_entry: MergeConflictEntry,
+    /// The file is binary so UIs should offer ours or theirs instead of a text merge
+    #[serde(default)]
+    pub is_binary: bool,
 }
 
 impl MergeConflict {
@@ -32,6 +38,7 @@ impl MergeConflict {
             lca_entry: self.lca_entry.to_commit_entry(),
             base_entry: self.base_entry.to_commit_entry(),
             merge_entry: self.merge_entry.to_commit_entry(),
+            is_binary: self.is_binary,
         }
     }
 }
@@ -64,13 +71,40 @@ impl EntryMergeConflict {
             lca_entry: self.lca_entry.to_merge_conflict_entry(),
             base_entry: self.base_entry.to_merge_conflict_entry(),
             merge_entry: self.merge_entry.to_merge_conflict_entry(),
+            is_binary: self.is_binary,
         }
     }
 }
 
 impl NodeMergeConflict {
+    /// Path of the conflicting file, relative to the repository root
+    pub fn path(&self) -> &Path {
+        &self.base_entry.1
+    }
+
+    /// Hash of the file in the common ancestor, the base commit's version if the file was added on both sides
+    pub fn lca_hash(&self) -> MerkleHash {
+        *self.lca_entry.0.hash()
+    }
+
+    /// Hash of our version of the file, from the commit being merged into
+    pub fn base_hash(&self) -> MerkleHash {
+        *self.base_entry.0.hash()
+    }
+
+    /// Hash of their version of the file, from the commit being merged in
+    pub fn merge_hash(&self) -> MerkleHash {
+        *self.merge_entry.0.hash()
+    }
+
+    /// Whether either side of the conflict is a file that cannot be merged by content
+    pub fn is_binary(&self) -> bool {
+        self.base_entry.0.is_binary_conflict() || self.merge_entry.0.is_binary_conflict()
+    }
+
     pub fn to_merge_conflict(&self) -> MergeConflict {
         MergeConflict {
+            is_binary: self.is_binary(),
             lca_entry: to_merge_conflict_entry(&self.lca_entry.0, &self.lca_entry.1),
             base_entry: to_merge_conflict_entry(&self.base_entry.0, &self.base_entry.1),
             merge_entry: to_merge_conflict_entry(&self.merge_entry.0, &self.merge_entry.1),
@@ -2,7 +2,7 @@ pub mod merkle_hash;
 pub mod node;
 pub mod node_type;
 
-pub use crate::model::merkle_tree::merkle_hash::MerkleHash;
+pub use crate::model::merkle_tree::merkle_hash::{MerkleHash, MerkleHashEncoding};
 pub use crate::model::merkle_tree::node_type::{
     MerkleTreeNodeIdType, MerkleTreeNodeType, TMerkleTreeNode,
 };
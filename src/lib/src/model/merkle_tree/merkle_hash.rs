@@ -3,6 +3,20 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// How to render a hash for display. This never changes the on-disk format.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MerkleHashEncoding {
+    #[default]
+    Hex,
+    Base58,
+}
+
+/// A 128 bit content hash.
+///
+/// The `Display` impl (lowercase hex, no zero padding) is the stable key format used by the
+/// version store and the merkle tree dbs. Use `to_short_string` or `encode` for UI output only.
 #[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct MerkleHash(u128);
 
@@ -29,13 +43,46 @@ impl MerkleHash {
             str
         }
     }
+
+    /// The first `len` characters of the hex hash, for abbreviated UI output
+    pub fn to_short_string(&self, len: usize) -> String {
+        let str = self.to_string();
+        str.chars().take(len).collect()
+    }
+
+    /// Render the hash in the given encoding, for UI output
+    pub fn encode(&self, encoding: MerkleHashEncoding) -> String {
+        match encoding {
+            MerkleHashEncoding::Hex => self.to_string(),
+            MerkleHashEncoding::Base58 => self.to_base58_string(),
+        }
+    }
+
+    pub fn to_base58_string(&self) -> String {
+        let mut value = self.0;
+        if value == 0 {
+            return (BASE58_ALPHABET[0] as char).to_string();
+        }
+        let mut chars = Vec::new();
+        while value > 0 {
+            chars.push(BASE58_ALPHABET[(value % 58) as usize]);
+            value /= 58;
+        }
+        chars.reverse();
+        String::from_utf8(chars).unwrap()
+    }
 }
 
+/// Parses the `Display` format, rejecting anything that is not 1 to 32 hex characters
+/// (`u128::from_str_radix` alone would accept a leading `+`).
 impl std::str::FromStr for MerkleHash {
     type Err = OxenError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let hash = u128::from_str_radix(s, 16)?;
+        if s.is_empty() || s.len() > 32 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(OxenError::invalid_hash(s));
+        }
+        let hash = u128::from_str_radix(s, 16).map_err(|_| OxenError::invalid_hash(s))?;
         Ok(Self(hash))
     }
 }
@@ -57,3 +104,66 @@ impl Hash for MerkleHash {
         self.0.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_to_short_string() {
+        let hash = MerkleHash::from_str("7783cda74ceeced8d45fae3155382c").unwrap();
+        assert_eq!(hash.to_short_string(7), "7783cda");
+        assert_eq!(hash.to_short_string(100), hash.to_string());
+    }
+
+    #[test]
+    fn test_from_str_round_trips_valid_hashes() {
+        for s in [
+            "0",
+            "abc",
+            "7783cda74ceeced8d45fae3155382c",
+            "ffffffffffffffffffffffffffffffff",
+        ] {
+            let hash = MerkleHash::from_str(s).unwrap();
+            assert_eq!(hash.to_string(), s);
+        }
+        // Uppercase parses, but displays as lowercase
+        let hash = MerkleHash::from_str("ABC").unwrap();
+        assert_eq!(hash, MerkleHash::new(0xabc));
+    }
+
+    #[test]
+    fn test_from_str_rejects_malformed_hashes() {
+        for s in [
+            "",
+            "+abc",
+            "-1",
+            "xyz",
+            " abc",
+            "abc\n",
+            "0x1f",
+            "1ffffffffffffffffffffffffffffffff",
+        ] {
+            let err = MerkleHash::from_str(s).unwrap_err();
+            assert_eq!(err.kind(), "invalid_hash", "{s:?}");
+        }
+    }
+
+    #[test]
+    fn test_display_is_stable_key_format() {
+        // The version store keys on this, do not change it
+        let hash = MerkleHash::new(0x0abc);
+        assert_eq!(hash.to_string(), "abc");
+        assert_eq!(hash.encode(MerkleHashEncoding::Hex), "abc");
+    }
+
+    #[test]
+    fn test_to_base58_string() {
+        assert_eq!(MerkleHash::new(0).to_base58_string(), "1");
+        assert_eq!(MerkleHash::new(57).to_base58_string(), "z");
+        assert_eq!(MerkleHash::new(58).to_base58_string(), "21");
+        assert_eq!(MerkleHash::new(58).encode(MerkleHashEncoding::Base58), "21");
+    }
+}
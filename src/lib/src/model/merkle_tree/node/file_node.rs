@@ -5,6 +5,7 @@ use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::merkle_tree::node::file_node_types::{FileChunkType, FileStorageType};
 use crate::model::metadata::generic_metadata::GenericMetadata;
+use crate::model::metadata::MetadataPointer;
 use crate::model::{
     EntryDataType, LocalRepository, MerkleHash, MerkleTreeNodeIdType, MerkleTreeNodeType,
     TMerkleTreeNode,
@@ -51,7 +52,10 @@ pub trait TFileNode {
     fn chunk_hashes(&self) -> &Vec<u128>;
     fn set_chunk_hashes(&mut self, chunk_hashes: Vec<u128>);
     fn chunk_type(&self) -> &FileChunkType;
+    fn set_chunk_type(&mut self, chunk_type: FileChunkType);
     fn storage_backend(&self) -> &FileStorageType;
+    fn executable(&self) -> bool;
+    fn set_executable(&mut self, executable: bool);
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -85,6 +89,7 @@ impl FileNode {
                     chunk_hashes: vec![],
                     chunk_type: FileChunkType::SingleFile,
                     storage_backend: FileStorageType::Disk,
+                    executable: false,
                 }),
             }),
             _ => Err(OxenError::basic_str(
@@ -179,10 +184,31 @@ impl FileNode {
         self.node().data_type()
     }
 
+    /// Binary, image and video files cannot be merged by content, so a conflict on one is only
+    /// resolved by taking our or their version
+    pub fn is_binary_conflict(&self) -> bool {
+        matches!(
+            self.data_type(),
+            EntryDataType::Binary | EntryDataType::Image | EntryDataType::Video
+        )
+    }
+
     pub fn metadata(&self) -> Option<GenericMetadata> {
         self.node().metadata()
     }
 
+    /// The external reference if this file was added with `oxen add --pointer`
+    pub fn pointer(&self) -> Option<MetadataPointer> {
+        match self.metadata() {
+            Some(GenericMetadata::MetadataPointer(pointer)) => Some(pointer),
+            _ => None,
+        }
+    }
+
+    pub fn is_pointer(&self) -> bool {
+        self.pointer().is_some()
+    }
+
     pub fn get_mut_metadata(&mut self) -> &mut Option<GenericMetadata> {
         self.mut_node().get_mut_metadata()
     }
@@ -211,9 +237,38 @@ impl FileNode {
         self.node().chunk_type()
     }
 
+    pub fn set_chunk_type(&mut self, chunk_type: FileChunkType) {
+        self.mut_node().set_chunk_type(chunk_type);
+    }
+
     pub fn storage_backend(&self) -> &FileStorageType {
         self.node().storage_backend()
     }
+
+    /// Whether the file is restored with the executable bit set, see `oxen add --chmod`
+    pub fn is_executable(&self) -> bool {
+        self.node().executable()
+    }
+
+    pub fn set_executable(&mut self, executable: bool) {
+        self.mut_node().set_executable(executable);
+    }
+
+    /// Metadata is computed from the contents, data type and extension of a file,
+    /// so if these all match the other node's metadata can be reused instead of recomputed
+    pub fn metadata_equivalent(&self, other: &FileNode) -> bool {
+        self.has_metadata_inputs(other.hash(), other.data_type(), other.extension())
+    }
+
+    /// Same check as `metadata_equivalent`, for when the new file node has not been built yet
+    pub fn has_metadata_inputs(
+        &self,
+        hash: &MerkleHash,
+        data_type: &EntryDataType,
+        extension: &str,
+    ) -> bool {
+        self.hash() == hash && self.data_type() == data_type && self.extension() == extension
+    }
 }
 
 impl Default for FileNode {
@@ -236,6 +291,7 @@ impl Default for FileNode {
                 chunk_hashes: vec![],
                 chunk_type: FileChunkType::SingleFile,
                 storage_backend: FileStorageType::Disk,
+                executable: false,
             }),
         }
     }
@@ -281,6 +337,7 @@ impl fmt::Debug for FileNode {
         writeln!(f, "\tchunk_hashes: {:?}", self.chunk_hashes())?;
         writeln!(f, "\tchunk_type: {:?}", self.chunk_type())?;
         writeln!(f, "\tstorage_backend: {:?}", self.storage_backend())?;
+        writeln!(f, "\texecutable: {}", self.is_executable())?;
         writeln!(f, "\tlast_commit_id: {}", self.last_commit_id())?;
         writeln!(
             f,
@@ -323,3 +380,55 @@ impl PartialEq for FileNode {
 }
 
 impl Eq for FileNode {}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::OxenError;
+    use crate::model::merkle_tree::node::file_node::FileNodeOpts;
+    use crate::model::merkle_tree::node::FileNode;
+    use crate::model::{EntryDataType, LocalRepository, MerkleHash};
+    use crate::test;
+
+    fn file_node(
+        repo: &LocalRepository,
+        name: &str,
+        hash: u128,
+        data_type: EntryDataType,
+    ) -> Result<FileNode, OxenError> {
+        FileNode::new(
+            repo,
+            FileNodeOpts {
+                name: name.to_string(),
+                hash: MerkleHash::new(hash),
+                combined_hash: MerkleHash::new(hash),
+                metadata_hash: None,
+                num_bytes: 0,
+                last_modified_seconds: 0,
+                last_modified_nanoseconds: 0,
+                data_type,
+                metadata: None,
+                mime_type: "text/csv".to_string(),
+                extension: "csv".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_metadata_equivalent() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let node = file_node(&repo, "data.csv", 1, EntryDataType::Tabular)?;
+
+            // Renames and mtime changes reuse the metadata
+            let renamed = file_node(&repo, "renamed.csv", 1, EntryDataType::Tabular)?;
+            assert!(node.metadata_equivalent(&renamed));
+
+            // New contents or a new data type means the metadata is recomputed
+            let new_contents = file_node(&repo, "data.csv", 2, EntryDataType::Tabular)?;
+            assert!(!node.metadata_equivalent(&new_contents));
+            let new_type = file_node(&repo, "data.csv", 1, EntryDataType::Binary)?;
+            assert!(!node.metadata_equivalent(&new_type));
+
+            Ok(())
+        })
+    }
+}
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum FileChunkType {
     SingleFile,
-    // Chunked type is not used yet
+    // Stored as the content-defined chunks in `chunk_hashes`, see `oxen add --chunked-hash`
     Chunked,
 }
 
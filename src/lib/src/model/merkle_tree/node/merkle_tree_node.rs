@@ -563,6 +563,62 @@ impl MerkleTreeNode {
         Ok(None)
     }
 
+    /// Look up many paths with a single walk of the subtree instead of calling `get_by_path` per path.
+    /// Paths that are not in the tree are left out of the result.
+    pub fn get_by_paths(
+        &self,
+        paths: &[impl AsRef<Path>],
+    ) -> Result<HashMap<PathBuf, MerkleTreeNode>, OxenError> {
+        let targets: HashSet<PathBuf> = paths.iter().map(|p| p.as_ref().to_path_buf()).collect();
+        // Only the directories on the way to a target have to be visited
+        let dirs: HashSet<PathBuf> = targets
+            .iter()
+            .flat_map(|path| path.ancestors().skip(1).map(Path::to_path_buf))
+            .collect();
+        let mut found = HashMap::new();
+        self.get_by_paths_helper(Path::new(""), &targets, &dirs, &mut found);
+        Ok(found)
+    }
+
+    fn get_by_paths_helper(
+        &self,
+        traversed_path: &Path,
+        targets: &HashSet<PathBuf>,
+        dirs: &HashSet<PathBuf>,
+        found: &mut HashMap<PathBuf, MerkleTreeNode>,
+    ) {
+        match &self.node {
+            EMerkleTreeNode::File(file_node) => {
+                let file_path = traversed_path.join(file_node.name());
+                if targets.contains(&file_path) {
+                    found.insert(file_path, self.clone());
+                }
+                return;
+            }
+            EMerkleTreeNode::Directory(_) => {
+                if targets.contains(traversed_path) {
+                    found.insert(traversed_path.to_path_buf(), self.clone());
+                }
+                if !dirs.contains(traversed_path) {
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        for child in &self.children {
+            if found.len() == targets.len() {
+                return;
+            }
+            if let EMerkleTreeNode::Directory(dir_node) = &child.node {
+                let child_path = traversed_path.join(dir_node.name());
+                child.get_by_paths_helper(&child_path, targets, dirs, found);
+            } else {
+                child.get_by_paths_helper(traversed_path, targets, dirs, found);
+            }
+        }
+    }
+
     pub fn to_node(&self) -> EMerkleTreeNode {
         self.node.clone()
     }
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Metadata for a file added with `oxen add --pointer`. The bytes are not in the version store,
+/// they are fetched from `url` and checked against `hash` when the file is pulled.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MetadataPointer {
+    pub pointer: MetadataPointerImpl,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MetadataPointerImpl {
+    pub url: String,
+    pub num_bytes: u64,
+    pub hash: String,
+}
+
+impl MetadataPointer {
+    pub fn new(url: impl AsRef<str>, num_bytes: u64, hash: impl AsRef<str>) -> Self {
+        Self {
+            pointer: MetadataPointerImpl {
+                url: url.as_ref().to_string(),
+                num_bytes,
+                hash: hash.as_ref().to_string(),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for MetadataPointer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "MetadataPointer({}, {} bytes)",
+            self.pointer.url, self.pointer.num_bytes
+        )
+    }
+}
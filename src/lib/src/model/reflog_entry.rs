@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// A single update to a ref, as recorded in `.oxen/logs/refs/<name>`
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReflogEntry {
+    /// The commit id the ref pointed to before the update, `None` if it was created
+    pub old_id: Option<String>,
+    /// The commit id the ref points to after the update, `None` if it was deleted
+    pub new_id: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub timestamp: OffsetDateTime,
+    /// What changed the ref, ie "create", "update", "delete" or "rename"
+    pub operation: String,
+}
+
+impl ReflogEntry {
+    pub fn new(
+        old_id: Option<&str>,
+        new_id: Option<&str>,
+        operation: impl AsRef<str>,
+    ) -> ReflogEntry {
+        ReflogEntry {
+            old_id: old_id.map(String::from),
+            new_id: new_id.map(String::from),
+            timestamp: OffsetDateTime::now_utc(),
+            operation: operation.as_ref().to_string(),
+        }
+    }
+}
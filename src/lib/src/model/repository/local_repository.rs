@@ -1,12 +1,12 @@
-use crate::config::RepositoryConfig;
+use crate::config::{CommitConfig, RepositoryConfig};
 use crate::constants::SHALLOW_FLAG;
 use crate::constants::{self, DEFAULT_VNODE_SIZE, MIN_OXEN_VERSION};
 use crate::core::versions::MinOxenVersion;
-use crate::error;
 use crate::error::OxenError;
 use crate::model::{MetadataEntry, Remote, RemoteRepository};
 use crate::storage::{create_version_store, StorageConfig, VersionStore};
 use crate::util;
+use crate::view::fork::ForkSource;
 use crate::view::RepositoryView;
 
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,11 @@ pub struct LocalRepository {
     vnode_size: Option<u64>,     // Size of the vnodes
     subtree_paths: Option<Vec<PathBuf>>, // If the user clones a subtree, we store the paths here so that we know we don't have the full tree
     pub depth: Option<i32>, // If the user clones with a depth, we store the depth here so that we know we don't have the full tree
+    enable_hooks: Option<bool>, // Opt-in to running the scripts in .oxen/hooks
+    forked_from: Option<Vec<ForkSource>>, // If the repo is a fork, its upstream sources from nearest to furthest
+    strict_mtime: Option<bool>, // Hash files even when their mtime matches the committed node
+    normalize_line_endings: Option<bool>, // Hash and store text files with LF line endings
+    commit: Option<CommitConfig>, // Conventions commit messages must follow
 
     // Skip this field during serialization/deserialization
     #[serde(skip)]
@@ -51,6 +56,11 @@ impl LocalRepository {
             vnode_size: config.vnode_size,
             subtree_paths: config.subtree_paths.clone(),
             depth: config.depth,
+            enable_hooks: config.enable_hooks,
+            forked_from: config.forked_from,
+            strict_mtime: config.strict_mtime,
+            normalize_line_endings: config.normalize_line_endings,
+            commit: config.commit,
             version_store: None,
         };
 
@@ -83,6 +93,17 @@ impl LocalRepository {
         Ok(())
     }
 
+    /// Use the version store described by `storage`, it is written to the config on `save`
+    pub fn init_version_store_from_config(
+        &mut self,
+        storage: &StorageConfig,
+    ) -> Result<(), OxenError> {
+        storage.validate()?;
+        let store = create_version_store(&self.path, Some(storage))?;
+        self.version_store = Some(store);
+        Ok(())
+    }
+
     /// Initialize the default version store
     pub fn init_default_version_store(&mut self) -> Result<(), OxenError> {
         let store = create_version_store(&self.path, None)?;
@@ -93,8 +114,9 @@ impl LocalRepository {
     /// Load a repository from the current directory
     /// this traverses up the directory tree until it finds a .oxen/ directory
     pub fn from_current_dir() -> Result<LocalRepository, OxenError> {
-        let repo_dir = util::fs::get_repo_root_from_current_dir()
-            .ok_or(OxenError::basic_str(error::NO_REPO_FOUND))?;
+        let repo_dir = util::fs::get_repo_root_from_current_dir().ok_or_else(|| {
+            OxenError::not_a_repository(std::env::current_dir().unwrap_or_default())
+        })?;
 
         LocalRepository::from_dir(&repo_dir)
     }
@@ -113,6 +135,11 @@ impl LocalRepository {
             vnode_size: None,
             subtree_paths: None,
             depth: None,
+            enable_hooks: None,
+            strict_mtime: None,
+            normalize_line_endings: None,
+            commit: None,
+            forked_from: None,
             version_store: None,
         };
 
@@ -133,6 +160,11 @@ impl LocalRepository {
             vnode_size: None,
             subtree_paths: None,
             depth: None,
+            enable_hooks: None,
+            strict_mtime: None,
+            normalize_line_endings: None,
+            commit: None,
+            forked_from: None,
             version_store: None,
         };
 
@@ -149,6 +181,11 @@ impl LocalRepository {
             vnode_size: None,
             subtree_paths: None,
             depth: None,
+            enable_hooks: None,
+            strict_mtime: None,
+            normalize_line_endings: None,
+            commit: None,
+            forked_from: None,
             version_store: None,
         };
 
@@ -165,6 +202,11 @@ impl LocalRepository {
             vnode_size: None,
             subtree_paths: None,
             depth: None,
+            enable_hooks: None,
+            strict_mtime: None,
+            normalize_line_endings: None,
+            commit: None,
+            forked_from: None,
             version_store: None,
         };
 
@@ -232,6 +274,73 @@ impl LocalRepository {
         self.depth = depth;
     }
 
+    pub fn hooks_enabled(&self) -> bool {
+        self.enable_hooks.unwrap_or(false)
+    }
+
+    pub fn set_hooks_enabled(&mut self, enabled: bool) {
+        self.enable_hooks = Some(enabled);
+    }
+
+    /// If true, files whose mtime matches the committed node are still hashed to catch content changes
+    pub fn strict_mtime(&self) -> bool {
+        self.strict_mtime.unwrap_or(false)
+    }
+
+    pub fn set_strict_mtime(&mut self, strict: bool) {
+        self.strict_mtime = Some(strict);
+    }
+
+    /// If true, CRLF line endings in text files are converted to LF before hashing and storing,
+    /// and checked out files get the platform's native line endings back
+    pub fn normalize_line_endings(&self) -> bool {
+        self.normalize_line_endings.unwrap_or(false)
+    }
+
+    pub fn set_normalize_line_endings(&mut self, normalize: bool) {
+        self.normalize_line_endings = Some(normalize);
+    }
+
+    /// Regex that commit messages must match, from `commit.message_pattern` in the config
+    pub fn commit_message_pattern(&self) -> Option<&str> {
+        self.commit
+            .as_ref()
+            .and_then(|commit| commit.message_pattern.as_deref())
+    }
+
+    pub fn set_commit_message_pattern(&mut self, pattern: Option<String>) {
+        self.commit
+            .get_or_insert_with(CommitConfig::default)
+            .message_pattern = pattern;
+    }
+
+    /// Reject `message` if it does not match the repo's `commit.message_pattern`
+    pub fn validate_commit_message(&self, message: impl AsRef<str>) -> Result<(), OxenError> {
+        let Some(pattern) = self.commit_message_pattern() else {
+            return Ok(());
+        };
+        let message = message.as_ref();
+        let re = regex::Regex::new(pattern).map_err(|err| {
+            OxenError::basic_str(format!(
+                "Invalid commit.message_pattern {:?} in repository config: {}",
+                pattern, err
+            ))
+        })?;
+        if !re.is_match(message) {
+            return Err(OxenError::commit_message_rejected(message, pattern));
+        }
+        Ok(())
+    }
+
+    /// The repos this one was forked from, starting with the repo it was forked from directly
+    pub fn forked_from(&self) -> &[ForkSource] {
+        self.forked_from.as_deref().unwrap_or_default()
+    }
+
+    pub fn set_forked_from(&mut self, lineage: Vec<ForkSource>) {
+        self.forked_from = Some(lineage);
+    }
+
     /// Save the repository configuration to disk
     pub fn save(&self) -> Result<(), OxenError> {
         let config_path = util::fs::config_filepath(&self.path);
@@ -250,6 +359,11 @@ impl LocalRepository {
             min_version: self.min_version.clone(),
             vnode_size: self.vnode_size,
             storage,
+            enable_hooks: self.enable_hooks,
+            forked_from: self.forked_from.clone(),
+            strict_mtime: self.strict_mtime,
+            normalize_line_endings: self.normalize_line_endings,
+            commit: self.commit.clone(),
         };
 
         config.save(&config_path)
@@ -318,6 +432,23 @@ impl LocalRepository {
         }
     }
 
+    /// Errors with `not_a_repository` if the .oxen directory is missing, so commands that write to
+    /// the staged db fail clearly instead of with a db error about the missing parent directory
+    pub fn check_exists(&self) -> Result<(), OxenError> {
+        if util::fs::oxen_hidden_dir(&self.path).is_dir() {
+            Ok(())
+        } else {
+            Err(OxenError::not_a_repository(&self.path))
+        }
+    }
+
+    /// True if the repo was cloned or pulled without all of its version files
+    pub fn is_shallow(&self) -> bool {
+        util::fs::oxen_hidden_dir(&self.path)
+            .join(SHALLOW_FLAG)
+            .exists()
+    }
+
     pub fn write_is_shallow(&self, shallow: bool) -> Result<(), OxenError> {
         let shallow_flag_path = util::fs::oxen_hidden_dir(&self.path).join(SHALLOW_FLAG);
         log::debug!("Write is shallow [{shallow}] to path: {shallow_flag_path:?}");
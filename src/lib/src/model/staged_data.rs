@@ -26,6 +26,31 @@ pub const MSG_OXEN_RESTORE_STAGED_FILE: &str =
 pub const MSG_OXEN_SHOW_SCHEMA_STAGED: &str =
     "  (use \"oxen schemas --staged <PATH_OR_HASH>\" to view staged schema)\n";
 
+/// A problem in the staged db, see `repositories::status::check_staged_consistency`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// A staged entry whose parent dir entry is missing, so commit would not find it
+    MissingAncestor { path: PathBuf, ancestor: PathBuf },
+    /// A staged dir with nothing staged below it, that is missing or not empty on disk
+    OrphanDir(PathBuf),
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Inconsistency::MissingAncestor { path, ancestor } => {
+                write!(
+                    f,
+                    "{path:?} is staged but its parent dir {ancestor:?} is not"
+                )
+            }
+            Inconsistency::OrphanDir(path) => {
+                write!(f, "dir {path:?} is staged with nothing staged below it")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StagedDataOpts {
     pub paths: Vec<PathBuf>, // Paths to start the search at
@@ -34,6 +59,7 @@ pub struct StagedDataOpts {
     pub print_all: bool,
     pub is_remote: bool,
     pub ignore: Option<HashSet<PathBuf>>,
+    pub use_mtime_index: bool, // Skip dirs whose mtime and file stats are unchanged since they were last found clean
 }
 
 impl StagedDataOpts {
@@ -54,6 +80,7 @@ impl Default for StagedDataOpts {
             print_all: false,
             is_remote: false,
             ignore: None,
+            use_mtime_index: false,
         }
     }
 }
@@ -212,10 +239,15 @@ impl StagedData {
                 //     conflict.merge_entry.version_file()
                 // );
 
-                vec![
+                let mut row = vec![
                     "  both modified: ".to_string().yellow(),
-                    format!("{}\n", path.to_str().unwrap()).yellow().bold(),
-                ]
+                    path.to_str().unwrap().to_string().yellow().bold(),
+                ];
+                if conflict.is_binary {
+                    row.push(" (binary, use --ours or --theirs)".to_string().yellow());
+                }
+                row.push("\n".normal());
+                row
             },
             outputs,
             opts,
@@ -1,8 +1,66 @@
 use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+
+use crate::util::progress_bar::ProgressFormat;
 
 #[derive(Clone, Debug)]
 pub struct AddOpts {
     pub paths: Vec<PathBuf>,
     pub directory: Option<PathBuf>,
     pub is_remote: bool,
+    /// When expanding a glob, also match files in the head commit that were removed from disk
+    pub include_head_removed: bool,
+    /// Track files as pointers to this url instead of storing their bytes, see `MetadataPointer`
+    pub pointer_url: Option<String>,
+    /// Number of staged entries to write at a time, defaults to `DEFAULT_STAGED_DB_BATCH_SIZE`
+    pub batch_size: Option<usize>,
+    /// Write a `.oxenkeep` marker into empty directories so they are tracked and recreated on checkout
+    pub track_empty_dirs: bool,
+    /// Skip parsing metadata (schemas, image sizes, ...) to speed up large imports. Tabular diffs and
+    /// schemas do not work for these files until their metadata is backfilled, see `repositories::metadata::backfill`.
+    pub no_metadata: bool,
+    /// Print a line with the status and short hash of every file as it is staged
+    pub verbose: bool,
+    /// Only stage the modified and removed files that are already tracked, skipping untracked files
+    pub update: bool,
+    /// Report progress with the spinner or as JSON lines on stderr
+    pub progress: ProgressFormat,
+    /// `oxen add --continue`, skip the directories an interrupted add already staged
+    pub resume: bool,
+    /// `oxen add --chmod +x/-x`, record the executable bit in the file node regardless of the one on disk
+    pub chmod: Option<bool>,
+    /// Walk each directory in name order instead of the filesystem's, one file at a time. Slower, but the
+    /// staged db writes and progress output are the same on every machine.
+    pub sort: bool,
+    /// Store files over `CHUNKED_HASH_MIN_FILE_SIZE` as content-defined chunks, so versions of a large file
+    /// that only changed in places share the unchanged chunks
+    pub chunked_hash: bool,
+    /// Fail on the first file or directory that can't be read, instead of skipping it and reporting it at the end
+    pub strict: bool,
+    /// Send the `verbose` lines here instead of printing them to stdout
+    pub verbose_sink: Option<Sender<String>>,
+}
+
+impl Default for AddOpts {
+    fn default() -> Self {
+        AddOpts {
+            paths: Vec::new(),
+            directory: None,
+            is_remote: false,
+            include_head_removed: true,
+            pointer_url: None,
+            batch_size: None,
+            track_empty_dirs: false,
+            no_metadata: false,
+            verbose: false,
+            update: false,
+            progress: ProgressFormat::Human,
+            resume: false,
+            chmod: None,
+            sort: false,
+            chunked_hash: false,
+            strict: false,
+            verbose_sink: None,
+        }
+    }
 }
@@ -0,0 +1,10 @@
+/// Options for `oxen clean`
+#[derive(Clone, Debug, Default)]
+pub struct CleanOpts {
+    /// List the paths that would be removed without removing them
+    pub dry_run: bool,
+    /// Also remove untracked directories, otherwise only untracked files in tracked directories go
+    pub directories: bool,
+    /// Also remove the files matched by .oxenignore
+    pub ignored: bool,
+}
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use crate::constants::{DEFAULT_BRANCH_NAME, DEFAULT_REMOTE_NAME};
+use crate::util::progress_bar::ProgressFormat;
 
 #[derive(Clone, Debug)]
 pub struct FetchOpts {
@@ -17,6 +18,14 @@ pub struct FetchOpts {
     pub all: bool,
     // Defaults to true, but on pull we want to only update the branch head if there are no conflicts
     pub should_update_branch_head: bool,
+    // On pull, remove working tree files that were deleted in the pulled commits
+    pub prune: bool,
+    // Max number of version files to download at once, defaults to the number of CPUs
+    pub jobs: Option<usize>,
+    // Only fetch the commits and merkle trees, version files are downloaded on demand when the working tree needs them
+    pub blobless: bool,
+    // Report the download progress with the bar or as JSON lines on stderr
+    pub progress: ProgressFormat,
 }
 
 impl Default for FetchOpts {
@@ -35,6 +44,10 @@ impl FetchOpts {
             depth: None,
             all: false,
             should_update_branch_head: true,
+            prune: false,
+            jobs: None,
+            blobless: false,
+            progress: ProgressFormat::Human,
         }
     }
 
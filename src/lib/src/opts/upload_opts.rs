@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
@@ -9,4 +10,16 @@ pub struct UploadOpts {
     pub host: String,
     pub scheme: String,
     pub remote: String,
+    /// Remote paths to remove in the same commit as the upload
+    pub delete: Vec<PathBuf>,
+    /// Destination directory by lowercase file extension, falling back to `dst` when unmatched
+    pub routes: HashMap<String, PathBuf>,
+    /// Upload directories recursively, mirroring their layout under `dst/<dir name>/`
+    pub preserve_structure: bool,
+    /// Upload into this workspace, creating it if it does not exist, so several uploads can build up one commit
+    pub workspace_id: Option<String>,
+    /// Commit the workspace after uploading. Always set without `workspace_id`, since the workspace is thrown away
+    pub commit: bool,
+    /// Hash the local files first and only upload the ones that differ from the file at their destination on the branch
+    pub skip_unchanged: bool,
 }
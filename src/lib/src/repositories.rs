@@ -33,6 +33,7 @@ pub mod download;
 pub mod entries;
 pub mod fetch;
 pub mod fork;
+pub mod gc;
 pub mod init;
 pub mod load;
 pub mod merge;
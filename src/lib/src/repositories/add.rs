@@ -7,7 +7,11 @@ use crate::core;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::LocalRepository;
-use std::path::Path;
+use crate::opts::AddOpts;
+use crate::util;
+use futures::StreamExt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 /// # Stage files into repository
 ///
@@ -51,14 +55,105 @@ pub fn add_with_version(
     }
 }
 
+/// Stage each of the paths in `opts.paths`, respecting the rest of the options
+pub fn add_with_opts(repo: &LocalRepository, opts: &AddOpts) -> Result<(), OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => {
+            for path in &opts.paths {
+                core::v_latest::add::add_with_opts(repo, path, opts)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// URL schemes that `add_from_url` knows how to download
+pub const DOWNLOADABLE_SCHEMES: [&str; 3] = ["http", "https", "s3"];
+
+/// Whether the path given to add is a URL we can download, ie `https://example.com/data.csv`
+pub fn is_downloadable_url(path: impl AsRef<str>) -> bool {
+    match url::Url::parse(path.as_ref()) {
+        Ok(url) => DOWNLOADABLE_SCHEMES.contains(&url.scheme()),
+        Err(_) => false,
+    }
+}
+
+/// Download a file into the working tree and stage it.
+///
+/// The file is written to `dst` relative to the repository root, or to the URL's file name if
+/// `dst` is None. `s3://bucket/key` URLs are fetched from the bucket's public https endpoint.
+/// Returns the full path to the downloaded file.
+pub async fn add_from_url(
+    repo: &LocalRepository,
+    url: impl AsRef<str>,
+    dst: Option<&Path>,
+) -> Result<PathBuf, OxenError> {
+    let url = url::Url::parse(url.as_ref())?;
+    let url = match url.scheme() {
+        "http" | "https" => url,
+        "s3" => {
+            let bucket = url
+                .host_str()
+                .ok_or(OxenError::basic_str(format!("Invalid s3 url: {url}")))?;
+            url::Url::parse(&format!("https://{bucket}.s3.amazonaws.com{}", url.path()))?
+        }
+        scheme => {
+            return Err(OxenError::basic_str(format!(
+                "Cannot download `{url}`, unsupported scheme `{scheme}`"
+            )))
+        }
+    };
+
+    let dst = match dst {
+        Some(dst) => repo.path.join(dst),
+        None => {
+            let file_name = url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|name| !name.is_empty())
+                .ok_or(OxenError::basic_str(format!(
+                    "Could not get a file name from `{url}`, please specify a path to save it to"
+                )))?;
+            repo.path.join(file_name)
+        }
+    };
+
+    log::debug!("add_from_url downloading {} to {:?}", url, dst);
+    let res = reqwest::get(url.clone()).await?;
+    if !res.status().is_success() {
+        return Err(OxenError::basic_str(format!(
+            "Could not download `{url}`, server responded with {}",
+            res.status()
+        )));
+    }
+
+    if let Some(parent) = dst.parent() {
+        util::fs::create_dir_all(parent)?;
+    }
+    let mut file = util::fs::file_create(&dst)?;
+    let mut stream = res.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk)?;
+    }
+    file.flush()?;
+
+    add(repo, &dst)?;
+    Ok(dst)
+}
+
 #[cfg(test)]
 mod tests {
 
     use std::path::Path;
     use std::path::PathBuf;
 
+    use crate::constants;
     use crate::error::OxenError;
+    use crate::model::{LocalRepository, StagedEntryStatus};
     use crate::opts::clone_opts::CloneOpts;
+    use crate::opts::AddOpts;
     use crate::repositories;
     use crate::test;
     use crate::util;
@@ -159,6 +254,208 @@ A: Oxen.ai
         })
     }
 
+    #[test]
+    fn test_command_add_oxen_dir_file_fails() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let config_file =
+                util::fs::oxen_hidden_dir(&repo.path).join(constants::REPO_CONFIG_FILENAME);
+            assert!(config_file.exists());
+
+            let result = repositories::add(&repo, &config_file);
+            assert!(result.is_err());
+
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.staged_files.len(), 0);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_outside_repository_errors() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let repo = LocalRepository::new(dir)?;
+            let hello_file = dir.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+
+            let result = repositories::add(&repo, &hello_file);
+            assert!(matches!(result, Err(OxenError::NotARepository(_))));
+            assert!(!dir.join(constants::OXEN_HIDDEN_DIR).exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_strict_mtime_detects_change_with_same_mtime() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            repositories::add(&repo, &hello_file)?;
+            repositories::commit(&repo, "Adding hello")?;
+
+            // Same size content, with the mtime reset to what was committed
+            let metadata = std::fs::metadata(&hello_file)?;
+            let mtime = filetime::FileTime::from_last_modification_time(&metadata);
+            util::fs::write_to_path(&hello_file, "Jello")?;
+            filetime::set_file_mtime(&hello_file, mtime)?;
+
+            // The default fast path trusts the mtime
+            repositories::add(&repo, &hello_file)?;
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.staged_files.len(), 0);
+
+            repo.set_strict_mtime(true);
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.modified_files.len(), 1);
+
+            repositories::add(&repo, &hello_file)?;
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.staged_files.len(), 1);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_normalize_line_endings_ignores_crlf() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello\nWorld\n")?;
+            repositories::add(&repo, &hello_file)?;
+            repositories::commit(&repo, "Adding hello")?;
+
+            // Only the line endings change
+            util::fs::write_to_path(&hello_file, "Hello\r\nWorld\r\n")?;
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.modified_files.len(), 1);
+
+            repo.set_normalize_line_endings(true);
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.modified_files.len(), 0);
+            repositories::add(&repo, &hello_file)?;
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.staged_files.len(), 0);
+
+            // New files are stored with LF line endings
+            let crlf_file = repo.path.join("crlf.txt");
+            util::fs::write_to_path(&crlf_file, "a\r\nb\r\n")?;
+            repositories::add(&repo, &crlf_file)?;
+            let commit = repositories::commit(&repo, "Adding crlf")?;
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, "crlf.txt")?.unwrap();
+            assert_eq!(file_node.num_bytes(), 4);
+            let version = repo
+                .version_store()?
+                .get_version(&file_node.hash().to_string())?;
+            assert_eq!(version, b"a\nb\n");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_modified_file_same_mtime_updates_num_bytes() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            repositories::add(&repo, &hello_file)?;
+            repositories::commit(&repo, "Adding hello")?;
+
+            let metadata = std::fs::metadata(&hello_file)?;
+            let mtime = filetime::FileTime::from_last_modification_time(&metadata);
+            let new_contents = "Hello, there, this is longer";
+            util::fs::write_to_path(&hello_file, new_contents)?;
+            filetime::set_file_mtime(&hello_file, mtime)?;
+
+            repositories::add(&repo, &hello_file)?;
+            let repo_status = repositories::status(&repo)?;
+            assert_eq!(repo_status.staged_files.len(), 1);
+
+            let commit = repositories::commit(&repo, "Modifying hello")?;
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, "hello.txt")?.unwrap();
+            assert_eq!(file_node.num_bytes(), new_contents.len() as u64);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_update_only_stages_tracked_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let a_file = repo.path.join("a.txt");
+            let b_file = repo.path.join("data").join("b.txt");
+            let c_file = repo.path.join("data").join("c.txt");
+            util::fs::write_to_path(&a_file, "a")?;
+            util::fs::create_dir_all(b_file.parent().unwrap())?;
+            util::fs::write_to_path(&b_file, "b")?;
+            repositories::add(&repo, &repo.path)?;
+            repositories::commit(&repo, "Adding a and b")?;
+
+            util::fs::write_to_path(&a_file, "a modified")?;
+            util::fs::remove_file(&b_file)?;
+            util::fs::write_to_path(&c_file, "scratch")?;
+
+            let opts = AddOpts {
+                paths: vec![repo.path.clone()],
+                update: true,
+                ..Default::default()
+            };
+            repositories::add::add_with_opts(&repo, &opts)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(
+                status.staged_files[Path::new("a.txt")].status,
+                StagedEntryStatus::Modified
+            );
+            assert_eq!(
+                status.staged_files[&Path::new("data").join("b.txt")].status,
+                StagedEntryStatus::Removed
+            );
+            assert!(!status
+                .staged_files
+                .contains_key(&Path::new("data").join("c.txt")));
+            assert!(status
+                .untracked_files
+                .contains(&Path::new("data").join("c.txt")));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_pointer_file_skips_version_store() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let large_file = repo.path.join("data").join("large.bin");
+            util::fs::create_dir_all(large_file.parent().unwrap())?;
+            util::fs::write_to_path(&large_file, "pretend this is very large")?;
+
+            let opts = AddOpts {
+                paths: vec![large_file.clone()],
+                pointer_url: Some("https://example.com/bucket/".to_string()),
+                ..Default::default()
+            };
+            repositories::add::add_with_opts(&repo, &opts)?;
+            let commit = repositories::commit(&repo, "Adding pointer file")?;
+
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("data/large.bin"))?
+                    .unwrap();
+            let pointer = file_node.pointer().unwrap();
+            assert_eq!(
+                pointer.pointer.url,
+                "https://example.com/bucket/data/large.bin"
+            );
+            assert_eq!(pointer.pointer.num_bytes, file_node.num_bytes());
+
+            let version_store = repo.version_store()?;
+            assert!(!version_store.version_exists(&file_node.hash().to_string())?);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_command_add_modified_file_in_subdirectory() -> Result<(), OxenError> {
         test::run_training_data_repo_test_fully_committed(|repo| {
@@ -205,6 +502,52 @@ A: Oxen.ai
         })
     }
 
+    #[test]
+    fn test_add_glob_without_head_removed() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let keep_file = repo.path.join("keep.txt");
+            let removed_file = repo.path.join("removed.txt");
+            util::fs::write_to_path(&keep_file, "Keep")?;
+            util::fs::write_to_path(&removed_file, "Removed")?;
+            repositories::add(&repo, &repo.path)?;
+            repositories::commit(&repo, "Adding files")?;
+
+            util::fs::write_to_path(&keep_file, "Keep modified")?;
+            util::fs::remove_file(&removed_file)?;
+
+            let opts = AddOpts {
+                paths: vec![repo.path.join("*.txt")],
+                include_head_removed: false,
+                ..Default::default()
+            };
+            repositories::add::add_with_opts(&repo, &opts)?;
+
+            // Only the file on disk is staged, the removal is left alone
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+            assert!(status.staged_files.contains_key(Path::new("keep.txt")));
+            assert_eq!(status.removed_files.len(), 1);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_is_downloadable_url() -> Result<(), OxenError> {
+        assert!(repositories::add::is_downloadable_url(
+            "https://example.com/data.csv"
+        ));
+        assert!(repositories::add::is_downloadable_url(
+            "s3://bucket/data.csv"
+        ));
+        assert!(!repositories::add::is_downloadable_url("data.csv"));
+        assert!(!repositories::add::is_downloadable_url("/tmp/data.csv"));
+        assert!(!repositories::add::is_downloadable_url(
+            "ftp://example.com/data.csv"
+        ));
+        Ok(())
+    }
+
     // At some point we were adding rocksdb inside the working dir...def should not do that
     #[test]
     fn test_command_add_dot_should_not_add_new_files() -> Result<(), OxenError> {
@@ -386,6 +729,40 @@ A: Oxen.ai
         })
     }
 
+    #[test]
+    fn test_add_many_identical_files_in_one_dir() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            // Every file has the same hash, so the version is stored by many threads at once
+            let dir = repo.path.join("copies");
+            util::fs::create_dir_all(&dir)?;
+            let num_files = 200;
+            for i in 0..num_files {
+                util::fs::write_to_path(dir.join(format!("copy_{i}.txt")), "same contents")?;
+            }
+
+            repositories::add(&repo, &dir)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), num_files);
+            assert!(status.untracked_files.is_empty());
+
+            let commit = repositories::commit(&repo, "Adding copies")?;
+            let version_store = repo.version_store()?;
+            let entry = repositories::entries::get_file(
+                &repo,
+                &commit,
+                Path::new("copies").join("copy_0.txt"),
+            )?
+            .unwrap();
+            assert_eq!(
+                version_store.get_version(&entry.hash().to_string())?,
+                b"same contents"
+            );
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_add_empty_dir() -> Result<(), OxenError> {
         test::run_empty_local_repo_test(|repo| {
@@ -9,7 +9,7 @@ use crate::constants::{BRANCH_LOCKS_DIR, OXEN_HIDDEN_DIR};
 use crate::core::refs::with_ref_manager;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
-use crate::model::{Branch, Commit, CommitEntry, LocalRepository};
+use crate::model::{Branch, Commit, CommitEntry, LocalRepository, ReflogEntry};
 use crate::repositories;
 use crate::{core, util};
 
@@ -159,6 +159,16 @@ pub fn force_delete(repo: &LocalRepository, name: impl AsRef<str>) -> Result<Bra
     with_ref_manager(repo, |manager| manager.delete_branch(name))
 }
 
+/// The history of updates to a branch, oldest first. The log is kept after the
+/// branch is deleted, so its last commit id can still be recovered.
+pub fn reflog(
+    repo: &LocalRepository,
+    name: impl AsRef<str>,
+) -> Result<Vec<ReflogEntry>, OxenError> {
+    let name = name.as_ref();
+    with_ref_manager(repo, |manager| manager.reflog(name))
+}
+
 /// Check if a branch is checked out
 pub fn is_checked_out(repo: &LocalRepository, name: &str) -> bool {
     if let Ok(Some(current_branch)) = with_ref_manager(repo, |manager| manager.get_current_branch())
@@ -125,12 +125,18 @@ pub fn checkout_combine<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Resu
         .iter()
         .find(|c| c.merge_entry.path == path.as_ref())
     {
+        if conflict.is_binary {
+            return Err(OxenError::basic_str(format!(
+                "Cannot combine binary file {:?}, use --ours or --theirs",
+                conflict.base_entry.path
+            )));
+        }
         if util::fs::is_tabular(&conflict.base_entry.path) {
-            let df_base_path = util::fs::version_path_from_hash_and_filename(
-                repo,
+            let version_store = repo.version_store()?;
+            let df_base_path = version_store.get_version_file(
                 &conflict.base_entry.hash,
-                &conflict.base_entry.filename,
-            );
+                &util::fs::file_extension(&conflict.base_entry.path),
+            )?;
             let df_base = tabular::maybe_read_df_with_extension(
                 repo,
                 &df_base_path,
@@ -138,14 +144,13 @@ pub fn checkout_combine<P: AsRef<Path>>(repo: &LocalRepository, path: P) -> Resu
                 &conflict.base_entry.commit_id,
                 &DFOpts::empty(),
             )?;
-            let df_merge_path = util::fs::version_path_from_hash_and_filename(
-                repo,
+            let df_merge_path = version_store.get_version_file(
                 &conflict.merge_entry.hash,
-                &conflict.merge_entry.filename,
-            );
+                &util::fs::file_extension(&conflict.merge_entry.path),
+            )?;
             let df_merge = tabular::maybe_read_df_with_extension(
                 repo,
-                df_merge_path,
+                &df_merge_path,
                 &conflict.merge_entry.path,
                 &conflict.merge_entry.commit_id,
                 &DFOpts::empty(),
@@ -3,6 +3,7 @@
 //! Create, read, and list commits
 //!
 
+use crate::core::v_latest::add::CumulativeStats;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::{Commit, LocalRepository, MerkleHash};
@@ -106,6 +107,22 @@ pub fn get_by_id(
     }
 }
 
+/// Check the integrity of a commit's merkle tree, returning the first inconsistency found or None
+/// if the tree is intact. See `core::v_latest::commits::verify` for what is checked.
+pub fn verify(
+    repo: &LocalRepository,
+    commit_id: impl AsRef<str>,
+) -> Result<Option<String>, OxenError> {
+    let commit_id = commit_id.as_ref();
+    let Some(commit) = get_by_id(repo, commit_id)? else {
+        return Err(OxenError::revision_not_found(commit_id.into()));
+    };
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::commits::verify(repo, &commit),
+    }
+}
+
 /// Commit id exists
 pub fn commit_id_exists(
     repo: &LocalRepository,
@@ -127,6 +144,21 @@ pub fn create_empty_commit(
     }
 }
 
+/// Squash the commits from `from` to `to` into a single commit with the tree at `to`.
+/// The new commit takes the parent of `from` and the current branch is moved to it.
+/// `to` has to be the head of the current branch and the range can not contain merge commits.
+pub fn squash(
+    repo: &LocalRepository,
+    from: &Commit,
+    to: &Commit,
+    message: impl AsRef<str>,
+) -> Result<Commit, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::commits::squash(repo, from, to, message),
+    }
+}
+
 /// List commits on the current branch from HEAD
 pub fn list(repo: &LocalRepository) -> Result<Vec<Commit>, OxenError> {
     match repo.min_version() {
@@ -195,6 +227,18 @@ pub fn list_from(repo: &LocalRepository, revision: &str) -> Result<Vec<Commit>,
     }
 }
 
+/// List the history for a revision, stopping cleanly at the edge of a shallow clone.
+/// The bool is true when there is earlier history that was not fetched.
+pub fn list_from_until_shallow(
+    repo: &LocalRepository,
+    revision: &str,
+) -> Result<(Vec<Commit>, bool), OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::commits::list_from_until_shallow(repo, revision),
+    }
+}
+
 pub fn list_from_with_depth(
     repo: &LocalRepository,
     revision: &str,
@@ -243,6 +287,18 @@ pub fn first_by_message(
         .find(|commit| commit.message == msg.as_ref()))
 }
 
+/// Aggregate the file count, bytes and data type counts of the subtree at `path` in `commit`
+pub fn dir_stats(
+    repo: &LocalRepository,
+    commit: &Commit,
+    path: impl AsRef<Path>,
+) -> Result<CumulativeStats, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::commits::dir_stats(repo, commit, path),
+    }
+}
+
 /// Retrieve entries with filepaths matching a provided glob pattern
 pub fn search_entries(
     repo: &LocalRepository,
@@ -255,6 +311,18 @@ pub fn search_entries(
     }
 }
 
+/// Retrieve directories with paths matching a provided glob pattern
+pub fn search_dirs(
+    repo: &LocalRepository,
+    commit: &Commit,
+    pattern: &str,
+) -> Result<HashSet<PathBuf>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::commits::search_dirs(repo, commit, pattern),
+    }
+}
+
 /// List paginated commits starting from the given revision
 pub fn list_from_paginated(
     repo: &LocalRepository,
@@ -275,6 +343,18 @@ pub fn list_from_paginated(
     })
 }
 
+/// List the commits that changed a file, newest to oldest, optionally following renames
+pub fn history_for_path(
+    repo: &LocalRepository,
+    path: impl AsRef<Path>,
+    follow_renames: bool,
+) -> Result<Vec<Commit>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::commits::history_for_path(repo, path, follow_renames),
+    }
+}
+
 /// List paginated commits by resource
 pub fn list_by_path_from_paginated(
     repo: &LocalRepository,
@@ -330,6 +410,7 @@ mod tests {
     use std::path::Path;
     use std::str::FromStr;
 
+    use crate::core::db::merkle_node::merkle_node_db::node_db_path;
     use crate::error::OxenError;
     use crate::model::EntryDataType;
     use crate::model::MerkleHash;
@@ -342,6 +423,197 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_commit_message_must_match_pattern() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            repo.set_commit_message_pattern(Some(r"^[A-Z]+-[0-9]+: ".to_string()));
+            repo.save()?;
+            let repo = LocalRepository::from_dir(&repo.path)?;
+
+            util::fs::write_to_path(repo.path.join("hello.txt"), "Hello")?;
+            repositories::add(&repo, repo.path.join("hello.txt"))?;
+            assert!(repositories::commit(&repo, "Adding hello").is_err());
+
+            let commit = repositories::commit(&repo, "DATA-12: Adding hello")?;
+            assert_eq!(commit.message, "DATA-12: Adding hello");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_dir_stats() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let train_dir = repo.path.join("data").join("train");
+            let test_dir = repo.path.join("data").join("test");
+            util::fs::create_dir_all(&train_dir)?;
+            util::fs::create_dir_all(&test_dir)?;
+            util::fs::write_to_path(train_dir.join("notes.txt"), "Hello")?;
+            util::fs::write_to_path(train_dir.join("labels.csv"), "a,b\n1,2\n")?;
+            util::fs::write_to_path(test_dir.join("notes.txt"), "Goodbye")?;
+            repositories::add(&repo, &repo.path)?;
+            let commit = repositories::commit(&repo, "Adding data")?;
+
+            let stats =
+                repositories::commits::dir_stats(&repo, &commit, Path::new("data").join("train"))?;
+            assert_eq!(stats.total_files, 2);
+            assert_eq!(stats.total_bytes, 13);
+            assert_eq!(stats.data_type_counts.get(&EntryDataType::Text), Some(&1));
+            assert_eq!(
+                stats.data_type_counts.get(&EntryDataType::Tabular),
+                Some(&1)
+            );
+
+            let stats = repositories::commits::dir_stats(&repo, &commit, "")?;
+            assert_eq!(stats.total_files, 3);
+            assert_eq!(stats.total_bytes, 20);
+
+            let stats = repositories::commits::dir_stats(
+                &repo,
+                &commit,
+                Path::new("data").join("test").join("notes.txt"),
+            )?;
+            assert_eq!(stats.total_files, 1);
+
+            assert!(repositories::commits::dir_stats(&repo, &commit, "missing").is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_squash_commits() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let mut commits = vec![];
+            for i in 0..4 {
+                util::fs::write_to_path(repo.path.join(format!("file_{i}.txt")), format!("{i}"))?;
+                repositories::add(&repo, &repo.path)?;
+                commits.push(repositories::commit(&repo, &format!("Adding file {i}"))?);
+            }
+
+            let squashed = repositories::commits::squash(
+                &repo,
+                &commits[1],
+                &commits[3],
+                "Adding files 1 to 3",
+            )?;
+            assert_eq!(squashed.parent_ids, vec![commits[0].id.clone()]);
+            assert_eq!(squashed.message, "Adding files 1 to 3");
+
+            let history = repositories::commits::list(&repo)?;
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].id, squashed.id);
+            for i in 0..4 {
+                let path = format!("file_{i}.txt");
+                assert!(repositories::tree::get_file_by_path(&repo, &squashed, &path)?.is_some());
+            }
+
+            // Can only squash up to the tip of the branch
+            let result = repositories::commits::squash(&repo, &commits[0], &commits[2], "Squash");
+            assert!(result.is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_list_from_until_shallow() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|mut repo| {
+            let mut commits = vec![];
+            for i in 0..3 {
+                util::fs::write_to_path(repo.path.join(format!("file_{i}.txt")), format!("{i}"))?;
+                repositories::add(&repo, &repo.path)?;
+                commits.push(repositories::commit(&repo, &format!("Adding file {i}"))?);
+            }
+
+            // Remove the first commit as if it was never fetched
+            let first_hash = MerkleHash::from_str(&commits[0].id)?;
+            util::fs::remove_dir_all(node_db_path(&repo, &first_hash))?;
+
+            // Without a depth this is a broken history
+            let head_id = &commits[2].id;
+            assert!(repositories::commits::list_from_until_shallow(&repo, head_id).is_err());
+
+            repo.set_depth(Some(2));
+            let (history, shallow) =
+                repositories::commits::list_from_until_shallow(&repo, head_id)?;
+            assert!(shallow);
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].id, commits[2].id);
+            assert_eq!(history[1].id, commits[1].id);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_verify_commit() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            let nested_file = repo.path.join("a").join("b").join("data.csv");
+            util::fs::create_dir_all(nested_file.parent().unwrap())?;
+            util::fs::write_to_path(&nested_file, "a,b\n1,2\n")?;
+            repositories::add(&repo, &repo.path)?;
+            let commit = repositories::commit(&repo, "Adding files")?;
+
+            assert_eq!(repositories::commits::verify(&repo, &commit.id)?, None);
+
+            // Lose the version of the nested file
+            let node = repositories::tree::get_file_by_path(
+                &repo,
+                &commit,
+                Path::new("a").join("b").join("data.csv"),
+            )?
+            .unwrap();
+            repo.version_store()?
+                .delete_version(&node.hash().to_string())?;
+
+            let problem = repositories::commits::verify(&repo, &commit.id)?.unwrap();
+            assert!(problem.contains("missing version file"));
+            assert!(problem.contains("data.csv"));
+
+            assert!(repositories::commits::verify(&repo, "not-a-commit").is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_history_for_path_follows_renames() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let old_path = repo.path.join("old.txt");
+            util::fs::write_to_path(&old_path, "Hello")?;
+            repositories::add(&repo, &old_path)?;
+            let first = repositories::commit(&repo, "Adding old.txt")?;
+
+            util::fs::write_to_path(&old_path, "Hello World")?;
+            repositories::add(&repo, &old_path)?;
+            let second = repositories::commit(&repo, "Modifying old.txt")?;
+
+            let new_path = repo.path.join("new.txt");
+            util::fs::rename(&old_path, &new_path)?;
+            repositories::add(&repo, &old_path)?;
+            repositories::add(&repo, &new_path)?;
+            let third = repositories::commit(&repo, "Renaming old.txt to new.txt")?;
+
+            let other_path = repo.path.join("other.txt");
+            util::fs::write_to_path(&other_path, "Other")?;
+            repositories::add(&repo, &other_path)?;
+            repositories::commit(&repo, "Adding other.txt")?;
+
+            let history = repositories::commits::history_for_path(&repo, "new.txt", false)?;
+            let ids: Vec<String> = history.into_iter().map(|c| c.id).collect();
+            assert_eq!(ids, vec![third.id.clone()]);
+
+            let history = repositories::commits::history_for_path(&repo, "new.txt", true)?;
+            let ids: Vec<String> = history.into_iter().map(|c| c.id).collect();
+            assert_eq!(ids, vec![third.id, second.id, first.id]);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_command_commit_file() -> Result<(), OxenError> {
         test::run_empty_local_repo_test(|repo| {
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
 
+use filetime::FileTime;
 use indicatif::{ProgressBar, ProgressStyle};
 use rocksdb::{DBWithThreadMode, SingleThreaded};
 use std::path::PathBuf;
@@ -14,11 +15,14 @@ use crate::config::UserConfig;
 use crate::constants::DEFAULT_BRANCH_NAME;
 use crate::constants::MERGE_HEAD_FILE;
 use crate::constants::ORIG_HEAD_FILE;
+use crate::constants::PRE_COMMIT_HOOK;
 use crate::constants::{HEAD_FILE, STAGED_DIR};
 use crate::core::db;
 use crate::core::db::key_val::str_val_db;
 use crate::core::db::merkle_node::MerkleNodeDB;
+use crate::core::hooks;
 use crate::core::refs::with_ref_manager;
+use crate::core::status_index::{self, FileStamp};
 use crate::core::v_latest::index::CommitMerkleTree;
 use crate::core::v_latest::status;
 use crate::error::OxenError;
@@ -73,6 +77,7 @@ impl EntryVNode {
 }
 
 pub fn commit(repo: &LocalRepository, message: impl AsRef<str>) -> Result<Commit, OxenError> {
+    repo.validate_commit_message(message.as_ref())?;
     let cfg = UserConfig::get()?;
     commit_with_cfg(repo, message, &cfg, None)
 }
@@ -91,10 +96,7 @@ pub fn commit_with_user(
     message: impl AsRef<str>,
     user: &User,
 ) -> Result<Commit, OxenError> {
-    let cfg = UserConfig {
-        name: user.name.clone(),
-        email: user.email.clone(),
-    };
+    let cfg = UserConfig::from_user(user);
     commit_with_cfg(repo, message, &cfg, None)
 }
 
@@ -108,6 +110,8 @@ pub fn commit_with_cfg(
     let start_time = Instant::now();
     let message = message.as_ref();
 
+    repo.check_exists()?;
+
     // Read the staged files from the staged db
     let opts = db::key_val::opts::default();
     let staged_db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
@@ -130,6 +134,12 @@ pub fn commit_with_cfg(
         return Err(OxenError::basic_str("No changes to commit"));
     }
 
+    if repo.hooks_enabled() {
+        let staged_paths = staged_file_paths(&dir_entries);
+        hooks::run(repo, PRE_COMMIT_HOOK, &staged_paths)?;
+    }
+    let committed_stamps = staged_file_stamps(&dir_entries);
+
     // let mut dir_tree = entries_to_dir_tree(&dir_entries)?;
     // dir_tree.print();
 
@@ -183,6 +193,9 @@ pub fn commit_with_cfg(
         manager.set_head_commit_id(&commit_id)
     })?;
 
+    // The dirs `status --fast` found clean are still clean against the new commit
+    status_index::advance(repo, &commit.parent_ids, &commit_id, committed_stamps)?;
+
     // Print that we finished
     println!(
         "🐂 commit {} in {}",
@@ -195,6 +208,53 @@ pub fn commit_with_cfg(
     Ok(commit)
 }
 
+fn staged_file_paths(dir_entries: &HashMap<PathBuf, Vec<StagedMerkleTreeNode>>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = dir_entries
+        .iter()
+        .flat_map(|(dir, entries)| {
+            entries
+                .iter()
+                .filter_map(move |entry| match &entry.node.node {
+                    EMerkleTreeNode::File(file_node) => {
+                        let name = Path::new(file_node.name()).file_name()?;
+                        Some(dir.join(name))
+                    }
+                    _ => None,
+                })
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// The size and mtime each staged file was added with, for `status_index::advance`
+fn staged_file_stamps(
+    dir_entries: &HashMap<PathBuf, Vec<StagedMerkleTreeNode>>,
+) -> Vec<(PathBuf, FileStamp)> {
+    dir_entries
+        .iter()
+        .flat_map(|(dir, entries)| {
+            entries
+                .iter()
+                .filter_map(move |entry| match &entry.node.node {
+                    EMerkleTreeNode::File(file_node)
+                        if entry.status != StagedEntryStatus::Removed =>
+                    {
+                        let name = Path::new(file_node.name()).file_name()?;
+                        let mtime = FileTime::from_unix_time(
+                            file_node.last_modified_seconds(),
+                            file_node.last_modified_nanoseconds(),
+                        );
+                        let stamp =
+                            FileStamp::new(name.to_string_lossy(), file_node.num_bytes(), mtime);
+                        Some((dir.join(name), stamp))
+                    }
+                    _ => None,
+                })
+        })
+        .collect()
+}
+
 pub fn commit_dir_entries_with_parents(
     repo: &LocalRepository,
     parent_commits: Vec<String>,
@@ -357,8 +417,8 @@ pub fn commit_dir_entries_new(
             parent_ids: new_commit
                 .parent_ids
                 .iter()
-                .map(|id: &String| MerkleHash::from_str(id).unwrap())
-                .collect(),
+                .map(|id: &String| MerkleHash::from_str(id))
+                .collect::<Result<Vec<_>, OxenError>>()?,
             email: new_commit.email.clone(),
             author: new_commit.author.clone(),
             message: message.to_string(),
@@ -779,7 +839,7 @@ fn split_into_vnodes(
     Ok(results)
 }
 
-fn compute_commit_id(new_commit: &NewCommit) -> Result<MerkleHash, OxenError> {
+pub fn compute_commit_id(new_commit: &NewCommit) -> Result<MerkleHash, OxenError> {
     let mut hasher = xxhash_rust::xxh3::Xxh3::new();
     hasher.update(b"commit");
     hasher.update(format!("{:?}", new_commit.parent_ids).as_bytes());
@@ -23,7 +23,9 @@ use crate::model::diff::tabular_diff::{
     TabularDiffSummary, TabularSchemaDiff,
 };
 
-use crate::model::{Commit, CommitEntry, DataFrameDiff, DiffEntry, LocalRepository, Schema};
+use crate::model::{
+    Commit, CommitEntry, DataFrameDiff, DiffEntry, LocalRepository, MerkleHash, Schema,
+};
 
 use crate::{constants, repositories, util};
 
@@ -37,6 +39,7 @@ use std::str::FromStr;
 use crate::model::diff::diff_entries_counts::DiffEntriesCounts;
 use crate::model::diff::schema_diff::SchemaDiff;
 use crate::model::diff::AddRemoveModifyCounts;
+use crate::model::diff::ChangeType;
 use crate::model::diff::DiffResult;
 
 use crate::opts::DFOpts;
@@ -132,12 +135,10 @@ pub fn diff(
                 )
             })?;
 
-        let hash = file_node.hash().to_string();
-
-        let committed_file = util::fs::version_path_from_node(&repository, &hash, &path_1);
-        // log::debug!("committed file: {:?}", committed_file);
+        let committed_file =
+            repositories::revisions::get_version_file_from_node(&repository, &file_node, &path_1)?;
         let result =
-            repositories::diffs::diff_files(committed_file, path_1, keys, targets, vec![])?;
+            repositories::diffs::diff_files(&committed_file, path_1, keys, targets, vec![])?;
 
         return Ok(result);
     };
@@ -241,12 +242,12 @@ pub fn diff_tabular_file_nodes(
     targets: Vec<String>,
     display: Vec<String>,
 ) -> Result<DiffResult, OxenError> {
-    let version_path_1 = util::fs::version_path_from_hash(repo, file_1.hash().to_string());
-    let version_path_2 = util::fs::version_path_from_hash(repo, file_2.hash().to_string());
+    let version_file_1 = repositories::revisions::get_version_file_from_node(repo, file_1, "")?;
+    let version_file_2 = repositories::revisions::get_version_file_from_node(repo, file_2, "")?;
     let df_1 =
-        tabular::read_df_with_extension(version_path_1, file_1.extension(), &DFOpts::empty())?;
+        tabular::read_df_with_extension(&version_file_1, file_1.extension(), &DFOpts::empty())?;
     let df_2 =
-        tabular::read_df_with_extension(version_path_2, file_2.extension(), &DFOpts::empty())?;
+        tabular::read_df_with_extension(&version_file_2, file_2.extension(), &DFOpts::empty())?;
 
     let schema_1 = Schema::from_polars(&df_1.schema());
     let schema_2 = Schema::from_polars(&df_2.schema());
@@ -261,10 +262,10 @@ pub fn diff_text_file_nodes(
     file_1: &FileNode,
     file_2: &FileNode,
 ) -> Result<DiffResult, OxenError> {
-    let version_path_1 = util::fs::version_path_from_hash(repo, file_1.hash().to_string());
-    let version_path_2 = util::fs::version_path_from_hash(repo, file_2.hash().to_string());
+    let version_file_1 = repositories::revisions::get_version_file_from_node(repo, file_1, "")?;
+    let version_file_2 = repositories::revisions::get_version_file_from_node(repo, file_2, "")?;
 
-    let result = utf8_diff::diff(&version_path_1, &version_path_2)?;
+    let result = utf8_diff::diff(&version_file_1, &version_file_2)?;
     Ok(DiffResult::Text(result))
 }
 
@@ -704,6 +705,19 @@ pub fn compute_new_columns_from_dfs(
     })
 }
 
+/// List the files that changed between two commits by diffing their merkle trees.
+/// Returns the path, how it changed, and its hash (the hash in `from_commit` for removed files), sorted by path.
+pub fn changed_entries(
+    repo: &LocalRepository,
+    from_commit: &Commit,
+    to_commit: &Commit,
+) -> Result<Vec<(PathBuf, ChangeType, MerkleHash)>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::diff::changed_entries(repo, from_commit, to_commit),
+    }
+}
+
 pub fn diff_entries(
     repo: &LocalRepository,
     file_path: impl AsRef<Path>,
@@ -1027,13 +1041,17 @@ pub fn get_diff_dir(repo: &LocalRepository, compare_id: &str) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::path::Path;
     use std::path::PathBuf;
 
+    use crate::constants::MIN_OXEN_VERSION;
     use crate::error::OxenError;
     use crate::model::diff::diff_entry_status::DiffEntryStatus;
-    use crate::opts::RmOpts;
+    use crate::model::merkle_tree::node::FileChunkType;
+    use crate::opts::{AddOpts, DFOpts, RmOpts};
     use crate::repositories;
+    use crate::storage::StorageConfig;
     use crate::test;
     use crate::util;
 
@@ -1077,6 +1095,49 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_changed_entries_between_commits() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let keep_file = repo.path.join("keep.txt");
+            let modify_file = repo.path.join("dir").join("modify.txt");
+            let remove_file = repo.path.join("dir").join("remove.txt");
+            test::write_txt_file_to_path(&keep_file, "Keep")?;
+            test::write_txt_file_to_path(&modify_file, "Before")?;
+            test::write_txt_file_to_path(&remove_file, "Remove")?;
+            repositories::add(&repo, &repo.path)?;
+            let base_commit = repositories::commit(&repo, "Adding files")?;
+
+            let add_file = repo.path.join("new_dir").join("add.txt");
+            test::write_txt_file_to_path(&modify_file, "After")?;
+            test::write_txt_file_to_path(&add_file, "Add")?;
+            repositories::add(&repo, &modify_file)?;
+            repositories::add(&repo, &add_file)?;
+            let rm_opts = RmOpts::from_path(Path::new("dir").join("remove.txt"));
+            repositories::rm(&repo, &rm_opts)?;
+            let head_commit = repositories::commit(&repo, "Changing files")?;
+
+            let changes = repositories::diffs::changed_entries(&repo, &base_commit, &head_commit)?;
+            let changes: Vec<(PathBuf, ChangeType)> = changes
+                .into_iter()
+                .map(|(path, change_type, _)| (path, change_type))
+                .collect();
+            assert_eq!(
+                changes,
+                vec![
+                    (Path::new("dir").join("modify.txt"), ChangeType::Modified),
+                    (Path::new("dir").join("remove.txt"), ChangeType::Removed),
+                    (Path::new("new_dir").join("add.txt"), ChangeType::Added),
+                ]
+            );
+
+            // Diffing a commit against itself has no changes
+            let changes = repositories::diffs::changed_entries(&repo, &head_commit, &head_commit)?;
+            assert!(changes.is_empty());
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_diff_entries_modify_one_tabular() -> Result<(), OxenError> {
         test::run_bounding_box_csv_repo_test_fully_committed(|repo| {
@@ -1849,4 +1910,160 @@ train/cat_2.jpg,cat,30.5,44.0,333,396
         })
         .await
     }
+
+    #[test]
+    fn test_df_and_diff_read_compressed_versions() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|repo_dir| {
+            let storage = StorageConfig {
+                type_: "local".to_string(),
+                settings: HashMap::from([("compression".to_string(), "zstd".to_string())]),
+            };
+            let repo = repositories::init::init_with_storage(repo_dir, MIN_OXEN_VERSION, &storage)?;
+
+            let csv_path = PathBuf::from("data.csv");
+            let txt_path = PathBuf::from("notes.txt");
+            util::fs::write_to_path(repo.path.join(&csv_path), "a,b\n1,2\n3,4\n")?;
+            util::fs::write_to_path(repo.path.join(&txt_path), "hello\nhi\n")?;
+            repositories::add(&repo, &repo.path)?;
+            let commit_1 = repositories::commit(&repo, "first")?;
+
+            util::fs::write_to_path(repo.path.join(&csv_path), "a,b\n1,2\n3,4\n5,6\n")?;
+            util::fs::write_to_path(repo.path.join(&txt_path), "hello\nhey\n")?;
+            repositories::add(&repo, &repo.path)?;
+            let commit_2 = repositories::commit(&repo, "second")?;
+
+            // Versions are only stored compressed
+            let node_1 = repositories::entries::get_file(&repo, &commit_1, &csv_path)?.unwrap();
+            let version_store = repo.version_store()?;
+            assert!(version_store
+                .get_version_path(&node_1.hash().to_string())
+                .is_err());
+
+            let slice = repositories::data_frames::get_slice(
+                &repo,
+                &commit_1,
+                &csv_path,
+                &DFOpts::empty(),
+            )?;
+            assert_eq!(slice.total_entries, 2);
+
+            let diff = repositories::diffs::diff_commits(
+                &repo,
+                CommitPath {
+                    commit: Some(commit_1.clone()),
+                    path: csv_path.clone(),
+                },
+                CommitPath {
+                    commit: Some(commit_2.clone()),
+                    path: csv_path.clone(),
+                },
+                vec![],
+                vec![],
+                vec![],
+            )?;
+            match diff {
+                DiffResult::Tabular(diff) => {
+                    assert_eq!(diff.summary.modifications.row_counts.added, 1);
+                }
+                _ => panic!("expected tabular result"),
+            }
+
+            let txt_1 = repositories::entries::get_file(&repo, &commit_1, &txt_path)?.unwrap();
+            let txt_2 = repositories::entries::get_file(&repo, &commit_2, &txt_path)?.unwrap();
+            match repositories::diffs::diff_text_file_nodes(&repo, &txt_1, &txt_2)? {
+                DiffResult::Text(result) => {
+                    assert!(result
+                        .lines
+                        .iter()
+                        .any(|l| l.modification == ChangeType::Added && l.text == "hey"));
+                }
+                _ => panic!("expected text result"),
+            }
+
+            let version_file = repositories::revisions::get_version_file_from_commit_id(
+                &repo,
+                &commit_1.id,
+                &txt_path,
+            )?;
+            assert_eq!(util::fs::read_from_path(&version_file)?, "hello\nhi\n");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_df_and_diff_read_chunked_versions() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let csv_path = PathBuf::from("large.csv");
+            let mut csv = String::from("id,label,text\n");
+            let mut num_rows = 0;
+            while (csv.len() as u64) < util::chunker::CHUNKED_HASH_MIN_FILE_SIZE {
+                csv.push_str(&format!(
+                    "{num_rows},label_{},{}\n",
+                    num_rows % 10,
+                    "some text in a row ".repeat(4)
+                ));
+                num_rows += 1;
+            }
+            util::fs::write_to_path(repo.path.join(&csv_path), &csv)?;
+            let opts = AddOpts {
+                paths: vec![repo.path.join(&csv_path)],
+                chunked_hash: true,
+                ..Default::default()
+            };
+            repositories::add::add_with_opts(&repo, &opts)?;
+            let commit_1 = repositories::commit(&repo, "first")?;
+
+            let appended = format!("{csv}{num_rows},label_0,appended\n");
+            util::fs::write_to_path(repo.path.join(&csv_path), &appended)?;
+            repositories::add::add_with_opts(&repo, &opts)?;
+            let commit_2 = repositories::commit(&repo, "second")?;
+
+            // Versions are only stored as a manifest of their chunks
+            let node_1 = repositories::entries::get_file(&repo, &commit_1, &csv_path)?.unwrap();
+            assert_eq!(*node_1.chunk_type(), FileChunkType::Chunked);
+            let version_store = repo.version_store()?;
+            assert!(version_store
+                .get_version_path(&node_1.hash().to_string())
+                .is_err());
+
+            let slice = repositories::data_frames::get_slice(
+                &repo,
+                &commit_1,
+                &csv_path,
+                &DFOpts::empty(),
+            )?;
+            assert_eq!(slice.total_entries, num_rows);
+
+            let diff = repositories::diffs::diff_commits(
+                &repo,
+                CommitPath {
+                    commit: Some(commit_1.clone()),
+                    path: csv_path.clone(),
+                },
+                CommitPath {
+                    commit: Some(commit_2.clone()),
+                    path: csv_path.clone(),
+                },
+                vec![],
+                vec![],
+                vec![],
+            )?;
+            match diff {
+                DiffResult::Tabular(diff) => {
+                    assert_eq!(diff.summary.modifications.row_counts.added, 1);
+                }
+                _ => panic!("expected tabular result"),
+            }
+
+            let version_file = repositories::revisions::get_version_file_from_commit_id(
+                &repo,
+                &commit_1.id,
+                &csv_path,
+            )?;
+            assert_eq!(util::fs::read_from_path(&version_file)?, csv);
+
+            Ok(())
+        })
+    }
 }
@@ -1,6 +1,10 @@
 use crate::error::OxenError;
+use crate::model::LocalRepository;
+use crate::repositories;
 use crate::util::fs as oxen_fs;
-use crate::view::fork::{ForkStartResponse, ForkStatus, ForkStatusFile, ForkStatusResponse};
+use crate::view::fork::{
+    ForkSource, ForkStartResponse, ForkStatus, ForkStatusFile, ForkStatusResponse,
+};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::thread;
@@ -38,6 +42,7 @@ fn read_status(repo_path: &Path) -> Result<Option<ForkStatus>, OxenError> {
 
     Ok(Some(match status {
         ForkStatus::Started => ForkStatus::Started,
+        ForkStatus::Overwriting => ForkStatus::Overwriting,
         ForkStatus::InProgress(_) => ForkStatus::InProgress(status_file.progress.unwrap_or(0.0)),
         ForkStatus::Complete => ForkStatus::Complete,
         ForkStatus::Counting(_) => ForkStatus::Counting(status_file.progress.unwrap_or(0.0) as u32),
@@ -53,20 +58,49 @@ pub fn start_fork(
     original_path: PathBuf,
     new_path: PathBuf,
 ) -> Result<ForkStartResponse, OxenError> {
-    if new_path.exists() {
-        return Err(OxenError::basic_str(format!(
-            "A file already exists at the destination path: {}",
-            new_path.to_string_lossy()
-        )));
+    start_fork_with_force(original_path, new_path, false)
+}
+
+/// Fork the repo at `original_path` to `new_path`. If a repo already exists at `new_path`, it is
+/// deleted first when `force` is set, otherwise this errors. A destination that is still being
+/// forked into is never overwritten.
+pub fn start_fork_with_force(
+    original_path: PathBuf,
+    new_path: PathBuf,
+    force: bool,
+) -> Result<ForkStartResponse, OxenError> {
+    let overwrite = new_path.exists();
+    if overwrite {
+        check_can_overwrite(&original_path, &new_path, force)?;
     }
 
+    // Record where the fork came from before anything is copied, so the source's head is the commit we fork
+    let lineage = fork_lineage(&original_path)?;
+
     oxen_fs::create_dir_all(&new_path)?;
-    write_status(&new_path, &ForkStatus::Counting(0))?;
+    if overwrite {
+        write_status(&new_path, &ForkStatus::Overwriting)?;
+    } else {
+        write_status(&new_path, &ForkStatus::Counting(0))?;
+    }
 
     let new_path_clone = new_path.clone();
     let mut current_count = 0;
 
     thread::spawn(move || {
+        if overwrite {
+            if let Err(e) = clear_destination(&new_path) {
+                log::error!("Failed to clear fork destination: {}", e);
+                write_status(&new_path, &ForkStatus::Failed(e.to_string())).unwrap_or_else(|e| {
+                    log::error!("Failed to write error status: {}", e);
+                });
+                return;
+            }
+            write_status(&new_path, &ForkStatus::Counting(0)).unwrap_or_else(|e| {
+                log::error!("Failed to write counting status: {}", e);
+            });
+        }
+
         let total_items = match count_items(&original_path, &new_path, &mut current_count) {
             Ok(count) => count as f32,
             Err(e) => {
@@ -84,7 +118,9 @@ pub fn start_fork(
             &new_path,
             total_items,
             &mut copied_items,
-        ) {
+        )
+        .and_then(|_| write_lineage(&new_path, lineage))
+        {
             Ok(()) => {
                 write_status(&new_path, &ForkStatus::Complete).unwrap_or_else(|e| {
                     log::error!("Failed to write completion status: {}", e);
@@ -104,6 +140,59 @@ pub fn start_fork(
     })
 }
 
+fn check_can_overwrite(
+    original_path: &Path,
+    new_path: &Path,
+    force: bool,
+) -> Result<(), OxenError> {
+    if !force {
+        return Err(OxenError::repo_already_exists_at_destination(
+            "Repo already exists at destination.".into(),
+        ));
+    }
+    if oxen_fs::canonicalize(original_path)? == oxen_fs::canonicalize(new_path)? {
+        return Err(OxenError::basic_str("Cannot fork a repo over itself"));
+    }
+    if let Some(
+        ForkStatus::Started
+        | ForkStatus::Overwriting
+        | ForkStatus::Counting(_)
+        | ForkStatus::InProgress(_),
+    ) = read_status(new_path)?
+    {
+        return Err(OxenError::repo_already_exists_at_destination(
+            "A fork into the destination is still in progress.".into(),
+        ));
+    }
+    if !oxen_fs::oxen_hidden_dir(new_path).exists() {
+        return Err(OxenError::repo_already_exists_at_destination(
+            "The destination exists but is not a repo, refusing to overwrite it.".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Delete everything at the fork destination except the fork status, so the status keeps reporting
+/// the overwrite while the old repo is removed
+fn clear_destination(repo_path: &Path) -> Result<(), OxenError> {
+    let status_path = repo_path.join(FORK_STATUS_FILE);
+    let hidden_dir = oxen_fs::oxen_hidden_dir(repo_path);
+    for dir in [repo_path, hidden_dir.as_path()] {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path == hidden_dir || path == status_path {
+                continue;
+            }
+            if path.is_dir() {
+                oxen_fs::remove_dir_all(&path)?;
+            } else {
+                oxen_fs::remove_file(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn get_fork_status(repo_path: &Path) -> Result<ForkStatusResponse, OxenError> {
     let status = read_status(repo_path)?.ok_or_else(OxenError::fork_status_not_found)?;
 
@@ -111,6 +200,7 @@ pub fn get_fork_status(repo_path: &Path) -> Result<ForkStatusResponse, OxenError
         repository: repo_path.to_string_lossy().to_string(),
         status: match status {
             ForkStatus::Started => ForkStatus::Started.to_string(),
+            ForkStatus::Overwriting => ForkStatus::Overwriting.to_string(),
             ForkStatus::Counting(_) => ForkStatus::Counting(0).to_string(),
             ForkStatus::InProgress(_) => ForkStatus::InProgress(0.0).to_string(),
             ForkStatus::Complete => ForkStatus::Complete.to_string(),
@@ -128,6 +218,42 @@ pub fn get_fork_status(repo_path: &Path) -> Result<ForkStatusResponse, OxenError
     })
 }
 
+/// The repos that the repo at `repo_path` was forked from, nearest first
+pub fn get_lineage(repo_path: &Path) -> Result<Vec<ForkSource>, OxenError> {
+    let repo = LocalRepository::from_dir(repo_path)?;
+    Ok(repo.forked_from().to_vec())
+}
+
+/// The lineage of a fork of the repo at `original_path`: the original repo at its current head,
+/// followed by whatever the original was itself forked from. Repos live at `namespace/name`.
+fn fork_lineage(original_path: &Path) -> Result<Vec<ForkSource>, OxenError> {
+    let original_repo = LocalRepository::from_dir(original_path)?;
+    let name = original_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let namespace = original_path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|namespace| namespace.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let commit_id = repositories::commits::head_commit_maybe(&original_repo)?.map(|c| c.id);
+
+    let mut lineage = vec![ForkSource {
+        namespace,
+        name,
+        commit_id,
+    }];
+    lineage.extend(original_repo.forked_from().iter().cloned());
+    Ok(lineage)
+}
+
+fn write_lineage(repo_path: &Path, lineage: Vec<ForkSource>) -> Result<(), OxenError> {
+    let mut repo = LocalRepository::from_dir(repo_path)?;
+    repo.set_forked_from(lineage);
+    repo.save()
+}
+
 fn copy_dir_recursive(
     src: &Path,
     dst: &Path,
@@ -189,7 +315,100 @@ mod tests {
 
     use super::*;
     use crate::error::OxenError;
-    use crate::test;
+    use crate::{test, util};
+
+    async fn wait_for_fork(repo_path: &Path) -> Result<(), OxenError> {
+        for _ in 0..50 {
+            match get_fork_status(repo_path) {
+                Ok(status) if status.status == "complete" => return Ok(()),
+                Ok(status) if status.status == "failed" => {
+                    return Err(OxenError::basic_str(format!("Fork failed: {:?}", status)))
+                }
+                Ok(_) | Err(OxenError::ForkStatusNotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        Err(OxenError::basic_str("Fork operation timed out"))
+    }
+
+    #[tokio::test]
+    async fn test_fork_of_fork_chains_lineage() -> Result<(), OxenError> {
+        test::run_one_commit_local_repo_test_async(|original_repo| async move {
+            let head = repositories::commits::head_commit(&original_repo)?;
+            let forks_dir = original_repo
+                .path
+                .parent()
+                .unwrap()
+                .join(Uuid::new_v4().to_string());
+            let first_fork = forks_dir.join("first").join("fork");
+            let second_fork = forks_dir.join("second").join("fork");
+
+            start_fork(original_repo.path.clone(), first_fork.clone())?;
+            wait_for_fork(&first_fork).await?;
+            let lineage = get_lineage(&first_fork)?;
+            assert_eq!(lineage.len(), 1);
+            assert_eq!(lineage[0].commit_id, Some(head.id.clone()));
+            assert_eq!(
+                lineage[0].name,
+                original_repo.path.file_name().unwrap().to_string_lossy()
+            );
+
+            start_fork(first_fork.clone(), second_fork.clone())?;
+            wait_for_fork(&second_fork).await?;
+            let lineage = get_lineage(&second_fork)?;
+            assert_eq!(lineage.len(), 2);
+            assert_eq!(lineage[0].namespace, "first");
+            assert_eq!(lineage[0].name, "fork");
+            assert_eq!(lineage[0].commit_id, Some(head.id.clone()));
+            assert_eq!(lineage[1], get_lineage(&first_fork)?[0]);
+
+            // The original repo is not a fork
+            assert!(get_lineage(&original_repo.path)?.is_empty());
+
+            util::fs::remove_dir_all(&forks_dir)?;
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_fork_force_overwrites_destination() -> Result<(), OxenError> {
+        test::run_one_commit_local_repo_test_async(|original_repo| async move {
+            let forks_dir = original_repo
+                .path
+                .parent()
+                .unwrap()
+                .join(Uuid::new_v4().to_string());
+            let fork_path = forks_dir.join("mirror").join("fork");
+
+            start_fork(original_repo.path.clone(), fork_path.clone())?;
+            wait_for_fork(&fork_path).await?;
+            let stale_file = fork_path.join("stale.txt");
+            util::fs::write_to_path(&stale_file, "only in the old fork")?;
+
+            // Without force the existing repo is a conflict
+            let result = start_fork(original_repo.path.clone(), fork_path.clone());
+            assert!(matches!(
+                result,
+                Err(OxenError::RepoAlreadyExistsAtDestination(_))
+            ));
+
+            start_fork_with_force(original_repo.path.clone(), fork_path.clone(), true)?;
+            wait_for_fork(&fork_path).await?;
+            assert!(!stale_file.exists());
+            assert_eq!(get_lineage(&fork_path)?.len(), 1);
+
+            // A fork that is still running is never overwritten
+            write_status(&fork_path, &ForkStatus::InProgress(50.0))?;
+            let result = start_fork_with_force(original_repo.path.clone(), fork_path.clone(), true);
+            assert!(result.is_err());
+
+            util::fs::remove_dir_all(&forks_dir)?;
+            Ok(())
+        })
+        .await
+    }
 
     #[tokio::test]
     async fn test_fork_operations() -> Result<(), OxenError> {
@@ -0,0 +1,63 @@
+//! # oxen gc
+//!
+//! Delete the version files that nothing references anymore
+//!
+
+use crate::core;
+use crate::core::v_latest::gc::GcResult;
+use crate::core::versions::MinOxenVersion;
+use crate::error::OxenError;
+use crate::model::LocalRepository;
+
+/// Delete the version files that are not referenced by any commit, staged file or workspace.
+/// With `dry_run` nothing is deleted, the result is what would be reclaimed.
+pub fn gc(repo: &LocalRepository, dry_run: bool) -> Result<GcResult, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::gc::gc(repo, dry_run),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::OxenError;
+    use crate::repositories;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_gc_dry_run_keeps_versions() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let committed = repo.path.join("committed.txt");
+            util::fs::write_to_path(&committed, "committed")?;
+            repositories::add(&repo, &committed)?;
+            repositories::commit(&repo, "Adding committed file")?;
+
+            let staged = repo.path.join("staged.txt");
+            util::fs::write_to_path(&staged, "staged")?;
+            repositories::add(&repo, &staged)?;
+
+            // A version nothing points to, like one left behind by an interrupted add
+            let version_store = repo.version_store()?;
+            let orphan = "0123456789abcdef0123456789abcdef";
+            version_store.store_version(orphan, b"orphan")?;
+            let num_versions = version_store.list_versions()?.len();
+
+            let result = repositories::gc::gc(&repo, true)?;
+            assert!(result.dry_run);
+            assert_eq!(result.num_files, 1);
+            assert_eq!(result.num_bytes, 6);
+            assert_eq!(version_store.list_versions()?.len(), num_versions);
+
+            let result = repositories::gc::gc(&repo, false)?;
+            assert_eq!(result.num_files, 1);
+            assert!(!version_store.version_exists(orphan)?);
+            assert_eq!(version_store.list_versions()?.len(), num_versions - 1);
+
+            // Nothing left to collect
+            assert_eq!(repositories::gc::gc(&repo, true)?.num_files, 0);
+
+            Ok(())
+        })
+    }
+}
@@ -10,6 +10,7 @@ use crate::core;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::LocalRepository;
+use crate::storage::StorageConfig;
 
 /// # Initialize an Empty Oxen Repository
 /// ```
@@ -41,10 +42,30 @@ pub fn init_with_version(
     }
 }
 
+/// # Initialize a repository with a pinned version store
+/// The storage config is validated before anything is created and is saved in the repository
+/// config, so the repository keeps using that backend from then on.
+pub fn init_with_storage(
+    path: impl AsRef<Path>,
+    version: MinOxenVersion,
+    storage: &StorageConfig,
+) -> Result<LocalRepository, OxenError> {
+    let path = path.as_ref();
+    match version {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::init_with_storage(path, version, Some(storage)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use crate::constants::MIN_OXEN_VERSION;
     use crate::error::OxenError;
+    use crate::model::LocalRepository;
     use crate::repositories;
+    use crate::storage::StorageConfig;
     use crate::test;
     use crate::util;
 
@@ -63,4 +84,38 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn test_init_with_storage() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|repo_dir| {
+            // S3 without a bucket is rejected before anything is created
+            let s3 = StorageConfig {
+                type_: "s3".to_string(),
+                settings: HashMap::new(),
+            };
+            let result = repositories::init::init_with_storage(repo_dir, MIN_OXEN_VERSION, &s3);
+            assert!(result.is_err());
+            assert!(!util::fs::oxen_hidden_dir(repo_dir).exists());
+
+            let local = StorageConfig {
+                type_: "local".to_string(),
+                settings: HashMap::from([("compression".to_string(), "zstd".to_string())]),
+            };
+            repositories::init::init_with_storage(repo_dir, MIN_OXEN_VERSION, &local)?;
+
+            // The pinned backend is what the repository loads from then on
+            let repo = LocalRepository::from_dir(repo_dir)?;
+            let store = repo.version_store()?;
+            assert_eq!(store.storage_type(), "local");
+            assert_eq!(
+                store
+                    .storage_settings()
+                    .get("compression")
+                    .map(|c| c.as_str()),
+                Some("zstd")
+            );
+
+            Ok(())
+        })
+    }
 }
@@ -4,6 +4,7 @@ use crate::core;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::merge_conflict::MergeConflict;
+use crate::model::merkle_tree::node::FileNode;
 use crate::model::Commit;
 use crate::model::{Branch, LocalRepository};
 
@@ -20,6 +21,29 @@ impl MergeCommits {
     }
 }
 
+/// The three versions of a conflicting file, passed to a `MergeResolver`
+pub struct ConflictContext<'a> {
+    pub repo: &'a LocalRepository,
+    pub path: &'a Path,
+    pub lca: &'a FileNode,
+    pub base: &'a FileNode,
+    pub merge: &'a FileNode,
+}
+
+/// What a `MergeResolver` decided for a conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the version on the base branch, like `oxen checkout --ours`
+    Base,
+    /// Take the version from the merge branch, like `oxen checkout --theirs`
+    Merge,
+    /// Record it as a conflict for the user to resolve
+    Unresolved,
+}
+
+/// Called for every conflict in a three way merge, so embedders can resolve conflicts with their own policy
+pub type MergeResolver = dyn Fn(&ConflictContext) -> Resolution;
+
 pub fn list_conflicts(repo: &LocalRepository) -> Result<Vec<MergeConflict>, OxenError> {
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
@@ -129,6 +153,18 @@ pub fn merge(
     }
 }
 
+/// Same as `merge`, asking the resolver about each conflict before recording it
+pub fn merge_with_resolver(
+    repo: &LocalRepository,
+    branch_name: impl AsRef<str>,
+    resolver: &MergeResolver,
+) -> Result<Option<Commit>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::merge::merge_with_resolver(repo, branch_name, resolver),
+    }
+}
+
 pub fn merge_commit_into_base(
     repo: &LocalRepository,
     merge_commit: &Commit,
@@ -157,6 +193,26 @@ pub fn merge_commit_into_base_on_branch(
     }
 }
 
+/// Same as `merge_commit_into_base_on_branch`, asking the resolver about each conflict before recording it
+pub fn merge_commit_into_base_on_branch_with_resolver(
+    repo: &LocalRepository,
+    merge_commit: &Commit,
+    base_commit: &Commit,
+    branch: &Branch,
+    resolver: &MergeResolver,
+) -> Result<Option<Commit>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::merge::merge_commit_into_base_on_branch_with_resolver(
+            repo,
+            merge_commit,
+            base_commit,
+            branch,
+            resolver,
+        ),
+    }
+}
+
 pub fn has_file(repo: &LocalRepository, path: &Path) -> Result<bool, OxenError> {
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
@@ -208,6 +264,7 @@ mod tests {
     use crate::model::{Commit, LocalRepository};
     use crate::opts::DFOpts;
     use crate::repositories;
+    use crate::repositories::merge::Resolution;
     use crate::test;
     use crate::util;
 
@@ -487,7 +544,7 @@ mod tests {
             util::fs::write_to_path(&a_path, "a")?;
             repositories::add(&repo, &a_path)?;
             // Return the lowest common ancestor for the tests
-            repositories::commit(&repo, "Committing a.txt file")?;
+            let lca_commit = repositories::commit(&repo, "Committing a.txt file")?;
 
             // Make changes on B
             let merge_branch_name = "B";
@@ -503,7 +560,7 @@ mod tests {
             repositories::add(&repo, &a_path)?;
 
             // Commit changes
-            repositories::commit(&repo, "Committing b.txt file")?;
+            let merge_commit = repositories::commit(&repo, "Committing b.txt file")?;
 
             // Checkout main branch again to make another change
             repositories::checkout(&repo, &a_branch.name).await?;
@@ -524,7 +581,7 @@ mod tests {
             let d_path = repo.path.join("d.txt");
             util::fs::write_to_path(&d_path, "d")?;
             repositories::add(&repo, &d_path)?;
-            repositories::commit(&repo, "Committing d.txt file")?;
+            let base_commit = repositories::commit(&repo, "Committing d.txt file")?;
 
             // Checkout merge branch (B) to make another change
             repositories::checkout(&repo, merge_branch_name).await?;
@@ -549,6 +606,52 @@ mod tests {
 
             let local_a_path = util::fs::path_relative_to_dir(&a_path, &repo.path)?;
             assert_eq!(conflicts[0].base_entry.1, local_a_path);
+            assert_eq!(conflicts[0].path(), local_a_path);
+
+            // All three versions of a.txt are recorded
+            let hash_at = |commit| -> Result<_, OxenError> {
+                let node = repositories::tree::get_file_by_path(&repo, commit, &local_a_path)?;
+                Ok(*node.unwrap().hash())
+            };
+            assert_eq!(conflicts[0].lca_hash(), hash_at(&lca_commit)?);
+            assert_eq!(conflicts[0].base_hash(), hash_at(&base_commit)?);
+            assert_eq!(conflicts[0].merge_hash(), hash_at(&merge_commit)?);
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_merge_with_resolver_takes_merge_side() -> Result<(), OxenError> {
+        test::run_one_commit_local_repo_test_async(|repo| async move {
+            let og_branch = repositories::branches::current_branch(&repo)?.unwrap();
+            let labels_path = repo.path.join("labels.txt");
+            util::fs::write_to_path(&labels_path, "cat\ndog")?;
+            repositories::add(&repo, &labels_path)?;
+            repositories::commit(&repo, "Add labels.txt")?;
+
+            let branch_name = "add-fish";
+            repositories::branches::create_checkout(&repo, branch_name)?;
+            test::modify_txt_file(&labels_path, "cat\ndog\nfish")?;
+            repositories::add(&repo, &labels_path)?;
+            repositories::commit(&repo, "Adding fish")?;
+
+            repositories::checkout(&repo, &og_branch.name).await?;
+            test::modify_txt_file(&labels_path, "cat\ndog\nhuman")?;
+            repositories::add(&repo, &labels_path)?;
+            repositories::commit(&repo, "Adding human")?;
+
+            let commit =
+                repositories::merge::merge_with_resolver(&repo, branch_name, &|context| {
+                    assert_eq!(context.path, Path::new("labels.txt"));
+                    Resolution::Merge
+                })?;
+
+            // No conflicts are recorded and the merge commit has the branch version
+            assert!(commit.is_some());
+            assert!(!NodeMergeConflictReader::new(&repo)?.has_conflicts()?);
+            assert_eq!(util::fs::read_from_path(&labels_path)?, "cat\ndog\nfish");
 
             Ok(())
         })
@@ -706,6 +809,49 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_binary_merge_conflict_only_takes_a_side() -> Result<(), OxenError> {
+        test::run_one_commit_local_repo_test_async(|repo| async move {
+            let og_branch = repositories::branches::current_branch(&repo)?.unwrap();
+            let weights_path = repo.path.join("weights.bin");
+            util::fs::write(&weights_path, [0u8, 159, 146, 150, 1])?;
+            repositories::add(&repo, &weights_path)?;
+            repositories::commit(&repo, "Add weights")?;
+
+            let branch_name = "retrain";
+            repositories::branches::create_checkout(&repo, branch_name)?;
+            util::fs::write(&weights_path, [0u8, 159, 146, 150, 2, 2])?;
+            repositories::add(&repo, &weights_path)?;
+            repositories::commit(&repo, "Retrain weights")?;
+
+            repositories::checkout(&repo, &og_branch.name).await?;
+            util::fs::write(&weights_path, [0u8, 159, 146, 150, 3, 3, 3])?;
+            repositories::add(&repo, &weights_path)?;
+            repositories::commit(&repo, "Fine tune weights")?;
+
+            let result = repositories::merge::merge(&repo, branch_name)?;
+            assert!(result.is_none());
+
+            let conflicts = repositories::merge::list_conflicts(&repo)?;
+            assert_eq!(conflicts.len(), 1);
+            assert!(conflicts[0].is_binary);
+
+            let status = repositories::status(&repo)?;
+            assert!(status.merge_conflicts[0].is_binary);
+
+            // There is no content merge for binary files, only ours or theirs
+            assert!(repositories::checkout::checkout_combine(&repo, "weights.bin").is_err());
+            repositories::checkout::checkout_theirs(&repo, "weights.bin")?;
+            assert_eq!(
+                util::fs::read_bytes_from_path(&weights_path)?,
+                [0u8, 159, 146, 150, 2, 2]
+            );
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_command_merge_dataframe_conflict_both_added_rows_checkout_theirs(
     ) -> Result<(), OxenError> {
@@ -162,6 +162,40 @@ pub fn get_cli(
     }
 }
 
+/// Counts from `backfill`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MetadataBackfillStats {
+    /// Files whose metadata was computed and staged
+    pub num_backfilled: usize,
+    /// Files no metadata could be computed for, ie tabular files that failed to parse or missing versions
+    pub num_failed: usize,
+    /// Files skipped because they were staged by a previous, interrupted, run
+    pub num_already_staged: usize,
+    /// Files left for another run because of the limit
+    pub num_remaining: usize,
+}
+
+/// Stage metadata for the files in the head `commit` that were added with `oxen add --no-metadata`.
+/// Commit the staged changes to finish the backfill.
+pub fn backfill(
+    repo: &LocalRepository,
+    commit: &Commit,
+) -> Result<MetadataBackfillStats, OxenError> {
+    backfill_with_limit(repo, commit, None)
+}
+
+/// Like `backfill`, but only processes up to `limit` files so a huge repo can be done incrementally
+pub fn backfill_with_limit(
+    repo: &LocalRepository,
+    commit: &Commit,
+    limit: Option<usize>,
+) -> Result<MetadataBackfillStats, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::metadata::backfill(repo, commit, limit),
+    }
+}
+
 /// Returns the file size in bytes.
 pub fn get_file_size(path: impl AsRef<Path>) -> Result<u64, OxenError> {
     let metadata = util::fs::metadata(path.as_ref())?;
@@ -226,9 +260,15 @@ pub fn get_file_metadata(
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
+    use crate::error::OxenError;
+    use crate::model::metadata::generic_metadata::GenericMetadata;
     use crate::model::EntryDataType;
+    use crate::opts::AddOpts;
     use crate::repositories;
     use crate::test;
+    use crate::util;
 
     #[test]
     fn test_get_metadata_audio_flac() {
@@ -241,4 +281,48 @@ mod tests {
         assert_eq!(metadata.data_type, EntryDataType::Audio);
         assert_eq!(metadata.mime_type, "audio/x-flac");
     }
+
+    #[test]
+    fn test_backfill_metadata_after_no_metadata_add() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(repo.path.join("a.csv"), "a,b\n1,2\n")?;
+            util::fs::write_to_path(repo.path.join("b.csv"), "x,y,z\n1,2,3\n4,5,6\n")?;
+            // Binary files never get metadata so are not counted as remaining
+            std::fs::write(repo.path.join("c.bin"), [0u8, 159, 146, 150, 0, 1])?;
+            let opts = AddOpts {
+                paths: vec![repo.path.clone()],
+                no_metadata: true,
+                ..Default::default()
+            };
+            repositories::add::add_with_opts(&repo, &opts)?;
+            let commit = repositories::commit(&repo, "Adding without metadata")?;
+
+            // Only do one file at a time
+            let stats = repositories::metadata::backfill_with_limit(&repo, &commit, Some(1))?;
+            assert_eq!(stats.num_backfilled, 1);
+            assert_eq!(stats.num_remaining, 1);
+            let commit = repositories::commit(&repo, "Backfill metadata")?;
+
+            let stats = repositories::metadata::backfill(&repo, &commit)?;
+            assert_eq!(stats.num_backfilled, 1);
+            assert_eq!(stats.num_remaining, 0);
+            let commit = repositories::commit(&repo, "Backfill metadata")?;
+
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("b.csv"))?.unwrap();
+            match file_node.metadata() {
+                Some(GenericMetadata::MetadataTabular(metadata)) => {
+                    assert_eq!(metadata.tabular.width, 3);
+                    assert_eq!(metadata.tabular.height, 2);
+                }
+                _ => panic!("Expected tabular metadata"),
+            }
+
+            // Nothing left to do
+            let stats = repositories::metadata::backfill(&repo, &commit)?;
+            assert_eq!(stats.num_backfilled, 0);
+
+            Ok(())
+        })
+    }
 }
@@ -50,6 +50,7 @@ mod tests {
     use crate::opts::CloneOpts;
     use crate::opts::DFOpts;
     use crate::opts::FetchOpts;
+    use crate::opts::RestoreOpts;
     use crate::opts::RmOpts;
     use crate::repositories;
     use crate::test;
@@ -1088,6 +1089,145 @@ mod tests {
         })
         .await
     }
+    #[tokio::test]
+    async fn test_pull_prune_removes_deleted_files() -> Result<(), OxenError> {
+        test::run_one_commit_sync_repo_test(|_, remote_repo| async move {
+            let remote_repo_copy = remote_repo.clone();
+
+            test::run_empty_dir_test_async(|user_a_repo_dir| async move {
+                let user_a_repo_dir_copy = user_a_repo_dir.join("user_a_repo");
+                let user_a_repo =
+                    repositories::clone_url(&remote_repo.remote.url, &user_a_repo_dir_copy).await?;
+
+                // Add a file that will later be removed on the remote
+                let file_1 = "file_1.txt";
+                test::write_txt_file_to_path(user_a_repo.path.join(file_1), "File 1")?;
+                repositories::add(&user_a_repo, user_a_repo.path.join(file_1))?;
+                repositories::commit(&user_a_repo, "Adding file_1")?;
+                repositories::push(&user_a_repo).await?;
+
+                test::run_empty_dir_test_async(|user_b_repo_dir| async move {
+                    // User B pulls with --prune, user C without
+                    let user_b_repo = repositories::clone_url(
+                        &remote_repo.remote.url,
+                        &user_b_repo_dir.join("b"),
+                    )
+                    .await?;
+                    let user_c_repo = repositories::clone_url(
+                        &remote_repo.remote.url,
+                        &user_b_repo_dir.join("c"),
+                    )
+                    .await?;
+                    assert!(user_b_repo.path.join(file_1).exists());
+
+                    // Commit locally so the pull is a three way merge, not a fast forward
+                    for repo in [&user_b_repo, &user_c_repo] {
+                        let local_file = repo.path.join("local.txt");
+                        test::write_txt_file_to_path(&local_file, "Local")?;
+                        repositories::add(repo, &local_file)?;
+                        repositories::commit(repo, "Adding a local file")?;
+                    }
+
+                    // Remove the file on the remote
+                    let rm_opts = RmOpts::from_path(file_1);
+                    repositories::rm(&user_a_repo, &rm_opts)?;
+                    repositories::commit(&user_a_repo, "Removing file_1")?;
+                    repositories::push(&user_a_repo).await?;
+
+                    let fetch_opts = FetchOpts {
+                        prune: true,
+                        ..FetchOpts::new()
+                    };
+                    repositories::pull_remote_branch(&user_b_repo, &fetch_opts).await?;
+                    assert!(!user_b_repo.path.join(file_1).exists());
+                    assert!(user_b_repo.path.join("local.txt").exists());
+                    // The merge commit records the removal
+                    let head = repositories::commits::head_commit(&user_b_repo)?;
+                    assert!(
+                        repositories::tree::get_file_by_path(&user_b_repo, &head, file_1)?
+                            .is_none()
+                    );
+
+                    // Without --prune the merge keeps the file
+                    repositories::pull_remote_branch(&user_c_repo, &FetchOpts::new()).await?;
+                    assert!(user_c_repo.path.join(file_1).exists());
+
+                    Ok(user_b_repo_dir)
+                })
+                .await?;
+
+                Ok(user_a_repo_dir_copy)
+            })
+            .await?;
+
+            Ok(remote_repo_copy)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_pull_prune_restores_pruned_files_on_conflict() -> Result<(), OxenError> {
+        test::run_one_commit_sync_repo_test(|_, remote_repo| async move {
+            let remote_repo_copy = remote_repo.clone();
+
+            test::run_empty_dir_test_async(|user_a_repo_dir| async move {
+                let user_a_repo_dir_copy = user_a_repo_dir.join("user_a_repo");
+                let user_a_repo =
+                    repositories::clone_url(&remote_repo.remote.url, &user_a_repo_dir_copy).await?;
+
+                let file_1 = "file_1.txt";
+                let file_2 = "file_2.txt";
+                test::write_txt_file_to_path(user_a_repo.path.join(file_1), "File 1")?;
+                test::write_txt_file_to_path(user_a_repo.path.join(file_2), "File 2")?;
+                repositories::add(&user_a_repo, user_a_repo.path.join(file_1))?;
+                repositories::add(&user_a_repo, user_a_repo.path.join(file_2))?;
+                repositories::commit(&user_a_repo, "Adding file_1 and file_2")?;
+                repositories::push(&user_a_repo).await?;
+
+                test::run_empty_dir_test_async(|user_b_repo_dir| async move {
+                    let user_b_repo_dir_copy = user_b_repo_dir.join("user_b_repo");
+                    let user_b_repo =
+                        repositories::clone_url(&remote_repo.remote.url, &user_b_repo_dir_copy)
+                            .await?;
+
+                    // User B changes file_2 locally
+                    let local_file_2 = user_b_repo.path.join(file_2);
+                    test::write_txt_file_to_path(&local_file_2, "User B's File 2")?;
+                    repositories::add(&user_b_repo, &local_file_2)?;
+                    repositories::commit(&user_b_repo, "Changing file_2 on B")?;
+
+                    // User A removes file_1 and changes file_2 differently
+                    repositories::rm(&user_a_repo, &RmOpts::from_path(file_1))?;
+                    test::write_txt_file_to_path(user_a_repo.path.join(file_2), "User A's File 2")?;
+                    repositories::add(&user_a_repo, user_a_repo.path.join(file_2))?;
+                    repositories::commit(&user_a_repo, "Removing file_1, changing file_2")?;
+                    repositories::push(&user_a_repo).await?;
+
+                    let fetch_opts = FetchOpts {
+                        prune: true,
+                        ..FetchOpts::new()
+                    };
+                    let result = repositories::pull_remote_branch(&user_b_repo, &fetch_opts).await;
+                    assert!(result.is_err());
+
+                    // The conflict doesn't leave the pruned removal behind
+                    assert!(user_b_repo.path.join(file_1).exists());
+                    let status = repositories::status(&user_b_repo)?;
+                    assert!(!status.staged_files.contains_key(Path::new(file_1)));
+
+                    Ok(user_b_repo_dir_copy)
+                })
+                .await?;
+
+                Ok(user_a_repo_dir_copy)
+            })
+            .await?;
+
+            Ok(remote_repo_copy)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_pull_does_not_remove_untracked_files() -> Result<(), OxenError> {
         // Push the Remote Repo
@@ -1507,6 +1647,130 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_pull_blobless_updates_working_tree() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|_, remote_repo| async move {
+            let remote_repo_copy = remote_repo.clone();
+            test::run_empty_dir_test_async(|user_a_repo_dir| async move {
+                let user_a_repo_dir_copy = user_a_repo_dir.join("repo_a");
+                let user_a_repo =
+                    repositories::clone_url(&remote_repo.remote.url, &user_a_repo_dir_copy).await?;
+                let previous_head = repositories::commits::head_commit(&user_a_repo)?;
+
+                // User B pushes a new file
+                let new_file = "new_file.txt";
+                test::run_empty_dir_test_async(|user_b_repo_dir| async move {
+                    let user_b_repo_dir_copy = user_b_repo_dir.join("repo_b");
+                    let user_b_repo =
+                        repositories::clone_url(&remote_repo.remote.url, &user_b_repo_dir_copy)
+                            .await?;
+                    let new_file_path = user_b_repo.path.join(new_file);
+                    test::write_txt_file_to_path(&new_file_path, "hello from a file")?;
+                    repositories::add(&user_b_repo, &new_file_path)?;
+                    repositories::commit(&user_b_repo, "Adding new file")?;
+                    repositories::push(&user_b_repo).await?;
+
+                    Ok(user_b_repo_dir_copy)
+                })
+                .await?;
+
+                repositories::pull_remote_branch(
+                    &user_a_repo,
+                    &FetchOpts {
+                        blobless: true,
+                        ..FetchOpts::new()
+                    },
+                )
+                .await?;
+
+                // The branch and the working tree moved, only the new file was downloaded
+                let head = repositories::commits::head_commit(&user_a_repo)?;
+                assert_ne!(head.id, previous_head.id);
+                assert!(user_a_repo.path.join(new_file).exists());
+                assert!(!core::commit_sync_status::commit_is_synced(
+                    &user_a_repo,
+                    &MerkleHash::from_str(&head.id)?,
+                ));
+
+                // Nothing looks removed or modified right after the pull
+                let status = repositories::status(&user_a_repo)?;
+                status.print();
+                assert!(status.is_clean());
+
+                Ok(user_a_repo_dir_copy)
+            })
+            .await?;
+
+            Ok(remote_repo_copy)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_restore_fetches_versions_skipped_by_blobless_pull() -> Result<(), OxenError> {
+        test::run_training_data_fully_sync_remote(|_, remote_repo| async move {
+            let remote_repo_copy = remote_repo.clone();
+            test::run_empty_dir_test_async(|user_a_repo_dir| async move {
+                let user_a_repo_dir_copy = user_a_repo_dir.join("repo_a");
+                let user_a_repo =
+                    repositories::clone_url(&remote_repo.remote.url, &user_a_repo_dir_copy).await?;
+
+                // User B pushes two versions of a new file
+                let new_file = "new_file.txt";
+                test::run_empty_dir_test_async(|user_b_repo_dir| async move {
+                    let user_b_repo_dir_copy = user_b_repo_dir.join("repo_b");
+                    let user_b_repo =
+                        repositories::clone_url(&remote_repo.remote.url, &user_b_repo_dir_copy)
+                            .await?;
+                    let new_file_path = user_b_repo.path.join(new_file);
+                    test::write_txt_file_to_path(&new_file_path, "first version")?;
+                    repositories::add(&user_b_repo, &new_file_path)?;
+                    repositories::commit(&user_b_repo, "Adding new file")?;
+                    test::write_txt_file_to_path(&new_file_path, "second version")?;
+                    repositories::add(&user_b_repo, &new_file_path)?;
+                    repositories::commit(&user_b_repo, "Changing new file")?;
+                    repositories::push(&user_b_repo).await?;
+
+                    Ok(user_b_repo_dir_copy)
+                })
+                .await?;
+
+                repositories::pull_remote_branch(
+                    &user_a_repo,
+                    &FetchOpts {
+                        blobless: true,
+                        ..FetchOpts::new()
+                    },
+                )
+                .await?;
+
+                // Only the version in the working tree was downloaded
+                let new_file_path = user_a_repo.path.join(new_file);
+                assert_eq!(util::fs::read_from_path(&new_file_path)?, "second version");
+                let first_commit = repositories::revisions::resolve(&user_a_repo, "HEAD~1")?;
+                let first_node =
+                    repositories::entries::get_file(&user_a_repo, &first_commit, new_file)?
+                        .unwrap();
+                let version_store = user_a_repo.version_store()?;
+                assert!(!version_store.version_exists(&first_node.hash().to_string())?);
+
+                // Restoring the first version downloads it
+                repositories::restore(
+                    &user_a_repo,
+                    RestoreOpts::from_path_ref(new_file, &first_commit.id),
+                )?;
+                assert_eq!(util::fs::read_from_path(&new_file_path)?, "first version");
+                assert!(version_store.version_exists(&first_node.hash().to_string())?);
+
+                Ok(user_a_repo_dir_copy)
+            })
+            .await?;
+
+            Ok(remote_repo_copy)
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_pull_full_commit_history_after_shallow_clone() -> Result<(), OxenError> {
         test::run_training_data_repo_test_fully_committed_async(|mut repo| async move {
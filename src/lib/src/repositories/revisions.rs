@@ -1,17 +1,26 @@
 //! Revisions can either be commits by id or head commits on branches by name
+//!
+//! Any revision can also be suffixed with `~N` to walk back N first parents,
+//! ie `HEAD~2` or `main~1`. A bare `HEAD` is the current head commit.
 
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 use crate::core;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
+use crate::model::merkle_tree::node::FileNode;
 use crate::model::{Commit, LocalRepository};
 use crate::repositories;
+use crate::storage::VersionFile;
 
 /// Get a commit object from a commit id or branch name
 /// Returns Ok(None) if the revision does not exist
 pub fn get(repo: &LocalRepository, revision: impl AsRef<str>) -> Result<Option<Commit>, OxenError> {
     let revision = revision.as_ref();
+    if is_relative(revision) {
+        return get_relative(repo, revision);
+    }
+
     if repositories::branches::exists(repo, revision)? {
         log::debug!("revision is a branch: {}", revision);
         let branch = repositories::branches::get_by_name(repo, revision)?;
@@ -25,28 +34,239 @@ pub fn get(repo: &LocalRepository, revision: impl AsRef<str>) -> Result<Option<C
     }
 }
 
-/// Get the version file path from a commit id
+/// Resolve a revision to a commit, trying in order: an exact commit id, a branch name,
+/// then a unique commit id prefix. Errors if nothing matches or the prefix is ambiguous.
+///
+/// Tags are not supported by this repository format yet, so there is no tag lookup.
+pub fn resolve(repo: &LocalRepository, revision: impl AsRef<str>) -> Result<Commit, OxenError> {
+    let revision = revision.as_ref();
+    if revision.is_empty() {
+        return Err(OxenError::local_revision_not_found(revision));
+    }
+
+    if is_relative(revision) {
+        return resolve_relative(repo, revision);
+    }
+
+    if let Some(commit) = repositories::commits::get_by_id(repo, revision)? {
+        log::debug!("resolved revision {} as a commit id", revision);
+        return Ok(commit);
+    }
+
+    if let Some(branch) = repositories::branches::get_by_name(repo, revision)? {
+        log::debug!("resolved revision {} as a branch", revision);
+        return repositories::commits::get_by_id(repo, &branch.commit_id)?
+            .ok_or(OxenError::commit_id_does_not_exist(&branch.commit_id));
+    }
+
+    let mut matches: Vec<Commit> = repositories::commits::list_all(repo)?
+        .into_iter()
+        .filter(|commit| commit.id.starts_with(revision))
+        .collect();
+    match matches.len() {
+        0 => Err(OxenError::local_revision_not_found(revision)),
+        1 => Ok(matches.remove(0)),
+        n => Err(OxenError::basic_str(format!(
+            "Revision `{revision}` is ambiguous, it matches {n} commits. Use a longer commit id."
+        ))),
+    }
+}
+
+/// Whether the revision uses `HEAD` or the `<rev>~N` ancestor syntax
+pub fn is_relative(revision: impl AsRef<str>) -> bool {
+    let revision = revision.as_ref();
+    revision == "HEAD" || revision.contains('~')
+}
+
+/// Split `<rev>~N` into the base revision and the number of parents to walk.
+/// A bare `~` means one parent, like `HEAD~`.
+fn parse_relative(revision: &str) -> Result<(&str, usize), OxenError> {
+    let Some((base, count)) = revision.split_once('~') else {
+        return Ok((revision, 0));
+    };
+    if base.is_empty() {
+        return Err(OxenError::local_revision_not_found(revision));
+    }
+    let count = if count.is_empty() {
+        1
+    } else {
+        count.parse::<usize>().map_err(|_| {
+            OxenError::basic_str(format!(
+                "Invalid revision `{revision}`, expected a number of parents after `~`"
+            ))
+        })?
+    };
+    Ok((base, count))
+}
+
+/// Like `resolve_relative`, but None if the base does not exist or the walk goes past the root.
+/// Only malformed syntax is an error.
+fn get_relative(repo: &LocalRepository, revision: &str) -> Result<Option<Commit>, OxenError> {
+    let (base, count) = parse_relative(revision)?;
+    let base_commit = if base == "HEAD" {
+        repositories::commits::head_commit_maybe(repo)?
+    } else {
+        get(repo, base)?
+    };
+    let Some(mut commit) = base_commit else {
+        return Ok(None);
+    };
+
+    for _ in 0..count {
+        let Some(parent_id) = commit.parent_ids.first() else {
+            return Ok(None);
+        };
+        let Some(parent) = repositories::commits::get_by_id(repo, parent_id)? else {
+            return Ok(None);
+        };
+        commit = parent;
+    }
+    Ok(Some(commit))
+}
+
+fn resolve_relative(repo: &LocalRepository, revision: &str) -> Result<Commit, OxenError> {
+    let (base, count) = parse_relative(revision)?;
+    let mut commit = if base == "HEAD" {
+        repositories::commits::head_commit(repo)?
+    } else {
+        resolve(repo, base)?
+    };
+
+    for walked in 0..count {
+        // Merge commits are walked through their first parent, the branch that was merged into
+        let Some(parent_id) = commit.parent_ids.first() else {
+            return Err(OxenError::basic_str(format!(
+                "Revision `{revision}` goes past the root commit, `{base}` only has {walked} ancestors"
+            )));
+        };
+        commit = repositories::commits::get_by_id(repo, parent_id)?
+            .ok_or(OxenError::commit_id_does_not_exist(parent_id))?;
+    }
+    log::debug!("resolved revision {} to commit {}", revision, commit.id);
+    Ok(commit)
+}
+
+/// Get the version file from a commit id
 pub fn get_version_file(
     repo: &LocalRepository,
     revision: impl AsRef<str>,
     path: impl AsRef<Path>,
-) -> Result<PathBuf, OxenError> {
-    let commit_id = match get(repo, &revision)? {
-        Some(commit) => commit.id,
-        None => return Err(OxenError::commit_id_does_not_exist(revision.as_ref())),
-    };
-
+) -> Result<VersionFile, OxenError> {
+    let commit_id = resolve(repo, revision)?.id;
     get_version_file_from_commit_id(repo, commit_id, path)
 }
 
-/// Get the version file path from a commit id
+/// Get the version file from a commit id, or a relative revision such as `HEAD~1`. Compressed and
+/// chunked versions are unpacked to a temp file that lives as long as the returned `VersionFile`.
 pub fn get_version_file_from_commit_id(
     repo: &LocalRepository,
     commit_id: impl AsRef<str>,
     path: impl AsRef<Path>,
-) -> Result<PathBuf, OxenError> {
+) -> Result<VersionFile, OxenError> {
+    let commit_id = commit_id.as_ref();
+    let commit_id = if is_relative(commit_id) {
+        resolve_relative(repo, commit_id)?.id
+    } else {
+        commit_id.to_string()
+    };
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
         _ => core::v_latest::revisions::get_version_file_from_commit_id(repo, commit_id, path),
     }
 }
+
+/// Get a file on disk with the contents of `file_node`, however its version is stored
+pub fn get_version_file_from_node(
+    repo: &LocalRepository,
+    file_node: &FileNode,
+    path: impl AsRef<Path>,
+) -> Result<VersionFile, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::revisions::get_version_file_from_node(repo, file_node, path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::OxenError;
+    use crate::repositories;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_resolve_commit_id_branch_and_prefix() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            repositories::add(&repo, &hello_file)?;
+            let commit = repositories::commit(&repo, "Adding hello")?;
+            repositories::branches::create_from_head(&repo, "my-branch")?;
+
+            assert_eq!(repositories::revisions::resolve(&repo, &commit.id)?, commit);
+            assert_eq!(
+                repositories::revisions::resolve(&repo, "my-branch")?,
+                commit
+            );
+            assert_eq!(
+                repositories::revisions::resolve(&repo, &commit.id[..8])?,
+                commit
+            );
+            assert!(repositories::revisions::resolve(&repo, "not-a-revision").is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_resolve_head_relative_revisions() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            let mut commits = vec![];
+            for i in 0..3 {
+                util::fs::write_to_path(&hello_file, format!("Hello {i}"))?;
+                repositories::add(&repo, &hello_file)?;
+                commits.push(repositories::commit(&repo, &format!("Commit {i}"))?);
+            }
+
+            assert_eq!(repositories::revisions::resolve(&repo, "HEAD")?, commits[2]);
+            assert_eq!(
+                repositories::revisions::resolve(&repo, "HEAD~")?,
+                commits[1]
+            );
+            assert_eq!(
+                repositories::revisions::resolve(&repo, "HEAD~2")?,
+                commits[0]
+            );
+            assert_eq!(
+                repositories::revisions::get(&repo, "main~1")?,
+                Some(commits[1].clone())
+            );
+            assert_eq!(
+                repositories::revisions::resolve(&repo, format!("{}~1", commits[1].id))?,
+                commits[0]
+            );
+
+            let result = repositories::revisions::resolve(&repo, "HEAD~3");
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("past the root commit"));
+            assert!(repositories::revisions::resolve(&repo, "HEAD~x").is_err());
+
+            // get only errors on malformed revisions
+            assert_eq!(repositories::revisions::get(&repo, "HEAD~3")?, None);
+            assert_eq!(repositories::revisions::get(&repo, "not-a-branch~1")?, None);
+            assert!(repositories::revisions::get(&repo, "HEAD~x").is_err());
+
+            let version_file = repositories::revisions::get_version_file_from_commit_id(
+                &repo,
+                "HEAD~2",
+                "hello.txt",
+            )?;
+            assert_eq!(util::fs::read_from_path(&version_file)?, "Hello 0");
+
+            Ok(())
+        })
+    }
+}
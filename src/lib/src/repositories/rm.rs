@@ -20,7 +20,7 @@ use crate::util;
 pub fn rm(repo: &LocalRepository, opts: &RmOpts) -> Result<(), OxenError> {
     log::debug!("Rm with opts: {opts:?}");
     let path: &Path = opts.path.as_ref();
-    let paths: HashSet<PathBuf> = parse_glob_path(path, repo)?;
+    let paths: HashSet<PathBuf> = parse_glob_path(path, repo, opts.recursive)?;
 
     log::debug!("paths: {paths:?}");
     p_rm(&paths, repo, opts)?;
@@ -41,23 +41,43 @@ fn p_rm(paths: &HashSet<PathBuf>, repo: &LocalRepository, opts: &RmOpts) -> Resu
 
 // TODO: Should removing dirs from staged require -r?
 // Collect paths for removal. Returns error if dir found and -r not set
-fn parse_glob_path(path: &Path, repo: &LocalRepository) -> Result<HashSet<PathBuf>, OxenError> {
+fn parse_glob_path(
+    path: &Path,
+    repo: &LocalRepository,
+    recursive: bool,
+) -> Result<HashSet<PathBuf>, OxenError> {
     let mut paths: HashSet<PathBuf> = HashSet::new();
     log::debug!("Parsing paths: {path:?}");
 
     if let Some(path_str) = path.to_str() {
         if util::fs::is_glob_path(path_str) {
-            // Match against any untracked entries in the current dir
-
-            for entry in glob(path_str)? {
-                paths.insert(entry?.to_path_buf());
+            // Match against any untracked entries in the working directory
+            let full_pattern = repo.path.join(path);
+            if let Some(full_pattern) = full_pattern.to_str() {
+                for entry in glob(full_pattern)? {
+                    paths.insert(util::fs::path_relative_to_dir(entry?, &repo.path)?);
+                }
             }
 
+            // Match against the committed entries, which may already be deleted from disk
             if let Some(commit) = repositories::commits::head_commit_maybe(repo)? {
+                let relative_pattern = util::fs::path_relative_to_dir(path, &repo.path)?;
+                let relative_pattern = relative_pattern.to_string_lossy();
                 let pattern_entries =
-                    repositories::commits::search_entries(repo, &commit, path_str)?;
+                    repositories::commits::search_entries(repo, &commit, &relative_pattern)?;
                 log::debug!("pattern entries: {:?}", pattern_entries);
                 paths.extend(pattern_entries);
+
+                if recursive {
+                    let pattern_dirs =
+                        repositories::commits::search_dirs(repo, &commit, &relative_pattern)?;
+                    log::debug!("pattern dirs: {:?}", pattern_dirs);
+                    paths.extend(pattern_dirs);
+                }
+            }
+
+            if recursive {
+                paths = without_nested_paths(paths);
             }
         } else {
             // Non-glob path
@@ -69,6 +89,20 @@ fn parse_glob_path(path: &Path, repo: &LocalRepository) -> Result<HashSet<PathBu
     Ok(paths)
 }
 
+/// A directory removed with -r takes everything below it, so drop the matches inside of it
+fn without_nested_paths(paths: HashSet<PathBuf>) -> HashSet<PathBuf> {
+    paths
+        .iter()
+        .filter(|path| {
+            !path
+                .ancestors()
+                .skip(1)
+                .any(|ancestor| paths.contains(ancestor))
+        })
+        .cloned()
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -718,6 +752,54 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_rm_recursive_glob_deleted_files() -> Result<(), OxenError> {
+        test::run_empty_data_repo_test_no_commits_async(|repo| async move {
+            let data_dir = repo.path.join("data");
+            for dir in ["a", "b/c"] {
+                let dir = data_dir.join(dir);
+                util::fs::create_dir_all(&dir)?;
+                util::fs::write_to_path(dir.join("keep.txt"), "keep")?;
+                util::fs::write_to_path(dir.join("scratch.tmp"), "scratch")?;
+            }
+            util::fs::write_to_path(data_dir.join("scratch.tmp"), "scratch")?;
+            repositories::add(&repo, &data_dir)?;
+            repositories::commit(&repo, "Adding data")?;
+
+            // One of the tmp files is already gone from disk
+            std::fs::remove_file(data_dir.join("b").join("c").join("scratch.tmp"))?;
+
+            // Absolute patterns, as passed by the CLI, match committed entries too
+            let rm_opts = RmOpts {
+                path: repo.path.join("data").join("**").join("*.tmp"),
+                recursive: false,
+                staged: false,
+            };
+            repositories::rm(&repo, &rm_opts)?;
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 3);
+            assert!(data_dir.join("a").join("keep.txt").exists());
+            assert!(!data_dir.join("a").join("scratch.tmp").exists());
+
+            // Directory globs need -r
+            let mut rm_opts = RmOpts {
+                path: PathBuf::from("data/b*"),
+                recursive: false,
+                staged: false,
+            };
+            assert!(repositories::rm(&repo, &rm_opts).is_err());
+
+            rm_opts.recursive = true;
+            repositories::rm(&repo, &rm_opts)?;
+            assert!(!data_dir.join("b").exists());
+            assert!(data_dir.join("a").join("keep.txt").exists());
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_rm_staged_file() -> Result<(), OxenError> {
         test::run_select_data_repo_test_no_commits_async("README", |repo| async move {
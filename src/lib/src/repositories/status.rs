@@ -4,13 +4,15 @@
 //! and which files are staged for commit.
 //!
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::core;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
-use crate::model::staged_data::StagedDataOpts;
+use crate::model::diff::ChangeType;
+use crate::model::staged_data::{Inconsistency, StagedDataOpts};
 use crate::model::{LocalRepository, StagedData};
+use crate::opts::CleanOpts;
 
 /// # oxen status
 ///
@@ -84,6 +86,18 @@ pub fn status_from_opts(
     }
 }
 
+/// What is staged below `opts.paths`, read from the staged db only. This is fast in huge working
+/// trees because nothing on disk is scanned, so untracked, modified and removed files are not listed.
+pub fn status_staged(
+    repo: &LocalRepository,
+    opts: &StagedDataOpts,
+) -> Result<StagedData, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::status::status_staged(repo, opts),
+    }
+}
+
 pub fn status_from_dir(
     repo: &LocalRepository,
     dir: impl AsRef<Path>,
@@ -94,20 +108,251 @@ pub fn status_from_dir(
     }
 }
 
+/// # oxen clean
+///
+/// Remove the untracked files from the working directory, see `CleanOpts` for which ones.
+/// Returns the removed paths relative to the repository, or the ones that would be with `dry_run`.
+pub fn clean(repo: &LocalRepository, opts: &CleanOpts) -> Result<Vec<PathBuf>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::status::clean(repo, opts),
+    }
+}
+
+/// Verify that every staged file and dir has all its ancestor dir entries staged, and flag staged
+/// dirs with nothing staged below them. Commit builds the tree from these entries, so either
+/// would leave files out of it or add empty dirs.
+pub fn check_staged_consistency(repo: &LocalRepository) -> Result<Vec<Inconsistency>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::status::check_staged_consistency(repo),
+    }
+}
+
+/// Stage the missing ancestor dirs and remove the orphan dirs found by `check_staged_consistency`,
+/// returning what was fixed
+pub fn fix_staged_consistency(repo: &LocalRepository) -> Result<Vec<Inconsistency>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::status::fix_staged_consistency(repo),
+    }
+}
+
+/// Compare the working directory directly against the head commit, without looking at
+/// or writing to the staged db. Returns every path classified as unchanged, modified,
+/// removed (deleted from disk), or added (not in the head commit), sorted by path.
+pub fn diff_working_tree(repo: &LocalRepository) -> Result<Vec<(PathBuf, ChangeType)>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::status::diff_working_tree(repo),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::constants::DEFAULT_BRANCH_NAME;
+    use crate::constants::STAGED_DIR;
+    use crate::core;
+    use crate::core::db;
+    use crate::core::status_index;
     use crate::error::OxenError;
-    use crate::model::staged_data::StagedDataOpts;
+    use crate::model::diff::ChangeType;
+    use crate::model::staged_data::{Inconsistency, StagedDataOpts};
     use crate::model::StagedEntryStatus;
+    use crate::opts::CleanOpts;
     use crate::opts::RestoreOpts;
     use crate::opts::RmOpts;
     use crate::repositories;
     use crate::test;
     use crate::util;
 
+    use rocksdb::{DBWithThreadMode, MultiThreaded};
     use std::collections::HashSet;
     use std::path::Path;
     use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_clean_removes_untracked_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(repo.path.join(".oxenignore"), "*.log\n")?;
+            let data_dir = repo.path.join("data");
+            util::fs::write_to_path(data_dir.join("tracked.txt"), "tracked")?;
+            repositories::add(&repo, &repo.path)?;
+            repositories::commit(&repo, "Adding data")?;
+
+            util::fs::write_to_path(data_dir.join("untracked.txt"), "untracked")?;
+            util::fs::write_to_path(data_dir.join("staged.txt"), "staged")?;
+            repositories::add(&repo, data_dir.join("staged.txt"))?;
+            util::fs::write_to_path(repo.path.join("scratch").join("notes.txt"), "notes")?;
+            util::fs::write_to_path(repo.path.join("run.log"), "log")?;
+
+            // A dry run only lists, and untracked dirs need -d
+            let opts = CleanOpts {
+                dry_run: true,
+                ..Default::default()
+            };
+            let paths = repositories::status::clean(&repo, &opts)?;
+            assert_eq!(paths, vec![PathBuf::from("data").join("untracked.txt")]);
+            assert!(data_dir.join("untracked.txt").exists());
+
+            let opts = CleanOpts {
+                directories: true,
+                ..Default::default()
+            };
+            let paths = repositories::status::clean(&repo, &opts)?;
+            assert_eq!(
+                paths,
+                vec![
+                    PathBuf::from("data").join("untracked.txt"),
+                    PathBuf::from("scratch")
+                ]
+            );
+            assert!(!data_dir.join("untracked.txt").exists());
+            assert!(!repo.path.join("scratch").exists());
+            assert!(data_dir.join("tracked.txt").exists());
+            assert!(data_dir.join("staged.txt").exists());
+            assert!(repo.path.join("run.log").exists());
+
+            // -x removes the ignored files too
+            let opts = CleanOpts {
+                ignored: true,
+                ..Default::default()
+            };
+            let paths = repositories::status::clean(&repo, &opts)?;
+            assert_eq!(paths, vec![PathBuf::from("run.log")]);
+            assert!(!repo.path.join("run.log").exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_clean_directories_keeps_ignored_files_in_untracked_dirs() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            util::fs::write_to_path(repo.path.join(".oxenignore"), "*.log\n")?;
+            repositories::add(&repo, repo.path.join(".oxenignore"))?;
+            repositories::commit(&repo, "Adding .oxenignore")?;
+
+            let scratch_dir = repo.path.join("scratch");
+            util::fs::write_to_path(scratch_dir.join("notes.txt"), "notes")?;
+            util::fs::write_to_path(scratch_dir.join("run.log"), "log")?;
+            util::fs::write_to_path(scratch_dir.join("tmp").join("a.txt"), "a")?;
+
+            let opts = CleanOpts {
+                directories: true,
+                ..Default::default()
+            };
+            let paths = repositories::status::clean(&repo, &opts)?;
+            assert_eq!(
+                paths,
+                vec![
+                    PathBuf::from("scratch").join("notes.txt"),
+                    PathBuf::from("scratch").join("tmp")
+                ]
+            );
+            assert!(!scratch_dir.join("notes.txt").exists());
+            assert!(!scratch_dir.join("tmp").exists());
+            assert!(scratch_dir.join("run.log").exists());
+
+            // -x takes the whole dir
+            let opts = CleanOpts {
+                directories: true,
+                ignored: true,
+                ..Default::default()
+            };
+            let paths = repositories::status::clean(&repo, &opts)?;
+            assert_eq!(paths, vec![PathBuf::from("scratch")]);
+            assert!(!scratch_dir.exists());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_diff_working_tree() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let keep = repo.path.join("keep.txt");
+            let modify = repo.path.join("dir").join("modify.txt");
+            let delete = repo.path.join("dir").join("delete.txt");
+            util::fs::write_to_path(&keep, "keep")?;
+            util::fs::write_to_path(&modify, "modify")?;
+            util::fs::write_to_path(&delete, "delete")?;
+            repositories::add(&repo, &repo.path)?;
+            repositories::commit(&repo, "Adding files")?;
+
+            util::fs::write_to_path(&modify, "modified")?;
+            util::fs::remove_file(&delete)?;
+            util::fs::write_to_path(repo.path.join("new.txt"), "new")?;
+
+            let changes = repositories::status::diff_working_tree(&repo)?;
+            assert_eq!(
+                changes,
+                vec![
+                    (PathBuf::from("dir/delete.txt"), ChangeType::Removed),
+                    (PathBuf::from("dir/modify.txt"), ChangeType::Modified),
+                    (PathBuf::from("keep.txt"), ChangeType::Unchanged),
+                    (PathBuf::from("new.txt"), ChangeType::Added),
+                ]
+            );
+
+            // Nothing was staged along the way
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 0);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_check_and_fix_staged_consistency() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let nested = repo.path.join("data").join("train").join("a.txt");
+            util::fs::write_to_path(&nested, "a")?;
+            repositories::add(&repo, &nested)?;
+            // A staged empty dir is not an orphan
+            let empty_dir = repo.path.join("empty_dir");
+            util::fs::create_dir_all(&empty_dir)?;
+            repositories::add(&repo, &empty_dir)?;
+            assert!(repositories::status::check_staged_consistency(&repo)?.is_empty());
+
+            // Drop an ancestor and stage a dir with nothing in it, like a partial write would
+            {
+                let db_path = util::fs::oxen_hidden_dir(&repo.path).join(STAGED_DIR);
+                let opts = db::key_val::opts::default();
+                let staged_db: DBWithThreadMode<MultiThreaded> =
+                    DBWithThreadMode::open(&opts, dunce::simplified(&db_path))?;
+                staged_db.delete("data")?;
+                let seen_dirs = Arc::new(Mutex::new(HashSet::new()));
+                core::v_latest::add::add_dir_to_staged_db(&staged_db, "empty", &seen_dirs)?;
+            }
+
+            let inconsistencies = repositories::status::check_staged_consistency(&repo)?;
+            assert_eq!(
+                inconsistencies,
+                vec![
+                    Inconsistency::MissingAncestor {
+                        path: PathBuf::from("data/train"),
+                        ancestor: PathBuf::from("data"),
+                    },
+                    Inconsistency::OrphanDir(PathBuf::from("empty")),
+                ]
+            );
+
+            let fixed = repositories::status::fix_staged_consistency(&repo)?;
+            assert_eq!(fixed, inconsistencies);
+            assert!(repositories::status::check_staged_consistency(&repo)?.is_empty());
+
+            let status = repositories::status(&repo)?;
+            assert_eq!(status.staged_files.len(), 1);
+            assert!(status
+                .staged_dirs
+                .paths
+                .contains_key(Path::new("empty_dir")));
+
+            Ok(())
+        })
+    }
 
     #[test]
     fn test_command_status_empty() -> Result<(), OxenError> {
@@ -665,6 +910,155 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_status_staged_skips_working_tree() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let staged_file = repo.path.join("staged.txt");
+            util::fs::write_to_path(&staged_file, "staged")?;
+            repositories::add(&repo, &staged_file)?;
+
+            // Neither of these are staged, so they should not show up
+            util::fs::write_to_path(repo.path.join("untracked.txt"), "untracked")?;
+            util::fs::remove_file(repo.path.join("README.md"))?;
+
+            let opts = StagedDataOpts::from_paths(&[repo.path.clone()]);
+            let status = repositories::status::status_staged(&repo, &opts)?;
+            assert_eq!(status.staged_files.len(), 1);
+            assert_eq!(
+                status.staged_files[Path::new("staged.txt")].status,
+                StagedEntryStatus::Added
+            );
+            assert!(status.untracked_files.is_empty());
+            assert!(status.removed_files.is_empty());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_status_fast_rescans_touched_dirs() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let opts = StagedDataOpts {
+                paths: vec![repo.path.clone()],
+                use_mtime_index: true,
+                ..Default::default()
+            };
+
+            // The first run scans everything and remembers the clean dirs
+            let status = repositories::status::status_from_opts(&repo, &opts)?;
+            assert!(status.is_clean());
+            let head = repositories::commits::head_commit(&repo)?;
+            let index = status_index::read(&repo, &head.id).expect("status index");
+            assert!(index
+                .dirs
+                .contains_key(Path::new("annotations").join("train").as_path()));
+
+            // Touch one dir by adding a file, and another by removing one
+            let new_file = repo.path.join("annotations").join("train").join("new.txt");
+            util::fs::write_to_path(&new_file, "new")?;
+            util::fs::remove_file(repo.path.join("train").join("dog_1.jpg"))?;
+
+            let status = repositories::status::status_from_opts(&repo, &opts)?;
+            assert_eq!(
+                status.untracked_files,
+                vec![Path::new("annotations").join("train").join("new.txt")]
+            );
+            assert!(status
+                .removed_files
+                .contains(&Path::new("train").join("dog_1.jpg")));
+
+            // Untouched dirs stay in the index, the dirty ones are dropped until they are clean again
+            let index = status_index::read(&repo, &head.id).expect("status index");
+            assert!(index.dirs.contains_key(Path::new("test").as_path()));
+            assert!(!index.dirs.contains_key(Path::new("train").as_path()));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_status_fast_sees_in_place_edits() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let opts = StagedDataOpts {
+                paths: vec![repo.path.clone()],
+                use_mtime_index: true,
+                ..Default::default()
+            };
+            let status = repositories::status::status_from_opts(&repo, &opts)?;
+            assert!(status.is_clean());
+
+            // Rewriting a file in place leaves its dir's mtime alone
+            let one_shot_path = Path::new("annotations").join("train").join("one_shot.csv");
+            util::fs::write_to_path(repo.path.join(&one_shot_path), "file,label\nedited,1\n")?;
+
+            let status = repositories::status::status_from_opts(&repo, &opts)?;
+            assert_eq!(
+                status.modified_files,
+                HashSet::from([one_shot_path.clone()])
+            );
+
+            // Committing the edit makes the dir clean against the new commit
+            repositories::add(&repo, repo.path.join(&one_shot_path))?;
+            let commit = repositories::commit(&repo, "Edit one shot")?;
+            let status = repositories::status::status_from_opts(&repo, &opts)?;
+            assert!(status.is_clean());
+            let index = status_index::read(&repo, &commit.id).expect("status index");
+            assert!(index
+                .dirs
+                .contains_key(Path::new("annotations").join("train").as_path()));
+
+            Ok(())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_status_fast_index_is_carried_over_checkout() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed_async(|repo| async move {
+            let opts = StagedDataOpts {
+                paths: vec![repo.path.clone()],
+                use_mtime_index: true,
+                ..Default::default()
+            };
+            let main_commit = repositories::commits::head_commit(&repo)?;
+
+            // Edit a file on a branch
+            let one_shot_path = Path::new("annotations").join("train").join("one_shot.csv");
+            repositories::branches::create_checkout(&repo, "edit")?;
+            util::fs::write_to_path(repo.path.join(&one_shot_path), "file,label\nedited,1\n")?;
+            repositories::add(&repo, repo.path.join(&one_shot_path))?;
+            let edit_commit = repositories::commit(&repo, "Edit one shot")?;
+            let status = repositories::status::status_from_opts(&repo, &opts)?;
+            assert!(status.is_clean());
+
+            // Checkouts rewrite the file in place and carry the index over with its new stamp,
+            // both for a branch and for a commit id
+            for (revision, commit_id) in [
+                (DEFAULT_BRANCH_NAME, &main_commit.id),
+                (edit_commit.id.as_str(), &edit_commit.id),
+                (DEFAULT_BRANCH_NAME, &main_commit.id),
+            ] {
+                repositories::checkout::checkout(&repo, revision).await?;
+                let index = status_index::read(&repo, commit_id).expect("status index");
+                assert!(index
+                    .dirs
+                    .contains_key(Path::new("annotations").join("train").as_path()));
+                let status = repositories::status::status_from_opts(&repo, &opts)?;
+                assert!(status.is_clean());
+            }
+
+            // And an edit after the checkout is still seen
+            util::fs::write_to_path(repo.path.join(&one_shot_path), "file,label\nagain,2\n")?;
+            let status = repositories::status::status_from_opts(&repo, &opts)?;
+            assert_eq!(
+                status.modified_files,
+                HashSet::from([one_shot_path.clone()])
+            );
+
+            Ok(())
+        })
+        .await
+    }
+
     #[test]
     fn test_status_remove_file_in_subdirectory() -> Result<(), OxenError> {
         test::run_training_data_repo_test_fully_committed(|repo| {
@@ -1464,6 +1464,37 @@ mod tests {
         .await
     }
 
+    #[test]
+    fn test_get_by_paths_matches_get_by_path() -> Result<(), OxenError> {
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let tree = repositories::tree::get_root_with_children(&repo, &commit)?.unwrap();
+
+            let paths = vec![
+                PathBuf::from("README.md"),
+                PathBuf::from("annotations").join("train"),
+                PathBuf::from("annotations")
+                    .join("train")
+                    .join("bounding_box.csv"),
+                PathBuf::from("train").join("dog_1.jpg"),
+                PathBuf::from("train").join("not_a_file.jpg"),
+            ];
+            let found = tree.get_by_paths(&paths)?;
+
+            for path in &paths {
+                let expected = tree.get_by_path(path)?;
+                assert_eq!(
+                    found.get(path).map(|node| node.hash),
+                    expected.map(|node| node.hash),
+                    "mismatch for {path:?}"
+                );
+            }
+            assert_eq!(found.len(), 4);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_get_node_hashes_between_commits() -> Result<(), OxenError> {
         test::run_local_repo_training_data_committed(|repo| {
@@ -547,10 +547,85 @@ mod tests {
     use super::*;
     use crate::api;
     use crate::constants::DEFAULT_BRANCH_NAME;
+    use crate::model::metadata::generic_metadata::GenericMetadata;
+    use crate::model::EntryDataType;
+    use crate::opts::AddOpts;
     use crate::repositories;
     use crate::test;
     use crate::util;
 
+    #[test]
+    fn test_workspace_upload_extensionless_csv_is_tabular() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let hello_file = repo.path.join("hello.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            repositories::add(&repo, &hello_file)?;
+            let commit = repositories::commit(&repo, "Adding hello")?;
+
+            let temp_workspace = create_temporary(&repo, &commit)?;
+            let labels_file = temp_workspace.dir().join("labels");
+            util::fs::write_to_path(&labels_file, "file,label\na.jpg,cat\nb.jpg,dog\n")?;
+            repositories::workspaces::files::add(&temp_workspace, &labels_file)?;
+            let commit = repositories::workspaces::commit(
+                &temp_workspace,
+                &NewCommitBody {
+                    message: "Uploading labels".to_string(),
+                    author: "Bessie".to_string(),
+                    email: "bessie@oxen.ai".to_string(),
+                },
+                DEFAULT_BRANCH_NAME,
+            )?;
+
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("labels"))?.unwrap();
+            assert_eq!(file_node.data_type(), &EntryDataType::Tabular);
+            match file_node.metadata() {
+                Some(GenericMetadata::MetadataTabular(metadata)) => {
+                    assert_eq!(metadata.tabular.width, 2);
+                    assert_eq!(metadata.tabular.height, 2);
+                }
+                _ => panic!("Expected tabular metadata"),
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_workspace_upload_keeps_executable_bit() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test(|repo| {
+            let script_path = repo.path.join("run.sh");
+            util::fs::write_to_path(&script_path, "echo hello\n")?;
+            let opts = AddOpts {
+                paths: vec![script_path],
+                chmod: Some(true),
+                ..Default::default()
+            };
+            repositories::add::add_with_opts(&repo, &opts)?;
+            let commit = repositories::commit(&repo, "Adding executable script")?;
+
+            let temp_workspace = create_temporary(&repo, &commit)?;
+            let workspace_script = temp_workspace.dir().join("run.sh");
+            util::fs::write_to_path(&workspace_script, "echo goodbye\n")?;
+            repositories::workspaces::files::add(&temp_workspace, &workspace_script)?;
+            let commit = repositories::workspaces::commit(
+                &temp_workspace,
+                &NewCommitBody {
+                    message: "Updating script".to_string(),
+                    author: "Bessie".to_string(),
+                    email: "bessie@oxen.ai".to_string(),
+                },
+                DEFAULT_BRANCH_NAME,
+            )?;
+
+            let file_node =
+                repositories::tree::get_file_by_path(&repo, &commit, Path::new("run.sh"))?.unwrap();
+            assert!(file_node.is_executable());
+
+            Ok(())
+        })
+    }
+
     #[tokio::test]
     async fn test_can_commit_different_files_workspaces_without_merge_conflicts(
     ) -> Result<(), OxenError> {
@@ -764,6 +839,45 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_workspace_rm_file_commits_removal() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|repo| async move {
+            let hello_file = repo.path.join("hello.txt");
+            let goodbye_file = repo.path.join("goodbye.txt");
+            util::fs::write_to_path(&hello_file, "Hello")?;
+            util::fs::write_to_path(&goodbye_file, "Goodbye")?;
+            repositories::add(&repo, &hello_file)?;
+            repositories::add(&repo, &goodbye_file)?;
+            let commit = repositories::commit(&repo, "Adding hello and goodbye files")?;
+
+            {
+                let temp_workspace = create_temporary(&repo, &commit)?;
+
+                // Removing a path that is not in the commit is an error
+                let result = repositories::workspaces::files::rm(&temp_workspace, "missing.txt");
+                assert!(result.is_err());
+
+                repositories::workspaces::files::rm(&temp_workspace, "hello.txt")?;
+                repositories::workspaces::commit(
+                    &temp_workspace,
+                    &NewCommitBody {
+                        message: "Removing hello file".to_string(),
+                        author: "Bessie".to_string(),
+                        email: "bessie@oxen.ai".to_string(),
+                    },
+                    DEFAULT_BRANCH_NAME,
+                )?;
+            }
+
+            let head = repositories::commits::head_commit(&repo)?;
+            assert!(repositories::tree::get_file_by_path(&repo, &head, "hello.txt")?.is_none());
+            assert!(repositories::tree::get_file_by_path(&repo, &head, "goodbye.txt")?.is_some());
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_concurrent_workspace_commits() -> Result<(), OxenError> {
         test::run_one_commit_sync_repo_test(|repo, remote_repo| async move {
@@ -2,16 +2,18 @@ use polars::frame::DataFrame;
 
 use sql_query_builder::Select;
 
-use crate::constants::{MODS_DIR, OXEN_HIDDEN_DIR};
+use crate::constants::{DEFAULT_PAGE_NUM, DEFAULT_PAGE_SIZE, MODS_DIR, OXEN_HIDDEN_DIR};
 use crate::constants::{OXEN_COLS, TABLE_NAME};
 use crate::core;
 use crate::core::db::data_frames::workspace_df_db::select_cols_from_schema;
 use crate::core::db::data_frames::{df_db, workspace_df_db};
-use crate::core::df::sql;
+use crate::core::df::{sql, tabular};
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
-use crate::model::{Commit, LocalRepository, Workspace};
-use crate::opts::DFOpts;
+use crate::model::data_frame::DataFrameSchemaSize;
+use crate::model::{Commit, DataFrameSize, LocalRepository, NewCommitBody, Workspace};
+use crate::opts::{DFOpts, PaginateOpts};
+use crate::view::data_frames::IndexedDataFrame;
 use crate::{repositories, util};
 
 use crate::model::diff::tabular_diff::{
@@ -20,6 +22,7 @@ use crate::model::diff::tabular_diff::{
 };
 use crate::model::diff::{AddRemoveModifyCounts, DiffResult, TabularDiff};
 
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 pub mod columns;
@@ -45,6 +48,36 @@ pub fn is_indexed(workspace: &Workspace, path: impl AsRef<Path>) -> Result<bool,
     Ok(table_exists)
 }
 
+/// Every tabular file in the workspace commit that is indexed for editing, with its schema and row count
+pub fn list_indexed(workspace: &Workspace) -> Result<Vec<IndexedDataFrame>, OxenError> {
+    // The file nodes are named with their full path
+    let mut paths: Vec<String> =
+        repositories::tree::list_tabular_files_in_repo(&workspace.base_repo, &workspace.commit)?
+            .into_iter()
+            .map(|node| node.name().to_string())
+            .collect();
+    paths.sort();
+
+    let mut data_frames = Vec::new();
+    for path in paths {
+        // Check the db exists first, opening a connection would create it
+        let db_path = duckdb_path(workspace, &path);
+        if !db_path.exists() {
+            continue;
+        }
+        let conn = df_db::get_connection(&db_path)?;
+        if !df_db::table_exists(&conn, TABLE_NAME)? {
+            continue;
+        }
+        data_frames.push(IndexedDataFrame {
+            path,
+            schema: workspace_df_db::schema_without_oxen_cols(&conn, TABLE_NAME)?,
+            num_rows: df_db::count(&conn, TABLE_NAME)?,
+        });
+    }
+    Ok(data_frames)
+}
+
 pub fn is_queryable_data_frame_indexed(
     repo: &LocalRepository,
     path: impl AsRef<Path>,
@@ -120,6 +153,130 @@ pub fn restore(
     Ok(())
 }
 
+/// Discard every staged row and column change to the data frame and reindex it from the committed version.
+/// The row key is kept so clients can keep addressing rows the same way.
+pub fn reset(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+) -> Result<DataFrameSchemaSize, OxenError> {
+    let path = path.as_ref();
+    if is_indexed(workspace, path)? {
+        unindex(workspace, path)?;
+    }
+
+    for changes_path in [
+        row_changes_path(workspace, path),
+        row_ops_path(workspace, path),
+        row_history_path(workspace, path),
+        column_changes_path(workspace, path),
+    ] {
+        if changes_path.exists() {
+            util::fs::remove_dir_all(&changes_path)?;
+        }
+    }
+
+    index(&workspace.base_repo, workspace, path)?;
+
+    // The data frame matches the commit again, so it is no longer staged
+    core::v_latest::rm::remove_staged_recursively(
+        &workspace.workspace_repo,
+        &HashSet::from([path.to_path_buf()]),
+    )?;
+
+    schema_size(workspace, path)
+}
+
+/// The schema and size of the indexed data frame, with its current edits
+pub fn schema_size(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+) -> Result<DataFrameSchemaSize, OxenError> {
+    let conn = df_db::get_connection(duckdb_path(workspace, path))?;
+    let schema = workspace_df_db::schema_without_oxen_cols(&conn, TABLE_NAME)?;
+    let size = DataFrameSize {
+        height: df_db::count(&conn, TABLE_NAME)?,
+        width: schema.fields.len(),
+    };
+    Ok(DataFrameSchemaSize::from_df_size(&size, &schema))
+}
+
+/// Commit the row and column edits to the data frame onto `branch_name`.
+/// The edited frame is exported on commit, anything else staged in the workspace stays staged.
+pub fn commit(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    new_commit: &NewCommitBody,
+    branch_name: impl AsRef<str>,
+) -> Result<Commit, OxenError> {
+    let path = path.as_ref();
+    if !is_indexed(workspace, path)? {
+        return Err(OxenError::resource_not_found(format!(
+            "Data frame {path:?} is not indexed in workspace {}",
+            workspace.id
+        )));
+    }
+
+    if !repositories::workspaces::files::exists(workspace, path)? {
+        return Err(OxenError::basic_str(format!(
+            "No changes to commit for data frame {path:?}"
+        )));
+    }
+
+    match workspace.workspace_repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::workspaces::commit::commit_path(
+            workspace,
+            path,
+            new_commit,
+            branch_name,
+        ),
+    }
+}
+
+/// Address rows by a composite key (ie `image_id` and `bbox_id`) instead of their `_oxen_id`
+pub fn set_row_key(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    columns: &[String],
+) -> Result<(), OxenError> {
+    let path = path.as_ref();
+    if columns.is_empty() {
+        return Err(OxenError::basic_str(
+            "Row key must have at least one column",
+        ));
+    }
+
+    let db_path = duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+    let schema = df_db::get_schema(&conn, TABLE_NAME)?;
+    for column in columns {
+        if !schema.fields.iter().any(|field| &field.name == column) {
+            return Err(OxenError::basic_str(format!(
+                "Row key column `{}` not found in {:?}",
+                column, path
+            )));
+        }
+    }
+
+    let json = serde_json::to_string(columns)?;
+    util::fs::write_to_path(row_key_path(workspace, path), json)?;
+    Ok(())
+}
+
+/// The composite key columns for the data frame, if one was set with `set_row_key`
+pub fn get_row_key(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+) -> Result<Option<Vec<String>>, OxenError> {
+    let key_path = row_key_path(workspace, path);
+    if !key_path.exists() {
+        return Ok(None);
+    }
+    let json = util::fs::read_from_path(&key_path)?;
+    let columns: Vec<String> = serde_json::from_str(&json)?;
+    Ok(Some(columns))
+}
+
 pub fn count(workspace: &Workspace, path: impl AsRef<Path>) -> Result<usize, OxenError> {
     let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
     let conn = df_db::get_connection(db_path)?;
@@ -159,6 +316,82 @@ pub fn query(
     Ok(df)
 }
 
+/// Run the sql query or filter in `opts` over the whole data frame, instead of over a page of it.
+/// Returns the page of the derived view that was asked for, and the total number of rows in the view.
+pub fn query_derived(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    opts: &DFOpts,
+) -> Result<(DataFrame, usize), OxenError> {
+    let path = path.as_ref();
+    // Nearest neighbor order comes from the embeddings query, so those are still filtered in memory
+    if opts.get_sort_by_embedding_query().is_some() {
+        return query_derived_in_memory(workspace, path, opts);
+    }
+
+    let db_path = duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+    let base_sql = match &opts.sql {
+        Some(sql) => sql.clone(),
+        None => {
+            let schema = df_db::get_schema(&conn, TABLE_NAME)?;
+            let col_names = select_cols_from_schema(&schema)?;
+            Select::new()
+                .select(&col_names)
+                .from(TABLE_NAME)
+                .as_string()
+        }
+    };
+    // The view keeps the oxen columns a page needs, and is filtered by duckdb before it is counted
+    let mut sql = format!(
+        "SELECT * FROM ({}) AS derived",
+        df_db::prepare_sql(&conn, &base_sql, None)?
+    );
+    if let Some(filter) = opts.get_filter()? {
+        sql.push_str(&format!(" WHERE {}", filter.to_sql()));
+    }
+
+    let count_sql = format!("SELECT count(*) FROM ({}) AS filtered", sql);
+    let total: usize = conn.query_row(&count_sql, [], |row| row.get(0))?;
+
+    if opts.sql.is_none() {
+        if let Some(sort_by) = &opts.sort_by {
+            sql.push_str(&format!(" ORDER BY \"{}\"", sort_by));
+        }
+    }
+    let page_num = opts.page.unwrap_or(DEFAULT_PAGE_NUM).max(1);
+    let page_size = opts.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    sql.push_str(&format!(
+        " LIMIT {} OFFSET {}",
+        page_size,
+        (page_num - 1) * page_size
+    ));
+    let df = df_db::select_raw(&conn, &sql)?;
+    Ok((df, total))
+}
+
+fn query_derived_in_memory(
+    workspace: &Workspace,
+    path: &Path,
+    opts: &DFOpts,
+) -> Result<(DataFrame, usize), OxenError> {
+    let mut full_opts = opts.clone();
+    full_opts.page = None;
+    full_opts.page_size = None;
+    let df = query(workspace, path, &full_opts)?;
+
+    let mut filter_opts = DFOpts::empty();
+    filter_opts.filter = opts.filter.clone();
+    let df = tabular::transform(df, filter_opts)?;
+    let total = df.height();
+
+    let page_opts = PaginateOpts {
+        page_num: opts.page.unwrap_or(DEFAULT_PAGE_NUM),
+        page_size: opts.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+    };
+    Ok((tabular::paginate_df(df, &page_opts)?, total))
+}
+
 pub fn export(
     workspace: &Workspace,
     path: impl AsRef<Path>,
@@ -302,6 +535,41 @@ pub fn row_changes_path(workspace: &Workspace, path: impl AsRef<Path>) -> PathBu
         .join("row_changes")
 }
 
+/// The ordered log of row operations used by `rows::undo`
+pub fn row_ops_path(workspace: &Workspace, path: impl AsRef<Path>) -> PathBuf {
+    let path_hash = util::hasher::hash_str(path.as_ref().to_string_lossy());
+    workspace
+        .dir()
+        .join(OXEN_HIDDEN_DIR)
+        .join(MODS_DIR)
+        .join("duckdb")
+        .join(path_hash)
+        .join("row_ops")
+}
+
+/// Every edit made to each row, used by `rows::get_row_history`
+pub fn row_history_path(workspace: &Workspace, path: impl AsRef<Path>) -> PathBuf {
+    let path_hash = util::hasher::hash_str(path.as_ref().to_string_lossy());
+    workspace
+        .dir()
+        .join(OXEN_HIDDEN_DIR)
+        .join(MODS_DIR)
+        .join("duckdb")
+        .join(path_hash)
+        .join("row_history")
+}
+
+pub fn row_key_path(workspace: &Workspace, path: impl AsRef<Path>) -> PathBuf {
+    let path_hash = util::hasher::hash_str(path.as_ref().to_string_lossy());
+    workspace
+        .dir()
+        .join(OXEN_HIDDEN_DIR)
+        .join(MODS_DIR)
+        .join("duckdb")
+        .join(path_hash)
+        .join("row_key.json")
+}
+
 // Add this function after the existing imports
 fn add_exclude_to_sql(sql: &str) -> Result<String, OxenError> {
     // Create the EXCLUDE clause
@@ -354,7 +622,7 @@ mod tests {
 
     use super::*;
     use crate::config::UserConfig;
-    use crate::constants::{DEFAULT_BRANCH_NAME, OXEN_ID_COL};
+    use crate::constants::{DEFAULT_BRANCH_NAME, DEFAULT_ROW_CHANGE_AUTHOR, OXEN_ID_COL};
     use crate::core::df;
     use crate::error::OxenError;
     use crate::model::diff::DiffResult;
@@ -362,6 +630,7 @@ mod tests {
     use crate::opts::DFOpts;
     use crate::repositories::workspaces;
     use crate::test;
+    use crate::view::JsonDataFrameView;
     use crate::{repositories, util};
 
     #[test]
@@ -577,88 +846,191 @@ mod tests {
     }
 
     #[test]
-    fn test_delete_committed_row() -> Result<(), OxenError> {
+    fn test_composite_row_key() -> Result<(), OxenError> {
         // Skip duckdb if on windows
         if std::env::consts::OS == "windows" {
             return Ok(());
         }
 
         test::run_training_data_repo_test_fully_committed(|repo| {
-            let branch_name = "test-append";
-            let branch = repositories::branches::create_checkout(&repo, branch_name)?;
-            let commit = repositories::commits::get_by_id(&repo, &branch.commit_id)?.unwrap();
+            let commit = repositories::commits::head_commit(&repo)?;
             let workspace_id = UserConfig::identifier()?;
             let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
-            let file_path = Path::new("annotations")
-                .join("train")
-                .join("bounding_box.csv");
-
-            // Index the dataset
+            let file_path = test::test_bounding_box_csv();
             workspaces::data_frames::index(&repo, &workspace, &file_path)?;
 
-            // Preview the dataset to grab some ids
-            let mut page_opts = DFOpts::empty();
-            page_opts.page = Some(0);
-            page_opts.page_size = Some(10);
+            // Unknown columns are rejected
+            let result =
+                workspaces::data_frames::set_row_key(&workspace, &file_path, &["nope".to_string()]);
+            assert!(result.is_err());
 
-            let staged_df = workspaces::data_frames::query(&workspace, &file_path, &page_opts)?;
+            let key = vec!["file".to_string(), "min_x".to_string()];
+            workspaces::data_frames::set_row_key(&workspace, &file_path, &key)?;
+            assert_eq!(
+                workspaces::data_frames::get_row_key(&workspace, &file_path)?,
+                Some(key)
+            );
 
-            let id_to_delete = staged_df.column(OXEN_ID_COL)?.get(0)?.to_string();
-            let id_to_delete = id_to_delete.replace('"', "");
+            // The file names contain a '/' so they have to be JSON encoded
+            let row_key = r#"["train/dog_1.jpg", 101.5]"#;
+            let row = workspaces::data_frames::rows::get_by_id(&workspace, &file_path, row_key)?;
+            assert_eq!(row.height(), 1);
 
-            // Stage a deletion
-            workspaces::data_frames::rows::delete(&repo, &workspace, &file_path, &id_to_delete)?;
+            // The _oxen_id still addresses the row once a key is set
+            let oxen_id = workspaces::data_frames::rows::get_row_id(&row)?.unwrap();
+            let by_oxen_id =
+                workspaces::data_frames::rows::get_by_id(&workspace, &file_path, &oxen_id)?;
+            assert_eq!(by_oxen_id.height(), 1);
+            assert_eq!(
+                workspaces::data_frames::rows::get_row_id(&by_oxen_id)?,
+                Some(oxen_id)
+            );
+            let result = workspaces::data_frames::rows::get_by_id(
+                &workspace,
+                &file_path,
+                "train/dog_1.jpg/101.5",
+            );
+            assert!(result.is_err());
 
-            // List the files that are changed
+            workspaces::data_frames::rows::delete(&repo, &workspace, &file_path, row_key)?;
+            let status = workspaces::status::status(&workspace)?;
+            assert_eq!(status.staged_files.len(), 1);
+
+            // dog_1.jpg has two bounding boxes labeled dog, so this key is not unique
+            let key = vec!["file".to_string(), "label".to_string()];
+            workspaces::data_frames::set_row_key(&workspace, &file_path, &key)?;
+            let result = workspaces::data_frames::rows::get_by_id(
+                &workspace,
+                &file_path,
+                r#"{"file": "train/dog_1.jpg", "label": "dog"}"#,
+            );
+            assert!(result.is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_reset_discards_row_changes() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = test::test_bounding_box_csv();
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+            let og_count = workspaces::data_frames::count(&workspace, &file_path)?;
+
+            let json_data = json!({
+                "file": "dawg1.jpg",
+                "label": "dog",
+                "min_x": 13,
+                "min_y": 14,
+                "width": 100,
+                "height": 100
+            });
+            workspaces::data_frames::rows::add(&repo, &workspace, &file_path, &json_data)?;
             let status = workspaces::status::status(&workspace)?;
             assert_eq!(status.staged_files.len(), 1);
 
+            let reset = workspaces::data_frames::reset(&workspace, &file_path)?;
+            assert_eq!(reset.size.height, og_count);
+            assert_eq!(
+                workspaces::data_frames::count(&workspace, &file_path)?,
+                og_count
+            );
+
+            let status = workspaces::status::status(&workspace)?;
+            assert_eq!(status.staged_files.len(), 0);
+
             let diff = workspaces::diff(&repo, &workspace, &file_path)?;
             match diff {
                 DiffResult::Tabular(tabular_diff) => {
-                    let removed_rows = tabular_diff.summary.modifications.row_counts.removed;
-                    assert_eq!(removed_rows, 1);
+                    assert!(!tabular_diff.has_changes());
                 }
                 _ => panic!("Expected tabular diff result"),
             }
 
-            let status = repositories::status(&repo)?;
-            log::debug!("got this status {:?}", status);
+            Ok(())
+        })
+    }
 
-            // Commit the new file
+    #[test]
+    fn test_undo_row_operations() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
 
-            let new_commit = NewCommitBody {
-                author: "author".to_string(),
-                email: "email".to_string(),
-                message: "Deleting a row allegedly".to_string(),
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace = repositories::workspaces::create(&repo, &commit, "undoer", true)?;
+            let file_path = test::test_bounding_box_csv();
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+            let og_count = workspaces::data_frames::count(&workspace, &file_path)?;
+
+            let mut page_opts = DFOpts::empty();
+            page_opts.page = Some(0);
+            page_opts.page_size = Some(10);
+            let df = workspaces::data_frames::query(&workspace, &file_path, &page_opts)?;
+            let row_id = |i: usize| -> Result<String, OxenError> {
+                Ok(df.column(OXEN_ID_COL)?.get(i)?.to_string().replace('"', ""))
             };
-            let commit_2 = workspaces::commit(&workspace, &new_commit, branch_name)?;
 
-            let file_1 = repositories::revisions::get_version_file_from_commit_id(
-                &repo, &commit.id, &file_path,
+            let json_data = json!({
+                "file": "dawg1.jpg",
+                "label": "dog",
+                "min_x": 13,
+                "min_y": 14,
+                "width": 100,
+                "height": 100
+            });
+            workspaces::data_frames::rows::add(&repo, &workspace, &file_path, &json_data)?;
+            workspaces::data_frames::rows::update(
+                &repo,
+                &workspace,
+                &file_path,
+                &row_id(0)?,
+                &json!({ "label": "cat" }),
             )?;
-            // copy the file to the same path but with .csv as the extension
-            let file_1_csv = file_1.with_extension("csv");
-            util::fs::copy(&file_1, &file_1_csv)?;
-            log::debug!("copied file 1 to {:?}", file_1_csv);
-
-            let file_2 = repositories::revisions::get_version_file_from_commit_id(
+            workspaces::data_frames::rows::update(
                 &repo,
-                commit_2.id,
+                &workspace,
                 &file_path,
+                &row_id(0)?,
+                &json!({ "label": "bird" }),
             )?;
-            let file_2_csv = file_2.with_extension("csv");
-            util::fs::copy(&file_2, &file_2_csv)?;
-            log::debug!("copied file 2 to {:?}", file_2_csv);
-            let diff_result =
-                repositories::diffs::diff_files(file_1_csv, file_2_csv, vec![], vec![], vec![])?;
+            workspaces::data_frames::rows::delete(&repo, &workspace, &file_path, &row_id(1)?)?;
+
+            // Undoing the delete and the second update leaves the first update
+            let undone = workspaces::data_frames::rows::undo(&repo, &workspace, &file_path, 2)?;
+            assert_eq!(undone.len(), 2);
+            assert_eq!(undone[0].operation, "deleted");
+            assert_eq!(undone[1].operation, "updated");
+            let row = workspaces::data_frames::rows::get_by_id(&workspace, &file_path, row_id(0)?)?;
+            assert_eq!(row.column("label")?.get(0)?.get_str(), Some("cat"));
+            let history = workspaces::data_frames::rows::get_row_history(
+                &workspace,
+                &file_path,
+                &row_id(1)?,
+            )?;
+            assert!(history.is_empty());
 
-            log::debug!("diff result is {:?}", diff_result);
-            match diff_result {
-                DiffResult::Tabular(tabular_diff) => {
-                    let removed_rows = tabular_diff.summary.modifications.row_counts.removed;
-                    assert_eq!(removed_rows, 1);
-                }
+            // Asking for more than is left undoes the rest
+            let undone = workspaces::data_frames::rows::undo(&repo, &workspace, &file_path, 10)?;
+            assert_eq!(undone.len(), 2);
+            assert_eq!(
+                workspaces::data_frames::count(&workspace, &file_path)?,
+                og_count
+            );
+            let status = workspaces::status::status(&workspace)?;
+            assert_eq!(status.staged_files.len(), 0);
+            match workspaces::diff(&repo, &workspace, &file_path)? {
+                DiffResult::Tabular(tabular_diff) => assert!(!tabular_diff.has_changes()),
                 _ => panic!("Expected tabular diff result"),
             }
 
@@ -666,6 +1038,676 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_undo_batch_update_one_row_at_a_time() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace = repositories::workspaces::create(&repo, &commit, "batcher", true)?;
+            let file_path = test::test_bounding_box_csv();
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            let mut page_opts = DFOpts::empty();
+            page_opts.page = Some(0);
+            page_opts.page_size = Some(10);
+            let df = workspaces::data_frames::query(&workspace, &file_path, &page_opts)?;
+            let row_id = |i: usize| -> Result<String, OxenError> {
+                Ok(df.column(OXEN_ID_COL)?.get(i)?.to_string().replace('"', ""))
+            };
+            let label = |id: &str| -> Result<Option<String>, OxenError> {
+                let row = workspaces::data_frames::rows::get_by_id(&workspace, &file_path, id)?;
+                Ok(row.column("label")?.get(0)?.get_str().map(str::to_owned))
+            };
+            let og_label = label(&row_id(1)?)?;
+
+            let data = json!([
+                { "row_id": row_id(0)?, "value": { "label": "cat" } },
+                { "row_id": row_id(1)?, "value": { "label": "bird" } }
+            ]);
+            workspaces::data_frames::rows::batch_update(&repo, &workspace, &file_path, &data)?;
+
+            // Each row of the batch is its own operation
+            let undone = workspaces::data_frames::rows::undo(&repo, &workspace, &file_path, 1)?;
+            assert_eq!(undone.len(), 1);
+            assert_eq!(undone[0].operation, "updated");
+            assert_eq!(undone[0].row_id, row_id(1)?);
+            assert_eq!(label(&row_id(1)?)?, og_label);
+            assert_eq!(label(&row_id(0)?)?, Some("cat".to_string()));
+
+            // Restoring the row drops its operation, so there is nothing left to undo
+            workspaces::data_frames::rows::restore(&repo, &workspace, &file_path, row_id(0)?)?;
+            let undone = workspaces::data_frames::rows::undo(&repo, &workspace, &file_path, 1)?;
+            assert!(undone.is_empty());
+            let history = workspaces::data_frames::rows::get_row_history(
+                &workspace,
+                &file_path,
+                &row_id(0)?,
+            )?;
+            assert!(history.is_empty());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_compare_row_diffs_between_workspaces() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let file_path = test::test_bounding_box_csv();
+            let base = repositories::workspaces::create(&repo, &commit, "base-annotator", true)?;
+            let other = repositories::workspaces::create(&repo, &commit, "other-annotator", true)?;
+
+            let mut page_opts = DFOpts::empty();
+            page_opts.page = Some(0);
+            page_opts.page_size = Some(10);
+
+            // Label the first row differently in each workspace
+            for (workspace, label) in [(&base, "cat"), (&other, "bird")] {
+                workspaces::data_frames::index(&repo, workspace, &file_path)?;
+                let df = workspaces::data_frames::query(workspace, &file_path, &page_opts)?;
+                let row_id = df.column(OXEN_ID_COL)?.get(0)?.to_string().replace('"', "");
+                workspaces::data_frames::rows::update(
+                    &repo,
+                    workspace,
+                    &file_path,
+                    &row_id,
+                    &json!({ "label": label }),
+                )?;
+            }
+
+            // Only the base workspace adds a row
+            let json_data = json!({
+                "file": "dawg1.jpg",
+                "label": "dog",
+                "min_x": 13,
+                "min_y": 14,
+                "width": 100,
+                "height": 100
+            });
+            workspaces::data_frames::rows::add(&repo, &base, &file_path, &json_data)?;
+
+            let rows = workspaces::data_frames::rows::compare_row_diffs(&base, &other, &file_path)?;
+            assert_eq!(rows.len(), 2);
+
+            let conflicts: Vec<_> = rows.iter().filter(|row| row.conflict).collect();
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(conflicts[0].base.as_ref().unwrap().operation, "updated");
+            assert_eq!(conflicts[0].other.as_ref().unwrap().operation, "updated");
+
+            let added: Vec<_> = rows.iter().filter(|row| !row.conflict).collect();
+            assert_eq!(added[0].base.as_ref().unwrap().operation, "added");
+            assert!(added[0].other.is_none());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_merge_row_diffs_between_workspaces() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let file_path = test::test_bounding_box_csv();
+            let target =
+                repositories::workspaces::create(&repo, &commit, "target-annotator", true)?;
+            let source =
+                repositories::workspaces::create(&repo, &commit, "source-annotator", true)?;
+
+            let mut page_opts = DFOpts::empty();
+            page_opts.page = Some(0);
+            page_opts.page_size = Some(10);
+
+            for (workspace, label) in [(&target, "cat"), (&source, "bird")] {
+                workspaces::data_frames::index(&repo, workspace, &file_path)?;
+                let df = workspaces::data_frames::query(workspace, &file_path, &page_opts)?;
+                let row_id = df.column(OXEN_ID_COL)?.get(0)?.to_string().replace('"', "");
+                workspaces::data_frames::rows::update(
+                    &repo,
+                    workspace,
+                    &file_path,
+                    &row_id,
+                    &json!({ "label": label }),
+                )?;
+            }
+            let og_count = workspaces::data_frames::count(&target, &file_path)?;
+            let df = workspaces::data_frames::query(&source, &file_path, &page_opts)?;
+            let source_id = |i: usize| -> Result<String, OxenError> {
+                Ok(df.column(OXEN_ID_COL)?.get(i)?.to_string().replace('"', ""))
+            };
+
+            // The source relabels a row, deletes a row and adds a row the target never touched
+            workspaces::data_frames::rows::update(
+                &repo,
+                &source,
+                &file_path,
+                &source_id(1)?,
+                &json!({ "label": "fish" }),
+            )?;
+            workspaces::data_frames::rows::delete(&repo, &source, &file_path, &source_id(2)?)?;
+            let json_data = json!({
+                "file": "dawg1.jpg",
+                "label": "dog",
+                "min_x": 13,
+                "min_y": 14,
+                "width": 100,
+                "height": 100
+            });
+            // Two rows with the same values are still two rows
+            let mut added_ids = vec![];
+            for _ in 0..2 {
+                let row =
+                    workspaces::data_frames::rows::add(&repo, &source, &file_path, &json_data)?;
+                added_ids.push(
+                    row.column(OXEN_ID_COL)?
+                        .get(0)?
+                        .to_string()
+                        .replace('"', ""),
+                );
+            }
+
+            let merge = workspaces::data_frames::rows::merge_row_diffs(
+                &repo, &target, &source, &file_path, "merger",
+            )?;
+            assert_eq!(merge.applied.len(), 4);
+            assert_eq!(merge.conflicts.len(), 1);
+            assert_eq!(
+                workspaces::data_frames::count(&target, &file_path)?,
+                og_count + 1
+            );
+            // The added rows keep their ids from the source
+            for row_id in &added_ids {
+                let row =
+                    workspaces::data_frames::rows::get_by_oxen_id(&target, &file_path, row_id)?;
+                assert_eq!(row.height(), 1);
+            }
+
+            // Merging again has nothing left to apply
+            let merge = workspaces::data_frames::rows::merge_row_diffs(
+                &repo, &target, &source, &file_path, "merger",
+            )?;
+            assert!(merge.applied.is_empty());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_add_row_coerces_string_numbers() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace = repositories::workspaces::create(&repo, &commit, "coercer", true)?;
+            let file_path = test::test_bounding_box_csv();
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            let json_data = json!({
+                "file": "dawg1.jpg",
+                "label": "dog",
+                "min_x": "13.5",
+                "min_y": " 14 ",
+                "width": "100",
+                "height": 100
+            });
+            let row =
+                workspaces::data_frames::rows::add(&repo, &workspace, &file_path, &json_data)?;
+            assert_eq!(row.column("min_x")?.get(0)?.to_string(), "13.5");
+            assert_eq!(row.column("width")?.get(0)?.to_string(), "100");
+
+            let json_data = json!({
+                "file": "dawg2.jpg",
+                "label": "dog",
+                "min_x": 1.0,
+                "min_y": 2.0,
+                "width": "wide",
+                "height": 100
+            });
+            let result =
+                workspaces::data_frames::rows::add(&repo, &workspace, &file_path, &json_data);
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains("`width`"), "{err}");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_commit_data_frame_row_edits() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = test::test_bounding_box_csv();
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+            let og_count = workspaces::data_frames::count(&workspace, &file_path)?;
+
+            let new_commit = NewCommitBody {
+                message: "Adding a row".to_string(),
+                author: "Bessie".to_string(),
+                email: "bessie@oxen.ai".to_string(),
+            };
+
+            // Nothing to commit until we edit the frame
+            let result = workspaces::data_frames::commit(
+                &workspace,
+                &file_path,
+                &new_commit,
+                DEFAULT_BRANCH_NAME,
+            );
+            assert!(result.is_err());
+
+            // Other files staged in the workspace are not part of the data frame commit
+            let notes_file = workspace.dir().join("notes.txt");
+            util::fs::write_to_path(&notes_file, "Some notes")?;
+            workspaces::files::add(&workspace, &notes_file)?;
+
+            let json_data = json!({
+                "file": "dawg1.jpg",
+                "label": "dog",
+                "min_x": 13,
+                "min_y": 14,
+                "width": 100,
+                "height": 100
+            });
+            workspaces::data_frames::rows::add(&repo, &workspace, &file_path, &json_data)?;
+
+            let new_commit = workspaces::data_frames::commit(
+                &workspace,
+                &file_path,
+                &new_commit,
+                DEFAULT_BRANCH_NAME,
+            )?;
+            let head = repositories::commits::head_commit(&repo)?;
+            assert_eq!(head.id, new_commit.id);
+
+            let entry =
+                repositories::entries::get_commit_entry(&repo, &new_commit, &file_path)?.unwrap();
+            let version_file = util::fs::version_path(&repo, &entry);
+            let extension = entry.path.extension().unwrap().to_str().unwrap();
+            let data_frame =
+                df::tabular::read_df_with_extension(version_file, extension, &DFOpts::empty())?;
+            assert_eq!(data_frame.height(), og_count + 1);
+
+            assert!(
+                repositories::tree::get_file_by_path(&repo, &new_commit, "notes.txt")?.is_none()
+            );
+            let status = workspaces::status::status(&workspace)?;
+            assert_eq!(status.staged_files.len(), 1);
+            assert!(status.staged_files.contains_key(Path::new("notes.txt")));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_rows_by_index() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = test::test_bounding_box_csv();
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            let row = workspaces::data_frames::rows::get_by_index(&workspace, &file_path, 2)?;
+            assert_eq!(row.height(), 1);
+            assert_eq!(workspaces::data_frames::rows::get_row_idx(&row)?, Some(2));
+
+            let rows =
+                workspaces::data_frames::rows::get_by_index_range(&workspace, &file_path, 1, 4)?;
+            assert_eq!(rows.height(), 3);
+
+            // Past the end of the data frame is empty, not an error
+            let rows = workspaces::data_frames::rows::get_by_index_range(
+                &workspace, &file_path, 1000, 1010,
+            )?;
+            assert_eq!(rows.height(), 0);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_delete_committed_row() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let branch_name = "test-append";
+            let branch = repositories::branches::create_checkout(&repo, branch_name)?;
+            let commit = repositories::commits::get_by_id(&repo, &branch.commit_id)?.unwrap();
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+
+            // Index the dataset
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            // Preview the dataset to grab some ids
+            let mut page_opts = DFOpts::empty();
+            page_opts.page = Some(0);
+            page_opts.page_size = Some(10);
+
+            let staged_df = workspaces::data_frames::query(&workspace, &file_path, &page_opts)?;
+
+            let id_to_delete = staged_df.column(OXEN_ID_COL)?.get(0)?.to_string();
+            let id_to_delete = id_to_delete.replace('"', "");
+
+            // Stage a deletion
+            workspaces::data_frames::rows::delete(&repo, &workspace, &file_path, &id_to_delete)?;
+
+            // List the files that are changed
+            let status = workspaces::status::status(&workspace)?;
+            assert_eq!(status.staged_files.len(), 1);
+
+            let diff = workspaces::diff(&repo, &workspace, &file_path)?;
+            match diff {
+                DiffResult::Tabular(tabular_diff) => {
+                    let removed_rows = tabular_diff.summary.modifications.row_counts.removed;
+                    assert_eq!(removed_rows, 1);
+                }
+                _ => panic!("Expected tabular diff result"),
+            }
+
+            let status = repositories::status(&repo)?;
+            log::debug!("got this status {:?}", status);
+
+            // Commit the new file
+
+            let new_commit = NewCommitBody {
+                author: "author".to_string(),
+                email: "email".to_string(),
+                message: "Deleting a row allegedly".to_string(),
+            };
+            let commit_2 = workspaces::commit(&workspace, &new_commit, branch_name)?;
+
+            let file_1 = repositories::revisions::get_version_file_from_commit_id(
+                &repo, &commit.id, &file_path,
+            )?;
+            // copy the file to the same path but with .csv as the extension
+            let file_1_csv = file_1.with_extension("csv");
+            util::fs::copy(&file_1, &file_1_csv)?;
+            log::debug!("copied file 1 to {:?}", file_1_csv);
+
+            let file_2 = repositories::revisions::get_version_file_from_commit_id(
+                &repo,
+                commit_2.id,
+                &file_path,
+            )?;
+            let file_2_csv = file_2.with_extension("csv");
+            util::fs::copy(&file_2, &file_2_csv)?;
+            log::debug!("copied file 2 to {:?}", file_2_csv);
+            let diff_result =
+                repositories::diffs::diff_files(file_1_csv, file_2_csv, vec![], vec![], vec![])?;
+
+            log::debug!("diff result is {:?}", diff_result);
+            match diff_result {
+                DiffResult::Tabular(tabular_diff) => {
+                    let removed_rows = tabular_diff.summary.modifications.row_counts.removed;
+                    assert_eq!(removed_rows, 1);
+                }
+                _ => panic!("Expected tabular diff result"),
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_get_added_row_preserves_nulls() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            // "label" is explicitly null and "file" is not given at all
+            let json_data = json!({
+                "label": null,
+                "min_x": 13,
+                "min_y": 14,
+                "width": 100,
+                "height": 100
+            });
+            let new_row =
+                workspaces::data_frames::rows::add(&repo, &workspace, &file_path, &json_data)?;
+            let row_id = new_row.column(OXEN_ID_COL)?.get(0)?;
+            let row_id = row_id.get_str().unwrap();
+
+            let row_df = workspaces::data_frames::rows::get_by_id(&workspace, &file_path, row_id)?;
+            let schema = crate::model::Schema::from_polars(&row_df.schema());
+            let view = JsonDataFrameView::from_df_opts(row_df, schema, &DFOpts::empty());
+            let row = view.data[0].as_object().unwrap();
+
+            assert_eq!(row.get("label"), Some(&serde_json::Value::Null));
+            assert_eq!(row.get("file"), Some(&serde_json::Value::Null));
+            assert_eq!(row.get("min_x"), Some(&json!(13)));
+            assert!(!row.contains_key("not_a_column"));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_query_derived_filters_before_paging() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            let mut opts = DFOpts::empty();
+            opts.filter = Some("label == cat".to_string());
+            opts.page = Some(1);
+            opts.page_size = Some(1);
+            let (page, total) =
+                workspaces::data_frames::query_derived(&workspace, &file_path, &opts)?;
+            assert_eq!(total, 2);
+            assert_eq!(page.height(), 1);
+            assert_eq!(page.column("label")?.get(0)?.get_str(), Some("cat"));
+
+            let mut opts = DFOpts::empty();
+            opts.sql = Some("SELECT label, COUNT(*) AS n FROM df GROUP BY label".to_string());
+            let (page, total) =
+                workspaces::data_frames::query_derived(&workspace, &file_path, &opts)?;
+            assert_eq!(total, 2);
+            assert_eq!(page.height(), 2);
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_list_indexed_data_frames() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            assert!(workspaces::data_frames::list_indexed(&workspace)?.is_empty());
+
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            let indexed = workspaces::data_frames::list_indexed(&workspace)?;
+            assert_eq!(indexed.len(), 1);
+            assert_eq!(Path::new(&indexed[0].path), file_path);
+            assert_eq!(
+                indexed[0].num_rows,
+                workspaces::data_frames::count(&workspace, &file_path)?
+            );
+            assert!(indexed[0].schema.has_field_name("label"));
+            assert!(!indexed[0].schema.has_field_name(OXEN_ID_COL));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_list_rows_by_cursor() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+            let count = workspaces::data_frames::count(&workspace, &file_path)?;
+
+            let (first_page, cursor) =
+                workspaces::data_frames::rows::list_page(&workspace, &file_path, None, 2)?;
+            assert_eq!(first_page.height(), 2);
+            assert!(cursor.is_some());
+
+            // A row inserted while paging shows up at the end instead of shifting the pages
+            workspaces::data_frames::rows::add(
+                &repo,
+                &workspace,
+                &file_path,
+                &json!({"file": "dawg1.jpg", "label": "dog", "min_x": 13, "min_y": 14, "width": 100, "height": 100}),
+            )?;
+
+            let mut seen = first_page.height();
+            let mut cursor = cursor;
+            while let Some(next) = cursor {
+                let (page, next_cursor) = workspaces::data_frames::rows::list_page(
+                    &workspace,
+                    &file_path,
+                    Some(&next),
+                    2,
+                )?;
+                assert!(page.height() <= 2);
+                seen += page.height();
+                cursor = next_cursor;
+            }
+            assert_eq!(seen, count + 1);
+
+            assert!(workspaces::data_frames::rows::list_page(
+                &workspace,
+                &file_path,
+                Some("nope"),
+                2
+            )
+            .is_err());
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_row_changes_record_author() -> Result<(), OxenError> {
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace_id = UserConfig::identifier()?;
+            let workspace = repositories::workspaces::create(&repo, &commit, workspace_id, true)?;
+            let file_path = Path::new("annotations")
+                .join("train")
+                .join("bounding_box.csv");
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+
+            let json_data = json!({
+                "file": "dawg1.jpg",
+                "label": "dog",
+                "min_x": 13,
+                "min_y": 14,
+                "width": 100,
+                "height": 100
+            });
+            let new_row = workspaces::data_frames::rows::add_with_author(
+                &repo,
+                &workspace,
+                &file_path,
+                &json_data,
+                "ox@oxen.ai",
+            )?;
+            let row_id = new_row.column(OXEN_ID_COL)?.get(0)?;
+            let row_id = row_id.get_str().unwrap();
+
+            let history =
+                workspaces::data_frames::rows::get_row_history(&workspace, &file_path, row_id)?;
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].operation, "added");
+            assert_eq!(history[0].author, "ox@oxen.ai");
+
+            // Edits without an identity are attributed to anonymous
+            workspaces::data_frames::rows::update(
+                &repo,
+                &workspace,
+                &file_path,
+                row_id,
+                &json!({"label": "cat"}),
+            )?;
+            // Every edit is kept, not just the last one
+            let history =
+                workspaces::data_frames::rows::get_row_history(&workspace, &file_path, row_id)?;
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[0].author, "ox@oxen.ai");
+            assert_eq!(history[1].operation, "updated");
+            assert_eq!(history[1].author, DEFAULT_ROW_CHANGE_AUTHOR);
+
+            // Undoing an edit drops it from the history
+            workspaces::data_frames::rows::undo(&repo, &workspace, &file_path, 1)?;
+            let history =
+                workspaces::data_frames::rows::get_row_history(&workspace, &file_path, row_id)?;
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].operation, "added");
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_modify_added_row() -> Result<(), OxenError> {
         if std::env::consts::OS == "windows" {
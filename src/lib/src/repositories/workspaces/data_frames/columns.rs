@@ -1,27 +1,46 @@
+use crate::constants::TABLE_NAME;
 use crate::core;
 use crate::core::db;
 use crate::core::db::data_frames::column_changes_db::get_all_data_frame_column_changes;
+use crate::core::db::data_frames::df_db;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::{LocalRepository, Schema, Workspace};
 use crate::repositories;
 
 use crate::view::data_frames::columns::{
-    ColumnToDelete, ColumnToRestore, ColumnToUpdate, NewColumn,
+    ColumnStats, ColumnToDelete, ColumnToRestore, ColumnToUpdate, NewColumn,
 };
 use crate::view::data_frames::DataFrameColumnChange;
 use crate::view::JsonDataFrameViews;
 
 use polars::frame::DataFrame;
 use rocksdb::DB;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use rocksdb::IteratorMode;
+use sql_query_builder::Select;
 
 use crate::model::data_frame::schema::field::{Changes, PreviousField};
 use crate::model::data_frame::schema::Field;
 
+/// Summary statistics over every row of the workspace's copy of the data frame,
+/// limited to `columns` if given
+pub fn stats(
+    workspace: &Workspace,
+    file_path: impl AsRef<Path>,
+    columns: Option<&[String]>,
+) -> Result<BTreeMap<String, ColumnStats>, OxenError> {
+    let file_path = file_path.as_ref();
+    let schema = repositories::workspaces::data_frames::schemas::get_by_path(workspace, file_path)?;
+    let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, file_path);
+    let conn = df_db::get_connection(db_path)?;
+    let select = Select::new().select("*").from(TABLE_NAME);
+    let df = df_db::select(&conn, &select, None)?;
+    core::df::tabular::column_stats(&df, &schema, columns)
+}
+
 pub fn add(
     repo: &LocalRepository,
     workspace: &Workspace,
@@ -1,9 +1,12 @@
 use crate::core::db::data_frames::row_changes_db::get_all_data_frame_row_changes;
+use crate::core::db::data_frames::row_history_db;
+use crate::core::v_latest::workspaces::data_frames::rows::MergedRowChange;
 use crate::core::versions::MinOxenVersion;
 use crate::error::OxenError;
 use crate::model::data_frame::update_result::UpdateResult;
 use crate::model::Workspace;
-use crate::view::data_frames::DataFrameRowChange;
+use crate::view::data_frames::{DataFrameRowChange, WorkspaceRowDiff, WorkspaceRowMerge};
+use crate::view::JsonDataFrameView;
 
 use polars::datatypes::AnyValue;
 
@@ -14,13 +17,16 @@ use crate::{core, repositories};
 use rocksdb::DB;
 use sql_query_builder::Select;
 
-use crate::constants::{DIFF_STATUS_COL, OXEN_ID_COL, OXEN_ROW_ID_COL, TABLE_NAME};
+use crate::constants::{
+    DEFAULT_ROW_CHANGE_AUTHOR, DIFF_STATUS_COL, OXEN_COLS, OXEN_ID_COL, OXEN_ROW_ID_COL, TABLE_NAME,
+};
 use crate::core::db;
 
 use crate::core::db::data_frames::df_db;
 use crate::model::staged_row_status::StagedRowStatus;
 use crate::model::LocalRepository;
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 pub fn add(
@@ -28,12 +34,26 @@ pub fn add(
     workspace: &Workspace,
     file_path: impl AsRef<Path>,
     data: &serde_json::Value,
+) -> Result<DataFrame, OxenError> {
+    add_with_author(repo, workspace, file_path, data, DEFAULT_ROW_CHANGE_AUTHOR)
+}
+
+/// Add a row, recording `author` on the change in the workspace's change log
+pub fn add_with_author(
+    repo: &LocalRepository,
+    workspace: &Workspace,
+    file_path: impl AsRef<Path>,
+    data: &serde_json::Value,
+    author: &str,
 ) -> Result<DataFrame, OxenError> {
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
-        _ => {
-            core::v_latest::workspaces::data_frames::rows::add(workspace, file_path.as_ref(), data)
-        }
+        _ => core::v_latest::workspaces::data_frames::rows::add(
+            workspace,
+            file_path.as_ref(),
+            data,
+            author,
+        ),
     }
 }
 
@@ -41,11 +61,272 @@ pub fn get_row_diff(
     workspace: &Workspace,
     file_path: impl AsRef<Path>,
 ) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    let file_path = file_path.as_ref();
     let row_changes_path =
         repositories::workspaces::data_frames::row_changes_path(workspace, file_path);
     let opts = db::key_val::opts::default();
     let db = DB::open_for_read_only(&opts, dunce::simplified(&row_changes_path), false)?;
-    get_all_data_frame_row_changes(&db)
+    with_row_keys(workspace, file_path, get_all_data_frame_row_changes(&db)?)
+}
+
+/// Every uncommitted edit to a single row with its author, oldest first. Empty if the row is unchanged
+pub fn get_row_history(
+    workspace: &Workspace,
+    file_path: impl AsRef<Path>,
+    row_id: &str,
+) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    let file_path = file_path.as_ref();
+    let row_id = resolve_row_id(workspace, file_path, row_id)?;
+    let row_history_path =
+        repositories::workspaces::data_frames::row_history_path(workspace, file_path);
+    if !row_history_path.exists() {
+        return Ok(vec![]);
+    }
+    let opts = db::key_val::opts::default();
+    let db = DB::open_for_read_only(&opts, dunce::simplified(&row_history_path), false)?;
+    with_row_keys(
+        workspace,
+        file_path,
+        row_history_db::get_row_history(&db, &row_id)?,
+    )
+}
+
+/// The values of the composite row key columns in a single row, None if the data frame has no row key
+pub fn get_row_key_values(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    row_df: &DataFrame,
+) -> Result<Option<serde_json::Map<String, serde_json::Value>>, OxenError> {
+    let Some(columns) = repositories::workspaces::data_frames::get_row_key(workspace, path)? else {
+        return Ok(None);
+    };
+    let row = JsonDataFrameView::json_from_df(&mut row_df.clone());
+    Ok(Some(row_key_from_json(&columns, &first_row(row))))
+}
+
+/// Fill in the row key of each change, so clients of a keyed data frame can tell the rows apart
+fn with_row_keys(
+    workspace: &Workspace,
+    file_path: &Path,
+    mut changes: Vec<DataFrameRowChange>,
+) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    let Some(columns) = repositories::workspaces::data_frames::get_row_key(workspace, file_path)?
+    else {
+        return Ok(changes);
+    };
+    for change in &mut changes {
+        let row = first_row(change.value.to_owned());
+        change.row_key = Some(row_key_from_json(&columns, &row));
+    }
+    Ok(changes)
+}
+
+fn row_key_from_json(
+    columns: &[String],
+    row: &serde_json::Value,
+) -> serde_json::Map<String, serde_json::Value> {
+    columns
+        .iter()
+        .map(|column| {
+            let value = row.get(column).cloned().unwrap_or_default();
+            (column.to_owned(), value)
+        })
+        .collect()
+}
+
+/// Compare the pending row edits of two workspaces on the same data frame.
+///
+/// Each workspace indexes the frame with its own `_oxen_id`s, so rows from the base commit are
+/// matched by `_oxen_row_id`. Rows edited differently on both sides are flagged as conflicts.
+pub fn compare_row_diffs(
+    base: &Workspace,
+    other: &Workspace,
+    file_path: impl AsRef<Path>,
+) -> Result<Vec<WorkspaceRowDiff>, OxenError> {
+    let file_path = file_path.as_ref();
+    let mut rows: BTreeMap<String, WorkspaceRowDiff> = BTreeMap::new();
+
+    for change in get_row_diff_or_empty(base, file_path)? {
+        let row_id = comparable_row_id(&change);
+        rows.entry(row_id.clone())
+            .or_insert_with(|| empty_row_diff(row_id))
+            .base = Some(change);
+    }
+
+    for change in get_row_diff_or_empty(other, file_path)? {
+        let row_id = comparable_row_id(&change);
+        rows.entry(row_id.clone())
+            .or_insert_with(|| empty_row_diff(row_id))
+            .other = Some(change);
+    }
+
+    Ok(rows
+        .into_values()
+        .map(|mut row| {
+            row.conflict = match (&row.base, &row.other) {
+                (Some(base), Some(other)) => {
+                    base.operation != other.operation
+                        || without_oxen_cols(&base.new_value) != without_oxen_cols(&other.new_value)
+                }
+                _ => false,
+            };
+            row
+        })
+        .collect())
+}
+
+/// Apply the row edits of `source` onto `target` for the same data frame. Rows that only `source`
+/// changed are added, updated or deleted in `target`, rows already changed the same way are skipped,
+/// and rows edited differently in both are returned as conflicts without touching `target`.
+/// The edits are applied together, if one fails none of them are.
+pub fn merge_row_diffs(
+    repo: &LocalRepository,
+    target: &Workspace,
+    source: &Workspace,
+    file_path: impl AsRef<Path>,
+    author: &str,
+) -> Result<WorkspaceRowMerge, OxenError> {
+    let file_path = file_path.as_ref();
+    if !repositories::workspaces::data_frames::is_indexed(target, file_path)? {
+        return Err(OxenError::basic_str(format!(
+            "{:?} is not indexed in workspace {}",
+            file_path, target.id
+        )));
+    }
+
+    let mut changes = vec![];
+    let mut merge = WorkspaceRowMerge {
+        applied: vec![],
+        conflicts: vec![],
+    };
+    for row in compare_row_diffs(target, source, file_path)? {
+        if row.conflict {
+            merge.conflicts.push(row);
+            continue;
+        }
+        // Only rows that the source changed and the target has not touched need applying
+        let (None, Some(change)) = (&row.base, &row.other) else {
+            continue;
+        };
+
+        let merged_change = match change.operation.as_str() {
+            // Added rows keep the source's `_oxen_id`, so one that was already merged is not added twice
+            "added" => {
+                if get_by_oxen_id(target, file_path, &change.row_id)?.height() > 0 {
+                    continue;
+                }
+                let data = without_oxen_cols(&Some(first_row(change.value.to_owned())))
+                    .unwrap_or_default();
+                MergedRowChange::Added {
+                    row_id: change.row_id.to_owned(),
+                    data,
+                }
+            }
+            "updated" => {
+                let data = without_oxen_cols(&change.new_value.to_owned().map(first_row))
+                    .ok_or_else(|| OxenError::basic_str("Updated row is missing its new value"))?;
+                MergedRowChange::Updated {
+                    row_id: target_row_id(target, file_path, change)?,
+                    data,
+                }
+            }
+            "deleted" => MergedRowChange::Deleted {
+                row_id: target_row_id(target, file_path, change)?,
+            },
+            operation => {
+                return Err(OxenError::basic_str(format!(
+                    "Unknown row operation: {operation}"
+                )))
+            }
+        };
+        changes.push(merged_change);
+        merge.applied.push(row);
+    }
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::workspaces::data_frames::rows::apply_merged_changes(
+            target, file_path, &changes, author,
+        )?,
+    }
+    Ok(merge)
+}
+
+/// The `_oxen_id` in `workspace` of the committed row a change from another workspace was made to
+fn target_row_id(
+    workspace: &Workspace,
+    file_path: &Path,
+    change: &DataFrameRowChange,
+) -> Result<String, OxenError> {
+    let row_idx = first_row(change.value.to_owned())
+        .get(OXEN_ROW_ID_COL)
+        .and_then(|idx| idx.as_u64())
+        .ok_or_else(|| OxenError::basic_str(format!("Row {} has no index", change.row_id)))?;
+    let row = get_by_index(workspace, file_path, row_idx as usize)?;
+    get_row_id(&row)?.ok_or_else(|| {
+        OxenError::resource_not_found(format!("row {} in workspace {}", row_idx, workspace.id))
+    })
+}
+
+fn first_row(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Array(mut values) if !values.is_empty() => values.swap_remove(0),
+        value => value,
+    }
+}
+
+fn get_row_diff_or_empty(
+    workspace: &Workspace,
+    file_path: &Path,
+) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    let row_changes_path =
+        repositories::workspaces::data_frames::row_changes_path(workspace, file_path);
+    if !row_changes_path.exists() {
+        return Ok(vec![]);
+    }
+    get_row_diff(workspace, file_path)
+}
+
+fn empty_row_diff(row_id: String) -> WorkspaceRowDiff {
+    WorkspaceRowDiff {
+        row_id,
+        base: None,
+        other: None,
+        conflict: false,
+    }
+}
+
+/// Added rows only exist in one workspace, every other row is keyed by its position in the commit
+fn comparable_row_id(change: &DataFrameRowChange) -> String {
+    if change.operation != "added" {
+        let row = match &change.value {
+            serde_json::Value::Array(values) => values.first(),
+            value => Some(value),
+        };
+        if let Some(row_idx) = row.and_then(|row| row.get(OXEN_ROW_ID_COL)) {
+            return format!("{}:{}", OXEN_ROW_ID_COL, row_idx);
+        }
+    }
+    change.row_id.to_owned()
+}
+
+fn without_oxen_cols(value: &Option<serde_json::Value>) -> Option<serde_json::Value> {
+    let strip = |value: &serde_json::Value| -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(obj) => serde_json::Value::Object(
+                obj.iter()
+                    .filter(|(key, _)| !OXEN_COLS.contains(&key.as_str()))
+                    .map(|(key, val)| (key.to_owned(), val.to_owned()))
+                    .collect(),
+            ),
+            value => value.to_owned(),
+        }
+    };
+    value.as_ref().map(|value| match value {
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.iter().map(strip).collect())
+        }
+        value => strip(value),
+    })
 }
 
 pub fn update(
@@ -55,13 +336,34 @@ pub fn update(
     row_id: &str,
     data: &serde_json::Value,
 ) -> Result<DataFrame, OxenError> {
+    update_with_author(
+        repo,
+        workspace,
+        path,
+        row_id,
+        data,
+        DEFAULT_ROW_CHANGE_AUTHOR,
+    )
+}
+
+/// Update a row, recording `author` on the change in the workspace's change log
+pub fn update_with_author(
+    repo: &LocalRepository,
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    row_id: &str,
+    data: &serde_json::Value,
+    author: &str,
+) -> Result<DataFrame, OxenError> {
+    let row_id = resolve_row_id(workspace, &path, row_id)?;
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
         _ => core::v_latest::workspaces::data_frames::rows::update(
             workspace,
             path.as_ref(),
-            row_id,
+            &row_id,
             data,
+            author,
         ),
     }
 }
@@ -71,6 +373,17 @@ pub fn batch_update(
     workspace: &Workspace,
     path: impl AsRef<Path>,
     data: &serde_json::Value,
+) -> Result<Vec<UpdateResult>, OxenError> {
+    batch_update_with_author(repo, workspace, path, data, DEFAULT_ROW_CHANGE_AUTHOR)
+}
+
+/// Update many rows, recording one change per row with `author` in the workspace's change log
+pub fn batch_update_with_author(
+    repo: &LocalRepository,
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    data: &serde_json::Value,
+    author: &str,
 ) -> Result<Vec<UpdateResult>, OxenError> {
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
@@ -78,6 +391,7 @@ pub fn batch_update(
             workspace,
             path.as_ref(),
             data,
+            author,
         ),
     }
 }
@@ -88,11 +402,40 @@ pub fn delete(
     path: impl AsRef<Path>,
     row_id: &str,
 ) -> Result<DataFrame, OxenError> {
+    delete_with_author(repo, workspace, path, row_id, DEFAULT_ROW_CHANGE_AUTHOR)
+}
+
+/// Delete a row, recording `author` on the change in the workspace's change log
+pub fn delete_with_author(
+    repo: &LocalRepository,
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    row_id: &str,
+    author: &str,
+) -> Result<DataFrame, OxenError> {
+    let row_id = resolve_row_id(workspace, &path, row_id)?;
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
-        _ => {
-            core::v_latest::workspaces::data_frames::rows::delete(workspace, path.as_ref(), row_id)
-        }
+        _ => core::v_latest::workspaces::data_frames::rows::delete(
+            workspace,
+            path.as_ref(),
+            &row_id,
+            author,
+        ),
+    }
+}
+
+/// Undo the last `count` add, update and delete row operations in the workspace, newest first.
+/// Returns the operations that were undone, fewer than `count` if the log runs out.
+pub fn undo(
+    repo: &LocalRepository,
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    count: usize,
+) -> Result<Vec<DataFrameRowChange>, OxenError> {
+    match repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::workspaces::data_frames::rows::undo(workspace, path.as_ref(), count),
     }
 }
 
@@ -102,11 +445,14 @@ pub fn restore(
     path: impl AsRef<Path>,
     row_id: impl AsRef<str>,
 ) -> Result<DataFrame, OxenError> {
+    let row_id = resolve_row_id(workspace, &path, row_id.as_ref())?;
     match repo.min_version() {
         MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
-        _ => {
-            core::v_latest::workspaces::data_frames::rows::restore(workspace, path.as_ref(), row_id)
-        }
+        _ => core::v_latest::workspaces::data_frames::rows::restore(
+            workspace,
+            path.as_ref(),
+            &row_id,
+        ),
     }
 }
 
@@ -114,6 +460,17 @@ pub fn get_by_id(
     workspace: &Workspace,
     path: impl AsRef<Path>,
     row_id: impl AsRef<str>,
+) -> Result<DataFrame, OxenError> {
+    let path = path.as_ref();
+    let row_id = resolve_row_id(workspace, path, row_id.as_ref())?;
+    get_by_oxen_id(workspace, path, row_id)
+}
+
+/// Get a row by its `_oxen_id`, ignoring any composite row key
+pub fn get_by_oxen_id(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    row_id: impl AsRef<str>,
 ) -> Result<DataFrame, OxenError> {
     let path = path.as_ref();
     let row_id = row_id.as_ref();
@@ -130,6 +487,202 @@ pub fn get_by_id(
     Ok(data)
 }
 
+/// Get a row by its index (`_oxen_row_id`) in the data frame
+pub fn get_by_index(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    index: usize,
+) -> Result<DataFrame, OxenError> {
+    get_by_index_range(workspace, path, index, index + 1)
+}
+
+/// Get the rows with an index (`_oxen_row_id`) in `start..end`, ordered by index
+pub fn get_by_index_range(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    start: usize,
+    end: usize,
+) -> Result<DataFrame, OxenError> {
+    let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+
+    let query = Select::new()
+        .select("*")
+        .from(TABLE_NAME)
+        .where_clause(&format!(
+            "{} >= {} AND {} < {}",
+            OXEN_ROW_ID_COL, start, OXEN_ROW_ID_COL, end
+        ))
+        .order_by(OXEN_ROW_ID_COL);
+    df_db::select(&conn, &query, None)
+}
+
+/// Get up to `limit` rows ordered by `_oxen_row_id`, starting after `cursor`, along with the cursor
+/// of the next page if there are more rows. Unlike offset paging each page is a range scan on the row
+/// id, and new rows always get a larger id so inserts while scrolling do not shift the pages.
+pub fn list_page(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    cursor: Option<&str>,
+    limit: usize,
+) -> Result<(DataFrame, Option<String>), OxenError> {
+    if limit == 0 {
+        return Err(OxenError::basic_str("limit must be greater than 0"));
+    }
+    let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+
+    let mut query = Select::new()
+        .select("*")
+        .from(TABLE_NAME)
+        .order_by(OXEN_ROW_ID_COL)
+        // Fetch one extra row to know whether there is a next page
+        .limit(&limit.saturating_add(1).to_string());
+    if let Some(cursor) = cursor {
+        let after = decode_row_cursor(cursor)?;
+        query = query.where_clause(&format!("{} > {}", OXEN_ROW_ID_COL, after));
+    }
+    let df = df_db::select(&conn, &query, None)?;
+
+    if df.height() <= limit {
+        return Ok((df, None));
+    }
+    let df = df.slice(0, limit);
+    let next_cursor = get_row_idx(&df.slice(limit as i64 - 1, 1))?.map(encode_row_cursor);
+    Ok((df, next_cursor))
+}
+
+fn encode_row_cursor(row_idx: usize) -> String {
+    format!("r{:x}", row_idx)
+}
+
+fn decode_row_cursor(cursor: &str) -> Result<usize, OxenError> {
+    cursor
+        .strip_prefix('r')
+        .and_then(|hex| usize::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| OxenError::basic_str(format!("Invalid row cursor: {cursor}")))
+}
+
+/// Resolve the `_oxen_id` of a row. If the data frame has a composite row key (see
+/// `data_frames::set_row_key`), `row_id` holds the key values, either slash separated (`a/b`),
+/// as a JSON array in key column order, or as a JSON object of column to value. The `_oxen_id`
+/// returned by create, get and update still addresses the row when it isn't a key that matches.
+pub fn resolve_row_id(
+    workspace: &Workspace,
+    path: impl AsRef<Path>,
+    row_id: &str,
+) -> Result<String, OxenError> {
+    let path = path.as_ref();
+    let Some(columns) = repositories::workspaces::data_frames::get_row_key(workspace, path)? else {
+        return Ok(row_id.to_string());
+    };
+
+    let db_path = repositories::workspaces::data_frames::duckdb_path(workspace, path);
+    let conn = df_db::get_connection(db_path)?;
+    let values = match parse_row_key(&columns, row_id) {
+        Ok(values) => values,
+        Err(err) => return oxen_id_or_err(&conn, row_id, err),
+    };
+    let where_clause = columns
+        .iter()
+        .zip(values.iter())
+        .map(|(column, value)| {
+            format!(
+                "CAST(\"{}\" AS VARCHAR) = '{}'",
+                column.replace('"', "\"\""),
+                value.replace('\'', "''")
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" AND ");
+
+    let query = Select::new()
+        .select(OXEN_ID_COL)
+        .from(TABLE_NAME)
+        .where_clause(&where_clause);
+    let data = df_db::select(&conn, &query, None)?;
+
+    match data.height() {
+        0 => oxen_id_or_err(
+            &conn,
+            row_id,
+            OxenError::resource_not_found(format!(
+                "row with key {} = {}",
+                columns.join("/"),
+                row_id
+            )),
+        ),
+        1 => get_row_id(&data)?.ok_or(OxenError::basic_str("Row id not found")),
+        n => Err(OxenError::basic_str(format!(
+            "Row key {} = {} does not uniquely identify a row, it matches {} rows",
+            columns.join("/"),
+            row_id,
+            n
+        ))),
+    }
+}
+
+/// `row_id` if it is the `_oxen_id` of a row, otherwise `err` from looking it up as a row key
+fn oxen_id_or_err(
+    conn: &duckdb::Connection,
+    row_id: &str,
+    err: OxenError,
+) -> Result<String, OxenError> {
+    let query = Select::new()
+        .select(OXEN_ID_COL)
+        .from(TABLE_NAME)
+        .where_clause(&format!(
+            "{} = '{}'",
+            OXEN_ID_COL,
+            row_id.replace('\'', "''")
+        ));
+    if df_db::select(conn, &query, None)?.height() == 1 {
+        Ok(row_id.to_string())
+    } else {
+        Err(err)
+    }
+}
+
+fn parse_row_key(columns: &[String], row_id: &str) -> Result<Vec<String>, OxenError> {
+    let values: Vec<String> = if row_id.starts_with('[') || row_id.starts_with('{') {
+        match serde_json::from_str::<serde_json::Value>(row_id)? {
+            serde_json::Value::Array(values) => values.iter().map(row_key_value).collect(),
+            serde_json::Value::Object(values) => columns
+                .iter()
+                .map(|column| {
+                    values.get(column).map(row_key_value).ok_or_else(|| {
+                        OxenError::basic_str(format!("Row key is missing column `{}`", column))
+                    })
+                })
+                .collect::<Result<Vec<String>, OxenError>>()?,
+            _ => {
+                return Err(OxenError::basic_str(
+                    "Row key must be a JSON array or object",
+                ))
+            }
+        }
+    } else {
+        row_id.split('/').map(String::from).collect()
+    };
+
+    if values.len() != columns.len() {
+        return Err(OxenError::basic_str(format!(
+            "Row key has {} values but the data frame is keyed by {} columns: {}. Use a JSON array if values contain '/'",
+            values.len(),
+            columns.len(),
+            columns.join(", ")
+        )));
+    }
+    Ok(values)
+}
+
+fn row_key_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(value) => value.to_owned(),
+        value => value.to_string(),
+    }
+}
+
 pub fn get_row_id(row_df: &DataFrame) -> Result<Option<String>, OxenError> {
     let oxen_id_col = PlSmallStr::from_str(OXEN_ID_COL);
     if row_df.height() == 1 && row_df.get_column_names().contains(&&oxen_id_col) {
@@ -11,6 +11,7 @@ use crate::error::OxenError;
 use crate::model::diff::DiffResult;
 use crate::model::LocalRepository;
 use crate::model::Workspace;
+use crate::view::workspaces::WorkspaceFileDiff;
 
 pub fn diff(
     repo: &LocalRepository,
@@ -22,3 +23,83 @@ pub fn diff(
         _ => core::v_latest::workspaces::diff::diff(workspace, path),
     }
 }
+
+/// Preview what committing the workspace would change on `branch_name`: the files added, modified
+/// or removed compared to the head of the branch, with row counts for indexed data frames
+pub fn diff_branch(
+    workspace: &Workspace,
+    branch_name: impl AsRef<str>,
+) -> Result<Vec<WorkspaceFileDiff>, OxenError> {
+    match workspace.workspace_repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::workspaces::diff::diff_branch(workspace, branch_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::constants::{DEFAULT_BRANCH_NAME, OXEN_ID_COL};
+    use crate::error::OxenError;
+    use crate::model::diff::diff_entry_status::DiffEntryStatus;
+    use crate::opts::DFOpts;
+    use crate::repositories;
+    use crate::repositories::workspaces;
+    use crate::test;
+    use crate::util;
+
+    #[test]
+    fn test_diff_branch() -> Result<(), OxenError> {
+        // Skip duckdb if on windows
+        if std::env::consts::OS == "windows" {
+            return Ok(());
+        }
+
+        test::run_training_data_repo_test_fully_committed(|repo| {
+            let commit = repositories::commits::head_commit(&repo)?;
+            let workspace = workspaces::create(&repo, &commit, "diff-annotator", true)?;
+
+            let notes_file = workspace.dir().join("notes.txt");
+            util::fs::write_to_path(&notes_file, "Some notes")?;
+            workspaces::files::add(&workspace, &notes_file)?;
+
+            let file_path = test::test_bounding_box_csv();
+            workspaces::data_frames::index(&repo, &workspace, &file_path)?;
+            let mut page_opts = DFOpts::empty();
+            page_opts.page = Some(0);
+            page_opts.page_size = Some(10);
+            let df = workspaces::data_frames::query(&workspace, &file_path, &page_opts)?;
+            let row_id = df.column(OXEN_ID_COL)?.get(0)?.to_string().replace('"', "");
+            workspaces::data_frames::rows::delete(&repo, &workspace, &file_path, &row_id)?;
+            workspaces::data_frames::rows::update(
+                &repo,
+                &workspace,
+                &file_path,
+                &df.column(OXEN_ID_COL)?.get(1)?.to_string().replace('"', ""),
+                &json!({ "label": "fish" }),
+            )?;
+
+            let diffs = workspaces::diff::diff_branch(&workspace, DEFAULT_BRANCH_NAME)?;
+            assert_eq!(diffs.len(), 2);
+
+            let bbox = &diffs[0];
+            assert_eq!(bbox.path, file_path.to_string_lossy());
+            assert_eq!(bbox.status, DiffEntryStatus::Modified);
+            let row_counts = bbox
+                .row_counts
+                .as_ref()
+                .expect("indexed frame has row counts");
+            assert_eq!(row_counts.removed, 1);
+            assert_eq!(row_counts.modified, 1);
+            assert_eq!(row_counts.added, 0);
+
+            let notes = &diffs[1];
+            assert_eq!(notes.path, "notes.txt");
+            assert_eq!(notes.status, DiffEntryStatus::Added);
+            assert!(notes.row_counts.is_none());
+
+            Ok(())
+        })
+    }
+}
@@ -26,6 +26,13 @@ pub fn delete(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), OxenE
     }
 }
 
+pub fn rm(workspace: &Workspace, path: impl AsRef<Path>) -> Result<(), OxenError> {
+    match workspace.base_repo.min_version() {
+        MinOxenVersion::V0_10_0 => panic!("v0.10.0 no longer supported"),
+        _ => core::v_latest::workspaces::files::rm(workspace, path),
+    }
+}
+
 pub async fn import(
     url: &str,
     auth: &str,
@@ -19,6 +19,7 @@ pub async fn upload(repo: &RemoteRepository, opts: &UploadOpts) -> Result<(), Ox
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::path::Path;
 
     use super::*;
@@ -73,6 +74,12 @@ mod tests {
                 host: remote_repo.host(),
                 scheme: remote_repo.scheme(),
                 remote: remote_repo.name.clone(),
+                delete: vec![],
+                routes: HashMap::new(),
+                preserve_structure: false,
+                workspace_id: None,
+                commit: true,
+                skip_unchanged: false,
                 branch: None,
                 message: "adding new file".to_string(),
             };
@@ -90,6 +97,109 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_remote_upload_skip_unchanged_files() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|mut repo| async move {
+            let dir = repo.path.join("train");
+            util::fs::create_dir_all(&dir)?;
+            let file = dir.join("file.txt");
+            util::fs::write_to_path(&file, "lol hi")?;
+            repositories::add(&repo, &dir)?;
+            repositories::commit(&repo, "adding text file")?;
+
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+            let remote_repo = test::create_remote_repo(&repo).await?;
+            repositories::push(&repo).await?;
+
+            let branch = api::client::branches::get_by_name(&remote_repo, DEFAULT_BRANCH_NAME)
+                .await?
+                .unwrap();
+
+            // Upload the same file to the same location
+            let opts = UploadOpts {
+                paths: vec![file.to_path_buf()],
+                dst: Path::new("train").to_path_buf(),
+                host: remote_repo.host(),
+                scheme: remote_repo.scheme(),
+                remote: remote_repo.name.clone(),
+                delete: vec![],
+                routes: HashMap::new(),
+                preserve_structure: false,
+                workspace_id: None,
+                commit: true,
+                skip_unchanged: true,
+                branch: None,
+                message: "uploading unchanged file".to_string(),
+            };
+            upload(&remote_repo, &opts).await?;
+
+            // Nothing changed, so no new commit was made
+            let after = api::client::branches::get_by_name(&remote_repo, DEFAULT_BRANCH_NAME)
+                .await?
+                .unwrap();
+            assert_eq!(branch.commit_id, after.commit_id);
+
+            Ok(())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_remote_upload_batches_into_workspace() -> Result<(), OxenError> {
+        test::run_empty_local_repo_test_async(|mut repo| async move {
+            let readme = repo.path.join("README.md");
+            util::fs::write_to_path(&readme, "readme")?;
+            repositories::add(&repo, &readme)?;
+            repositories::commit(&repo, "adding readme")?;
+
+            let remote = test::repo_remote_url_from(&repo.dirname());
+            command::config::set_remote(&mut repo, constants::DEFAULT_REMOTE_NAME, &remote)?;
+            let remote_repo = test::create_remote_repo(&repo).await?;
+            repositories::push(&repo).await?;
+
+            let workspace_id = "upload-batches";
+            let upload_opts = |paths: Vec<std::path::PathBuf>, commit: bool| UploadOpts {
+                paths,
+                dst: Path::new("").to_path_buf(),
+                host: remote_repo.host(),
+                scheme: remote_repo.scheme(),
+                remote: remote_repo.name.clone(),
+                delete: vec![],
+                routes: HashMap::new(),
+                preserve_structure: false,
+                workspace_id: Some(workspace_id.to_string()),
+                commit,
+                skip_unchanged: false,
+                branch: None,
+                message: "adding both batches".to_string(),
+            };
+
+            // Two uploads into the same workspace do not commit anything
+            let first = repo.path.join("first.txt");
+            util::fs::write_to_path(&first, "first")?;
+            upload(&remote_repo, &upload_opts(vec![first], false)).await?;
+            let second = repo.path.join("second.txt");
+            util::fs::write_to_path(&second, "second")?;
+            upload(&remote_repo, &upload_opts(vec![second], false)).await?;
+
+            let entries =
+                api::client::dir::list(&remote_repo, DEFAULT_BRANCH_NAME, Path::new(""), 1, 10)
+                    .await?;
+            assert_eq!(entries.entries.len(), 1);
+
+            // Then one commit has both files
+            upload(&remote_repo, &upload_opts(vec![], true)).await?;
+            let entries =
+                api::client::dir::list(&remote_repo, DEFAULT_BRANCH_NAME, Path::new(""), 1, 10)
+                    .await?;
+            assert_eq!(entries.entries.len(), 3);
+
+            Ok(())
+        })
+        .await
+    }
+
     #[tokio::test]
     async fn test_remote_upload_file_to_sub_dir() -> Result<(), OxenError> {
         test::run_empty_local_repo_test_async(|mut repo| async move {
@@ -128,6 +238,12 @@ mod tests {
                 host: remote_repo.host(),
                 scheme: remote_repo.scheme(),
                 remote: remote_repo.name.clone(),
+                delete: vec![],
+                routes: HashMap::new(),
+                preserve_structure: false,
+                workspace_id: None,
+                commit: true,
+                skip_unchanged: false,
                 branch: None,
                 message: "adding new file".to_string(),
             };
@@ -195,6 +311,12 @@ mod tests {
                 host: remote_repo.host(),
                 scheme: remote_repo.scheme(),
                 remote: remote_repo.name.clone(),
+                delete: vec![],
+                routes: HashMap::new(),
+                preserve_structure: false,
+                workspace_id: None,
+                commit: true,
+                skip_unchanged: false,
                 branch: Some(branch_name.clone()),
                 message: "adding new file".to_string(),
             };
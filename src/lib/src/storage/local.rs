@@ -1,20 +1,41 @@
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{self, Read, Write};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
-use crate::constants::{VERSION_CHUNKS_DIR, VERSION_CHUNK_FILE_NAME, VERSION_FILE_NAME};
+use crate::constants::{
+    COMPRESSED_VERSION_FILE_NAME, VERSION_CHUNKS_DIR, VERSION_CHUNK_FILE_NAME, VERSION_FILE_NAME,
+    VERSION_MANIFEST_FILE_NAME,
+};
 use crate::error::OxenError;
 use crate::storage::version_store::ReadSeek;
 use crate::util;
+use tempfile::NamedTempFile;
+use walkdir::WalkDir;
 
 use super::version_store::VersionStore;
 
+/// Extensions of data that is already compressed, compressing it again only costs time
+pub const DEFAULT_COMPRESSION_SKIP_EXTENSIONS: [&str; 20] = [
+    "png", "jpg", "jpeg", "gif", "webp", "heic", "mp4", "mov", "avi", "mkv", "webm", "mp3", "ogg",
+    "flac", "zip", "gz", "zst", "bz2", "xz", "parquet",
+];
+
+const COMPRESSION_LEVEL: i32 = 3;
+
 /// Local filesystem implementation of version storage
 #[derive(Debug)]
 pub struct LocalVersionStore {
     /// Root path where versions are stored
     root_path: PathBuf,
+    /// Compress version files with zstd on write
+    compress: bool,
+    /// Extensions that are stored as-is even when compression is enabled
+    skip_extensions: Vec<String>,
+    /// Re-hash uncompressed versions before moving them into place
+    verify_writes: bool,
+    /// Re-hash versions when they are read back to catch corruption on disk
+    verify_reads: bool,
 }
 
 impl LocalVersionStore {
@@ -25,7 +46,51 @@ impl LocalVersionStore {
     pub fn new(root_path: impl AsRef<Path>) -> Self {
         Self {
             root_path: root_path.as_ref().to_path_buf(),
+            compress: false,
+            skip_extensions: vec![],
+            verify_writes: false,
+            verify_reads: false,
+        }
+    }
+
+    /// Re-hash each uncompressed version after it is written and reject it if the hash does not
+    /// match the one it is being stored under.
+    pub fn with_write_verification(mut self) -> Self {
+        self.verify_writes = true;
+        self
+    }
+
+    /// Re-hash each version when it is read and error if it no longer matches the hash it is
+    /// stored under. This costs a full read and hash per version, so it is off by default.
+    pub fn with_read_verification(mut self) -> Self {
+        self.verify_reads = true;
+        self
+    }
+
+    fn verify_read(&self, hash: &str, data: &[u8]) -> Result<(), OxenError> {
+        if self.verify_reads {
+            check_version_hash(hash, &util::hasher::hash_buffer(data))?;
         }
+        Ok(())
+    }
+
+    fn verify_read_from_path(&self, hash: &str, path: &Path) -> Result<(), OxenError> {
+        if self.verify_reads {
+            check_version_hash(hash, &util::hasher::hash_file_contents(path)?)?;
+        }
+        Ok(())
+    }
+
+    /// Compress new version files with zstd, except for files with one of the `skip_extensions`.
+    /// Versions are still addressed by the hash of their uncompressed contents, and reads
+    /// transparently decompress, so compressed and uncompressed versions can live side by side.
+    pub fn with_compression(mut self, skip_extensions: Vec<String>) -> Self {
+        self.compress = true;
+        self.skip_extensions = skip_extensions
+            .into_iter()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect();
+        self
     }
 
     /// Get the directory containing a version file
@@ -40,6 +105,97 @@ impl LocalVersionStore {
         self.version_dir(hash).join(VERSION_FILE_NAME)
     }
 
+    /// Get the full path for a compressed version file
+    fn compressed_version_path(&self, hash: &str) -> PathBuf {
+        self.version_dir(hash).join(COMPRESSED_VERSION_FILE_NAME)
+    }
+
+    /// Get the full path for the chunk manifest of a version stored as chunks
+    fn manifest_path(&self, hash: &str) -> PathBuf {
+        self.version_dir(hash).join(VERSION_MANIFEST_FILE_NAME)
+    }
+
+    /// The chunk hashes of a version that is only stored as a manifest, None if the whole
+    /// version is on disk
+    fn read_manifest(&self, hash: &str) -> Result<Option<Vec<String>>, OxenError> {
+        let manifest_path = self.manifest_path(hash);
+        if self.version_path(hash).exists()
+            || self.compressed_version_path(hash).exists()
+            || !manifest_path.exists()
+        {
+            return Ok(None);
+        }
+        let contents = util::fs::read_from_path(&manifest_path)?;
+        Ok(Some(contents.lines().map(String::from).collect()))
+    }
+
+    /// Write the chunks of a manifest back out as one file
+    fn write_chunks(
+        &self,
+        chunk_hashes: &[String],
+        writer: &mut dyn Write,
+    ) -> Result<(), OxenError> {
+        for chunk_hash in chunk_hashes {
+            writer.write_all(&self.get_version(chunk_hash)?)?;
+        }
+        Ok(())
+    }
+
+    fn should_compress(&self, file_path: Option<&Path>) -> bool {
+        if !self.compress {
+            return false;
+        }
+        let Some(extension) = file_path.and_then(|path| path.extension()) else {
+            return true;
+        };
+        let extension = extension.to_string_lossy().to_lowercase();
+        !self.skip_extensions.contains(&extension)
+    }
+
+    fn write_compressed(&self, hash: &str, reader: &mut dyn Read) -> Result<(), OxenError> {
+        let compressed_path = self.compressed_version_path(hash);
+        write_atomically(&compressed_path, |file, _| {
+            zstd::stream::copy_encode(reader, file, COMPRESSION_LEVEL)?;
+            Ok(())
+        })
+    }
+
+    fn write_uncompressed(&self, hash: &str, reader: &mut dyn Read) -> Result<(), OxenError> {
+        let version_path = self.version_path(hash);
+        write_atomically(&version_path, |file, tmp_path| {
+            io::copy(reader, file)?;
+            file.sync_all()?;
+            if self.verify_writes {
+                let written_hash = util::hasher::hash_file_contents(tmp_path)?;
+                if written_hash != hash {
+                    return Err(OxenError::basic_str(format!(
+                        "Version written for {hash} has hash {written_hash}"
+                    )));
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Decompress or reassemble a version that is not stored as a plain file into a temp file
+    /// that is deleted once the returned handle is dropped, so the version dir keeps only the
+    /// compressed or chunked copy.
+    fn unpack_to_temp_file(&self, hash: &str) -> Result<File, OxenError> {
+        let version_dir = self.version_dir(hash);
+        let mut tmp_file = NamedTempFile::new_in(&version_dir)
+            .map_err(|err| OxenError::file_error(&version_dir, err))?;
+        if let Some(chunk_hashes) = self.read_manifest(hash)? {
+            self.write_chunks(&chunk_hashes, tmp_file.as_file_mut())?;
+        } else {
+            let compressed = File::open(self.compressed_version_path(hash))?;
+            zstd::stream::copy_decode(compressed, tmp_file.as_file_mut())?;
+        }
+        self.verify_read_from_path(hash, tmp_file.path())?;
+        let mut file = tmp_file.into_file();
+        file.seek(SeekFrom::Start(0))?;
+        Ok(file)
+    }
+
     /// Get the directory containing all the chunks for a version file
     fn version_chunks_dir(&self, hash: &str) -> PathBuf {
         self.version_dir(hash).join(VERSION_CHUNKS_DIR)
@@ -58,6 +214,37 @@ impl LocalVersionStore {
     }
 }
 
+fn check_version_hash(hash: &str, read_hash: &str) -> Result<(), OxenError> {
+    if read_hash != hash {
+        return Err(OxenError::basic_str(format!(
+            "Version {hash} is corrupted, its contents hash to {read_hash}"
+        )));
+    }
+    Ok(())
+}
+
+/// Write to a uniquely named temp file next to `final_path` and only move it into place once
+/// the write succeeded, so a crash or failed write is never mistaken for a complete version.
+/// Each writer gets its own temp file, so threads storing the same hash at once don't clobber
+/// each other, and whichever finishes last just finds the version already in place.
+fn write_atomically(
+    final_path: &Path,
+    write: impl FnOnce(&mut File, &Path) -> Result<(), OxenError>,
+) -> Result<(), OxenError> {
+    let dir = final_path.parent().unwrap_or(Path::new("."));
+    let mut tmp_file = NamedTempFile::new_in(dir).map_err(|err| OxenError::file_error(dir, err))?;
+    let tmp_path = tmp_file.path().to_path_buf();
+    // The temp file is removed when dropped if the write fails
+    write(tmp_file.as_file_mut(), &tmp_path)?;
+    if let Err(err) = tmp_file.persist(final_path) {
+        if final_path.exists() {
+            return Ok(());
+        }
+        return Err(OxenError::file_error(final_path, err.error));
+    }
+    Ok(())
+}
+
 impl VersionStore for LocalVersionStore {
     fn init(&self) -> Result<(), OxenError> {
         util::fs::create_dir_all(&self.root_path)
@@ -67,9 +254,16 @@ impl VersionStore for LocalVersionStore {
         let version_dir = self.version_dir(hash);
         util::fs::create_dir_all(&version_dir)?;
 
-        let version_path = self.version_path(hash);
-        if !version_path.exists() {
-            fs::copy(file_path, &version_path)?;
+        if self.version_exists(hash)? {
+            return Ok(());
+        }
+
+        if self.should_compress(Some(file_path)) {
+            let mut file = File::open(file_path)?;
+            self.write_compressed(hash, &mut file)?;
+        } else {
+            let mut file = File::open(file_path)?;
+            self.write_uncompressed(hash, &mut file)?;
         }
         Ok(())
     }
@@ -82,11 +276,14 @@ impl VersionStore for LocalVersionStore {
         let version_dir = self.version_dir(hash);
         util::fs::create_dir_all(&version_dir)?;
 
-        let version_path = self.version_path(hash);
+        if self.version_exists(hash)? {
+            return Ok(());
+        }
 
-        if !version_path.exists() {
-            let mut file = File::create(&version_path)?;
-            io::copy(reader, &mut file)?;
+        if self.should_compress(None) {
+            self.write_compressed(hash, reader)?;
+        } else {
+            self.write_uncompressed(hash, reader)?;
         }
 
         Ok(())
@@ -96,39 +293,99 @@ impl VersionStore for LocalVersionStore {
         let version_dir = self.version_dir(hash);
         util::fs::create_dir_all(&version_dir)?;
 
-        let version_path = self.version_path(hash);
+        if self.version_exists(hash)? {
+            return Ok(());
+        }
 
-        if !version_path.exists() {
-            let mut file = File::create(&version_path)?;
-            file.write_all(data)?;
+        if self.should_compress(None) {
+            self.write_compressed(hash, &mut &data[..])?;
+        } else {
+            self.write_uncompressed(hash, &mut &data[..])?;
         }
 
         Ok(())
     }
 
+    fn store_version_manifest(&self, hash: &str, chunk_hashes: &[String]) -> Result<(), OxenError> {
+        let version_dir = self.version_dir(hash);
+        util::fs::create_dir_all(&version_dir)?;
+
+        if self.version_exists(hash)? {
+            return Ok(());
+        }
+
+        let manifest_path = self.manifest_path(hash);
+        write_atomically(&manifest_path, |file, _| {
+            file.write_all(chunk_hashes.join("\n").as_bytes())?;
+            Ok(())
+        })
+    }
+
     fn open_version(&self, hash: &str) -> Result<Box<dyn ReadSeek>, OxenError> {
-        let path = self.version_path(hash);
-        let file = File::open(&path)?;
-        Ok(Box::new(file))
+        let version_path = self.version_path(hash);
+        if version_path.exists() || !self.version_exists(hash)? {
+            self.verify_read_from_path(hash, &version_path)?;
+            let file = File::open(&version_path)?;
+            return Ok(Box::new(file));
+        }
+        // zstd streams can't seek, so hand out a decompressed temp copy
+        Ok(Box::new(self.unpack_to_temp_file(hash)?))
     }
 
     fn get_version(&self, hash: &str) -> Result<Vec<u8>, OxenError> {
-        let path = self.version_path(hash);
-        Ok(fs::read(&path)?)
+        if let Some(chunk_hashes) = self.read_manifest(hash)? {
+            let mut data = vec![];
+            self.write_chunks(&chunk_hashes, &mut data)?;
+            self.verify_read(hash, &data)?;
+            return Ok(data);
+        }
+        let version_path = self.version_path(hash);
+        let compressed_path = self.compressed_version_path(hash);
+        let data = if !version_path.exists() && compressed_path.exists() {
+            let compressed = File::open(&compressed_path)?;
+            zstd::stream::decode_all(compressed)?
+        } else {
+            fs::read(&version_path)?
+        };
+        self.verify_read(hash, &data)?;
+        Ok(data)
     }
 
     fn get_version_path(&self, hash: &str) -> Result<PathBuf, OxenError> {
-        Ok(self.version_path(hash))
+        // Compressed and chunked versions have no plain file, read those with `get_version_file`
+        let version_path = self.version_path(hash);
+        if !version_path.exists() {
+            return Err(OxenError::path_does_not_exist(&version_path));
+        }
+        self.verify_read_from_path(hash, &version_path)?;
+        Ok(version_path)
     }
 
     fn copy_version_to_path(&self, hash: &str, dest_path: &Path) -> Result<(), OxenError> {
         let version_path = self.version_path(hash);
-        fs::copy(&version_path, dest_path)?;
+        let compressed_path = self.compressed_version_path(hash);
+        if let Some(chunk_hashes) = self.read_manifest(hash)? {
+            let mut dest = File::create(dest_path)?;
+            self.write_chunks(&chunk_hashes, &mut dest)?;
+        } else if !version_path.exists() && compressed_path.exists() {
+            let compressed = File::open(&compressed_path)?;
+            let dest = File::create(dest_path)?;
+            zstd::stream::copy_decode(compressed, dest)?;
+        } else {
+            fs::copy(&version_path, dest_path)?;
+        }
+        // Don't leave corrupted contents in the working tree
+        if let Err(err) = self.verify_read_from_path(hash, dest_path) {
+            let _ = fs::remove_file(dest_path);
+            return Err(err);
+        }
         Ok(())
     }
 
     fn version_exists(&self, hash: &str) -> Result<bool, OxenError> {
-        Ok(self.version_path(hash).exists())
+        Ok(self.version_path(hash).exists()
+            || self.compressed_version_path(hash).exists()
+            || self.manifest_path(hash).exists())
     }
 
     fn delete_version(&self, hash: &str) -> Result<(), OxenError> {
@@ -139,6 +396,24 @@ impl VersionStore for LocalVersionStore {
         Ok(())
     }
 
+    fn version_size(&self, hash: &str) -> Result<u64, OxenError> {
+        let version_dir = self.version_dir(hash);
+        if !version_dir.exists() {
+            return Ok(0);
+        }
+        let mut size = 0;
+        for entry in WalkDir::new(&version_dir) {
+            let entry = entry.map_err(|e| OxenError::basic_str(e.to_string()))?;
+            if entry.file_type().is_file() {
+                let metadata = entry
+                    .metadata()
+                    .map_err(|e| OxenError::basic_str(e.to_string()))?;
+                size += metadata.len();
+            }
+        }
+        Ok(size)
+    }
+
     fn list_versions(&self) -> Result<Vec<String>, OxenError> {
         let mut versions = Vec::new();
 
@@ -245,8 +520,21 @@ impl VersionStore for LocalVersionStore {
     }
 
     fn storage_settings(&self) -> HashMap<String, String> {
-        // Local storage doesn't need any special settings
-        HashMap::new()
+        let mut settings = HashMap::new();
+        if self.compress {
+            settings.insert("compression".to_string(), "zstd".to_string());
+            settings.insert(
+                "compression_skip_extensions".to_string(),
+                self.skip_extensions.join(","),
+            );
+        }
+        if self.verify_writes {
+            settings.insert("verify_writes".to_string(), "true".to_string());
+        }
+        if self.verify_reads {
+            settings.insert("verify_on_read".to_string(), "true".to_string());
+        }
+        settings
     }
 }
 
@@ -310,6 +598,71 @@ mod tests {
         assert_eq!(retrieved, data);
     }
 
+    struct FailingReader {
+        remaining: usize,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.remaining == 0 {
+                return Err(io::Error::other("disk went away"));
+            }
+            let n = buf.len().min(self.remaining);
+            buf[..n].fill(b'x');
+            self.remaining -= n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_failed_write_leaves_no_version() {
+        let (_temp_dir, store) = setup();
+        let hash = "abcdef1234567890";
+
+        let mut reader = FailingReader { remaining: 64 };
+        let result = store.store_version_from_reader(hash, &mut reader);
+        assert!(result.is_err());
+
+        assert!(!store.version_path(hash).exists());
+        // The temp file was cleaned up too
+        assert_eq!(fs::read_dir(store.version_dir(hash)).unwrap().count(), 0);
+        assert!(!store.version_exists(hash).unwrap());
+    }
+
+    #[test]
+    fn test_write_verification_rejects_wrong_hash() {
+        let (_temp_dir, store) = setup();
+        let store = store.with_write_verification();
+        let data = b"test data";
+
+        let result = store.store_version("abcdef1234567890", data);
+        assert!(result.is_err());
+        assert!(!store.version_path("abcdef1234567890").exists());
+
+        let hash = util::hasher::hash_buffer(data);
+        store.store_version(&hash, data).unwrap();
+        assert_eq!(store.get_version(&hash).unwrap(), data);
+    }
+
+    #[test]
+    fn test_read_verification_detects_corruption() {
+        let (temp_dir, store) = setup();
+        let store = store.with_read_verification();
+        let data = b"test data";
+        let hash = util::hasher::hash_buffer(data);
+        store.store_version(&hash, data).unwrap();
+        assert_eq!(store.get_version(&hash).unwrap(), data);
+
+        // Flip the contents on disk
+        fs::write(store.version_path(&hash), b"test dada").unwrap();
+        assert!(store.get_version(&hash).is_err());
+        assert!(store.open_version(&hash).is_err());
+
+        let dest = temp_dir.path().join("restored.txt");
+        assert!(store.copy_version_to_path(&hash, &dest).is_err());
+        assert!(!dest.exists());
+    }
+
     #[test]
     fn test_open_version() {
         let (_temp_dir, store) = setup();
@@ -330,6 +683,119 @@ mod tests {
         assert_eq!(retrieved, data);
     }
 
+    fn setup_compressed() -> (TempDir, LocalVersionStore) {
+        let temp_dir = TempDir::new().unwrap();
+        let store = LocalVersionStore::new(temp_dir.path())
+            .with_compression(vec!["png".to_string(), ".MP4".to_string()]);
+        store.init().unwrap();
+        (temp_dir, store)
+    }
+
+    #[test]
+    fn test_compressed_round_trip() {
+        let (temp_dir, store) = setup_compressed();
+        let hash = "abcdef1234567890";
+        let data = "a very compressible line\n".repeat(1000);
+
+        store.store_version(hash, data.as_bytes()).unwrap();
+        assert!(store.version_exists(hash).unwrap());
+        let compressed_path = store.compressed_version_path(hash);
+        assert!(compressed_path.exists());
+        assert!(!store.version_path(hash).exists());
+        assert!(fs::metadata(&compressed_path).unwrap().len() < data.len() as u64);
+
+        // Reads see the uncompressed contents
+        assert_eq!(store.get_version(hash).unwrap(), data.as_bytes());
+
+        let mut retrieved = Vec::new();
+        store
+            .open_version(hash)
+            .unwrap()
+            .read_to_end(&mut retrieved)
+            .unwrap();
+        assert_eq!(retrieved, data.as_bytes());
+
+        let dest_path = temp_dir.path().join("copied.txt");
+        store.copy_version_to_path(hash, &dest_path).unwrap();
+        assert_eq!(fs::read_to_string(&dest_path).unwrap(), data);
+
+        // Reading never leaves a decompressed copy behind in the version dir
+        assert!(!store.version_path(hash).exists());
+        assert_eq!(fs::read_dir(store.version_dir(hash)).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_compressed_version_file() {
+        let (_temp_dir, store) = setup_compressed();
+        let hash = "abcdef1234567891";
+        let data = "a,b\n1,2\n".repeat(100);
+        store.store_version(hash, data.as_bytes()).unwrap();
+
+        // There is no plain file to point at
+        assert!(store.get_version_path(hash).is_err());
+
+        // The version file is unpacked with the extension and removed once dropped
+        let version_file = store.get_version_file(hash, "csv").unwrap();
+        let unpacked_path = version_file.path().to_path_buf();
+        assert_eq!(unpacked_path.extension().unwrap(), "csv");
+        assert_eq!(fs::read_to_string(&version_file).unwrap(), data);
+        drop(version_file);
+        assert!(!unpacked_path.exists());
+
+        // Plain versions are read in place
+        let plain_hash = "cccccc1234567891";
+        let (_plain_dir, plain_store) = setup();
+        plain_store
+            .store_version(plain_hash, data.as_bytes())
+            .unwrap();
+        let version_file = plain_store.get_version_file(plain_hash, "csv").unwrap();
+        assert_eq!(version_file.path(), plain_store.version_path(plain_hash));
+        assert!(store.get_version_file("eeeeee1234567891", "csv").is_err());
+    }
+
+    #[test]
+    fn test_compressed_from_path_and_reader() {
+        let (temp_dir, store) = setup_compressed();
+        let text_path = temp_dir.path().join("data.jsonl");
+        let text = "{\"label\": \"dog\"}\n".repeat(100);
+        fs::write(&text_path, &text).unwrap();
+
+        store
+            .store_version_from_path("aaaaaa1234567890", &text_path)
+            .unwrap();
+        assert!(store.compressed_version_path("aaaaaa1234567890").exists());
+        assert_eq!(
+            store.get_version("aaaaaa1234567890").unwrap(),
+            text.as_bytes()
+        );
+
+        let mut cursor = Cursor::new(text.as_bytes().to_vec());
+        store
+            .store_version_from_reader("bbbbbb1234567890", &mut cursor)
+            .unwrap();
+        assert_eq!(
+            store.get_version("bbbbbb1234567890").unwrap(),
+            text.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_compression_skips_extensions() {
+        let (temp_dir, store) = setup_compressed();
+        let data = b"not really a png";
+        for (hash, name) in [
+            ("cccccc1234567890", "img.png"),
+            ("dddddd1234567890", "clip.mp4"),
+        ] {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, data).unwrap();
+            store.store_version_from_path(hash, &path).unwrap();
+            assert!(store.version_path(hash).exists());
+            assert!(!store.compressed_version_path(hash).exists());
+            assert_eq!(store.get_version(hash).unwrap(), data);
+        }
+    }
+
     #[test]
     fn test_version_exists() {
         let (_temp_dir, store) = setup();
@@ -409,6 +875,42 @@ mod tests {
         store.delete_version(hash).unwrap();
     }
 
+    #[test]
+    fn test_store_version_manifest_reassembles_chunks() {
+        let (temp_dir, store) = setup();
+        let chunks: [&[u8]; 3] = [b"first chunk ", b"second chunk ", b"first chunk "];
+        let mut chunk_hashes = vec![];
+        for chunk in chunks {
+            let chunk_hash = util::hasher::hash_buffer(chunk);
+            store.store_version(&chunk_hash, chunk).unwrap();
+            chunk_hashes.push(chunk_hash);
+        }
+        let data = chunks.concat();
+        let hash = util::hasher::hash_buffer(&data);
+        store.store_version_manifest(&hash, &chunk_hashes).unwrap();
+
+        // Only the manifest is stored for the whole version, the repeated chunk is stored once
+        assert!(store.version_exists(&hash).unwrap());
+        assert!(!store.version_path(&hash).exists());
+        assert_eq!(store.list_versions().unwrap().len(), 3);
+
+        assert_eq!(store.get_version(&hash).unwrap(), data);
+        let dest_path = temp_dir.path().join("restored.txt");
+        store.copy_version_to_path(&hash, &dest_path).unwrap();
+        assert_eq!(fs::read(&dest_path).unwrap(), data);
+        let mut retrieved = Vec::new();
+        store
+            .open_version(&hash)
+            .unwrap()
+            .read_to_end(&mut retrieved)
+            .unwrap();
+        assert_eq!(retrieved, data);
+
+        // Reassembling the chunks doesn't store the whole version after all
+        assert!(!store.version_path(&hash).exists());
+        assert_eq!(fs::read_dir(store.version_dir(&hash)).unwrap().count(), 1);
+    }
+
     #[test]
     fn test_store_and_get_version_chunk() {
         let (_temp_dir, store) = setup();
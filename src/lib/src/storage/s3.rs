@@ -53,6 +53,15 @@ impl VersionStore for S3VersionStore {
         Err(OxenError::basic_str("S3VersionStore not yet implemented"))
     }
 
+    fn store_version_manifest(
+        &self,
+        _hash: &str,
+        _chunk_hashes: &[String],
+    ) -> Result<(), OxenError> {
+        // TODO: Implement S3 chunk manifests
+        Err(OxenError::basic_str("S3VersionStore not yet implemented"))
+    }
+
     fn open_version(&self, _hash: &str) -> Result<Box<dyn ReadSeek>, OxenError> {
         // TODO: Implement S3 version opening
         Err(OxenError::basic_str("S3VersionStore not yet implemented"))
@@ -103,6 +112,11 @@ impl VersionStore for S3VersionStore {
         Err(OxenError::basic_str("S3VersionStore not yet implemented"))
     }
 
+    fn version_size(&self, _hash: &str) -> Result<u64, OxenError> {
+        // TODO: Implement S3 version size
+        Err(OxenError::basic_str("S3VersionStore not yet implemented"))
+    }
+
     fn list_versions(&self) -> Result<Vec<String>, OxenError> {
         // TODO: Implement S3 version listing
         Err(OxenError::basic_str("S3VersionStore not yet implemented"))
@@ -1,14 +1,17 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::io::{Read, Seek};
+use std::ops::Deref;
 use std::panic::RefUnwindSafe;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
 
 use crate::constants;
 use crate::error::OxenError;
+use crate::storage::local::DEFAULT_COMPRESSION_SKIP_EXTENSIONS;
 use crate::storage::{LocalVersionStore, S3VersionStore};
 use crate::util;
 
@@ -18,18 +21,73 @@ pub struct StorageConfig {
     /// Storage type: "local" or "s3"
     #[serde(rename = "type")]
     pub type_: String,
-    /// Backend-specific settings
+    /// Backend-specific settings, ie `bucket` for s3 or `compression = "zstd"` for local
     #[serde(default)]
     pub settings: HashMap<String, String>,
 }
 
-/// Trait for types that implement Read and Seek
-pub trait ReadSeek: Read + Seek {}
+impl StorageConfig {
+    /// Check the type is supported and the settings it needs are present, before anything is written
+    pub fn validate(&self) -> Result<(), OxenError> {
+        match self.type_.as_str() {
+            "local" => match self.settings.get("compression").map(|c| c.as_str()) {
+                None | Some("none") | Some("zstd") => Ok(()),
+                Some(other) => Err(OxenError::basic_str(format!(
+                    "Unsupported compression for local storage: {other}, expected none or zstd"
+                ))),
+            },
+            "s3" => match self.settings.get("bucket") {
+                Some(bucket) if !bucket.is_empty() => Ok(()),
+                _ => Err(OxenError::basic_str(
+                    "S3 storage needs a bucket, set it with `--storage-setting bucket=<name>`",
+                )),
+            },
+            other => Err(OxenError::basic_str(format!(
+                "Unsupported storage type: {other}, expected local or s3"
+            ))),
+        }
+    }
+}
+
+/// Trait for types that implement Read and Seek, and can be moved across threads
+pub trait ReadSeek: Read + Seek + Send {}
 
-/// Implement ReadSeek for any type that implements both Read and Seek
-impl<T: Read + Seek> ReadSeek for T {}
+/// Implement ReadSeek for any type that implements Read, Seek and Send
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// A version as a file on disk, for readers that need a path such as polars and duckdb. Plain
+/// versions are read in place. Compressed and chunked versions are unpacked into a temp file that
+/// is removed when this is dropped, so keep it alive for as long as the path is read.
+#[derive(Debug)]
+pub struct VersionFile {
+    path: PathBuf,
+    _unpacked: Option<NamedTempFile>,
+}
+
+impl VersionFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Deref for VersionFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for VersionFile {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
 
 /// Trait defining operations for version file storage backends
+///
+/// Versions are keyed by the `Display` form of their `MerkleHash`, the lowercase hex of the
+/// 128 bit content hash without zero padding. Changing this format would orphan existing versions.
 pub trait VersionStore: Debug + Send + Sync + RefUnwindSafe + 'static {
     /// Initialize the storage backend
     fn init(&self) -> Result<(), OxenError>;
@@ -56,6 +114,15 @@ pub trait VersionStore: Debug + Send + Sync + RefUnwindSafe + 'static {
     /// * `data` - The raw bytes to store
     fn store_version(&self, hash: &str, data: &[u8]) -> Result<(), OxenError>;
 
+    /// Store a version as the ordered list of chunks it is made of, instead of the whole file.
+    /// Each chunk must already be stored as a version of its own, so chunks shared between
+    /// versions are only stored once. Reads reassemble the chunks transparently.
+    ///
+    /// # Arguments
+    /// * `hash` - The content hash of the whole version
+    /// * `chunk_hashes` - The hashes of the chunks, in order
+    fn store_version_manifest(&self, hash: &str, chunk_hashes: &[String]) -> Result<(), OxenError>;
+
     /// Store a chunk of a version file, to be combined into a full version file
     ///
     /// # Arguments
@@ -102,12 +169,45 @@ pub trait VersionStore: Debug + Send + Sync + RefUnwindSafe + 'static {
     /// * `hash` - The content hash of the version to retrieve
     fn get_version(&self, hash: &str) -> Result<Vec<u8>, OxenError>;
 
-    /// Get the path to a version file
+    /// Get the path to a version stored as a plain file. Errors for versions stored compressed
+    /// or as chunks, read those with `get_version_file`, `open_version` or `copy_version_to_path`.
     ///
     /// # Arguments
     /// * `hash` - The content hash of the version to retrieve
     fn get_version_path(&self, hash: &str) -> Result<PathBuf, OxenError>;
 
+    /// Get a file on disk to read a version from, however it is stored. Versions that are not a
+    /// plain file are unpacked to a temp file ending in `.{extension}`, for readers that go by
+    /// the extension.
+    ///
+    /// # Arguments
+    /// * `hash` - The content hash of the version to retrieve
+    /// * `extension` - The extension of the file the version is a version of, may be empty
+    fn get_version_file(&self, hash: &str, extension: &str) -> Result<VersionFile, OxenError> {
+        if !self.version_exists(hash)? {
+            return Err(OxenError::basic_str(format!(
+                "Version {hash} is not in the version store"
+            )));
+        }
+        if let Ok(path) = self.get_version_path(hash) {
+            return Ok(VersionFile {
+                path,
+                _unpacked: None,
+            });
+        }
+        let suffix = if extension.is_empty() {
+            String::new()
+        } else {
+            format!(".{extension}")
+        };
+        let tmp_file = tempfile::Builder::new().suffix(&suffix).tempfile()?;
+        self.copy_version_to_path(hash, tmp_file.path())?;
+        Ok(VersionFile {
+            path: tmp_file.path().to_path_buf(),
+            _unpacked: Some(tmp_file),
+        })
+    }
+
     /// Copy a version to a destination path
     ///
     /// # Arguments
@@ -130,6 +230,12 @@ pub trait VersionStore: Debug + Send + Sync + RefUnwindSafe + 'static {
     /// List all versions
     fn list_versions(&self) -> Result<Vec<String>, OxenError>;
 
+    /// The number of bytes a version takes up in the store, including any chunks
+    ///
+    /// # Arguments
+    /// * `hash` - The content hash of the version
+    fn version_size(&self, hash: &str) -> Result<u64, OxenError>;
+
     /// Get the storage type identifier (e.g., "local", "s3")
     fn storage_type(&self) -> &str;
 
@@ -149,7 +255,25 @@ pub fn create_version_store(
                 let versions_dir = util::fs::oxen_hidden_dir(path)
                     .join(constants::VERSIONS_DIR)
                     .join(constants::FILES_DIR);
-                let store = LocalVersionStore::new(versions_dir);
+                let mut store = LocalVersionStore::new(versions_dir);
+                match config.settings.get("compression").map(|c| c.as_str()) {
+                    None | Some("none") => {}
+                    Some("zstd") => {
+                        store = store.with_compression(compression_skip_extensions(config));
+                    }
+                    Some(other) => {
+                        return Err(OxenError::basic_str(format!(
+                            "Unsupported compression: {}",
+                            other
+                        )));
+                    }
+                }
+                if config.settings.get("verify_writes").map(|v| v.as_str()) == Some("true") {
+                    store = store.with_write_verification();
+                }
+                if config.settings.get("verify_on_read").map(|v| v.as_str()) == Some("true") {
+                    store = store.with_read_verification();
+                }
                 store.init()?;
                 Ok(Arc::new(store))
             }
@@ -183,3 +307,19 @@ pub fn create_version_store(
         }
     }
 }
+
+/// Extensions to store uncompressed, from the comma separated `compression_skip_extensions` setting.
+/// Defaults to common image, video, audio and archive formats that are already compressed.
+fn compression_skip_extensions(config: &StorageConfig) -> Vec<String> {
+    match config.settings.get("compression_skip_extensions") {
+        Some(extensions) => extensions
+            .split(',')
+            .map(|ext| ext.trim().to_string())
+            .filter(|ext| !ext.is_empty())
+            .collect(),
+        None => DEFAULT_COMPRESSION_SKIP_EXTENSIONS
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect(),
+    }
+}
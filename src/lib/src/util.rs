@@ -1,6 +1,7 @@
 //! Various utility functions
 //!
 
+pub mod chunker;
 pub mod concurrency;
 pub mod fs;
 pub mod hasher;
@@ -0,0 +1,119 @@
+//! Content-defined chunking for `oxen add --chunked-hash`.
+//!
+//! Chunk boundaries are picked with a gear rolling hash over the bytes themselves rather than at
+//! fixed offsets, so an insert or append only changes the chunks around the edit and the rest
+//! dedup against the previous version.
+
+use std::io::Read;
+
+use crate::error::OxenError;
+
+/// Files smaller than this are always stored whole, chunking them would not save anything
+pub const CHUNKED_HASH_MIN_FILE_SIZE: u64 = 8 * 1024 * 1024;
+
+const MIN_CHUNK_SIZE: usize = 256 * 1024;
+const MAX_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+/// 20 bits set, for an average of ~1MB between boundaries after the minimum
+const BOUNDARY_MASK: u64 = 0xFFFFF << 44;
+
+const GEAR: [u64; 256] = gear_table();
+
+/// Fixed pseudo random values for each byte, generated with splitmix64. Changing these moves
+/// every chunk boundary, so they must stay the same across releases.
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Split the reader into content-defined chunks, calling `f` with each one in order
+pub fn for_each_chunk(
+    reader: &mut impl Read,
+    mut f: impl FnMut(&[u8]) -> Result<(), OxenError>,
+) -> Result<(), OxenError> {
+    let mut chunk = Vec::with_capacity(MAX_CHUNK_SIZE);
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut hash: u64 = 0;
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        for &byte in &buffer[..n] {
+            chunk.push(byte);
+            hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+            let at_boundary = chunk.len() >= MIN_CHUNK_SIZE && hash & BOUNDARY_MASK == 0;
+            if at_boundary || chunk.len() >= MAX_CHUNK_SIZE {
+                f(&chunk)?;
+                chunk.clear();
+                hash = 0;
+            }
+        }
+    }
+    if !chunk.is_empty() {
+        f(&chunk)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::util;
+
+    fn pseudo_random_bytes(len: usize) -> Vec<u8> {
+        let mut state: u64 = 42;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn chunk_hashes(data: &[u8]) -> Result<Vec<u128>, OxenError> {
+        let mut hashes = vec![];
+        let mut joined = vec![];
+        for_each_chunk(&mut &data[..], |chunk| {
+            assert!(chunk.len() <= MAX_CHUNK_SIZE);
+            hashes.push(util::hasher::hash_buffer_128bit(chunk));
+            joined.extend_from_slice(chunk);
+            Ok(())
+        })?;
+        assert_eq!(joined, data);
+        Ok(hashes)
+    }
+
+    #[test]
+    fn test_insert_only_changes_nearby_chunks() -> Result<(), OxenError> {
+        let original = pseudo_random_bytes(16 * 1024 * 1024);
+        let mut edited = original.clone();
+        edited.splice(1024..1024, b"a few inserted bytes".iter().copied());
+
+        let original_chunks = chunk_hashes(&original)?;
+        let edited_chunks = chunk_hashes(&edited)?;
+        assert!(original_chunks.len() > 4);
+
+        let original_set: HashSet<u128> = original_chunks.iter().copied().collect();
+        let shared = edited_chunks
+            .iter()
+            .filter(|hash| original_set.contains(hash))
+            .count();
+        // Only the chunks around the edit change, the boundaries resync after it
+        assert!(shared >= edited_chunks.len() - 2);
+        Ok(())
+    }
+}
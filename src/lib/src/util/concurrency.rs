@@ -25,6 +25,11 @@ pub fn num_threads_for_items(num_items: usize) -> usize {
     }
 }
 
+/// Returns the number of concurrent downloads to run, defaulting to the number of CPUs
+pub fn num_download_jobs(jobs: Option<usize>) -> usize {
+    jobs.unwrap_or_else(num_cpus::get).max(1)
+}
+
 fn get_default_num_workers() -> usize {
     // Check how many CPUs we have
     let num_cpus = num_cpus::get();
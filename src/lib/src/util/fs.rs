@@ -589,21 +589,24 @@ pub fn list_files_in_dir(dir: &Path) -> Vec<PathBuf> {
 }
 
 pub fn rlist_paths_in_dir(dir: &Path) -> Vec<PathBuf> {
-    let mut files: Vec<PathBuf> = vec![];
-    if !dir.is_dir() {
-        return files;
-    }
+    walk_paths_in_dir(dir).collect()
+}
 
-    for entry in WalkDir::new(dir) {
-        match entry {
-            Ok(val) => {
-                let path = val.path();
-                files.push(path);
+/// Lazily yields every path under a directory (including the directory itself),
+/// so large trees can be processed without collecting all the paths up front
+pub fn walk_paths_in_dir(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    // A missing dir yields nothing instead of an error entry
+    let walker = dir.is_dir().then(|| WalkDir::new(dir));
+    walker
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| match entry {
+            Ok(val) => Some(val.path()),
+            Err(err) => {
+                eprintln!("walk_paths_in_dir Could not iterate over dir... {err}");
+                None
             }
-            Err(err) => eprintln!("rlist_paths_in_dir Could not iterate over dir... {err}"),
-        }
-    }
-    files
+        })
 }
 
 /// Recursively lists directories in a repo that are not .oxen directories
@@ -944,6 +947,146 @@ pub fn is_utf8(path: &Path) -> bool {
     }
 }
 
+/// Set or clear the executable bits of a file, a no-op on platforms without unix permissions
+pub fn set_executable(path: &Path, executable: bool) -> Result<(), OxenError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut permissions = std::fs::metadata(path)
+            .map_err(|e| OxenError::file_error(path, e))?
+            .permissions();
+        let mode = permissions.mode();
+        let new_mode = if executable {
+            mode | 0o111
+        } else {
+            mode & !0o111
+        };
+        if new_mode != mode {
+            permissions.set_mode(new_mode);
+            std::fs::set_permissions(path, permissions)
+                .map_err(|e| OxenError::file_error(path, e))?;
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, executable);
+    }
+    Ok(())
+}
+
+/// Whether the line endings of the file can be converted, only text files are
+pub fn is_normalizable_text(path: &Path) -> bool {
+    file_data_type(path) == EntryDataType::Text && is_utf8(path)
+}
+
+/// Replace the CRLF line endings with LF, None if there are none to replace
+pub fn crlf_to_lf(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.windows(2).any(|w| w == b"\r\n") {
+        return None;
+    }
+    let mut normalized = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter().peekable();
+    while let Some(&byte) = iter.next() {
+        if byte == b'\r' && iter.peek() == Some(&&b'\n') {
+            continue;
+        }
+        normalized.push(byte);
+    }
+    Some(normalized)
+}
+
+/// Replace the LF line endings with CRLF, leaving existing CRLF line endings alone
+pub fn lf_to_crlf(bytes: &[u8]) -> Vec<u8> {
+    let mut converted = Vec::with_capacity(bytes.len());
+    let mut prev = None;
+    for &byte in bytes {
+        if byte == b'\n' && prev != Some(b'\r') {
+            converted.push(b'\r');
+        }
+        converted.push(byte);
+        prev = Some(byte);
+    }
+    converted
+}
+
+/// Guess the data type of a file from its first few KB, for files without an extension to go on.
+/// Returns the data type and the extension the contents look like, so metadata can be parsed with it.
+pub fn sniff_data_type(path: &Path) -> Option<(EntryDataType, &'static str)> {
+    let bytes = read_first_n_bytes(path, constants::SNIFF_NUM_BYTES).ok()?;
+    if bytes.is_empty() {
+        return None;
+    }
+
+    // Formats infer does not know about
+    if bytes.starts_with(b"PAR1") {
+        return Some((EntryDataType::Tabular, "parquet"));
+    }
+    if bytes.starts_with(b"ARROW1") {
+        return Some((EntryDataType::Tabular, "arrow"));
+    }
+
+    // Magic bytes, ie PNG, JPEG, MP4, WAV
+    if let Some(kind) = infer::get(&bytes) {
+        let data_type = datatype_from_mimetype(path, kind.mime_type());
+        return Some((data_type, kind.extension()));
+    }
+
+    // The read may have cut a multi-byte character in half, which is still text
+    let text = match std::str::from_utf8(&bytes) {
+        Ok(text) => text,
+        Err(err) if err.error_len().is_none() => {
+            std::str::from_utf8(&bytes[..err.valid_up_to()]).ok()?
+        }
+        Err(_) => return None,
+    };
+    let is_truncated = bytes.len() == constants::SNIFF_NUM_BYTES;
+    match sniff_tabular_text(text, is_truncated) {
+        Some(extension) => Some((EntryDataType::Tabular, extension)),
+        None => Some((EntryDataType::Text, "txt")),
+    }
+}
+
+/// Look for json lines, a json array, or consistently delimited rows in the start of a text file
+fn sniff_tabular_text(text: &str, is_truncated: bool) -> Option<&'static str> {
+    let mut lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    // The last line may have been cut off by the read
+    if is_truncated {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        return None;
+    }
+
+    if lines.iter().all(|line| {
+        let line = line.trim();
+        line.starts_with('{') && line.ends_with('}')
+    }) {
+        return Some("jsonl");
+    }
+    if text.trim_start().starts_with('[') {
+        return Some("json");
+    }
+
+    if lines.len() < 2 {
+        return None;
+    }
+    for (delimiter, extension) in [('\t', "tsv"), (',', "csv")] {
+        let num_columns = lines[0].matches(delimiter).count();
+        if num_columns > 0
+            && lines
+                .iter()
+                .all(|line| line.matches(delimiter).count() == num_columns)
+        {
+            return Some(extension);
+        }
+    }
+    None
+}
+
 pub fn data_type_from_extension(path: &Path) -> EntryDataType {
     let ext = path.extension().unwrap_or_default().to_string_lossy();
     match ext.as_ref() {
@@ -1590,6 +1733,17 @@ pub fn remove_paths(src: &Path) -> Result<(), OxenError> {
 }
 
 pub fn is_modified_from_node(path: &Path, node: &FileNode) -> Result<bool, OxenError> {
+    is_modified_from_node_with_opts(path, node, false, false)
+}
+
+/// With `strict_mtime` the contents are hashed even if the mtime matches the node.
+/// With `normalize_line_endings` a text file is compared with its CRLF line endings converted to LF.
+pub fn is_modified_from_node_with_opts(
+    path: &Path,
+    node: &FileNode,
+    strict_mtime: bool,
+    normalize_line_endings: bool,
+) -> Result<bool, OxenError> {
     // First, check if the file exists; return false if not
     if !path.exists() {
         log::debug!("is_modified_from_node found non-existant path {path:?}. Returning false");
@@ -1601,8 +1755,10 @@ pub fn is_modified_from_node(path: &Path, node: &FileNode) -> Result<bool, OxenE
 
     let file_size = meta.len();
     let node_size = node.num_bytes();
+    // Converting line endings changes the size, so only the hash can tell
+    let maybe_normalized = normalize_line_endings && *node.data_type() == EntryDataType::Text;
 
-    if file_size != node_size {
+    if file_size != node_size && !maybe_normalized {
         return Ok(true);
     }
 
@@ -1613,13 +1769,21 @@ pub fn is_modified_from_node(path: &Path, node: &FileNode) -> Result<bool, OxenE
         node.last_modified_nanoseconds(),
     );
 
-    if file_last_modified == node_last_modified {
+    if file_size == node_size && file_last_modified == node_last_modified && !strict_mtime {
         return Ok(false);
     }
 
     // Finally, check the hashes
     let node_hash = node.hash().to_u128();
-    let working_hash = util::hasher::get_hash_given_metadata(path, &meta)?;
+    let normalized = if maybe_normalized {
+        util::hasher::get_normalized_hash_and_size(path, &meta)?
+    } else {
+        None
+    };
+    let working_hash = match normalized {
+        Some((hash, _)) => hash,
+        None => util::hasher::get_hash_given_metadata(path, &meta)?,
+    };
 
     if node_hash == working_hash {
         Ok(false)
@@ -1853,6 +2017,33 @@ def add(a, b):
         })
     }
 
+    #[test]
+    fn walk_paths_in_dir_yields_all_paths() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let nested = dir.join("nested");
+            util::fs::create_dir_all(&nested)?;
+            util::fs::write_to_path(dir.join("a.txt"), "a")?;
+            util::fs::write_to_path(nested.join("b.txt"), "b")?;
+
+            let mut paths: Vec<PathBuf> = util::fs::walk_paths_in_dir(dir).collect();
+            paths.sort();
+            assert_eq!(
+                paths,
+                vec![
+                    dir.to_path_buf(),
+                    dir.join("a.txt"),
+                    nested.clone(),
+                    nested.join("b.txt"),
+                ]
+            );
+
+            // A missing dir yields nothing rather than an error
+            assert_eq!(util::fs::walk_paths_in_dir(&dir.join("missing")).count(), 0);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn detect_file_type_json_array() -> Result<(), OxenError> {
         test::run_empty_dir_test(|_| {
@@ -1898,6 +2089,52 @@ def add(a, b):
         Ok(())
     }
 
+    #[test]
+    fn test_sniff_data_type_without_extension() -> Result<(), OxenError> {
+        test::run_empty_dir_test(|dir| {
+            let csv = dir.join("labels");
+            util::fs::write_to_path(&csv, "file,label\na.jpg,cat\nb.jpg,dog\n")?;
+            assert_eq!(
+                util::fs::sniff_data_type(&csv),
+                Some((EntryDataType::Tabular, "csv"))
+            );
+
+            let jsonl = dir.join("rows");
+            util::fs::write_to_path(&jsonl, "{\"a\": 1}\n{\"a\": 2}\n")?;
+            assert_eq!(
+                util::fs::sniff_data_type(&jsonl),
+                Some((EntryDataType::Tabular, "jsonl"))
+            );
+
+            let text = dir.join("README");
+            util::fs::write_to_path(&text, "Just some notes, nothing tabular here.\nReally.\n")?;
+            assert_eq!(
+                util::fs::sniff_data_type(&text),
+                Some((EntryDataType::Text, "txt"))
+            );
+
+            let parquet = dir.join("table");
+            std::fs::write(&parquet, b"PAR1\x15\x04\x15")?;
+            assert_eq!(
+                util::fs::sniff_data_type(&parquet),
+                Some((EntryDataType::Tabular, "parquet"))
+            );
+
+            let png = dir.join("image");
+            std::fs::write(&png, b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR")?;
+            assert_eq!(
+                util::fs::sniff_data_type(&png),
+                Some((EntryDataType::Image, "png"))
+            );
+
+            let binary = dir.join("blob");
+            std::fs::write(&binary, [0x13, 0x37, 0xff, 0xfe])?;
+            assert_eq!(util::fs::sniff_data_type(&binary), None);
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn to_unix_str() {
         assert_eq!(
@@ -87,6 +87,20 @@ pub fn get_hash_given_metadata(
     }
 }
 
+/// Hash of a text file with its CRLF line endings converted to LF, and the size of the converted contents.
+/// None if the file is not text, is too large to convert in memory, or has no CRLF line endings.
+pub fn get_normalized_hash_and_size(
+    path: &Path,
+    metadata: &std::fs::Metadata,
+) -> Result<Option<(u128, u64)>, OxenError> {
+    if metadata.len() >= 1_000_000_000 || !util::fs::is_normalizable_text(path) {
+        return Ok(None);
+    }
+    let bytes = util::fs::read_bytes_from_path(path)?;
+    Ok(util::fs::crlf_to_lf(&bytes)
+        .map(|normalized| (hash_buffer_128bit(&normalized), normalized.len() as u64)))
+}
+
 pub fn get_combined_hash(
     oxen_metadata_hash: Option<u128>,
     content_hash: u128,
@@ -102,6 +116,18 @@ pub fn get_combined_hash(
     }
 }
 
+/// Fold the executable bit into the combined hash so a mode change gives the file a new node.
+/// Non executable files keep the combined hash as is, so existing trees hash the same.
+pub fn get_combined_hash_with_mode(combined_hash: u128, executable: bool) -> u128 {
+    if !executable {
+        return combined_hash;
+    }
+    let mut hasher = Xxh3::new();
+    hasher.update(&combined_hash.to_le_bytes());
+    hasher.update(b"executable");
+    hasher.digest128()
+}
+
 pub fn maybe_get_metadata_hash(
     oxen_metadata: &Option<GenericMetadata>,
 ) -> Result<Option<u128>, OxenError> {
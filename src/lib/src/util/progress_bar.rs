@@ -1,7 +1,97 @@
-use std::sync::Arc;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tokio::time::Duration;
 
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+
+use crate::error::OxenError;
+
+/// How add and pull report their progress: the interactive bar or newline delimited JSON on stderr
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProgressFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl FromStr for ProgressFormat {
+    type Err = OxenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(ProgressFormat::Human),
+            "json" => Ok(ProgressFormat::Json),
+            _ => Err(OxenError::basic_str(format!(
+                "Unknown progress format `{s}`, expected `human` or `json`"
+            ))),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ProgressEvent<'a> {
+    pub phase: &'a str,
+    pub files: u64,
+    pub bytes: u64,
+    pub rate_mbps: f64,
+}
+
+/// Writes `ProgressEvent`s to stderr, at most one per tick of the human spinner plus a final one
+pub struct JsonProgress {
+    phase: String,
+    start: Instant,
+    last_emit: Mutex<Option<Instant>>,
+}
+
+impl JsonProgress {
+    pub fn new(phase: impl AsRef<str>) -> JsonProgress {
+        JsonProgress {
+            phase: phase.as_ref().to_string(),
+            start: Instant::now(),
+            last_emit: Mutex::new(None),
+        }
+    }
+
+    pub fn update(&self, files: u64, bytes: u64) {
+        let now = Instant::now();
+        let mut last_emit = self.last_emit.lock().expect("json progress poisoned");
+        if last_emit.is_some_and(|last| now.duration_since(last) < Duration::from_millis(100)) {
+            return;
+        }
+        *last_emit = Some(now);
+        self.emit(files, bytes);
+    }
+
+    pub fn finish(&self, files: u64, bytes: u64) {
+        self.emit(files, bytes);
+    }
+
+    pub fn event(&self, files: u64, bytes: u64) -> ProgressEvent<'_> {
+        let seconds = self.start.elapsed().as_secs_f64();
+        let rate_mbps = if seconds > 0.0 {
+            (bytes as f64 / seconds) / 1_000_000.0
+        } else {
+            0.0
+        };
+        ProgressEvent {
+            phase: &self.phase,
+            files,
+            bytes,
+            // Two decimals is plenty for a progress readout
+            rate_mbps: (rate_mbps * 100.0).round() / 100.0,
+        }
+    }
+
+    fn emit(&self, files: u64, bytes: u64) {
+        if let Ok(line) = serde_json::to_string(&self.event(files, bytes)) {
+            let mut stderr = std::io::stderr().lock();
+            let _ = writeln!(stderr, "{line}");
+        }
+    }
+}
 
 pub enum ProgressBarType {
     Counter,
@@ -76,3 +166,26 @@ pub fn progress_type_to_template(progress_type: ProgressBarType) -> String {
         ProgressBarType::None => "{spinner:.green} [{elapsed_precise}] [{wide_bar}]".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::util::progress_bar::{JsonProgress, ProgressFormat};
+
+    #[test]
+    fn test_json_progress_event() {
+        assert_eq!(
+            ProgressFormat::from_str("json").unwrap(),
+            ProgressFormat::Json
+        );
+        assert!(ProgressFormat::from_str("xml").is_err());
+
+        let progress = JsonProgress::new("add");
+        let line = serde_json::to_value(progress.event(123, 456789)).unwrap();
+        assert_eq!(line["phase"], "add");
+        assert_eq!(line["files"], 123);
+        assert_eq!(line["bytes"], 456789);
+        assert!(line["rate_mbps"].is_number());
+    }
+}
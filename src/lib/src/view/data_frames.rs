@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::constants::DEFAULT_ROW_CHANGE_AUTHOR;
+use crate::model::data_frame::DataFrameSchemaSize;
+use crate::model::Schema;
+use crate::view::StatusMessage;
+
 pub mod columns;
 pub mod embeddings;
 
@@ -9,6 +14,48 @@ pub struct DataFramePayload {
     pub is_indexed: bool,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DataFrameSchemaSizeResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub data_frame: DataFrameSchemaSize,
+}
+
+/// The body of the row key endpoint, the columns that together identify a row
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RowKeyRequest {
+    pub columns: Vec<String>,
+}
+
+/// The composite row key of a data frame, None if rows are only addressed by `_oxen_id`
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DataFrameRowKeyResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub row_key: Option<Vec<String>>,
+}
+
+/// The body of the row undo endpoint, the number of row operations to revert
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RowUndoRequest {
+    pub count: usize,
+}
+
+/// A data frame that is indexed in a workspace, so its rows can be edited
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct IndexedDataFrame {
+    pub path: String,
+    pub schema: Schema,
+    pub num_rows: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct IndexedDataFramesResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub data_frames: Vec<IndexedDataFrame>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DataFrameColumnChange {
     pub operation: String,
@@ -28,4 +75,58 @@ pub struct DataFrameRowChange {
     pub operation: String,
     pub value: Value,
     pub new_value: Option<Value>,
+    /// Who made the edit, changes recorded before authors were tracked are "anonymous"
+    #[serde(default = "default_row_change_author")]
+    pub author: String,
+    /// The row's values for the data frame's composite row key columns, if it has a row key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_key: Option<serde_json::Map<String, Value>>,
+}
+
+fn default_row_change_author() -> String {
+    DEFAULT_ROW_CHANGE_AUTHOR.to_string()
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DataFrameRowHistoryResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub row_id: String,
+    pub history: Vec<DataFrameRowChange>,
+}
+
+/// A row changed in at least one of two workspaces editing the same data frame
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WorkspaceRowDiff {
+    pub row_id: String,
+    pub base: Option<DataFrameRowChange>,
+    pub other: Option<DataFrameRowChange>,
+    pub conflict: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct WorkspaceRowDiffResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub base_workspace_id: String,
+    pub other_workspace_id: String,
+    pub num_conflicts: usize,
+    pub rows: Vec<WorkspaceRowDiff>,
+}
+
+/// The outcome of merging the row edits of one workspace into another
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WorkspaceRowMerge {
+    pub applied: Vec<WorkspaceRowDiff>,
+    pub conflicts: Vec<WorkspaceRowDiff>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct WorkspaceRowMergeResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub source_workspace_id: String,
+    pub target_workspace_id: String,
+    #[serde(flatten)]
+    pub merge: WorkspaceRowMerge,
 }
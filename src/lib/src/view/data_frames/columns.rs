@@ -1,4 +1,9 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::view::StatusMessage;
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct NewColumn {
@@ -22,3 +27,21 @@ pub struct ColumnToUpdate {
     pub new_name: Option<String>,
     pub new_data_type: Option<String>,
 }
+
+/// Summary statistics for a single column. `mean` is only computed for numeric columns,
+/// `min` and `max` for columns with an ordering.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub data_type: String,
+    pub null_count: usize,
+    pub min: Option<Value>,
+    pub max: Option<Value>,
+    pub mean: Option<f64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ColumnStatsResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub columns: BTreeMap<String, ColumnStats>,
+}
@@ -2,15 +2,36 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
+use super::StatusMessage;
+
 #[derive(Deserialize)]
 pub struct ForkRequest {
     pub namespace: String,
     pub new_repo_name: Option<String>,
+    /// Delete the repo at the destination and fork fresh instead of erroring, ie to refresh a mirror
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// A repo that another repo was forked from, and the commit it was at when forked
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ForkSource {
+    pub namespace: String,
+    pub name: String,
+    pub commit_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ForkLineageResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub forked_from: Vec<ForkSource>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ForkStatus {
     Started,
+    Overwriting,
     Counting(u32),
     InProgress(f32),
     Complete,
@@ -66,6 +87,11 @@ impl From<ForkStatus> for ForkStatusFile {
                 progress: None,
                 error: None,
             },
+            ForkStatus::Overwriting => ForkStatusFile {
+                status: ForkStatus::Overwriting,
+                progress: None,
+                error: None,
+            },
         }
     }
 }
@@ -74,6 +100,7 @@ impl fmt::Display for ForkStatus {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ForkStatus::Started => write!(f, "started"),
+            ForkStatus::Overwriting => write!(f, "overwriting"),
             ForkStatus::Counting(_) => write!(f, "counting"),
             ForkStatus::InProgress(_) => write!(f, "in_progress"),
             ForkStatus::Complete => write!(f, "complete"),
@@ -92,6 +119,7 @@ impl FromStr for ForkStatus {
             "complete" => Ok(ForkStatus::Complete),
             "failed" => Ok(ForkStatus::Failed(String::new())),
             "started" => Ok(ForkStatus::Started),
+            "overwriting" => Ok(ForkStatus::Overwriting),
             _ => Err(format!("Invalid status: {}", s)),
         }
     }
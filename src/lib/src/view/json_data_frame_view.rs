@@ -70,6 +70,28 @@ pub struct JsonDataFrameRowResponse {
     pub derived_resource: Option<DerivedDFResource>,
     pub row_id: Option<String>,
     pub row_index: Option<usize>,
+    /// The row's values for the composite row key columns, if the data frame has a row key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_key: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neighbors: Option<JsonDataFrameRowNeighbors>,
+}
+
+/// A page of rows ordered by `_oxen_row_id`. Pass `next_cursor` back as `?cursor=` for the next page,
+/// it is None on the last page.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JsonDataFrameRowsPageResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub data_frame: JsonDataFrameViews,
+    pub next_cursor: Option<String>,
+}
+
+/// The rows surrounding a row, ordered by index
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JsonDataFrameRowNeighbors {
+    pub before: JsonDataFrameView,
+    pub after: JsonDataFrameView,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -278,7 +300,14 @@ impl JsonDataFrameView {
 
         let json_str = str::from_utf8(&buffer).unwrap();
 
-        serde_json::from_str(json_str).unwrap()
+        let mut json: serde_json::Value = serde_json::from_str(json_str).unwrap();
+        let columns: Vec<String> = df
+            .get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        fill_null_columns(&mut json, &columns);
+        json
     }
 
     fn empty_with_schema(
@@ -326,6 +355,22 @@ impl JsonDataFrameViews {
     }
 }
 
+/// Make sure every row has a key for every column, so a null cell is serialized as an explicit
+/// `null` and clients can tell it apart from a column that is not in the data frame.
+fn fill_null_columns(json: &mut serde_json::Value, columns: &[String]) {
+    let Some(rows) = json.as_array_mut() else {
+        return;
+    };
+    for row in rows {
+        if let Some(row) = row.as_object_mut() {
+            for column in columns {
+                row.entry(column.as_str())
+                    .or_insert(serde_json::Value::Null);
+            }
+        }
+    }
+}
+
 fn sanitize_df_for_serialization(df: &mut DataFrame) -> Result<(), OxenError> {
     let schema = df.schema();
     let mut updates: Vec<(usize, Column)> = Vec::new();
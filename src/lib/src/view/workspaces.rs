@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::StatusMessage;
+use crate::model::diff::diff_entry_status::DiffEntryStatus;
+use crate::model::diff::AddRemoveModifyCounts;
 use crate::model::Commit;
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -69,6 +71,25 @@ pub struct ListWorkspaceResponseView {
     pub workspaces: Vec<WorkspaceResponse>,
 }
 
+/// A file that committing a workspace would change on a branch
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WorkspaceFileDiff {
+    pub path: String,
+    pub status: DiffEntryStatus,
+    /// Row level changes, only for indexed data frames
+    pub row_counts: Option<AddRemoveModifyCounts>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct WorkspaceBranchDiffResponse {
+    #[serde(flatten)]
+    pub status: StatusMessage,
+    pub workspace_id: String,
+    pub branch: String,
+    pub commit_id: String,
+    pub entries: Vec<WorkspaceFileDiff>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct ValidateUploadFeasibilityRequest {
     pub size: u64,
@@ -78,3 +99,14 @@ pub struct ValidateUploadFeasibilityRequest {
 pub struct RenameRequest {
     pub new_path: String,
 }
+
+#[derive(Deserialize)]
+pub struct DataFrameCommitRequest {
+    pub message: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub email: String,
+    // Defaults to the main branch
+    pub branch: Option<String>,
+}
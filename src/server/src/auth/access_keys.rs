@@ -17,6 +17,12 @@ pub struct JWTClaim {
     email: String,
 }
 
+impl JWTClaim {
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+}
+
 pub struct AccessKeyManager {
     sync_dir: PathBuf,
     db: DBWithThreadMode<MultiThreaded>,
@@ -123,32 +129,33 @@ impl AccessKeyManager {
     }
 
     pub fn token_is_valid(&self, token: &str) -> bool {
+        self.get_valid_claim(token).is_some()
+    }
+
+    /// The claim behind `token` if it is in our db and signed with our secret key
+    pub fn get_valid_claim(&self, token: &str) -> Option<JWTClaim> {
         match self.get_claim(token) {
             Ok(Some(claim)) => {
-                let secret = self.read_secret_key();
-                if secret.is_err() {
-                    return false;
-                }
+                let secret = self.read_secret_key().ok()?;
 
                 let mut validator = Validation::new(Algorithm::HS256);
                 validator.set_required_spec_claims(&["email"]);
                 match decode::<JWTClaim>(
                     token,
-                    &DecodingKey::from_secret(secret.unwrap().as_ref()),
+                    &DecodingKey::from_secret(secret.as_ref()),
                     &validator,
                 ) {
-                    Ok(token_data) => {
-                        // Make sure we decoded the email is the one in our db
-                        token_data.claims == claim
-                    }
+                    // Make sure we decoded the email is the one in our db
+                    Ok(token_data) if token_data.claims == claim => Some(claim),
+                    Ok(_) => None,
                     _ => {
                         log::info!("auth token is not valid: {}", token);
-                        false
+                        None
                     }
                 }
             }
-            Ok(None) => false,
-            Err(_) => false,
+            Ok(None) => None,
+            Err(_) => None,
         }
     }
 
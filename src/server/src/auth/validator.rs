@@ -2,8 +2,13 @@ use crate::app_data::OxenAppData;
 use crate::auth;
 
 use actix_web::dev::ServiceRequest;
+use actix_web::HttpMessage;
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 
+/// The email of the authenticated user, set on the request by `validate`
+#[derive(Debug, Clone)]
+pub struct RequestAuthor(pub String);
+
 pub async fn validate(
     req: ServiceRequest,
     credentials: BearerAuth,
@@ -12,7 +17,10 @@ pub async fn validate(
     match auth::access_keys::AccessKeyManager::new_read_only(&app_data.path) {
         Ok(keygen) => {
             let token = credentials.token();
-            if keygen.token_is_valid(token) {
+            if let Some(claim) = keygen.get_valid_claim(token) {
+                // Resolved once here so handlers don't reopen the key db to know who is asking
+                req.extensions_mut()
+                    .insert(RequestAuthor(claim.email().to_string()));
                 Ok(req)
             } else {
                 Err((actix_web::error::ErrorUnauthorized("unauthorized"), req))
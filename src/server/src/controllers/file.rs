@@ -52,7 +52,11 @@ pub async fn get(
         log::debug!("entry {:?}", entry);
         let entry = entry.ok_or(OxenError::path_does_not_exist(path.clone()))?;
 
-        let version_path = util::fs::version_path_from_hash(&repo, entry.hash().to_string());
+        // Compressed and chunked versions are unpacked to a temp file, the open file handle keeps
+        // it readable after the temp file is removed
+        let version_file =
+            repositories::revisions::get_version_file_from_node(&repo, &entry, &path)?;
+        let version_path = version_file.path();
         log::debug!("version path {version_path:?}",);
 
         // TODO: refactor out of here and check for type,
@@ -68,7 +72,7 @@ pub async fn get(
                 img_resize.height,
             )?;
 
-            util::fs::resize_cache_image(&version_path, &resized_path, img_resize)?;
+            util::fs::resize_cache_image(version_path, &resized_path, img_resize)?;
 
             log::debug!("In the resize cache! {:?}", resized_path);
             return Ok(NamedFile::open(resized_path)?.into_response(&req));
@@ -83,6 +87,7 @@ pub async fn get(
         );
 
         let file = NamedFile::open(version_path)?;
+        drop(version_file);
 
         let mut response = file.into_response(&req);
 
@@ -296,6 +301,7 @@ pub async fn import(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::path::PathBuf;
 
     use actix_multipart::test::create_form_data_payload_and_headers;
@@ -303,14 +309,62 @@ mod tests {
     use liboxen::view::CommitResponse;
     use mime;
 
+    use liboxen::constants::MIN_OXEN_VERSION;
     use liboxen::error::OxenError;
     use liboxen::repositories;
+    use liboxen::storage::StorageConfig;
     use liboxen::util;
 
     use crate::app_data::OxenAppData;
     use crate::controllers;
     use crate::test;
 
+    #[actix_web::test]
+    async fn test_controllers_file_get_compressed_version() -> Result<(), OxenError> {
+        test::init_test_env();
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let repo_name = "Testing-Name";
+        let repo_dir = sync_dir.join(namespace).join(repo_name);
+        util::fs::create_dir_all(&repo_dir)?;
+        let storage = StorageConfig {
+            type_: "local".to_string(),
+            settings: HashMap::from([("compression".to_string(), "zstd".to_string())]),
+        };
+        let repo = repositories::init::init_with_storage(&repo_dir, MIN_OXEN_VERSION, &storage)?;
+        util::fs::create_dir_all(repo.path.join("data"))?;
+        let hello_file = repo.path.join("data/hello.txt");
+        util::fs::write_to_path(&hello_file, "Hello compressed")?;
+        repositories::add(&repo, &hello_file)?;
+        let _commit = repositories::commit(&repo, "First commit")?;
+
+        let uri = format!("/oxen/{namespace}/{repo_name}/file/main/data/hello.txt");
+        let req = actix_web::test::TestRequest::get()
+            .uri(&uri)
+            .app_data(OxenAppData::new(sync_dir.to_path_buf()))
+            .to_request();
+
+        let app = actix_web::test::init_service(
+            App::new()
+                .app_data(OxenAppData::new(sync_dir.clone()))
+                .route(
+                    "/oxen/{namespace}/{repo_name}/file/{resource:.*}",
+                    web::get().to(controllers::file::get),
+                ),
+        )
+        .await;
+
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let bytes = actix_http::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(std::str::from_utf8(&bytes).unwrap(), "Hello compressed");
+
+        // cleanup
+        test::cleanup_sync_dir(&sync_dir)?;
+
+        Ok(())
+    }
+
     #[actix_web::test]
     async fn test_controllers_file_put() -> Result<(), OxenError> {
         test::init_test_env();
@@ -4,7 +4,7 @@ use crate::params::{app_data, path_param};
 use actix_web::{web, HttpRequest, HttpResponse, Result};
 use liboxen::error::OxenError;
 use liboxen::repositories;
-use liboxen::view::fork::ForkRequest;
+use liboxen::view::fork::{ForkLineageResponse, ForkRequest};
 use liboxen::view::StatusMessage;
 
 pub async fn fork(
@@ -24,15 +24,18 @@ pub async fn fork(
 
     let new_repo_path = app_data.path.join(&new_repo_namespace).join(&new_repo_name);
 
-    match repositories::fork::start_fork(original_repo.path, new_repo_path.clone()) {
+    match repositories::fork::start_fork_with_force(
+        original_repo.path,
+        new_repo_path.clone(),
+        body.force,
+    ) {
         Ok(fork_start_response) => {
             log::info!("Successfully forked repository to {:?}", &new_repo_path);
             Ok(HttpResponse::Accepted().json(fork_start_response))
         }
-        Err(OxenError::RepoAlreadyExistsAtDestination(path)) => {
-            log::debug!("Repo already exists: {:?}", path);
-            Ok(HttpResponse::Conflict()
-                .json(StatusMessage::error("Repo already exists at destination.")))
+        Err(OxenError::RepoAlreadyExistsAtDestination(message)) => {
+            log::debug!("Cannot fork to {:?}: {}", new_repo_path, message);
+            Ok(HttpResponse::Conflict().json(StatusMessage::error(message.to_string())))
         }
         Err(err) => {
             log::error!("Failed to fork repository: {:?}", err);
@@ -61,3 +64,17 @@ pub async fn get_status(req: HttpRequest) -> Result<HttpResponse, OxenHttpError>
         }
     }
 }
+
+/// The repos this repo was forked from, nearest first. Empty if the repo is not a fork.
+pub async fn get_lineage(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+
+    let forked_from = repositories::fork::get_lineage(&repo.path)?;
+    Ok(HttpResponse::Ok().json(ForkLineageResponse {
+        status: StatusMessage::resource_found(),
+        forked_from,
+    }))
+}
@@ -9,7 +9,6 @@ use liboxen::constants::DEFAULT_BRANCH_NAME;
 use liboxen::error::OxenError;
 use liboxen::model::file::{FileContents, FileNew};
 use liboxen::repositories;
-use liboxen::util;
 use liboxen::view::http::{MSG_RESOURCE_FOUND, MSG_RESOURCE_UPDATED, STATUS_SUCCESS};
 use liboxen::view::repository::{
     DataTypeView, RepositoryCreationResponse, RepositoryCreationView, RepositoryDataTypesResponse,
@@ -442,13 +441,17 @@ pub async fn get_file_for_branch(req: HttpRequest) -> Result<NamedFile, OxenHttp
 
     let branch = repositories::branches::get_by_name(&repo, branch_name)?
         .ok_or(OxenError::remote_branch_not_found(branch_name))?;
-    let version_path = util::fs::version_path_for_commit_id(&repo, &branch.commit_id, &filepath)?;
+    let version_file = repositories::revisions::get_version_file_from_commit_id(
+        &repo,
+        &branch.commit_id,
+        &filepath,
+    )?;
     log::debug!(
         "get_file_for_branch looking for {:?} -> {:?}",
         filepath,
-        version_path
+        version_file.path()
     );
-    Ok(NamedFile::open(version_path)?)
+    Ok(NamedFile::open(version_file.path())?)
 }
 
 pub async fn get_file_for_commit_id(req: HttpRequest) -> Result<NamedFile, OxenHttpError> {
@@ -464,13 +467,17 @@ pub async fn get_file_for_commit_id(req: HttpRequest) -> Result<NamedFile, OxenH
             resource.version.to_string_lossy(),
         ))?;
 
-    let version_path = util::fs::version_path_for_commit_id(&repo, &commit.id, &resource.path)?;
+    let version_file = repositories::revisions::get_version_file_from_commit_id(
+        &repo,
+        &commit.id,
+        &resource.path,
+    )?;
     log::debug!(
         "get_file_for_commit_id looking for {:?} -> {:?}",
         resource.path,
-        version_path
+        version_file.path()
     );
-    Ok(NamedFile::open(version_path)?)
+    Ok(NamedFile::open(version_file.path())?)
 }
 
 #[cfg(test)]
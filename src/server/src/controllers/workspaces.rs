@@ -6,7 +6,9 @@ use liboxen::error::OxenError;
 use liboxen::model::NewCommitBody;
 use liboxen::repositories;
 use liboxen::view::merge::MergeableResponse;
-use liboxen::view::workspaces::{ListWorkspaceResponseView, NewWorkspace, WorkspaceResponse};
+use liboxen::view::workspaces::{
+    ListWorkspaceResponseView, NewWorkspace, WorkspaceBranchDiffResponse, WorkspaceResponse,
+};
 use liboxen::view::{
     CommitResponse, StatusMessage, StatusMessageDescription, WorkspaceResponseView,
 };
@@ -246,6 +248,33 @@ pub async fn mergeability(req: HttpRequest) -> Result<HttpResponse, OxenHttpErro
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Preview the files and rows that committing the workspace would change on the branch
+pub async fn diff_branch(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
+    let branch_name = path_param(&req, "branch")?;
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+    let Some(branch) = repositories::branches::get_by_name(&repo, &branch_name)? else {
+        return Ok(HttpResponse::NotFound().json(StatusMessageDescription::not_found(branch_name)));
+    };
+
+    let entries = repositories::workspaces::diff::diff_branch(&workspace, &branch.name)?;
+    Ok(HttpResponse::Ok().json(WorkspaceBranchDiffResponse {
+        status: StatusMessage::resource_found(),
+        workspace_id,
+        branch: branch.name,
+        commit_id: branch.commit_id,
+        entries,
+    }))
+}
+
 pub async fn commit(req: HttpRequest, body: String) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
 
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::errors::OxenHttpError;
+use crate::errors::{OxenHttpError, WorkspaceBranch};
 use crate::helpers::get_repo;
 use crate::params::{app_data, df_opts_query, path_param, DFOptsQuery, PageNumQuery};
 
@@ -10,17 +10,23 @@ use liboxen::constants::{self, TABLE_NAME};
 use liboxen::core::db::data_frames::df_db;
 use liboxen::core::db::data_frames::workspace_df_db::schema_without_oxen_cols;
 use liboxen::error::OxenError;
-use liboxen::model::Schema;
+use liboxen::model::{NewCommitBody, Schema};
 use liboxen::opts::DFOpts;
 use liboxen::repositories;
+use liboxen::util;
 use liboxen::util::paginate;
-use liboxen::view::data_frames::DataFramePayload;
+use liboxen::view::data_frames::{
+    DataFramePayload, DataFrameRowKeyResponse, IndexedDataFramesResponse, RowKeyRequest,
+};
 use liboxen::view::entries::ResourceVersion;
 use liboxen::view::entries::{PaginatedMetadataEntries, PaginatedMetadataEntriesResponse};
-use liboxen::view::json_data_frame_view::WorkspaceJsonDataFrameViewResponse;
-use liboxen::view::workspaces::RenameRequest;
+use liboxen::view::json_data_frame_view::{
+    DFResourceType, DerivedDFResource, WorkspaceJsonDataFrameViewResponse,
+};
+use liboxen::view::workspaces::{DataFrameCommitRequest, RenameRequest};
 use liboxen::view::{
-    JsonDataFrameViewResponse, JsonDataFrameViews, StatusMessage, StatusMessageDescription,
+    CommitResponse, JsonDataFrameViewResponse, JsonDataFrameViews, StatusMessage,
+    StatusMessageDescription,
 };
 
 use actix_web::web::Bytes;
@@ -127,10 +133,16 @@ pub async fn get(
 
     log::debug!("querying data frame {:?}", file_path);
     log::debug!("opts: {:?}", opts);
-    let count = repositories::workspaces::data_frames::count(&workspace, &file_path)?;
 
-    // Query the data frame
-    let df = repositories::workspaces::data_frames::query(&workspace, &file_path, &opts)?;
+    // A sql query or filter derives a new view from the whole data frame, otherwise page through it
+    let derived_resource = derived_query_resource(&workspace_id, &file_path, &opts);
+    let (df, count) = if derived_resource.is_some() {
+        repositories::workspaces::data_frames::query_derived(&workspace, &file_path, &opts)?
+    } else {
+        let count = repositories::workspaces::data_frames::count(&workspace, &file_path)?;
+        let df = repositories::workspaces::data_frames::query(&workspace, &file_path, &opts)?;
+        (df, count)
+    };
 
     let Some(mut df_schema) =
         repositories::data_frames::schemas::get_by_path(&repo, &workspace.commit, &file_path)?
@@ -175,13 +187,42 @@ pub async fn get(
         data_frame: Some(df_views),
         resource: Some(resource),
         commit: None, // Not at a committed state
-        derived_resource: None,
+        derived_resource,
         is_indexed,
     };
 
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Describes the view derived by the sql or filter in `opts`, None if there is neither
+fn derived_query_resource(
+    workspace_id: &str,
+    file_path: &std::path::Path,
+    opts: &DFOpts,
+) -> Option<DerivedDFResource> {
+    let params: Vec<String> = [("sql", &opts.sql), ("filter", &opts.filter)]
+        .into_iter()
+        .filter_map(|(name, value)| {
+            value
+                .as_ref()
+                .map(|value| format!("{name}={}", urlencoding::encode(value)))
+        })
+        .collect();
+    if params.is_empty() {
+        return None;
+    }
+
+    let query = params.join("&");
+    Some(DerivedDFResource {
+        resource_id: util::hasher::hash_str(format!("{}?{query}", file_path.display())),
+        path: format!(
+            "/workspaces/{workspace_id}/data_frames/resource/{}?{query}",
+            file_path.display()
+        ),
+        resource_type: DFResourceType::Query,
+    })
+}
+
 pub async fn get_schema(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
 
@@ -425,6 +466,26 @@ pub async fn get_by_branch(
     }))
 }
 
+/// List the data frames indexed in the workspace, with their schemas and row counts
+pub async fn list_indexed(req: HttpRequest) -> actix_web::Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    let data_frames = repositories::workspaces::data_frames::list_indexed(&workspace)?;
+    Ok(HttpResponse::Ok().json(IndexedDataFramesResponse {
+        status: StatusMessage::resource_found(),
+        data_frames,
+    }))
+}
+
 pub async fn diff(
     req: HttpRequest,
     query: web::Query<DFOptsQuery>,
@@ -567,3 +628,117 @@ pub async fn rename(req: HttpRequest, body: String) -> Result<HttpResponse, Oxen
 
     Ok(HttpResponse::Ok().json(StatusMessage::resource_updated()))
 }
+
+/// The composite row key of an indexed data frame, null if rows are only addressed by `_oxen_id`
+pub async fn get_row_key(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let path = PathBuf::from(path_param(&req, "path")?);
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+    if !repositories::workspaces::data_frames::is_indexed(&workspace, &path)? {
+        return Err(OxenHttpError::DatasetNotIndexed(path.into()));
+    }
+
+    let row_key = repositories::workspaces::data_frames::get_row_key(&workspace, &path)?;
+    Ok(HttpResponse::Ok().json(DataFrameRowKeyResponse {
+        status: StatusMessage::resource_found(),
+        row_key,
+    }))
+}
+
+/// Set the columns that together identify a row, so rows can be addressed with `?key=`
+pub async fn set_row_key(req: HttpRequest, body: String) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let path = PathBuf::from(path_param(&req, "path")?);
+    let body: RowKeyRequest = serde_json::from_str(&body)?;
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+    if !repositories::workspaces::data_frames::is_indexed(&workspace, &path)? {
+        return Err(OxenHttpError::DatasetNotIndexed(path.into()));
+    }
+
+    if let Err(err) =
+        repositories::workspaces::data_frames::set_row_key(&workspace, &path, &body.columns)
+    {
+        return Err(OxenHttpError::BadRequest(err.to_string().into()));
+    }
+    Ok(HttpResponse::Ok().json(DataFrameRowKeyResponse {
+        status: StatusMessage::resource_updated(),
+        row_key: Some(body.columns),
+    }))
+}
+
+/// Commit the row edits to a data frame, returning the new commit
+pub async fn commit(req: HttpRequest, body: String) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let path = PathBuf::from(path_param(&req, "path")?);
+
+    let body: DataFrameCommitRequest = serde_json::from_str(&body)?;
+    if body.message.is_empty() {
+        return Err(OxenHttpError::BadRequest("message cannot be empty".into()));
+    }
+    let branch_name = body
+        .branch
+        .unwrap_or(constants::DEFAULT_BRANCH_NAME.to_string());
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    let Some(branch) = repositories::branches::get_by_name(&repo, &branch_name)? else {
+        return Ok(HttpResponse::NotFound().json(StatusMessageDescription::not_found(branch_name)));
+    };
+
+    if !repositories::workspaces::data_frames::is_indexed(&workspace, &path)? {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::not_found(path.to_string_lossy())));
+    }
+    if !repositories::workspaces::files::exists(&workspace, &path)? {
+        return Err(OxenHttpError::BadRequest(
+            format!("No changes to commit for data frame {path:?}").into(),
+        ));
+    }
+
+    let new_commit = NewCommitBody {
+        message: body.message,
+        author: body.author,
+        email: body.email,
+    };
+    match repositories::workspaces::data_frames::commit(
+        &workspace,
+        &path,
+        &new_commit,
+        &branch_name,
+    ) {
+        Ok(commit) => Ok(HttpResponse::Ok().json(CommitResponse {
+            status: StatusMessage::resource_created(),
+            commit,
+        })),
+        Err(OxenError::WorkspaceBehind(workspace)) => {
+            Err(OxenHttpError::WorkspaceBehind(Box::new(WorkspaceBranch {
+                workspace: *workspace.clone(),
+                branch,
+            })))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
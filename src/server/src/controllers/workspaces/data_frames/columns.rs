@@ -2,16 +2,16 @@ use std::path::PathBuf;
 
 use crate::errors::OxenHttpError;
 use crate::helpers::get_repo;
-use crate::params::{app_data, path_param};
+use crate::params::{app_data, path_param, ColumnStatsQuery};
 
-use actix_web::{HttpRequest, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use liboxen::error::StringError;
 use liboxen::model::data_frame::DataFrameSchemaSize;
 use liboxen::model::Schema;
 use liboxen::opts::DFOpts;
 use liboxen::repositories;
 use liboxen::view::data_frames::columns::{
-    ColumnToDelete, ColumnToRestore, ColumnToUpdate, NewColumn,
+    ColumnStatsResponse, ColumnToDelete, ColumnToRestore, ColumnToUpdate, NewColumn,
 };
 use liboxen::view::json_data_frame_view::JsonDataFrameColumnResponse;
 use liboxen::view::{
@@ -397,3 +397,39 @@ pub async fn restore(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
         resource: None,
     }))
 }
+
+pub async fn stats(
+    req: HttpRequest,
+    query: web::Query<ColumnStatsQuery>,
+) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let file_path: PathBuf = PathBuf::from(path_param(&req, "path")?);
+
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    if !repositories::workspaces::data_frames::is_indexed(&workspace, &file_path)? {
+        return Err(OxenHttpError::DatasetNotIndexed(file_path.into()));
+    }
+
+    let column_names = query.column_names();
+    let columns = repositories::workspaces::data_frames::columns::stats(
+        &workspace,
+        &file_path,
+        column_names.as_deref(),
+    )
+    .map_err(|e| OxenHttpError::BadRequest(StringError::from(e.to_string())))?;
+
+    Ok(HttpResponse::Ok().json(ColumnStatsResponse {
+        status: StatusMessage::resource_found(),
+        columns,
+    }))
+}
@@ -1,32 +1,83 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use crate::errors::OxenHttpError;
-use crate::helpers::get_repo;
-use crate::params::{app_data, path_param};
+use crate::helpers::{get_repo, get_request_author};
+use crate::limits;
+use crate::params::{app_data, path_param, RowCursorQuery, RowKeyQuery, RowNeighborsQuery};
 
-use actix_web::{web::Bytes, HttpRequest, HttpResponse};
+use actix_web::http::header;
+use actix_web::{web, web::Bytes, HttpRequest, HttpResponse};
+use liboxen::constants;
 use liboxen::model::data_frame::update_result::UpdateResult;
 use liboxen::model::data_frame::DataFrameSchemaSize;
-use liboxen::model::Schema;
+use liboxen::model::{LocalRepository, Schema, Workspace};
 use liboxen::opts::DFOpts;
 use liboxen::repositories;
+use liboxen::view::data_frames::{
+    DataFrameRowHistoryResponse, DataFrameSchemaSizeResponse, RowUndoRequest,
+    WorkspaceRowDiffResponse, WorkspaceRowMergeResponse,
+};
 use liboxen::view::json_data_frame_view::{
-    BatchUpdateResponse, JsonDataFrameRowResponse, VecBatchUpdateResponse,
+    BatchUpdateResponse, JsonDataFrameRowNeighbors, JsonDataFrameRowResponse,
+    JsonDataFrameRowsPageResponse, VecBatchUpdateResponse,
 };
 use liboxen::view::{
     JsonDataFrameView, JsonDataFrameViews, StatusMessage, StatusMessageDescription,
 };
 
+/// Clients that send `Accept: application/x-ndjson` to batch_update get one result per line as the rows are written
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+/// Rows written per chunk when streaming batch_update results
+const BATCH_UPDATE_STREAM_CHUNK_SIZE: usize = 1000;
+/// Chunks of results buffered before waiting on a slow client
+const BATCH_UPDATE_STREAM_BUFFER: usize = 4;
+
+type FrameLocks = Mutex<HashMap<(String, PathBuf), Arc<tokio::sync::Mutex<()>>>>;
+
+static FRAME_LOCKS: OnceLock<FrameLocks> = OnceLock::new();
+
+/// Row edits mutate the indexed data frame, so edits to the same (workspace, file) are serialized
+/// through this lock to keep concurrent requests from interleaving their writes. Reads don't lock.
+fn frame_lock(workspace_id: &str, file_path: &Path) -> Arc<tokio::sync::Mutex<()>> {
+    let mut locks = FRAME_LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .expect("frame locks poisoned");
+    // Drop the locks no other request is holding or waiting on
+    locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+    locks
+        .entry((workspace_id.to_string(), file_path.to_path_buf()))
+        .or_default()
+        .clone()
+}
+
+/// The `?key=` composite row key if one was given, otherwise the `{row_id}` in the path
+fn row_id_param(req: &HttpRequest) -> Result<String, OxenHttpError> {
+    let query = web::Query::<RowKeyQuery>::from_query(req.query_string())
+        .map_err(|err| OxenHttpError::BadRequest(err.to_string().into()))?;
+    match query.into_inner().key {
+        Some(key) => Ok(key),
+        None => path_param(req, "row_id"),
+    }
+}
+
 pub async fn create(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
 
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
     let workspace_id = path_param(&req, "workspace_id")?;
+    limits::check_row_rate_limit(&namespace, &repo_name, &workspace_id)?;
     let repo = get_repo(&app_data.path, namespace.clone(), repo_name.clone())?;
     let file_path = PathBuf::from(path_param(&req, "path")?);
 
-    let data = String::from_utf8(bytes.to_vec()).expect("Could not parse bytes as utf8");
+    let Ok(data) = String::from_utf8(bytes.to_vec()) else {
+        return Err(OxenHttpError::BadRequest(
+            "Could not parse bytes as utf8".to_string().into(),
+        ));
+    };
 
     // If the json has an outer property of "data", serialize the inner object
     let json_value: serde_json::Value = serde_json::from_str(&data)?;
@@ -57,11 +108,18 @@ pub async fn create(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
         return Err(OxenHttpError::DatasetNotIndexed(file_path.into()));
     }
 
-    let row_df =
-        repositories::workspaces::data_frames::rows::add(&repo, &workspace, &file_path, data)?;
+    let lock = frame_lock(&workspace.id, &file_path);
+    let _guard = lock.lock().await;
+    let author = get_request_author(&req);
+    let row_df = repositories::workspaces::data_frames::rows::add_with_author(
+        &repo, &workspace, &file_path, data, &author,
+    )?;
     let row_id: Option<String> = repositories::workspaces::data_frames::rows::get_row_id(&row_df)?;
     let row_index: Option<usize> =
         repositories::workspaces::data_frames::rows::get_row_idx(&row_df)?;
+    let row_key = repositories::workspaces::data_frames::rows::get_row_key_values(
+        &workspace, &file_path, &row_df,
+    )?;
 
     let opts = DFOpts::empty();
     let row_schema = Schema::from_polars(&row_df.schema().clone());
@@ -82,12 +140,150 @@ pub async fn create(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
         resource: None,
         row_id,
         row_index,
+        row_key,
+        neighbors: None,
     };
 
     Ok(HttpResponse::Ok().json(response))
 }
 
-pub async fn get(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+/// Compare the pending row edits of this workspace with another workspace on the same file
+pub async fn compare(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let other_workspace_id = path_param(&req, "other_workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+    let Some(other_workspace) = repositories::workspaces::get(&repo, &other_workspace_id)? else {
+        return Ok(
+            HttpResponse::NotFound().json(StatusMessageDescription::workspace_not_found(
+                other_workspace_id,
+            )),
+        );
+    };
+
+    let rows = repositories::workspaces::data_frames::rows::compare_row_diffs(
+        &workspace,
+        &other_workspace,
+        &file_path,
+    )?;
+    let num_conflicts = rows.iter().filter(|row| row.conflict).count();
+
+    Ok(HttpResponse::Ok().json(WorkspaceRowDiffResponse {
+        status: StatusMessage::resource_found(),
+        base_workspace_id: workspace.id,
+        other_workspace_id: other_workspace.id,
+        num_conflicts,
+        rows,
+    }))
+}
+
+/// Apply the row edits of the other workspace onto this one, edits to rows that both workspaces
+/// changed differently are left alone and returned as conflicts to resolve by hand
+pub async fn merge(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let other_workspace_id = path_param(&req, "other_workspace_id")?;
+    limits::check_row_rate_limit(&namespace, &repo_name, &workspace_id)?;
+
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+    let Some(other_workspace) = repositories::workspaces::get(&repo, &other_workspace_id)? else {
+        return Ok(
+            HttpResponse::NotFound().json(StatusMessageDescription::workspace_not_found(
+                other_workspace_id,
+            )),
+        );
+    };
+
+    if !repositories::workspaces::data_frames::is_indexed(&workspace, &file_path)? {
+        return Err(OxenHttpError::DatasetNotIndexed(file_path.into()));
+    }
+
+    let author = get_request_author(&req);
+    let lock = frame_lock(&workspace.id, &file_path);
+    let merge = {
+        let _guard = lock.lock().await;
+        repositories::workspaces::data_frames::rows::merge_row_diffs(
+            &repo,
+            &workspace,
+            &other_workspace,
+            &file_path,
+            &author,
+        )?
+    };
+
+    Ok(HttpResponse::Ok().json(WorkspaceRowMergeResponse {
+        status: StatusMessage::resource_updated(),
+        source_workspace_id: other_workspace.id,
+        target_workspace_id: workspace.id,
+        merge,
+    }))
+}
+
+/// Page through the rows in `_oxen_row_id` order with `?cursor=&limit=`, starting from the first row
+/// without a cursor. The response holds the `next_cursor` to pass back, None after the last page.
+pub async fn list(
+    req: HttpRequest,
+    query: web::Query<RowCursorQuery>,
+) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    let limit = query
+        .limit
+        .unwrap_or(constants::DEFAULT_PAGE_SIZE)
+        .min(constants::MAX_PAGE_SIZE);
+    let (rows_df, next_cursor) = repositories::workspaces::data_frames::rows::list_page(
+        &workspace,
+        &file_path,
+        query.cursor.as_deref(),
+        limit,
+    )?;
+
+    let opts = DFOpts::empty();
+    let schema = Schema::from_polars(&rows_df.schema());
+    let source = DataFrameSchemaSize::from_df(&rows_df, &schema);
+    let height = rows_df.height();
+    let view = JsonDataFrameView::from_df_opts_unpaginated(rows_df, schema, height, &opts);
+
+    Ok(HttpResponse::Ok().json(JsonDataFrameRowsPageResponse {
+        status: StatusMessage::resource_found(),
+        data_frame: JsonDataFrameViews { source, view },
+        next_cursor,
+    }))
+}
+
+pub async fn get(
+    req: HttpRequest,
+    query: web::Query<RowNeighborsQuery>,
+) -> Result<HttpResponse, OxenHttpError> {
     let app_data = app_data(&req)?;
 
     let namespace = path_param(&req, "namespace")?;
@@ -96,20 +292,61 @@ pub async fn get(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
 
     let repo = get_repo(&app_data.path, namespace, repo_name)?;
     let file_path = path_param(&req, "path")?;
-    let row_id = path_param(&req, "row_id")?;
+    let row_id = row_id_param(&req)?;
 
     let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
         return Ok(HttpResponse::NotFound()
             .json(StatusMessageDescription::workspace_not_found(workspace_id)));
     };
     let row_df =
-        repositories::workspaces::data_frames::rows::get_by_id(&workspace, file_path, row_id)?;
+        repositories::workspaces::data_frames::rows::get_by_id(&workspace, &file_path, row_id)?;
 
     let row_id = repositories::workspaces::data_frames::rows::get_row_id(&row_df)?;
     let row_index = repositories::workspaces::data_frames::rows::get_row_idx(&row_df)?;
+    let row_key = repositories::workspaces::data_frames::rows::get_row_key_values(
+        &workspace, &file_path, &row_df,
+    )?;
 
     let opts = DFOpts::empty();
     let row_schema = Schema::from_polars(&row_df.schema().clone());
+
+    // Optionally return the rows around the target so clients can render it in context
+    let neighbors = match (row_index, query.before, query.after) {
+        (Some(index), before, after) if before.is_some() || after.is_some() => {
+            let before = before.unwrap_or(0);
+            let after = after.unwrap_or(0);
+            let before_df = repositories::workspaces::data_frames::rows::get_by_index_range(
+                &workspace,
+                &file_path,
+                index.saturating_sub(before),
+                index,
+            )?;
+            let after_df = repositories::workspaces::data_frames::rows::get_by_index_range(
+                &workspace,
+                &file_path,
+                index + 1,
+                index + 1 + after,
+            )?;
+            let before_height = before_df.height();
+            let after_height = after_df.height();
+            Some(JsonDataFrameRowNeighbors {
+                before: JsonDataFrameView::from_df_opts_unpaginated(
+                    before_df,
+                    row_schema.clone(),
+                    before_height,
+                    &opts,
+                ),
+                after: JsonDataFrameView::from_df_opts_unpaginated(
+                    after_df,
+                    row_schema.clone(),
+                    after_height,
+                    &opts,
+                ),
+            })
+        }
+        _ => None,
+    };
+
     let row_df_source = DataFrameSchemaSize::from_df(&row_df, &row_schema);
     let row_df_view = JsonDataFrameView::from_df_opts(row_df, row_schema, &opts);
 
@@ -125,6 +362,8 @@ pub async fn get(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
         resource: None,
         row_id,
         row_index,
+        row_key,
+        neighbors,
     };
 
     Ok(HttpResponse::Ok().json(response))
@@ -136,7 +375,8 @@ pub async fn update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
     let workspace_id = path_param(&req, "workspace_id")?;
-    let row_id = path_param(&req, "row_id")?;
+    limits::check_row_rate_limit(&namespace, &repo_name, &workspace_id)?;
+    let row_id = row_id_param(&req)?;
 
     let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
 
@@ -169,12 +409,20 @@ pub async fn update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
         file_path
     );
 
-    let modified_row = repositories::workspaces::data_frames::rows::update(
-        &repo, &workspace, &file_path, &row_id, data,
+    let lock = frame_lock(&workspace.id, &file_path);
+    let _guard = lock.lock().await;
+    let author = get_request_author(&req);
+    let modified_row = repositories::workspaces::data_frames::rows::update_with_author(
+        &repo, &workspace, &file_path, &row_id, data, &author,
     )?;
 
     let row_index = repositories::workspaces::data_frames::rows::get_row_idx(&modified_row)?;
     let row_id = repositories::workspaces::data_frames::rows::get_row_id(&modified_row)?;
+    let row_key = repositories::workspaces::data_frames::rows::get_row_key_values(
+        &workspace,
+        &file_path,
+        &modified_row,
+    )?;
 
     let diff = repositories::workspaces::data_frames::rows::get_row_diff(&workspace, &file_path)?;
 
@@ -192,6 +440,8 @@ pub async fn update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, Oxen
         resource: None,
         row_id,
         row_index,
+        row_key,
+        neighbors: None,
     }))
 }
 
@@ -201,7 +451,8 @@ pub async fn delete(req: HttpRequest, _bytes: Bytes) -> Result<HttpResponse, Oxe
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
     let workspace_id = path_param(&req, "workspace_id")?;
-    let row_id = path_param(&req, "row_id")?;
+    limits::check_row_rate_limit(&namespace, &repo_name, &workspace_id)?;
+    let row_id = row_id_param(&req)?;
 
     let repo = get_repo(&app_data.path, namespace, repo_name)?;
 
@@ -211,10 +462,16 @@ pub async fn delete(req: HttpRequest, _bytes: Bytes) -> Result<HttpResponse, Oxe
             .json(StatusMessageDescription::workspace_not_found(workspace_id)));
     };
 
-    let df = repositories::workspaces::data_frames::rows::delete(
-        &repo, &workspace, &file_path, &row_id,
+    let lock = frame_lock(&workspace.id, &file_path);
+    let _guard = lock.lock().await;
+    let author = get_request_author(&req);
+    let df = repositories::workspaces::data_frames::rows::delete_with_author(
+        &repo, &workspace, &file_path, &row_id, &author,
     )?;
     let diff = repositories::workspaces::data_frames::rows::get_row_diff(&workspace, &file_path)?;
+    let row_key = repositories::workspaces::data_frames::rows::get_row_key_values(
+        &workspace, &file_path, &df,
+    )?;
 
     let schema = Schema::from_polars(&df.schema());
     Ok(HttpResponse::Ok().json(JsonDataFrameRowResponse {
@@ -229,6 +486,8 @@ pub async fn delete(req: HttpRequest, _bytes: Bytes) -> Result<HttpResponse, Oxe
         resource: None,
         row_id: None,
         row_index: None,
+        row_key,
+        neighbors: None,
     }))
 }
 
@@ -238,7 +497,8 @@ pub async fn restore(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
     let workspace_id = path_param(&req, "workspace_id")?;
-    let row_id = path_param(&req, "row_id")?;
+    limits::check_row_rate_limit(&namespace, &repo_name, &workspace_id)?;
+    let row_id = row_id_param(&req)?;
 
     let repo = get_repo(&app_data.path, namespace, repo_name)?;
 
@@ -248,12 +508,19 @@ pub async fn restore(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
             .json(StatusMessageDescription::workspace_not_found(workspace_id)));
     };
 
+    let lock = frame_lock(&workspace.id, &file_path);
+    let _guard = lock.lock().await;
     let restored_row = repositories::workspaces::data_frames::rows::restore(
         &repo, &workspace, &file_path, &row_id,
     )?;
 
     let row_index = repositories::workspaces::data_frames::rows::get_row_idx(&restored_row)?;
     let row_id = repositories::workspaces::data_frames::rows::get_row_id(&restored_row)?;
+    let row_key = repositories::workspaces::data_frames::rows::get_row_key_values(
+        &workspace,
+        &file_path,
+        &restored_row,
+    )?;
 
     let diff = repositories::workspaces::data_frames::rows::get_row_diff(&workspace, &file_path)?;
 
@@ -271,6 +538,100 @@ pub async fn restore(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
         resource: None,
         row_id,
         row_index,
+        row_key,
+        neighbors: None,
+    }))
+}
+
+/// Every uncommitted edit to a row in the workspace with its author, oldest first
+pub async fn history(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let row_id = row_id_param(&req)?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    let history = repositories::workspaces::data_frames::rows::get_row_history(
+        &workspace, &file_path, &row_id,
+    )?;
+
+    Ok(HttpResponse::Ok().json(DataFrameRowHistoryResponse {
+        status: StatusMessage::resource_found(),
+        row_id,
+        history,
+    }))
+}
+
+/// Revert the last `count` row operations in the workspace, returning the schema and size of the frame
+pub async fn undo(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    limits::check_row_rate_limit(&namespace, &repo_name, &workspace_id)?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    let Ok(body) = String::from_utf8(bytes.to_vec()) else {
+        return Err(OxenHttpError::BadRequest(
+            "Could not parse bytes as utf8".to_string().into(),
+        ));
+    };
+    let request: RowUndoRequest = serde_json::from_str(&body)?;
+
+    let lock = frame_lock(&workspace.id, &file_path);
+    let _guard = lock.lock().await;
+    let undone = repositories::workspaces::data_frames::rows::undo(
+        &repo,
+        &workspace,
+        &file_path,
+        request.count,
+    )?;
+    log::debug!("undid {} row operations on {:?}", undone.len(), file_path);
+
+    let data_frame = repositories::workspaces::data_frames::schema_size(&workspace, &file_path)?;
+    Ok(HttpResponse::Ok().json(DataFrameSchemaSizeResponse {
+        status: StatusMessage::resource_updated(),
+        data_frame,
+    }))
+}
+
+/// Discard every uncommitted row edit to the data frame, returning the schema and size of the clean frame
+pub async fn reset(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+
+    let file_path = PathBuf::from(path_param(&req, "path")?);
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    let lock = frame_lock(&workspace.id, &file_path);
+    let _guard = lock.lock().await;
+    let data_frame = repositories::workspaces::data_frames::reset(&workspace, &file_path)?;
+
+    Ok(HttpResponse::Ok().json(DataFrameSchemaSizeResponse {
+        status: StatusMessage::resource_updated(),
+        data_frame,
     }))
 }
 
@@ -280,6 +641,7 @@ pub async fn batch_update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse
     let namespace = path_param(&req, "namespace")?;
     let repo_name = path_param(&req, "repo_name")?;
     let workspace_id = path_param(&req, "workspace_id")?;
+    limits::check_row_rate_limit(&namespace, &repo_name, &workspace_id)?;
 
     let repo = get_repo(&app_data.path, &namespace, &repo_name)?;
 
@@ -290,11 +652,10 @@ pub async fn batch_update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse
         ));
     };
 
-    let json_value: serde_json::Value = serde_json::from_str(&data)?;
-    let data = if let Some(data_obj) = json_value.get("data") {
-        data_obj
-    } else {
-        &json_value
+    let mut json_value: serde_json::Value = serde_json::from_str(&data)?;
+    let data = match json_value.get_mut("data") {
+        Some(data_obj) => data_obj.take(),
+        None => json_value,
     };
 
     let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
@@ -309,30 +670,405 @@ pub async fn batch_update(req: HttpRequest, bytes: Bytes) -> Result<HttpResponse
         file_path
     );
 
-    let modified_rows = repositories::workspaces::data_frames::rows::batch_update(
-        &repo, &workspace, &file_path, data,
-    )?;
-
-    let mut responses = Vec::new();
-
-    for modified_row in modified_rows {
-        let response = match modified_row {
-            UpdateResult::Success(row_id, _data_frame) => BatchUpdateResponse {
-                row_id,
-                code: 200,
-                error: None,
-            },
-            UpdateResult::Error(row_id, error) => BatchUpdateResponse {
-                row_id,
-                code: 500,
-                error: Some(error.to_string()),
-            },
+    let lock = frame_lock(&workspace.id, &file_path);
+    let author = get_request_author(&req);
+    if accepts_ndjson(&req) {
+        let serde_json::Value::Array(rows) = data else {
+            return Err(OxenHttpError::BadRequest(
+                "Data is not an array".to_string().into(),
+            ));
         };
-        responses.push(response);
+        return Ok(stream_batch_update(
+            repo, workspace, file_path, rows, lock, author,
+        ));
     }
 
+    let modified_rows = {
+        let _guard = lock.lock().await;
+        repositories::workspaces::data_frames::rows::batch_update_with_author(
+            &repo, &workspace, &file_path, &data, &author,
+        )?
+    };
+
+    let responses = modified_rows
+        .into_iter()
+        .map(batch_update_response)
+        .collect();
+
     Ok(HttpResponse::Ok().json(VecBatchUpdateResponse {
         status: StatusMessage::resource_updated(),
         rows: responses,
     }))
 }
+
+fn accepts_ndjson(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| accept.contains(NDJSON_CONTENT_TYPE))
+}
+
+fn batch_update_response(result: UpdateResult) -> BatchUpdateResponse {
+    match result {
+        UpdateResult::Success(row_id, _data_frame) => BatchUpdateResponse {
+            row_id,
+            code: 200,
+            error: None,
+        },
+        UpdateResult::Error(row_id, error) => BatchUpdateResponse {
+            row_id,
+            code: 500,
+            error: Some(error.to_string()),
+        },
+    }
+}
+
+/// Update the rows a chunk at a time, writing each chunk's results as NDJSON lines once it is done.
+/// The channel is bounded so a slow client holds back the updates instead of buffering every result.
+fn stream_batch_update(
+    repo: LocalRepository,
+    workspace: Workspace,
+    file_path: PathBuf,
+    rows: Vec<serde_json::Value>,
+    lock: Arc<tokio::sync::Mutex<()>>,
+    author: String,
+) -> HttpResponse {
+    let (tx, rx) = tokio::sync::mpsc::channel::<Bytes>(BATCH_UPDATE_STREAM_BUFFER);
+    actix_web::rt::spawn(async move {
+        let _guard = lock.lock().await;
+        for chunk in rows.chunks(BATCH_UPDATE_STREAM_CHUNK_SIZE) {
+            let chunk_data = serde_json::Value::Array(chunk.to_vec());
+            let responses: Vec<BatchUpdateResponse> =
+                match repositories::workspaces::data_frames::rows::batch_update_with_author(
+                    &repo,
+                    &workspace,
+                    &file_path,
+                    &chunk_data,
+                    &author,
+                ) {
+                    Ok(results) => results.into_iter().map(batch_update_response).collect(),
+                    // The chunk is written all at once, so an error applies to every row in it
+                    Err(err) => chunk
+                        .iter()
+                        .map(|row| BatchUpdateResponse {
+                            row_id: row
+                                .get("row_id")
+                                .and_then(serde_json::Value::as_str)
+                                .unwrap_or_default()
+                                .to_string(),
+                            code: 500,
+                            error: Some(err.to_string()),
+                        })
+                        .collect(),
+                };
+
+            let mut lines = Vec::new();
+            for response in responses {
+                if let Err(err) = serde_json::to_writer(&mut lines, &response) {
+                    log::error!("Could not serialize batch update response: {:?}", err);
+                    continue;
+                }
+                lines.push(b'\n');
+            }
+            if tx.send(Bytes::from(lines)).await.is_err() {
+                log::debug!("batch_update client disconnected, stopping");
+                return;
+            }
+        }
+    });
+
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv()
+            .await
+            .map(|lines| (Ok::<Bytes, actix_web::Error>(lines), rx))
+    });
+    HttpResponse::Ok()
+        .content_type(NDJSON_CONTENT_TYPE)
+        .streaming(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http;
+    use actix_web::web::Bytes;
+    use actix_web::ResponseError;
+    use polars::frame::DataFrame;
+    use serde_json::json;
+    use std::path::Path;
+    use std::sync::Barrier;
+
+    use liboxen::constants::OXEN_ID_COL;
+    use liboxen::error::OxenError;
+    use liboxen::opts::DFOpts;
+    use liboxen::repositories;
+    use liboxen::util;
+    use liboxen::view::json_data_frame_view::{BatchUpdateResponse, JsonDataFrameRowResponse};
+
+    use crate::app_data::OxenAppData;
+    use crate::controllers;
+    use crate::params::RowNeighborsQuery;
+    use crate::test;
+
+    fn string_column(df: &DataFrame, name: &str) -> Result<Vec<String>, OxenError> {
+        let column = df.column(name)?;
+        let mut values = Vec::new();
+        for i in 0..df.height() {
+            values.push(column.get(i)?.to_string().replace('"', ""));
+        }
+        Ok(values)
+    }
+
+    #[actix_web::test]
+    async fn test_create_row_invalid_utf8_is_bad_request() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let repo_name = "Testing-Repo";
+        test::create_local_repo(&sync_dir, namespace, repo_name)?;
+
+        let uri =
+            format!("/oxen/{namespace}/{repo_name}/workspaces/workspace/data_frames/rows/data.csv");
+        let req = actix_web::test::TestRequest::with_uri(&uri)
+            .app_data(OxenAppData::new(sync_dir.to_path_buf()))
+            .param("namespace", namespace)
+            .param("repo_name", repo_name)
+            .param("workspace_id", "workspace")
+            .param("path", "data.csv")
+            .to_http_request();
+        let body = Bytes::from_static(&[0xff, 0xfe, b'{', b'}']);
+        let err = controllers::workspaces::data_frames::rows::create(req, body)
+            .await
+            .expect_err("invalid utf8 should be rejected");
+        assert_eq!(err.error_response().status(), http::StatusCode::BAD_REQUEST);
+
+        test::cleanup_sync_dir(&sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_row_key_addresses_rows_over_http() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let repo_name = "Testing-Repo";
+        let repo = test::create_local_repo(&sync_dir, namespace, repo_name)?;
+
+        let file_path = Path::new("boxes.csv");
+        util::fs::write_to_path(
+            repo.path.join(file_path),
+            "image_id,bbox_id,label\n1,1,a\n1,2,b\n2,1,c\n",
+        )?;
+        repositories::add(&repo, repo.path.join(file_path))?;
+        let commit = repositories::commit(&repo, "Adding boxes.csv")?;
+        let workspace = repositories::workspaces::create(&repo, &commit, "workspace", true)?;
+        repositories::workspaces::data_frames::index(&repo, &workspace, file_path)?;
+
+        let request = |uri: String, row_id: Option<&str>| {
+            let mut req = actix_web::test::TestRequest::with_uri(&uri)
+                .app_data(OxenAppData::new(sync_dir.to_path_buf()))
+                .param("namespace", namespace)
+                .param("repo_name", repo_name)
+                .param("workspace_id", "workspace")
+                .param("path", "boxes.csv");
+            if let Some(row_id) = row_id {
+                req = req.param("row_id", row_id.to_string());
+            }
+            req.to_http_request()
+        };
+
+        let uri = format!(
+            "/oxen/{namespace}/{repo_name}/workspaces/workspace/data_frames/row_key/boxes.csv"
+        );
+        let body = json!({"columns": ["image_id", "bbox_id"]}).to_string();
+        let resp = controllers::workspaces::data_frames::set_row_key(request(uri, None), body)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // The key goes in the query, the row id in the path is ignored
+        let uri = format!(
+            "/oxen/{namespace}/{repo_name}/workspaces/workspace/data_frames/rows/_/resource/boxes.csv?key=1/2"
+        );
+        let query = actix_web::web::Query::<RowNeighborsQuery>::from_query("key=1/2").unwrap();
+        let resp = controllers::workspaces::data_frames::rows::get(request(uri, Some("_")), query)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let row: JsonDataFrameRowResponse = serde_json::from_slice(&body)?;
+        let row_key = row.row_key.unwrap();
+        assert_eq!(row_key["image_id"], json!(1));
+        assert_eq!(row_key["bbox_id"], json!(2));
+
+        // The _oxen_id in the path still addresses the same row once a key is set
+        let row_id = row.row_id.unwrap();
+        let uri = format!(
+            "/oxen/{namespace}/{repo_name}/workspaces/workspace/data_frames/rows/{row_id}/resource/boxes.csv"
+        );
+        let query = actix_web::web::Query::<RowNeighborsQuery>::from_query("").unwrap();
+        let resp =
+            controllers::workspaces::data_frames::rows::get(request(uri, Some(&row_id)), query)
+                .await
+                .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let row: JsonDataFrameRowResponse = serde_json::from_slice(&body)?;
+        assert_eq!(row.row_id, Some(row_id));
+        assert_eq!(row.row_key.unwrap()["bbox_id"], json!(2));
+
+        // The diff carries the key of each changed row
+        let uri = format!(
+            "/oxen/{namespace}/{repo_name}/workspaces/workspace/data_frames/rows/_/resource/boxes.csv?key=2/1"
+        );
+        let body = Bytes::from(json!({"data": {"label": "z"}}).to_string());
+        let resp =
+            controllers::workspaces::data_frames::rows::update(request(uri, Some("_")), body)
+                .await
+                .unwrap();
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let row: JsonDataFrameRowResponse = serde_json::from_slice(&body)?;
+        let diff = row.diff.unwrap();
+        assert_eq!(diff.len(), 1);
+        let row_key = diff[0].row_key.as_ref().unwrap();
+        assert_eq!(row_key["image_id"], json!(2));
+        assert_eq!(row_key["bbox_id"], json!(1));
+
+        test::cleanup_sync_dir(&sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_batch_update_streams_ndjson() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let repo_name = "Testing-Repo";
+        let repo = test::create_local_repo(&sync_dir, namespace, repo_name)?;
+
+        let file_path = Path::new("data.csv");
+        util::fs::write_to_path(repo.path.join(file_path), "id,label\n0,a\n1,b\n2,c\n")?;
+        repositories::add(&repo, repo.path.join(file_path))?;
+        let commit = repositories::commit(&repo, "Adding data.csv")?;
+
+        let workspace = repositories::workspaces::create(&repo, &commit, "workspace", true)?;
+        repositories::workspaces::data_frames::index(&repo, &workspace, file_path)?;
+        let df =
+            repositories::workspaces::data_frames::query(&workspace, file_path, &DFOpts::empty())?;
+        let row_ids = string_column(&df, OXEN_ID_COL)?;
+
+        let uri =
+            format!("/oxen/{namespace}/{repo_name}/workspaces/workspace/data_frames/rows/data.csv");
+        let req = actix_web::test::TestRequest::with_uri(&uri)
+            .app_data(OxenAppData::new(sync_dir.to_path_buf()))
+            .insert_header((http::header::ACCEPT, "application/x-ndjson"))
+            .param("namespace", namespace)
+            .param("repo_name", repo_name)
+            .param("workspace_id", "workspace")
+            .param("path", "data.csv")
+            .to_http_request();
+        let updates: Vec<_> = row_ids
+            .iter()
+            .map(|row_id| json!({"row_id": row_id, "value": {"label": "updated"}}))
+            .collect();
+        let body = Bytes::from(json!({ "data": updates }).to_string());
+        let resp = controllers::workspaces::data_frames::rows::batch_update(req, body)
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        // One result per line, in the order of the request
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let lines: Vec<BatchUpdateResponse> = String::from_utf8(body.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), row_ids.len());
+        for (line, row_id) in lines.iter().zip(&row_ids) {
+            assert_eq!(&line.row_id, row_id);
+            assert_eq!(line.code, 200);
+        }
+
+        test::cleanup_sync_dir(&sync_dir)?;
+
+        Ok(())
+    }
+
+    #[actix_web::test]
+    async fn test_concurrent_row_updates_are_not_lost() -> Result<(), OxenError> {
+        let sync_dir = test::get_sync_dir()?;
+        let namespace = "Testing-Namespace";
+        let repo_name = "Testing-Repo";
+        let repo = test::create_local_repo(&sync_dir, namespace, repo_name)?;
+
+        let num_rows = 20;
+        let mut csv = String::from("id,label\n");
+        for i in 0..num_rows {
+            csv.push_str(&format!("{i},original\n"));
+        }
+        let file_path = Path::new("data.csv");
+        util::fs::write_to_path(repo.path.join(file_path), &csv)?;
+        repositories::add(&repo, repo.path.join(file_path))?;
+        let commit = repositories::commit(&repo, "Adding data.csv")?;
+
+        let workspace = repositories::workspaces::create(&repo, &commit, "workspace", true)?;
+        repositories::workspaces::data_frames::index(&repo, &workspace, file_path)?;
+        let mut opts = DFOpts::empty();
+        opts.page_size = Some(num_rows);
+        let df = repositories::workspaces::data_frames::query(&workspace, file_path, &opts)?;
+        let row_ids = string_column(&df, OXEN_ID_COL)?;
+
+        // The controller never awaits, so each update runs on its own thread and system to overlap
+        let barrier = Barrier::new(row_ids.len());
+        let statuses: Vec<http::StatusCode> = std::thread::scope(|scope| {
+            let handles: Vec<_> = row_ids
+                .iter()
+                .enumerate()
+                .map(|(i, row_id)| {
+                    let barrier = &barrier;
+                    let sync_dir = &sync_dir;
+                    scope.spawn(move || {
+                        let uri = format!(
+                            "/oxen/{namespace}/{repo_name}/workspaces/workspace/data_frames/rows/{row_id}/data.csv"
+                        );
+                        let req = actix_web::test::TestRequest::with_uri(&uri)
+                            .app_data(OxenAppData::new(sync_dir.to_path_buf()))
+                            .param("namespace", namespace)
+                            .param("repo_name", repo_name)
+                            .param("workspace_id", "workspace")
+                            .param("path", "data.csv")
+                            .param("row_id", row_id.clone())
+                            .to_http_request();
+                        let body = Bytes::from(
+                            json!({"data": {"label": format!("updated_{i}")}}).to_string(),
+                        );
+                        barrier.wait();
+                        actix_web::rt::System::new().block_on(async move {
+                            controllers::workspaces::data_frames::rows::update(req, body)
+                                .await
+                                .unwrap()
+                                .status()
+                        })
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        for status in statuses {
+            assert_eq!(status, http::StatusCode::OK);
+        }
+
+        // Every row should have its own update, none should be lost
+        let df = repositories::workspaces::data_frames::query(&workspace, file_path, &opts)?;
+        let labels = string_column(&df, "label")?;
+        for i in 0..row_ids.len() {
+            assert!(labels.contains(&format!("updated_{i}")));
+        }
+
+        test::cleanup_sync_dir(&sync_dir)?;
+
+        Ok(())
+    }
+}
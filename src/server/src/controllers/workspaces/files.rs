@@ -109,6 +109,28 @@ pub async fn delete(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
     }
 }
 
+/// Stage the removal of a file that is committed on the workspace's base commit
+pub async fn rm(req: HttpRequest) -> Result<HttpResponse, OxenHttpError> {
+    let app_data = app_data(&req)?;
+    let namespace = path_param(&req, "namespace")?;
+    let repo_name = path_param(&req, "repo_name")?;
+    let workspace_id = path_param(&req, "workspace_id")?;
+    let repo = get_repo(&app_data.path, namespace, repo_name)?;
+    let path = PathBuf::from(path_param(&req, "path")?);
+
+    let Some(workspace) = repositories::workspaces::get(&repo, &workspace_id)? else {
+        return Ok(HttpResponse::NotFound()
+            .json(StatusMessageDescription::workspace_not_found(workspace_id)));
+    };
+
+    if repositories::tree::get_file_by_path(&repo, &workspace.commit, &path)?.is_none() {
+        return Ok(HttpResponse::NotFound().json(StatusMessage::resource_not_found()));
+    }
+
+    repositories::workspaces::files::rm(&workspace, &path)?;
+    Ok(HttpResponse::Ok().json(StatusMessage::resource_deleted()))
+}
+
 pub async fn validate(_req: HttpRequest, _body: String) -> Result<HttpResponse, OxenHttpError> {
     Ok(HttpResponse::Ok().json(StatusMessage::resource_found()))
 }
@@ -48,6 +48,7 @@ pub enum OxenHttpError {
     WorkspaceBehind(Box<WorkspaceBranch>),
     BasicError(StringError),
     FailedToReadRequestPayload,
+    TooManyRequests(StringError),
 
     // Translate OxenError to OxenHttpError
     InternalOxenError(OxenError),
@@ -114,6 +115,18 @@ impl error::ResponseError for OxenHttpError {
                 });
                 HttpResponse::BadRequest().json(error_json)
             }
+            OxenHttpError::TooManyRequests(desc) => {
+                let error_json = json!({
+                    "error": {
+                        "type": "too_many_requests",
+                        "title": "Too Many Requests",
+                        "detail": desc.to_string()
+                    },
+                    "status": STATUS_ERROR,
+                    "status_message": MSG_BAD_REQUEST,
+                });
+                HttpResponse::TooManyRequests().json(error_json)
+            }
             OxenHttpError::SQLParseError(query) => {
                 HttpResponse::BadRequest().json(SQLParseError::new(query.to_string()))
             }
@@ -541,6 +554,7 @@ impl error::ResponseError for OxenHttpError {
             OxenHttpError::ActixError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             OxenHttpError::SerdeError(_) => StatusCode::BAD_REQUEST,
             OxenHttpError::FailedToReadRequestPayload => StatusCode::BAD_REQUEST,
+            OxenHttpError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             OxenHttpError::InternalOxenError(error) => match error {
                 OxenError::RepoNotFound(_) => StatusCode::NOT_FOUND,
                 OxenError::RevisionNotFound(_) => StatusCode::NOT_FOUND,
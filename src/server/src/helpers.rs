@@ -1,10 +1,13 @@
 use std::path::Path;
 
+use actix_web::{HttpMessage, HttpRequest};
 // use liboxen::constants::DEFAULT_REDIS_URL;
+use liboxen::constants::DEFAULT_ROW_CHANGE_AUTHOR;
 use liboxen::error::OxenError;
 use liboxen::model::{LocalRepository, RepoNew};
 use liboxen::repositories;
 
+use crate::auth::validator::RequestAuthor;
 use crate::errors::OxenHttpError;
 
 pub fn get_repo(
@@ -22,6 +25,14 @@ pub fn get_repo(
     Ok(repo)
 }
 
+/// The email of the user the auth middleware resolved for the request, "anonymous" if there is none
+pub fn get_request_author(req: &HttpRequest) -> String {
+    req.extensions()
+        .get::<RequestAuthor>()
+        .map(|author| author.0.clone())
+        .unwrap_or_else(|| DEFAULT_ROW_CHANGE_AUTHOR.to_string())
+}
+
 // #[allow(dependency_on_unit_never_type_fallback)]
 // pub fn get_redis_connection() -> Result<r2d2::Pool<redis::Client>, OxenError> {
 //     let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| DEFAULT_REDIS_URL.to_string());
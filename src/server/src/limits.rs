@@ -0,0 +1,127 @@
+//! Size and rate limits for the publicly exposed workspace editing endpoints.
+//!
+//! Both are configurable through the environment:
+//! - `OXEN_MAX_ROW_BODY_BYTES` caps the request body of the row endpoints (default 8MB), bigger
+//!   bodies are rejected with a 413 by the `PayloadConfig` on the rows scope before they are buffered
+//! - `OXEN_ROW_RATE_LIMIT` caps the mutating row requests per workspace per minute (default 600, 0 disables)
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::errors::OxenHttpError;
+
+pub const MAX_ROW_BODY_BYTES_ENV: &str = "OXEN_MAX_ROW_BODY_BYTES";
+pub const ROW_RATE_LIMIT_ENV: &str = "OXEN_ROW_RATE_LIMIT";
+
+const DEFAULT_MAX_ROW_BODY_BYTES: usize = 8 * 1024 * 1024;
+const DEFAULT_ROW_RATE_LIMIT: u32 = 600;
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+static ROW_RATE_LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) => value.parse().unwrap_or_else(|_| {
+            log::warn!("Invalid value {:?} for {}, using the default", value, name);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+pub fn max_row_body_bytes() -> usize {
+    env_or(MAX_ROW_BODY_BYTES_ENV, DEFAULT_MAX_ROW_BODY_BYTES)
+}
+
+/// Count a mutating row request against the workspace, erroring with a 429 once it is over the limit
+pub fn check_row_rate_limit(
+    namespace: &str,
+    repo_name: &str,
+    workspace_id: &str,
+) -> Result<(), OxenHttpError> {
+    ROW_RATE_LIMITER
+        .get_or_init(|| {
+            RateLimiter::new(
+                env_or(ROW_RATE_LIMIT_ENV, DEFAULT_ROW_RATE_LIMIT),
+                RATE_LIMIT_WINDOW,
+            )
+        })
+        .check(&format!("{namespace}/{repo_name}/{workspace_id}"))
+}
+
+/// Fixed window counter keyed by an id, ie a workspace id
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    windows: Mutex<HashMap<String, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> RateLimiter {
+        RateLimiter {
+            limit,
+            window,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn check(&self, key: &str) -> Result<(), OxenHttpError> {
+        if self.limit == 0 {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().expect("rate limiter poisoned");
+        // Forget the windows that have expired so the map does not grow with every workspace
+        windows.retain(|_, (start, _)| now.duration_since(*start) < self.window);
+
+        let (_, count) = windows.entry(key.to_string()).or_insert((now, 0));
+        if *count >= self.limit {
+            return Err(OxenHttpError::TooManyRequests(
+                format!(
+                    "Too many row edits for {key}, the limit is {} per {} seconds",
+                    self.limit,
+                    self.window.as_secs()
+                )
+                .into(),
+            ));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RateLimiter;
+
+    #[test]
+    fn test_rate_limiter_is_per_key() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.check("workspace-a").is_ok());
+        assert!(limiter.check("workspace-a").is_ok());
+        assert!(limiter.check("workspace-a").is_err());
+        assert!(limiter.check("workspace-b").is_ok());
+    }
+
+    #[test]
+    fn test_rate_limiter_resets_after_window() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(10));
+        assert!(limiter.check("workspace").is_ok());
+        assert!(limiter.check("workspace").is_err());
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(limiter.check("workspace").is_ok());
+    }
+
+    #[test]
+    fn test_rate_limiter_zero_disables() {
+        let limiter = RateLimiter::new(0, Duration::from_secs(60));
+        for _ in 0..10 {
+            assert!(limiter.check("workspace").is_ok());
+        }
+    }
+}
@@ -10,6 +10,7 @@ pub mod auth;
 pub mod controllers;
 pub mod errors;
 pub mod helpers;
+pub mod limits;
 pub mod middleware;
 pub mod params;
 pub mod routes;
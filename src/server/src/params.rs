@@ -16,6 +16,9 @@ use percent_encoding::percent_decode;
 pub mod aggregate_query;
 pub use aggregate_query::AggregateQuery;
 
+pub mod column_stats_query;
+pub use column_stats_query::ColumnStatsQuery;
+
 pub mod name_param;
 pub use name_param::NameParam;
 
@@ -26,6 +29,9 @@ pub use page_num_query::PageNumVersionQuery;
 pub mod df_opts_query;
 pub use df_opts_query::DFOptsQuery;
 
+pub mod row_neighbors_query;
+pub use row_neighbors_query::{RowCursorQuery, RowKeyQuery, RowNeighborsQuery};
+
 pub mod tree_depth;
 pub use tree_depth::TreeDepthQuery;
 
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct ColumnStatsQuery {
+    /// Comma separated column names to summarize, defaults to all columns
+    pub columns: Option<String>,
+}
+
+impl ColumnStatsQuery {
+    pub fn column_names(&self) -> Option<Vec<String>> {
+        self.columns.as_ref().map(|columns| {
+            columns
+                .split(',')
+                .map(|column| column.trim().to_string())
+                .filter(|column| !column.is_empty())
+                .collect()
+        })
+    }
+}
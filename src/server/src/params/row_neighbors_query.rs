@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+pub struct RowNeighborsQuery {
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+}
+
+/// `?key=` addresses a row by its composite row key instead of the `{row_id}` in the path. The key
+/// values are slash separated or a JSON array or object, which don't fit in a path segment.
+#[derive(Deserialize, Debug)]
+pub struct RowKeyQuery {
+    pub key: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RowCursorQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<usize>,
+}
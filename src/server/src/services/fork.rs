@@ -7,4 +7,5 @@ pub fn fork() -> Scope {
     web::scope("/fork")
         .route("", web::post().to(controllers::fork::fork))
         .route("/status", web::get().to(controllers::fork::get_status))
+        .route("/lineage", web::get().to(controllers::fork::get_lineage))
 }
@@ -39,6 +39,10 @@ pub fn workspace() -> Scope {
                     "/files/{path:.*}",
                     web::delete().to(controllers::workspaces::files::delete),
                 )
+                .route(
+                    "/rm/{path:.*}",
+                    web::post().to(controllers::workspaces::files::rm),
+                )
                 .route(
                     "/validate",
                     web::post().to(controllers::workspaces::files::validate),
@@ -56,6 +60,10 @@ pub fn workspace() -> Scope {
                     "/merge/{branch:.*}",
                     web::get().to(controllers::workspaces::mergeability),
                 )
+                .route(
+                    "/diff/{branch:.*}",
+                    web::get().to(controllers::workspaces::diff_branch),
+                )
                 .service(data_frames::data_frames()),
         )
 }
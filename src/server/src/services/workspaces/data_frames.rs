@@ -21,6 +21,10 @@ pub fn data_frames() -> Scope {
             "/download/{path:.*}",
             web::get().to(controllers::workspaces::data_frames::download),
         )
+        .route(
+            "/indexed",
+            web::get().to(controllers::workspaces::data_frames::list_indexed),
+        )
         .route(
             "/rename/{path:.*}",
             web::put().to(controllers::workspaces::data_frames::rename),
@@ -29,6 +33,14 @@ pub fn data_frames() -> Scope {
             "/resource/{path:.*}",
             web::get().to(controllers::workspaces::data_frames::get),
         )
+        .route(
+            "/row_key/{path:.*}",
+            web::get().to(controllers::workspaces::data_frames::get_row_key),
+        )
+        .route(
+            "/row_key/{path:.*}",
+            web::put().to(controllers::workspaces::data_frames::set_row_key),
+        )
         .route(
             "/schema/{path:.*}",
             web::get().to(controllers::workspaces::data_frames::get_schema),
@@ -41,6 +53,11 @@ pub fn data_frames() -> Scope {
             "/resource/{path:.*}",
             web::delete().to(controllers::workspaces::data_frames::delete),
         )
+        // Registered after the prefixed routes so a data frame path can't shadow them
+        .route(
+            "/{path:.*}/commit",
+            web::post().to(controllers::workspaces::data_frames::commit),
+        )
         .service(rows::rows())
         .service(columns::columns())
         .service(embeddings::embeddings())
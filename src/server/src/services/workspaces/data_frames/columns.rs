@@ -9,6 +9,10 @@ pub fn columns() -> Scope {
             "/resource/{path:.*}",
             web::post().to(controllers::workspaces::data_frames::columns::create),
         )
+        .route(
+            "/stats/{path:.*}",
+            web::get().to(controllers::workspaces::data_frames::columns::stats),
+        )
         .route(
             "/schema/metadata/{path:.*}",
             web::post().to(controllers::workspaces::data_frames::columns::add_column_metadata),
@@ -2,13 +2,39 @@ use actix_web::web;
 use actix_web::Scope;
 
 use crate::controllers;
+use crate::limits;
 
 pub fn rows() -> Scope {
     web::scope("/rows")
+        .app_data(web::PayloadConfig::new(limits::max_row_body_bytes()))
+        .route(
+            "/reset/{path:.*}",
+            web::post().to(controllers::workspaces::data_frames::rows::reset),
+        )
+        .route(
+            "/undo/{path:.*}",
+            web::post().to(controllers::workspaces::data_frames::rows::undo),
+        )
+        .route(
+            "/compare/{other_workspace_id}/{path:.*}",
+            web::get().to(controllers::workspaces::data_frames::rows::compare),
+        )
+        .route(
+            "/merge/{other_workspace_id}/{path:.*}",
+            web::post().to(controllers::workspaces::data_frames::rows::merge),
+        )
+        .route(
+            "/{row_id}/history/{path:.*}",
+            web::get().to(controllers::workspaces::data_frames::rows::history),
+        )
         .route(
             "/{row_id}/restore/{path:.*}",
             web::post().to(controllers::workspaces::data_frames::rows::restore),
         )
+        .route(
+            "/resource/{path:.*}",
+            web::get().to(controllers::workspaces::data_frames::rows::list),
+        )
         .route(
             "/resource/{path:.*}",
             web::post().to(controllers::workspaces::data_frames::rows::create),